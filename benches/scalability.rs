@@ -0,0 +1,141 @@
+//! Per-tick cost of the systems that grow with the colony, at 1k, 5k and 10k ants.
+//! The bar is 10,000 ants at 60 FPS, so every group should stay well under 16ms per tick at 10k.
+//!
+//!     cargo bench --bench scalability
+
+use antsim::components::*;
+use antsim::config::SimConfig;
+use antsim::pheromones::{PheromoneGrid, PheromoneType};
+use antsim::policy::ActivePolicy;
+use antsim::spatial_index::{spatial_index_system, SpatialIndex};
+use antsim::systems::{ant_proximity_analysis_system, ant_timer_system, sensing_systems, spawn_ant, SensingClock};
+use bevy::diagnostic::DiagnosticsStore;
+use bevy::prelude::*;
+use bevy::tasks::{ComputeTaskPool, TaskPool};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::Duration;
+
+const ANT_COUNTS: [usize; 3] = [1_000, 5_000, 10_000];
+const TICK: Duration = Duration::from_nanos(1_000_000_000 / 60);
+/// Trails laid across the grid so sensing has gradients to follow
+const TRAILS: usize = 40;
+
+/// A 1000x1000 grid with food and nest trails along straight lines between random points
+fn trail_grid(rng: &mut StdRng) -> PheromoneGrid {
+    let mut grid = PheromoneGrid::new(1000, 1000);
+    for _ in 0..TRAILS {
+        let from = Vec2::new(rng.gen_range(-480.0..480.0), rng.gen_range(-480.0..480.0));
+        let to = Vec2::new(rng.gen_range(-480.0..480.0), rng.gen_range(-480.0..480.0));
+        let steps = from.distance(to) as usize;
+        for step in 0..steps {
+            let point = from.lerp(to, step as f32 / steps as f32);
+            grid.deposit(point.x, point.y, PheromoneType::Food, 20.0);
+            grid.deposit(point.x, point.y, PheromoneType::Nest, 20.0);
+        }
+    }
+    grid
+}
+
+/// A world holding `ants` ants scattered over the map, out of their startup delay, plus every
+/// resource sensing and the proximity pass read
+fn populated_world(ants: usize) -> World {
+    ComputeTaskPool::get_or_init(TaskPool::default);
+    let mut rng = StdRng::seed_from_u64(ants as u64);
+    let config = SimConfig::default();
+
+    let mut world = World::new();
+    world.insert_resource(trail_grid(&mut rng));
+    world.insert_resource(SpatialIndex::default());
+    world.insert_resource(SwarmDecisionLog::default());
+    world.insert_resource(SensingClock::default());
+    world.insert_resource(ViewSettings::default());
+    world.insert_resource(Events::<SimEvent>::default());
+    world.insert_resource(Events::<AntStuck>::default());
+    world.insert_resource(Time::<()>::default());
+    world.insert_resource(DiagnosticsStore::default());
+    {
+        let mut commands = world.commands();
+        for index in 0..ants {
+            spawn_ant(&mut commands, index, &config, 0.0, 0, Vec2::ZERO);
+        }
+    }
+    world.flush();
+    world.insert_resource(config);
+
+    // spawn_ant puts every ant on the nest ring; spread them out like a colony mid-run
+    let mut query = world.query::<(&mut Transform, &mut AntCore, &mut AntStats)>();
+    for (mut transform, mut ant, mut ant_stats) in query.iter_mut(&mut world) {
+        let position = Vec2::new(rng.gen_range(-480.0..480.0), rng.gen_range(-480.0..480.0));
+        transform.translation = position.extend(transform.translation.z);
+        ant.last_position = position;
+        ant.startup_timer = 0.0;
+        ant_stats.successful_deliveries = rng.gen_range(0..3);
+    }
+    world
+}
+
+/// Run `schedule` once as a fixed tick would, then drop the tick's events
+fn run_tick(world: &mut World, schedule: &mut Schedule) {
+    world.resource_mut::<Time>().advance_by(TICK);
+    schedule.run(world);
+    world.resource_mut::<Events<SimEvent>>().update();
+    world.resource_mut::<Events<AntStuck>>().update();
+}
+
+fn pheromone_update(c: &mut Criterion) {
+    let config = SimConfig::default();
+    let evap_rates = (config.evap_food, config.evap_nest, config.evap_alarm);
+    let diff_rates = (config.diff_food, config.diff_nest, config.diff_alarm);
+    let mut group = c.benchmark_group("pheromone_update");
+    for ants in ANT_COUNTS {
+        let mut rng = StdRng::seed_from_u64(ants as u64);
+        let mut grid = trail_grid(&mut rng);
+        let positions: Vec<Vec2> = (0..ants)
+            .map(|_| Vec2::new(rng.gen_range(-480.0..480.0), rng.gen_range(-480.0..480.0)))
+            .collect();
+        group.throughput(Throughput::Elements(ants as u64));
+        // One queued deposit per ant and one grid update, as pheromone_deposit_system and pheromone_update_system do each tick
+        let mut deposits = Vec::with_capacity(ants);
+        group.bench_with_input(BenchmarkId::from_parameter(ants), &positions, |b, positions| {
+            b.iter(|| {
+                for position in positions {
+                    grid.queue_deposit(&mut deposits, position.x, position.y, PheromoneType::Food, 1.0);
+                }
+                grid.apply_deposits(&mut deposits);
+                grid.update(evap_rates, diff_rates);
+            });
+        });
+    }
+    group.finish();
+}
+
+fn sensing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sensing");
+    group.sample_size(20);
+    for ants in ANT_COUNTS {
+        let mut world = populated_world(ants);
+        let mut schedule = Schedule::default();
+        schedule.add_systems((spatial_index_system, ant_timer_system, sensing_systems(ActivePolicy::default().0.decision_systems())).chain());
+        group.throughput(Throughput::Elements(ants as u64));
+        group.bench_function(BenchmarkId::from_parameter(ants), |b| b.iter(|| run_tick(&mut world, &mut schedule)));
+    }
+    group.finish();
+}
+
+fn proximity(c: &mut Criterion) {
+    let mut group = c.benchmark_group("proximity");
+    group.sample_size(20);
+    for ants in ANT_COUNTS {
+        let mut world = populated_world(ants);
+        let mut schedule = Schedule::default();
+        schedule.add_systems((spatial_index_system, ant_proximity_analysis_system).chain());
+        group.throughput(Throughput::Elements(ants as u64));
+        group.bench_function(BenchmarkId::from_parameter(ants), |b| b.iter(|| run_tick(&mut world, &mut schedule)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, pheromone_update, sensing, proximity);
+criterion_main!(benches);
@@ -52,37 +52,42 @@ if ! cargo run -- --challenge "$CHALLENGE_NUMBER"; then
 fi
 echo "✅ Simulation completed successfully!"
 
-# Find the most recent frames directory (by modification time)
-echo "📹 Looking for captured frames..."
-LATEST_FRAMES=$(find simulation_videos -name "*_frames" -type d -printf "%T@ %f\n" 2>/dev/null | sort -n | tail -1 | cut -d' ' -f2)
-if [ -z "$LATEST_FRAMES" ]; then
-    # Fallback for systems without -printf support
-    LATEST_FRAMES=$(ls -t simulation_videos/*_frames 2>/dev/null | head -1 | xargs basename 2>/dev/null)
-fi
-
-if [ -z "$LATEST_FRAMES" ]; then
-    echo "❌ No frame directories found in simulation_videos/"
-    read -p "Press Enter to exit"
-    exit 1
-fi
-
-VIDEO_NAME=${LATEST_FRAMES%_frames}
-
-echo "🎬 Found frames: $LATEST_FRAMES"
-
-# Convert frames to video - fix Windows path issues in Git Bash
-echo "🔄 Converting frames to MP4..."
-cd simulation_videos
-FFMPEG_PATH="../ffmpeg/ffmpeg-2025-08-20-git-4d7c609be3-full_build/bin/ffmpeg.exe"
-
-if ! "$FFMPEG_PATH" -framerate 30 -i "${LATEST_FRAMES}/frame_%04d.png" -c:v libx264 -pix_fmt yuv420p -y "${VIDEO_NAME}.mp4"; then
-    echo "❌ Video conversion failed"
+# The simulation encodes the MP4 itself; PNG frames are only left behind when ffmpeg could not run.
+# --pheromone-video companions (*_pheromones) stay in simulation_videos/
+echo "📹 Looking for the recorded video..."
+LATEST_VIDEO=$(ls -t simulation_videos/*.mp4 2>/dev/null | grep -v _pheromones | head -1 | xargs basename 2>/dev/null)
+LATEST_FRAMES=$(ls -td simulation_videos/*_frames 2>/dev/null | grep -v _pheromones | head -1 | xargs basename 2>/dev/null)
+
+if [ -n "$LATEST_FRAMES" ] && [ -z "$LATEST_VIDEO" -o "simulation_videos/$LATEST_FRAMES" -nt "simulation_videos/$LATEST_VIDEO" ]; then
+    VIDEO_NAME=${LATEST_FRAMES%_frames}
+    echo "🎬 Found frames: $LATEST_FRAMES"
+
+    # Convert frames to video - fix Windows path issues in Git Bash
+    echo "🔄 Converting frames to MP4..."
+    cd simulation_videos
+    FFMPEG_PATH="../ffmpeg/ffmpeg-2025-08-20-git-4d7c609be3-full_build/bin/ffmpeg.exe"
+
+    if ! "$FFMPEG_PATH" -framerate 30 -i "${LATEST_FRAMES}/frame_%04d.png" -c:v libx264 -pix_fmt yuv420p -y "${VIDEO_NAME}.mp4"; then
+        echo "❌ Video conversion failed"
+        cd ..
+        read -p "Press Enter to exit"
+        exit 1
+    fi
     cd ..
+    echo "✅ Video created: simulation_videos/${VIDEO_NAME}.mp4"
+
+    # Move frames to debug/ directory
+    DEBUG_FRAMES_PATH="debug/$LATEST_FRAMES"
+    mv "simulation_videos/$LATEST_FRAMES" "$DEBUG_FRAMES_PATH"
+    echo "🔍 Debug frames saved to: $DEBUG_FRAMES_PATH/"
+elif [ -n "$LATEST_VIDEO" ]; then
+    VIDEO_NAME=${LATEST_VIDEO%.mp4}
+    echo "🎬 Found video: $LATEST_VIDEO"
+else
+    echo "❌ No video or frame directories found in simulation_videos/"
     read -p "Press Enter to exit"
     exit 1
 fi
-cd ..
-echo "✅ Video created: simulation_videos/${VIDEO_NAME}.mp4"
 
 # Organize files
 echo "📁 Organizing files..."
@@ -91,10 +96,6 @@ echo "📁 Organizing files..."
 FINAL_VIDEO_PATH="videos/$VIDEO_NAME.mp4"
 mv "simulation_videos/${VIDEO_NAME}.mp4" "$FINAL_VIDEO_PATH"
 
-# Move frames to debug/ directory
-DEBUG_FRAMES_PATH="debug/$LATEST_FRAMES"
-mv "simulation_videos/$LATEST_FRAMES" "$DEBUG_FRAMES_PATH"
-
 # Move metadata if it exists
 METADATA_PATH="simulation_videos/${VIDEO_NAME}_metadata.txt"
 if [ -f "$METADATA_PATH" ]; then
@@ -102,7 +103,6 @@ if [ -f "$METADATA_PATH" ]; then
 fi
 
 echo "🎉 Complete! Video saved to: $FINAL_VIDEO_PATH"
-echo "🔍 Debug frames saved to: $DEBUG_FRAMES_PATH/"
 echo ""
 
 # Update generation info for next run
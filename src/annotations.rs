@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts};
+use crate::components::*;
+use crate::editor::EditorState;
+
+/// Flag colour in the world view and the video
+pub const ANNOTATION_RGB: (u8, u8, u8) = (255, 150, 0);
+const FLAG_HEIGHT: f32 = 24.0;
+
+/// A labelled marker dropped on the map; `time` is stage time when it was placed
+#[derive(Clone)]
+pub struct Annotation {
+    pub position: Vec2,
+    pub time: f32,
+    pub label: String,
+}
+
+/// Markers placed with Alt+click. They last for the whole run and are written with the run's metrics.
+#[derive(Resource, Default)]
+pub struct Annotations {
+    pub markers: Vec<Annotation>,
+    pub pending: Option<Annotation>, // Placed but still being named
+    pub show_in_video: bool,
+}
+
+impl Annotations {
+    pub fn to_json(&self) -> serde_json::Value {
+        self.markers.iter()
+            .map(|marker| serde_json::json!({
+                "time_seconds": marker.time,
+                "x": marker.position.x,
+                "y": marker.position.y,
+                "label": marker.label,
+            }))
+            .collect()
+    }
+
+    /// One `t=60.0s (120, -40): label` line per marker
+    pub fn summary(&self) -> String {
+        self.markers.iter()
+            .map(|marker| format!("t={:.1}s ({:.0}, {:.0}): {}\n", marker.time, marker.position.x, marker.position.y, marker.label))
+            .collect()
+    }
+}
+
+/// Alt+click starts a new marker at the cursor
+pub fn annotation_input_system(
+    mut annotations: ResMut<Annotations>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    input: Res<ButtonInput<KeyCode>>,
+    editor: Res<EditorState>,
+    debug_info: Res<DebugInfo>,
+    performance_tracker: Res<PerformanceTracker>,
+    time: Res<Time>,
+) {
+    if editor.active || !mouse_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+    if !(input.pressed(KeyCode::AltLeft) || input.pressed(KeyCode::AltRight)) {
+        return;
+    }
+    let stage_time = time.elapsed_seconds() - performance_tracker.simulation_start_time;
+    annotations.pending = Some(Annotation {
+        position: debug_info.cursor_world_pos,
+        time: stage_time,
+        label: format!("marker {}", annotations.markers.len() + 1),
+    });
+}
+
+/// Naming popup for the pending marker; Enter or Add keeps it, Escape or Cancel drops it
+pub fn annotation_ui_system(mut contexts: EguiContexts, mut annotations: ResMut<Annotations>) {
+    let annotations = &mut *annotations;
+    let Some(pending) = annotations.pending.as_mut() else { return };
+
+    let mut keep = false;
+    let mut discard = false;
+    egui::Window::new("New marker")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(format!("t={:.1}s at ({:.0}, {:.0})", pending.time, pending.position.x, pending.position.y));
+            let response = ui.text_edit_singleline(&mut pending.label);
+            response.request_focus();
+            if ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                keep = true;
+            }
+            if ui.input(|input| input.key_pressed(egui::Key::Escape)) {
+                discard = true;
+            }
+            ui.checkbox(&mut annotations.show_in_video, "show markers in the video");
+            ui.horizontal(|ui| {
+                keep |= ui.button("Add").clicked();
+                discard |= ui.button("Cancel").clicked();
+            });
+        });
+
+    if keep {
+        if let Some(marker) = annotations.pending.take() {
+            info!("🚩 Marker at t={:.1}s ({:.0}, {:.0}): {}", marker.time, marker.position.x, marker.position.y, marker.label);
+            annotations.markers.push(marker);
+        }
+    } else if discard {
+        annotations.pending = None;
+    }
+}
+
+/// Flags on poles with their labels beside them
+pub fn annotation_render_system(
+    mut gizmos: Gizmos,
+    mut contexts: EguiContexts,
+    annotations: Res<Annotations>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+) {
+    let (r, g, b) = ANNOTATION_RGB;
+    let color = Color::srgb_u8(r, g, b);
+    for marker in annotations.markers.iter().chain(annotations.pending.iter()) {
+        let top = marker.position + Vec2::Y * FLAG_HEIGHT;
+        gizmos.line_2d(marker.position, top, Color::WHITE);
+        gizmos.linestrip_2d([top, top + Vec2::new(14.0, -5.0), top - Vec2::Y * 10.0, top], color);
+    }
+
+    let (Ok((camera, camera_global)), Ok(window)) = (camera_query.get_single(), window_query.get_single()) else { return };
+    let font = egui::FontId::proportional(13.0);
+    let painter = contexts.ctx_mut().layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("annotations")));
+    for marker in &annotations.markers {
+        let anchor = (marker.position + Vec2::new(16.0, FLAG_HEIGHT - 5.0)).extend(0.0);
+        let Some(screen) = camera.world_to_viewport(camera_global, anchor) else { continue };
+        if screen.x < 0.0 || screen.y < 0.0 || screen.x > window.width() || screen.y > window.height() {
+            continue;
+        }
+        painter.text(egui::pos2(screen.x, screen.y) / ui_scale.0, egui::Align2::LEFT_CENTER, &marker.label, font.clone(), egui::Color32::from_rgb(r, g, b));
+    }
+}
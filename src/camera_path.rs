@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+
+/// Side of the square world, centred on the origin
+const WORLD_SIZE: f32 = 1000.0;
+
+/// World rectangle shown by the rendered video: the point in the frame centre and the world
+/// width and height spanned by the frame
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VideoView {
+    pub center: Vec2,
+    pub span: Vec2,
+}
+
+impl VideoView {
+    /// The whole world, stretched to the frame as the video has always shown it
+    pub const WHOLE_WORLD: Self = Self { center: Vec2::ZERO, span: Vec2::splat(WORLD_SIZE) };
+
+    /// Square view around `center` where zoom 1 is the whole world
+    pub fn zoomed(center: Vec2, zoom: f32) -> Self {
+        Self { center, span: Vec2::splat(WORLD_SIZE / zoom) }
+    }
+
+    pub fn from_corners(min: Vec2, max: Vec2) -> Self {
+        Self { center: (min + max) * 0.5, span: (max - min).max(Vec2::ONE) }
+    }
+
+    /// Widen the narrower side so a width x height frame shows the view without stretching
+    pub fn fit_aspect(self, width: u32, height: u32) -> Self {
+        let aspect = width as f32 / height.max(1) as f32;
+        let span = if self.span.x / self.span.y < aspect {
+            Vec2::new(self.span.y * aspect, self.span.y)
+        } else {
+            Vec2::new(self.span.x, self.span.x / aspect)
+        };
+        Self { span, ..self }
+    }
+}
+
+/// Where the rendered video camera should be at `at` seconds: the world point in the frame
+/// centre and the zoom, where 1 shows the whole world and 2 shows half of it across
+#[derive(Clone, Debug)]
+pub struct CameraKeyframe {
+    pub at: f32,
+    pub center: Vec2,
+    pub zoom: f32,
+}
+
+impl CameraKeyframe {
+    /// Parse an events file line `20 camera -150 80 2.5` (x, y, zoom); zoom defaults to 1
+    pub fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.get(1) != Some(&"camera") {
+            return None;
+        }
+        let number = |i: usize| fields.get(i).and_then(|field| field.parse::<f32>().ok());
+        Some(Self {
+            at: number(0)?,
+            center: Vec2::new(number(2)?, number(3)?),
+            zoom: number(4).unwrap_or(1.0).max(1.0),
+        })
+    }
+}
+
+/// Camera keyframes for the rendered video, sorted by time. Empty keeps the whole-world view.
+/// Screen-source videos film the window and ignore the path.
+#[derive(Resource, Default)]
+pub struct CameraPath {
+    pub keyframes: Vec<CameraKeyframe>,
+}
+
+impl CameraPath {
+    pub fn new(mut keyframes: Vec<CameraKeyframe>) -> Self {
+        keyframes.sort_by(|a, b| a.at.total_cmp(&b.at));
+        Self { keyframes }
+    }
+
+    /// The `camera` lines of an events file; the other lines are timed events
+    pub fn load_keyframes(path: &str) -> Result<Vec<CameraKeyframe>, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let mut keyframes = Vec::new();
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.split_whitespace().nth(1) != Some("camera") {
+                continue;
+            }
+            match CameraKeyframe::parse(line) {
+                Some(keyframe) => keyframes.push(keyframe),
+                None => return Err(format!("line {}: can't parse \"{}\"", line_number + 1, line).into()),
+            }
+        }
+        Ok(keyframes)
+    }
+
+    /// Centre and zoom at time `t`, eased between keyframes and held before the first and after the last.
+    /// Zoom moves geometrically so going from 1x to 4x feels as steady as 4x to 16x.
+    pub fn view_at(&self, t: f32) -> Option<VideoView> {
+        let first = self.keyframes.first()?;
+        let next = self.keyframes.iter().position(|keyframe| keyframe.at > t);
+        let (from, to) = match next {
+            Some(0) => return Some(VideoView::zoomed(first.center, first.zoom)),
+            Some(i) => (&self.keyframes[i - 1], &self.keyframes[i]),
+            None => {
+                let last = self.keyframes.last()?;
+                return Some(VideoView::zoomed(last.center, last.zoom));
+            }
+        };
+        let progress = ((t - from.at) / (to.at - from.at).max(0.001)).clamp(0.0, 1.0);
+        let eased = progress * progress * (3.0 - 2.0 * progress);
+        let zoom = from.zoom * (to.zoom / from.zoom).powf(eased);
+        Some(VideoView::zoomed(from.center.lerp(to.center, eased), zoom))
+    }
+}
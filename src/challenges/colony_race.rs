@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use super::*;
+
+/// Challenge 5: Two colonies on opposite sides race for shared central food
+pub struct ColonyRace {
+    pub target: u32, // Deliveries needed to win
+}
+
+impl ColonyRace {
+    fn colony_metrics(&self, tracker: &PerformanceTracker) -> String {
+        tracker.colony_deliveries.iter().enumerate()
+            .map(|(colony, deliveries)| {
+                let ants = tracker.colony_ant_counts.get(colony).copied().unwrap_or(0);
+                format!("   🐜 Colony {}: {} deliveries | {} ants", colony, deliveries, ants)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Challenge for ColonyRace {
+    fn number(&self) -> u32 {
+        5
+    }
+
+    fn name(&self) -> String {
+        "Colony Race".to_string()
+    }
+
+    fn describe(&self) -> String {
+        format!("Challenge 5: Two colonies race to {} deliveries from shared central food", self.target)
+    }
+
+    fn objective(&self) -> String {
+        format!("First colony to {} deliveries wins", self.target)
+    }
+
+    fn configure(&self, config: &mut SimConfig) {
+        // Shared food must stay in the middle when depleted
+        config.food_regrow_in_place = true;
+    }
+
+    fn nest_positions(&self) -> Vec<Vec2> {
+        // Far enough apart that the central cluster is MIN_FOOD_DISTANCE or more from both nests
+        vec![Vec2::new(-400.0, 0.0), Vec2::new(400.0, 0.0)]
+    }
+
+    fn setup(&self, world: &mut ChallengeWorld) {
+        // Shared food cluster midway between the rival nests
+        for i in 0..5 {
+            let angle = i as f32 * std::f32::consts::TAU / 5.0;
+            spawn_food_source(world, Vec2::new(angle.cos(), angle.sin()) * 50.0);
+        }
+    }
+
+    fn success(&self, tracker: &PerformanceTracker) -> Option<Outcome> {
+        // First colony to the delivery target wins the race
+        let winner = tracker.colony_deliveries.iter().position(|&deliveries| deliveries >= self.target)?;
+        Some(Outcome::Success(format!("Colony {} reached {} deliveries first\n{}", winner, self.target, self.colony_metrics(tracker))))
+    }
+
+    fn score(&self, tracker: &PerformanceTracker) -> f32 {
+        // Deliveries of the leading colony, so the score tracks the race
+        tracker.colony_deliveries.iter().copied().max().unwrap_or(0) as f32
+    }
+
+    fn pass_score(&self) -> f32 {
+        self.target as f32
+    }
+
+    fn progress(&self, tracker: &PerformanceTracker) -> Vec<ObjectiveProgress> {
+        (0..self.nest_positions().len())
+            .map(|colony| ObjectiveProgress {
+                label: format!("colony {} food delivered", colony),
+                current: tracker.colony_deliveries.get(colony).copied().unwrap_or(0) as f32,
+                target: self.target as f32,
+            })
+            .collect()
+    }
+
+    fn summary(&self, tracker: &PerformanceTracker) -> Option<String> {
+        Some(self.colony_metrics(tracker))
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use crate::maze::Maze;
+use crate::config::MIN_FOOD_DISTANCE;
+use super::*;
+
+/// Challenge 3: Maze between nest and food, food in the dead end farthest from the nest
+pub struct MazeRun;
+
+impl Challenge for MazeRun {
+    fn number(&self) -> u32 {
+        3
+    }
+
+    fn name(&self) -> String {
+        "Maze Run".to_string()
+    }
+
+    fn describe(&self) -> String {
+        "Challenge 3: Procedurally generated maze with food at the far end".to_string()
+    }
+
+    fn objective(&self) -> String {
+        "Find the food at the far end of the maze and hold the route".to_string()
+    }
+
+    fn configure(&self, config: &mut SimConfig) {
+        // The single maze food source must stay at the far end
+        config.food_regrow_in_place = true;
+    }
+
+    fn pass_score(&self) -> f32 {
+        // One long winding route, so far fewer round trips fit in a run
+        15.0
+    }
+
+    fn camera_path(&self) -> Vec<CameraKeyframe> {
+        // Follow the first scouts into the corridors around the nest, then pull back to the whole maze
+        vec![
+            CameraKeyframe { at: 0.0, center: Vec2::ZERO, zoom: 3.0 },
+            CameraKeyframe { at: 15.0, center: Vec2::ZERO, zoom: 3.0 },
+            CameraKeyframe { at: 40.0, center: Vec2::ZERO, zoom: 1.0 },
+        ]
+    }
+
+    fn setup(&self, world: &mut ChallengeWorld) {
+        let config = world.config;
+        let maze = Maze::generate(960.0, config.maze_corridor_width, config.maze_wall_thickness, config.maze_seed);
+        let wall_radius = config.maze_wall_thickness * 0.5;
+        let wall_segments = maze.wall_segments();
+        
+        for (start, end) in &wall_segments {
+            let length = start.distance(*end);
+            let midpoint = (*start + *end) * 0.5;
+            let size = if (end.x - start.x).abs() > (end.y - start.y).abs() {
+                Vec2::new(length + config.maze_wall_thickness, config.maze_wall_thickness)
+            } else {
+                Vec2::new(config.maze_wall_thickness, length + config.maze_wall_thickness)
+            };
+            
+            world.commands.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: world.color_config.rock,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform: Transform::from_xyz(midpoint.x, midpoint.y, 3.0),
+                ..default()
+            });
+            
+            // Collide as a chain of touching rocks so existing rock avoidance handles walls
+            let num_rocks = (length / config.maze_wall_thickness).ceil() as i32;
+            for i in 0..=num_rocks {
+                let rock_pos = start.lerp(*end, i as f32 / num_rocks as f32);
+                world.commands.spawn((
+                    SpatialBundle::from_transform(Transform::from_xyz(rock_pos.x, rock_pos.y, 3.0)),
+                    Rock { radius: wall_radius, blocked_count: 0 },
+                ));
+            }
+        }
+        
+        let Some((food_col, food_row)) = maze.farthest_cell(MIN_FOOD_DISTANCE) else {
+            error!("❌ Challenge 3: no maze cell is {:.0} units from the nest with {:.0}-unit corridors; no food placed",
+                MIN_FOOD_DISTANCE, config.maze_corridor_width);
+            return;
+        };
+        let food_pos = maze.cell_center(food_col, food_row);
+        spawn_food_source(world, food_pos);
+        
+        info!("🌀 Challenge 3: {}x{} maze (seed {}) with {} walls, food at ({:.0}, {:.0})",
+            maze.size, maze.size, config.maze_seed, wall_segments.len(), food_pos.x, food_pos.y);
+    }
+}
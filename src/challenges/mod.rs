@@ -0,0 +1,299 @@
+use bevy::prelude::*;
+use crate::components::*;
+use crate::config::*;
+use crate::colors::*;
+use crate::scoring::*;
+use crate::timed_events::TimedEvent;
+use crate::camera_path::CameraKeyframe;
+use crate::mapgen::GeneratedMap;
+use crate::worldmap::WorldMap;
+use bevy::ecs::system::{EntityCommands, SystemParam};
+
+mod sandbox;
+mod scattered_food;
+mod rock_obstacles;
+mod maze_run;
+mod predator_gauntlet;
+mod colony_race;
+mod moving_target;
+mod prey_hunt;
+
+pub use sandbox::Sandbox;
+pub use scattered_food::ScatteredFood;
+pub use rock_obstacles::RockObstacles;
+pub use maze_run::MazeRun;
+pub use predator_gauntlet::PredatorGauntlet;
+pub use colony_race::ColonyRace;
+pub use moving_target::MovingTarget;
+pub use prey_hunt::PreyHunt;
+
+/// Everything a challenge needs to build its world at startup
+pub struct ChallengeWorld<'a, 'w, 's> {
+    pub commands: &'a mut Commands<'w, 's>,
+    pub config: &'a SimConfig,
+    pub color_config: &'a ColorConfig,
+    pub prey_food: bool,
+}
+
+/// One progress bar in the objective HUD
+pub struct ObjectiveProgress {
+    pub label: String,
+    pub current: f32,
+    pub target: f32,
+}
+
+/// How a challenge ended before the time limit
+pub enum Outcome {
+    Success(String),
+    Failure(String),
+}
+
+/// A selectable scenario. New challenges are added by implementing this trait
+/// in their own module and listing them in `challenge_registry`.
+pub trait Challenge: Send + Sync {
+    /// Number used to select the challenge with `--challenge <n>`
+    fn number(&self) -> u32;
+
+    /// Short title shown in the objective HUD
+    fn name(&self) -> String;
+
+    /// One-line description printed at startup
+    fn describe(&self) -> String;
+
+    /// What the colony has to do, shown under the title in the objective HUD
+    fn objective(&self) -> String {
+        format!("Score {:.0} points before time runs out", self.pass_score())
+    }
+
+    /// Adjust simulation parameters before the app starts
+    fn configure(&self, _config: &mut SimConfig) {}
+
+    /// Nest positions, one colony per nest
+    fn nest_positions(&self) -> Vec<Vec2> {
+        vec![Vec2::ZERO]
+    }
+
+    /// Spawn food, obstacles and any other challenge entities
+    fn setup(&self, world: &mut ChallengeWorld);
+
+    /// Free-play challenges never end on their own: no time limit, verdict or video
+    fn free_play(&self) -> bool {
+        false
+    }
+
+    /// Scripted mid-run disruptions, fired by `timed_event_system`
+    fn timed_events(&self) -> Vec<TimedEvent> {
+        Vec::new()
+    }
+
+    /// Keyframes for the rendered video camera; empty films the whole world top-down
+    fn camera_path(&self) -> Vec<CameraKeyframe> {
+        Vec::new()
+    }
+
+    /// Checked every frame; returning an outcome ends the run early
+    fn success(&self, _tracker: &PerformanceTracker) -> Option<Outcome> {
+        None
+    }
+
+    /// Current score, by default deliveries weighted by time minus lost ants
+    fn score(&self, tracker: &PerformanceTracker) -> f32 {
+        time_weighted_score(tracker)
+    }
+
+    /// Score needed to count as a success when the time limit is reached
+    fn pass_score(&self) -> f32 {
+        DEFAULT_PASS_SCORE
+    }
+
+    /// Progress bars for the objective HUD (time remaining is always added)
+    fn progress(&self, tracker: &PerformanceTracker) -> Vec<ObjectiveProgress> {
+        vec![ObjectiveProgress {
+            label: "score".to_string(),
+            current: self.score(tracker),
+            target: self.pass_score(),
+        }]
+    }
+
+    /// Extra score lines printed when the run reaches its time limit
+    fn summary(&self, _tracker: &PerformanceTracker) -> Option<String> {
+        None
+    }
+}
+
+/// The challenge selected for this run
+#[derive(Resource)]
+pub struct ActiveChallenge(pub Box<dyn Challenge>);
+
+/// All known challenges in selection order
+pub fn challenge_registry(config: &SimConfig) -> Vec<Box<dyn Challenge>> {
+    vec![
+        Box::new(Sandbox),
+        Box::new(ScatteredFood),
+        Box::new(RockObstacles),
+        Box::new(MazeRun),
+        Box::new(PredatorGauntlet),
+        Box::new(ColonyRace { target: config.colony_race_target }),
+        Box::new(MovingTarget { interval: config.moving_target_interval, seed: config.moving_target_seed }),
+        Box::new(PreyHunt),
+    ]
+}
+
+/// Look up a challenge by number, falling back to challenge 1
+pub fn find_challenge(number: u32, config: &SimConfig) -> Box<dyn Challenge> {
+    let mut registry = challenge_registry(config);
+    match registry.iter().position(|challenge| challenge.number() == number) {
+        Some(index) => registry.swap_remove(index),
+        None => {
+            error!("❌ Unknown challenge {}, falling back to challenge 1", number);
+            let index = registry.iter().position(|challenge| challenge.number() == 1).unwrap_or(0);
+            registry.swap_remove(index)
+        }
+    }
+}
+
+/// Everything the starting layout is built from: the active challenge plus any loaded or generated map
+#[derive(SystemParam)]
+pub struct ChallengeLayout<'w> {
+    config: Res<'w, SimConfig>,
+    color_config: Res<'w, ColorConfig>,
+    challenge_config: Res<'w, ChallengeConfig>,
+    challenge: Res<'w, ActiveChallenge>,
+    generated_map: Option<Res<'w, GeneratedMap>>,
+    world_map: Option<Res<'w, WorldMap>>,
+}
+
+impl ChallengeLayout<'_> {
+    /// Spawn the nests, food, obstacles and other challenge entities, at startup and on restart
+    pub fn spawn(&self, commands: &mut Commands) {
+        let mut world = ChallengeWorld {
+            commands,
+            config: &self.config,
+            color_config: &self.color_config,
+            prey_food: self.challenge_config.prey_food,
+        };
+        
+        // Spawn one nest per colony (a loaded map's nests replace the challenge's)
+        let nest_positions = match self.world_map {
+            Some(ref map) if !map.nests.is_empty() => map.nests.clone(),
+            _ => self.challenge.0.nest_positions(),
+        };
+        for (colony, nest_pos) in nest_positions.iter().enumerate() {
+            spawn_nest(&mut world, *nest_pos, colony);
+        }
+        
+        if let Some(ref map) = self.world_map {
+            // Hand-drawn layout replaces the challenge's own food and obstacles
+            for (position, radius) in &map.rocks {
+                spawn_rock(&mut world, *position, *radius);
+            }
+            for position in &map.food {
+                spawn_food_source(&mut world, *position);
+            }
+            for position in map.water_cells() {
+                spawn_water_tile(&mut world, position);
+            }
+        } else {
+            self.challenge.0.setup(&mut world);
+        }
+        
+        // Procedural obstacles and food clusters layered on top of the challenge layout
+        if let Some(ref map) = self.generated_map {
+            for (position, radius) in &map.rocks {
+                spawn_rock(&mut world, *position, *radius);
+            }
+            for position in &map.food {
+                spawn_food_source(&mut world, *position);
+            }
+        }
+    }
+}
+
+/// A spot between MIN_FOOD_DISTANCE and 450 units from `nest` in a random direction, redrawn until it
+/// lies inside the world (the last draw is kept if none does)
+pub fn random_food_site(rng: &mut impl rand::Rng, nest: Vec2) -> Vec2 {
+    let mut site = nest;
+    for _ in 0..16 {
+        let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+        let distance = MIN_FOOD_DISTANCE + rng.gen::<f32>() * (450.0 - MIN_FOOD_DISTANCE);
+        site = nest + Vec2::from_angle(angle) * distance;
+        if site.x.abs() <= 470.0 && site.y.abs() <= 470.0 {
+            break;
+        }
+    }
+    site
+}
+
+/// Spawn a standard 30x30 food source
+pub fn spawn_food_source<'a>(world: &'a mut ChallengeWorld, position: Vec2) -> EntityCommands<'a> {
+    world.commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: world.color_config.food_source,
+                custom_size: Some(Vec2::new(30.0, 30.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(position.x, position.y, 2.0),
+            ..default()
+        },
+        FoodSource { amount: 100.0, max_amount: 100.0, quality: 1.0 },
+    ))
+}
+
+/// Spawn a circular rock obstacle; rock_mesh_system gives it a circle mesh child to draw it
+pub fn spawn_rock(world: &mut ChallengeWorld, position: Vec2, radius: f32) -> Entity {
+    world.commands.spawn((
+        SpatialBundle::from_transform(Transform::from_xyz(position.x, position.y, 3.0)),
+        Rock { radius, blocked_count: 0 },
+        RoundRock,
+    )).id()
+}
+
+/// Spawn a colony nest
+pub fn spawn_nest(world: &mut ChallengeWorld, position: Vec2, colony: usize) -> Entity {
+    world.commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: world.color_config.colony_tint(world.color_config.nest, colony),
+                custom_size: Some(Vec2::new(80.0, 80.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(position.x, position.y, 5.0),
+            ..default()
+        },
+        Nest { capacity: 10000.0, colony },
+    )).id()
+}
+
+/// Spawn a predator patrolling back and forth, starting `progress` (0-1) of the way along its route
+pub fn spawn_predator(world: &mut ChallengeWorld, patrol_start: Vec2, patrol_end: Vec2, progress: f32) -> Entity {
+    let start_pos = patrol_start.lerp(patrol_end, progress);
+    world.commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: world.color_config.predator,
+                custom_size: Some(Vec2::new(24.0, 24.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(start_pos.x, start_pos.y, 4.0),
+            ..default()
+        },
+        Predator { patrol_start, patrol_end, progress, direction: 1.0 },
+    )).id()
+}
+
+/// Spawn a 20x20 water tile sprite (the impassable cells live in `WorldMap`)
+pub fn spawn_water_tile(world: &mut ChallengeWorld, position: Vec2) -> Entity {
+    world.commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgb(0.1, 0.3, 0.8),
+                custom_size: Some(Vec2::new(20.0, 20.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(position.x, position.y, 1.0),
+            ..default()
+        },
+        WaterTile,
+    )).id()
+}
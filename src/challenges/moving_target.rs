@@ -0,0 +1,82 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use crate::scoring::TIME_LIMIT;
+use crate::timed_events::{ScriptedAction, TimedEvent};
+use super::*;
+
+/// Challenge 6: All food teleports at a fixed interval, so trails to the old sites go stale
+pub struct MovingTarget {
+    pub interval: f32, // Seconds between relocations
+    pub seed: u64,     // Starting sites; relocations draw from SimConfig::moving_target_seed too
+}
+
+impl MovingTarget {
+    /// Mean seconds from each relocation to the first delivery from the new sites
+    fn mean_recovery_time(&self, tracker: &PerformanceTracker) -> Option<f32> {
+        let recoveries: Vec<f32> = tracker.food_relocation_times.iter()
+            .filter_map(|&moved| tracker.delivery_timestamps.iter().find(|&&t| t > moved).map(|t| t - moved))
+            .collect();
+        if recoveries.is_empty() {
+            None
+        } else {
+            Some(recoveries.iter().sum::<f32>() / recoveries.len() as f32)
+        }
+    }
+}
+
+impl Challenge for MovingTarget {
+    fn number(&self) -> u32 {
+        6
+    }
+
+    fn name(&self) -> String {
+        "Moving Target".to_string()
+    }
+
+    fn describe(&self) -> String {
+        format!("Challenge 6: All food relocates every {:.0}s - measures how fast stale trails are abandoned", self.interval)
+    }
+
+    fn objective(&self) -> String {
+        format!("Food moves every {:.0}s: drop old trails and find the new sites fast", self.interval)
+    }
+
+    fn configure(&self, config: &mut SimConfig) {
+        // Food only moves on the relocation schedule
+        config.food_regrow_in_place = true;
+    }
+
+    fn pass_score(&self) -> f32 {
+        25.0
+    }
+
+    fn setup(&self, world: &mut ChallengeWorld) {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let nest = self.nest_positions()[0];
+        for _ in 0..4 {
+            let site = random_food_site(&mut rng, nest);
+            spawn_food_source(world, site);
+        }
+    }
+
+    fn timed_events(&self) -> Vec<TimedEvent> {
+        let interval = self.interval.max(1.0);
+        let relocations = (TIME_LIMIT / interval).ceil() as usize;
+        (1..relocations)
+            .map(|k| TimedEvent { at: k as f32 * interval, action: ScriptedAction::RelocateFood })
+            .collect()
+    }
+
+    fn summary(&self, tracker: &PerformanceTracker) -> Option<String> {
+        let recovery = self.mean_recovery_time(tracker)
+            .map_or("never recovered".to_string(), |t| format!("{:.1}s", t));
+        let clear_times = &tracker.stale_trail_clear_times;
+        let stale = if clear_times.is_empty() {
+            "never faded".to_string()
+        } else {
+            format!("{:.1}s", clear_times.iter().sum::<f32>() / clear_times.len() as f32)
+        };
+        Some(format!("🎯 Moving target: {} relocations | first delivery after a move: {} | stale trail half-life: {}",
+            tracker.food_relocation_times.len(), recovery, stale))
+    }
+}
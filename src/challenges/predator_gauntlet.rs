@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use super::*;
+
+/// Challenge 4: Predators patrol across the corridor between the nest and a food cluster
+pub struct PredatorGauntlet;
+
+impl Challenge for PredatorGauntlet {
+    fn number(&self) -> u32 {
+        4
+    }
+
+    fn name(&self) -> String {
+        "Predator Gauntlet".to_string()
+    }
+
+    fn describe(&self) -> String {
+        "Challenge 4: Predator gauntlet, scored by deliveries with a penalty per ant lost".to_string()
+    }
+
+    fn objective(&self) -> String {
+        format!("Carry food past the patrols - each ant lost costs {:.0} points", LOST_ANT_PENALTY)
+    }
+
+    fn configure(&self, config: &mut SimConfig) {
+        // Fixed food placement must stay put when depleted
+        config.food_regrow_in_place = true;
+    }
+
+    fn setup(&self, world: &mut ChallengeWorld) {
+        let food_center = Vec2::new(380.0, 0.0);
+        for i in 0..3 {
+            spawn_food_source(world, food_center + Vec2::new(0.0, (i as f32 - 1.0) * 60.0));
+        }
+        
+        // Evenly spaced patrol lines across the corridor, staggered so gaps open at different times
+        let predator_count = world.config.predator_count;
+        for i in 0..predator_count {
+            let t = (i as f32 + 1.0) / (predator_count as f32 + 1.0);
+            let x = 80.0 + t * (food_center.x - 120.0);
+            let progress = i as f32 / predator_count as f32;
+            let patrol_start = Vec2::new(x, -180.0);
+            let patrol_end = Vec2::new(x, 180.0);
+            spawn_predator(world, patrol_start, patrol_end, progress);
+        }
+        
+        info!("🕷️ Challenge 4: {} predators patrol between the nest and food at ({:.0}, {:.0})",
+            predator_count, food_center.x, food_center.y);
+    }
+
+    fn success(&self, tracker: &PerformanceTracker) -> Option<Outcome> {
+        // Every ant eaten - nothing left to score
+        if tracker.ants_lost > 0 && tracker.colony_ant_counts.iter().sum::<u32>() == 0 {
+            return Some(Outcome::Failure(format!("All ants lost to predators after {} deliveries", tracker.successful_deliveries)));
+        }
+        None
+    }
+
+    fn pass_score(&self) -> f32 {
+        25.0
+    }
+
+    fn summary(&self, tracker: &PerformanceTracker) -> Option<String> {
+        Some(format!("🕷️ Predator gauntlet: {} deliveries, {} ants lost",
+            tracker.successful_deliveries, tracker.ants_lost))
+    }
+}
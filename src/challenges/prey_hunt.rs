@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use super::*;
+
+/// Challenge 7: Every food source is prey that wanders, so trails to it go stale as it moves
+pub struct PreyHunt;
+
+impl PreyHunt {
+    /// Longest stretch without a delivery, counted from the start of the run
+    fn longest_drought(tracker: &PerformanceTracker) -> f32 {
+        let mut previous = 0.0;
+        let mut longest: f32 = 0.0;
+        for &t in &tracker.delivery_timestamps {
+            longest = longest.max(t - previous);
+            previous = t;
+        }
+        longest
+    }
+}
+
+impl Challenge for PreyHunt {
+    fn number(&self) -> u32 {
+        7
+    }
+
+    fn name(&self) -> String {
+        "Prey Hunt".to_string()
+    }
+
+    fn describe(&self) -> String {
+        "Challenge 7: Every food source is prey wandering at least 333 units from the nest, one point per delivery".to_string()
+    }
+
+    fn objective(&self) -> String {
+        format!("Track wandering prey and make {:.0} deliveries as the trails go stale", self.pass_score())
+    }
+
+    fn configure(&self, config: &mut SimConfig) {
+        // Eaten prey comes back as a new animal somewhere else, not in place
+        config.food_regrow_in_place = false;
+    }
+
+    fn setup(&self, world: &mut ChallengeWorld) {
+        let prey_speed = world.config.prey_speed;
+        for _ in 0..world.config.food_sources {
+            let site = random_food_site(&mut rand::thread_rng(), Vec2::ZERO);
+            spawn_food_source(world, site)
+                .insert(Prey { heading: rand::random::<f32>() * std::f32::consts::TAU, speed: prey_speed });
+        }
+        info!("🦗 Challenge 7: {} prey wandering at {:.0} units/s", world.config.food_sources, prey_speed);
+    }
+
+    fn score(&self, tracker: &PerformanceTracker) -> f32 {
+        // Moving food already punishes slow trail building, so deliveries count the same all run
+        tracker.successful_deliveries as f32
+    }
+
+    fn pass_score(&self) -> f32 {
+        30.0
+    }
+
+    fn summary(&self, tracker: &PerformanceTracker) -> Option<String> {
+        Some(format!("🦗 Prey hunt: {} deliveries, longest wait between deliveries {:.1}s",
+            tracker.successful_deliveries, Self::longest_drought(tracker)))
+    }
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use super::*;
+use super::scattered_food::spawn_distant_food;
+
+/// Challenge 2: Challenge 1 food with a rock halfway along each path
+pub struct RockObstacles;
+
+impl Challenge for RockObstacles {
+    fn number(&self) -> u32 {
+        2
+    }
+
+    fn name(&self) -> String {
+        "Rock Obstacles".to_string()
+    }
+
+    fn describe(&self) -> String {
+        "Challenge 2: Distant food with a rock blocking the straight path to each source".to_string()
+    }
+
+    fn objective(&self) -> String {
+        "Route trails around the rocks blocking each food source".to_string()
+    }
+
+    fn setup(&self, world: &mut ChallengeWorld) {
+        let food_positions = spawn_distant_food(world);
+        
+        // Add rocks halfway between nest and food sources
+        let nest_position = Vec2::new(0.0, 0.0);
+        let rock_radius = 15.0 * 1.5; // 50% wider than food sources (30.0 * 1.5 / 2)
+        
+        for food_pos in &food_positions {
+            // Place rock halfway between nest and food source
+            let midpoint = (nest_position + *food_pos) * 0.5;
+            
+            spawn_rock(world, midpoint, rock_radius);
+        }
+        
+        info!("🪨 Challenge 2: Spawned {} rocks with radius {:.1} as obstacles", food_positions.len(), rock_radius);
+    }
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use super::*;
+
+/// Challenge 0: Free play with no time limit, built up interactively in the editor
+pub struct Sandbox;
+
+impl Challenge for Sandbox {
+    fn number(&self) -> u32 {
+        0
+    }
+
+    fn name(&self) -> String {
+        "Sandbox".to_string()
+    }
+
+    fn describe(&self) -> String {
+        "Challenge 0: Sandbox - press E to place food, rocks, water, nests, ants and predators".to_string()
+    }
+
+    fn objective(&self) -> String {
+        "Free play: E edit (pauses) | Tab tool | Left click place | Right click remove".to_string()
+    }
+
+    fn configure(&self, config: &mut SimConfig) {
+        // Placed food stays where it was put
+        config.food_regrow_in_place = true;
+    }
+
+    fn setup(&self, world: &mut ChallengeWorld) {
+        // A single source just past MIN_FOOD_DISTANCE so the colony has something to do right away
+        spawn_food_source(world, Vec2::new(350.0, 0.0));
+    }
+
+    fn free_play(&self) -> bool {
+        true
+    }
+
+    fn progress(&self, tracker: &PerformanceTracker) -> Vec<ObjectiveProgress> {
+        vec![ObjectiveProgress {
+            label: "food delivered".to_string(),
+            current: tracker.successful_deliveries as f32,
+            target: tracker.successful_deliveries.max(1) as f32,
+        }]
+    }
+}
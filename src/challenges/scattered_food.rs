@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use super::*;
+
+/// Challenge 1: Food scattered far from the nest
+pub struct ScatteredFood;
+
+impl Challenge for ScatteredFood {
+    fn number(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> String {
+        "Scattered Food".to_string()
+    }
+
+    fn describe(&self) -> String {
+        "Challenge 1: Food sources scattered 333-500 units from the nest".to_string()
+    }
+
+    fn objective(&self) -> String {
+        "Find food scattered far from the nest and keep deliveries flowing".to_string()
+    }
+
+    fn setup(&self, world: &mut ChallengeWorld) {
+        spawn_distant_food(world);
+    }
+}
+
+/// CHALLENGE MODE: All food sources FAR from nest (minimum 1/3 world size away)
+pub fn spawn_distant_food(world: &mut ChallengeWorld) -> Vec<Vec2> {
+    let mut food_positions = Vec::new();
+    for _i in 0..world.config.food_sources {
+        let angle = rand::random::<f32>() * std::f32::consts::TAU;
+        // Minimum distance = 1/3 world size = 333 units from nest
+        // Maximum distance = 1/2 world size = 500 units from nest  
+        let distance = 333.0 + rand::random::<f32>() * 167.0; // 333-500 units away
+        let x = angle.cos() * distance;
+        let y = angle.sin() * distance;
+        
+        food_positions.push(Vec2::new(x, y));
+        
+        let prey_food = world.prey_food;
+        let prey_speed = world.config.prey_speed;
+        let mut food_entity = spawn_food_source(world, Vec2::new(x, y));
+        
+        if prey_food {
+            food_entity.insert(Prey { heading: rand::random::<f32>() * std::f32::consts::TAU, speed: prey_speed });
+        }
+    }
+    food_positions
+}
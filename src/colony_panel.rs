@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy_egui::{egui, EguiContexts};
+use crate::components::*;
+use crate::config::SimConfig;
+
+/// Colony status window, toggled with B
+#[derive(Resource, Default)]
+pub struct ColonyPanel {
+    pub visible: bool,
+}
+
+pub fn colony_panel_toggle_system(input: Res<ButtonInput<KeyCode>>, mut panel: ResMut<ColonyPanel>) {
+    if input.just_pressed(KeyCode::KeyB) {
+        panel.visible = !panel.visible;
+    }
+}
+
+/// Living ants by what they are doing; carriers are counted as carrying whatever their behavior state
+#[derive(Default)]
+struct StateCounts {
+    exploring: usize,
+    sensing: usize,
+    following: usize,
+    tracking: usize,
+    carrying: usize,
+    scouts: usize,
+}
+
+/// Everything the panel reports on
+#[derive(SystemParam)]
+pub struct ColonyStatus<'w, 's> {
+    performance_tracker: Res<'w, PerformanceTracker>,
+    colony: Res<'w, ColonyState>,
+    spawner: Res<'w, AntSpawner>,
+    nest_query: Query<'w, 's, &'static Nest>,
+    ant_query: Query<'w, 's, &'static AntCore>,
+}
+
+/// There is no separate nest store or brood: stored food is what has been delivered, births are the
+/// spawn schedule and deaths are predator kills
+pub fn colony_panel_system(
+    mut contexts: EguiContexts,
+    mut panel: ResMut<ColonyPanel>,
+    status: ColonyStatus,
+    config: Res<SimConfig>,
+    time: Res<Time>,
+) {
+    if !panel.visible {
+        return;
+    }
+    let ColonyStatus { performance_tracker, colony, spawner, nest_query, ant_query } = status;
+
+    let mut counts = StateCounts::default();
+    for ant in ant_query.iter() {
+        if ant.carrying_food {
+            counts.carrying += 1;
+        } else {
+            match ant.behavior_state {
+                AntBehaviorState::Exploring => counts.exploring += 1,
+                AntBehaviorState::Sensing => counts.sensing += 1,
+                AntBehaviorState::Following => counts.following += 1,
+                AntBehaviorState::Tracking => counts.tracking += 1,
+            }
+        }
+        if ant.role == AntRole::Scout {
+            counts.scouts += 1;
+        }
+    }
+    let living = ant_query.iter().count();
+
+    // Same trail strength colony_role_system uses to set the scout ratio
+    let trail_strength = (colony.trail_signal / config.trail_signal_saturation).clamp(0.0, 1.0);
+    let mode = if trail_strength >= 0.5 {
+        "recruiting onto trails"
+    } else if trail_strength > 0.1 {
+        "mixed"
+    } else {
+        "searching"
+    };
+
+    egui::Window::new("Colony (B to hide)")
+        .open(&mut panel.visible)
+        .default_pos(egui::pos2(400.0, 320.0))
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(egui::RichText::new("Nest").strong());
+            ui.label(format!("Stored food: {:.1} ({} deliveries)", performance_tracker.total_food_collected, performance_tracker.successful_deliveries));
+            if performance_tracker.colony_deliveries.len() > 1 {
+                for (colony_index, deliveries) in performance_tracker.colony_deliveries.iter().enumerate() {
+                    ui.label(format!("  colony {}: {} deliveries", colony_index, deliveries));
+                }
+            }
+            let capacity: f32 = nest_query.iter().map(|nest| nest.capacity).sum();
+            ui.label(format!("Nest capacity: {:.0}", capacity));
+
+            ui.separator();
+            ui.label(egui::RichText::new(format!("Ants: {} living", living)).strong());
+            egui::Grid::new("colony_states").num_columns(2).show(ui, |ui| {
+                for (label, count) in [
+                    ("exploring", counts.exploring),
+                    ("sensing", counts.sensing),
+                    ("following", counts.following),
+                    ("tracking", counts.tracking),
+                    ("carrying food", counts.carrying),
+                ] {
+                    ui.label(label);
+                    ui.label(count.to_string());
+                    ui.end_row();
+                }
+            });
+            ui.label(format!("Births: {} of {}", spawner.spawned, config.initial_ants));
+            if spawner.spawned < config.initial_ants {
+                ui.label(format!("  next batch in {:.1}s", (spawner.next_spawn_time - time.elapsed_seconds()).max(0.0)));
+            }
+            ui.label(format!("Deaths: {}", performance_tracker.ants_lost));
+
+            ui.separator();
+            ui.label(egui::RichText::new(format!("Mode: {}", mode)).strong());
+            ui.label(format!("Trail signal: {:.2} ({:.0}% of saturation)", colony.trail_signal, trail_strength * 100.0));
+            ui.label(format!("Scouts: {} (target {:.0}%)", counts.scouts, colony.scout_ratio * 100.0));
+        });
+}
@@ -14,6 +14,8 @@ pub struct ColorConfig {
     pub ant_exploring: Color,
     pub ant_carrying_food: Color,
     pub ant_collecting: Color,
+    pub predator: Color,
+    pub rock: Color,
     
     // UI colors
     pub text: Color,
@@ -34,6 +36,8 @@ impl Default for ColorConfig {
             ant_exploring: Color::srgb(1.0, 0.0, 0.0),  // Red
             ant_carrying_food: Color::srgb(1.0, 0.5, 0.0), // Orange
             ant_collecting: Color::srgb(1.0, 1.0, 0.0),    // Yellow
+            predator: Color::srgb(0.6, 0.1, 0.1),          // Dark red
+            rock: Color::srgb(0.35, 0.3, 0.25),            // Brown-gray
             
             // UI colors
             text: Color::WHITE,
@@ -43,6 +47,15 @@ impl Default for ColorConfig {
 }
 
 impl ColorConfig {
+    /// Darken ant colors for the second colony so rival ants stay distinguishable
+    pub fn colony_tint(&self, color: Color, colony: usize) -> Color {
+        if colony == 0 {
+            return color;
+        }
+        let srgba = color.to_srgba();
+        Color::srgba(srgba.red * 0.5, srgba.green * 0.5, srgba.blue * 0.5 + 0.4, srgba.alpha)
+    }
+    
     /// Get pheromone color as RGB bytes for video rendering
     pub fn food_pheromone_rgb(&self) -> (u8, u8, u8) {
         let [r, g, b, _] = self.food_pheromone.to_srgba().to_u8_array();
@@ -83,4 +96,14 @@ impl ColorConfig {
         let [r, g, b, _] = self.ant_collecting.to_srgba().to_u8_array();
         (r, g, b)
     }
-}
\ No newline at end of file
+    
+    pub fn predator_rgb(&self) -> (u8, u8, u8) {
+        let [r, g, b, _] = self.predator.to_srgba().to_u8_array();
+        (r, g, b)
+    }
+    
+    pub fn rock_rgb(&self) -> (u8, u8, u8) {
+        let [r, g, b, _] = self.rock.to_srgba().to_u8_array();
+        (r, g, b)
+    }
+}
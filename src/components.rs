@@ -1,10 +1,14 @@
 use bevy::prelude::*;
+use crate::video::{FramePool, FrameStream};
+use crate::contact_sheet::RunSamples;
+use crate::sonification::Soundtrack;
 
 #[derive(Resource, Default)]
 pub struct DebugInfo {
     pub cursor_world_pos: Vec2,
     pub hovered_entity: Option<Entity>,
     pub selected_entity: Option<Entity>,
+    pub follow_selected: bool, // Camera tracks the selected ant (F)
     pub pheromone_info: String,
     pub entity_info: String,
 }
@@ -26,25 +30,162 @@ pub struct PerformanceTracker {
     pub return_times: Vec<f32>, // Track individual return times
     pub average_time_since_goal: f32, // NEW METRIC: Average time since each ant reached its goal
     pub time_since_goal_samples: Vec<f32>, // Individual ant time-since-goal samples for this frame
+    pub food_depletion_times: Vec<f32>, // When each food source ran out (trails to it go stale)
+    pub ants_lost: u32, // Ants killed by predators
+    pub colony_deliveries: Vec<u32>, // Deliveries per colony (multi-colony challenges)
+    pub colony_ant_counts: Vec<u32>, // Living ants per colony, refreshed each frame
+    pub delivery_timestamps: Vec<f32>, // Elapsed time of each delivery, for time-weighted scoring
+    pub food_relocation_times: Vec<f32>, // Run time of each scripted food relocation
+    pub stale_trail_clear_times: Vec<f32>, // Seconds for the trail at old food sites to fade below half strength
 }
 
 #[derive(Resource)]
 pub struct VideoRecorder {
-    pub frames: Vec<Vec<u8>>, // Store RGBA frames
+    pub stream: FrameStream, // The main video
+    pub pheromone_stream: Option<FrameStream>, // Pheromone-only companion video (--pheromone-video)
+    pub run_samples: RunSamples, // Frames kept for the thumbnail and contact sheet
+    pub soundtrack: Option<Soundtrack>, // Audio cues muxed into the video (--video-audio)
     pub is_recording: bool,
     pub frame_width: u32,
     pub frame_height: u32,
-    pub max_frames: usize, // 30 seconds worth at ~60fps = 1800 frames
+    pub max_frames: usize, // Capture stops here; 3600 frames is two minutes at 30 fps
     pub test_number: u32,
     pub changes_description: String,
     pub frame_timer: f32, // Timer for frame capture interval
-    pub frame_interval: f32, // How often to capture frames (in seconds)
+    pub frame_interval: f32, // Simulation seconds between captures, speedup / fps
+    pub fps: u32, // Playback rate of the encoded video
+    pub speedup: f32, // Simulation seconds shown per second of video
+    pub target_duration: Option<f32>, // Video length to fit the run into; None keeps the fixed speedup
+    pub recording_started: f32, // Elapsed seconds when recording began, for the effective speedup
+    pub bitrate: String, // ffmpeg bitrate for the MP4, e.g. "4M"
+    pub format: VideoFormat,
+    pub source: VideoSource,
+    pub hardware_encoding: bool, // Try GPU H.264 encoders before libx264 (--hw-encode)
+    pub font: Option<ab_glyph::FontArc>, // Overlay text font, filled in by setup_video_camera
+    pub container_tags: Vec<(&'static str, String)>, // Container metadata (title, comment) for the run being recorded
+    pub crop: VideoCrop,
+    pub auto_crop: Option<(Vec2, Vec2)>, // Smoothed activity box for VideoCrop::Auto, corners in world units
+    pub memory_budget: usize, // Bytes of frames held at once across the replay, highlight clips and encoder queues (--video-memory)
+    pub frame_pool: FramePool, // Spent frame buffers, reused for the next captures
+}
+
+/// Shortest gap between captured frames, one simulation tick at 60 Hz
+pub const MIN_CAPTURE_INTERVAL: f32 = 1.0 / 60.0;
+/// Frame memory budget in MiB unless `--video-memory` says otherwise
+pub const DEFAULT_VIDEO_MEMORY_MB: usize = 768;
+
+impl VideoRecorder {
+    /// Set the playback rate and speedup together, since they fix the capture interval
+    pub fn set_cadence(&mut self, fps: u32, speedup: f32) {
+        self.fps = fps.max(1);
+        self.speedup = speedup.max(0.01);
+        self.frame_interval = self.speedup / self.fps as f32;
+    }
+
+    /// Fit a run of `run_length` simulation seconds into `duration` seconds of video. The speedup
+    /// follows from the two; fps drops below `requested_fps` when there are too few simulation ticks
+    /// between frames or max_frames would run out first.
+    pub fn plan_timelapse(&mut self, duration: f32, run_length: f32, requested_fps: u32) {
+        let duration = duration.max(1.0);
+        let speedup = run_length / duration;
+        let tick_limit = (speedup / MIN_CAPTURE_INTERVAL).floor() as u32;
+        let frame_limit = (self.max_frames as f32 / duration).floor() as u32;
+        self.target_duration = Some(duration);
+        self.set_cadence(requested_fps.min(tick_limit).min(frame_limit), speedup);
+    }
+
+    /// Bytes of frames the replay buffer may keep, half the memory budget
+    pub fn replay_budget(&self) -> usize {
+        self.memory_budget / 2
+    }
+
+    /// Bytes of frames kept for highlight clips, a quarter of the memory budget
+    pub fn highlight_budget(&self) -> usize {
+        self.memory_budget / 4
+    }
+
+    /// Bytes of frames a stream may have waiting, and again queued for its encoder; the main and
+    /// pheromone videos together take the last quarter of the budget
+    pub fn stream_budget(&self) -> usize {
+        self.memory_budget / 16
+    }
+}
+
+/// Where video frames come from (`--video-source`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VideoSource {
+    Rendered, // Software re-render of the simulation state with the text overlay
+    Screen,   // Screenshots of the window, including sprites, gizmos and UI exactly as shown
+}
+
+impl VideoSource {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "rendered" => Some(Self::Rendered),
+            "screen" => Some(Self::Screen),
+            _ => None,
+        }
+    }
+}
+
+/// Part of the world the rendered video shows when no camera path is set (`--video-crop`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VideoCrop {
+    Whole,
+    Rect(Vec2, Vec2), // World-space min and max corners, shown exactly
+    Auto, // Follows the bounding box of ants, food and nests
+}
+
+impl VideoCrop {
+    /// `auto`, `whole`, or `x0,y0,x1,y1` in world units
+    pub fn from_arg(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(Self::Auto),
+            "whole" => Some(Self::Whole),
+            _ => {
+                let numbers: Vec<f32> = value.split(',').map(|field| field.trim().parse().ok()).collect::<Option<_>>()?;
+                let [x0, y0, x1, y1] = numbers[..] else { return None };
+                let (min, max) = (Vec2::new(x0.min(x1), y0.min(y1)), Vec2::new(x0.max(x1), y0.max(y1)));
+                (max.x > min.x && max.y > min.y).then_some(Self::Rect(min, max))
+            }
+        }
+    }
+}
+
+/// Container written at the end of a run (`--video-format`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VideoFormat {
+    Mp4,
+    Webm, // VP9, plays inline in browsers and GitHub comments
+    Gif, // Downscaled and palette-quantized, small enough to attach to issues
+}
+
+impl VideoFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "mp4" => Some(Self::Mp4),
+            "webm" => Some(Self::Webm),
+            "gif" => Some(Self::Gif),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Mp4 => "mp4",
+            Self::Webm => "webm",
+            Self::Gif => "gif",
+        }
+    }
 }
 
 impl Default for VideoRecorder {
     fn default() -> Self {
         Self {
-            frames: Vec::new(),
+            stream: FrameStream::default(),
+            pheromone_stream: None,
+            run_samples: RunSamples::default(),
+            soundtrack: None,
             is_recording: false,
             frame_width: 406,
             frame_height: 720,
@@ -52,7 +193,21 @@ impl Default for VideoRecorder {
             test_number: 1,
             changes_description: "Default configuration".to_string(),
             frame_timer: 0.0,
-            frame_interval: 0.2, // Capture every 0.2 seconds to get exactly 450 frames over 90s (90/450=0.2)
+            frame_interval: 0.2, // 6x speedup at 30 fps: a 90s run becomes a 15s video
+            fps: 30,
+            speedup: 6.0,
+            target_duration: Some(15.0),
+            recording_started: 0.0,
+            bitrate: "4M".to_string(),
+            format: VideoFormat::Mp4,
+            source: VideoSource::Rendered,
+            hardware_encoding: false,
+            font: None,
+            container_tags: Vec::new(),
+            crop: VideoCrop::Whole,
+            auto_crop: None,
+            memory_budget: DEFAULT_VIDEO_MEMORY_MB * 1024 * 1024,
+            frame_pool: FramePool::default(),
         }
     }
 }
@@ -141,6 +296,13 @@ impl Default for PerformanceTracker {
             return_times: Vec::new(),
             average_time_since_goal: 0.0,
             time_since_goal_samples: Vec::new(),
+            food_depletion_times: Vec::new(),
+            ants_lost: 0,
+            colony_deliveries: Vec::new(),
+            colony_ant_counts: Vec::new(),
+            delivery_timestamps: Vec::new(),
+            food_relocation_times: Vec::new(),
+            stale_trail_clear_times: Vec::new(),
         }
     }
 }
@@ -151,12 +313,102 @@ pub struct PheromoneDebugText;
 #[derive(Component)]
 pub struct EntityDebugText;
 
+/// Panel listing the selected ant's diagnostics, hidden while nothing is selected
+#[derive(Component)]
+pub struct SelectedAntPanel;
+
+#[derive(Component)]
+pub struct SelectedAntText;
+
 #[derive(Component)]
 pub struct PerformanceText;
 
+#[derive(Component)]
+pub struct ScoreText;
+
+#[derive(Component)]
+pub struct ObjectiveText;
+
+#[derive(Component)]
+pub struct ObjectiveTitleText;
+
 #[derive(Component)]
 pub struct SelectedAnt;
 
+/// Ant driven by the arrow keys instead of the sensing systems (Q)
+#[derive(Component)]
+pub struct Possessed;
+
+/// One direction an ant sampled while sensing
+#[derive(Clone, Copy)]
+pub struct SensingRay {
+    pub angle: f32,
+    pub strength: f32,      // Raw pheromone reading (0 for pure pathfinding candidates)
+    pub score: Option<f32>, // Combined score, None when the direction was rejected outright
+}
+
+/// The primary selected ant's most recent sensing decision, recorded by the sensing systems
+#[derive(Component, Default)]
+pub struct SensingDebug {
+    pub rays: Vec<SensingRay>,
+    pub chosen: Option<f32>, // Heading picked from the rays, None when nothing qualified
+    pub mode: &'static str,  // Which branch of the sensing logic produced the rays
+    pub sensed_at: f32,
+}
+
+impl SensingDebug {
+    pub fn record(&mut self, mode: &'static str, rays: Vec<SensingRay>, chosen: Option<f32>, now: f32) {
+        self.mode = mode;
+        self.rays = rays;
+        self.chosen = chosen;
+        self.sensed_at = now;
+    }
+}
+
+/// Which CYCLE 22 swarm rule fired for an ant
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SwarmDecision {
+    CollectiveExploration, // Heading replaced by the suggested least-explored direction
+    Dispersion,            // Trail scores pushed away from the average failed direction
+}
+
+/// Snapshot of one swarm decision, drawn until it expires
+pub struct SwarmDecisionMarker {
+    pub decision: SwarmDecision,
+    pub position: Vec2,
+    pub direction: f32, // Suggested direction, or the penalised failed direction for dispersion
+    pub leaders: Vec<Vec2>,  // Nearby ants counted as successful
+    pub failures: Vec<Vec2>, // Nearby ants counted as struggling
+    pub at: f32,
+}
+
+/// Recent swarm decisions per ant, recorded by `trail_following_system` and `exploration_system` while their markers are shown
+#[derive(Resource, Default)]
+pub struct SwarmDecisionLog {
+    pub markers: std::collections::HashMap<Entity, SwarmDecisionMarker>,
+}
+
+/// Smoothed camera pan velocity and the zoom level the wheel is easing toward
+#[derive(Default)]
+pub struct CameraMotion {
+    pub velocity: Vec2,
+    pub zoom_target: Option<f32>,
+}
+
+/// Camera views saved with Ctrl+0-9, and where N / Tab continue cycling from
+#[derive(Default)]
+pub struct CameraBookmarks {
+    pub slots: [Option<Transform>; 10],
+    pub next_nest: usize,
+    pub next_food: usize,
+}
+
+/// Positions visited recently by the selected ant, oldest first
+#[derive(Component, Default)]
+pub struct TrajectoryTrail {
+    pub positions: std::collections::VecDeque<Vec2>,
+}
+
 #[derive(Component)]
 pub struct AntOutline;
 
@@ -168,53 +420,137 @@ pub enum AntBehaviorState {
     Tracking,   // Continuing in current direction while monitoring
 }
 
+/// Task allocation role, reassigned periodically from the colony trail signal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AntRole {
+    Scout,    // Wide-ranging Levy-flight exploration, only commits to strong trails
+    Forager,  // Exploits established trails
+}
+
+/// What an ant is and is doing right now: the state sensing decides from and movement acts on.
+/// An ant is the entity carrying this plus AntMemory, AntStats and AntDiagnostics, split so systems
+/// can borrow just the parts they touch.
 #[derive(Component)]
-pub struct AntState {
+pub struct AntCore {
+    pub index: usize, // Spawn order, shown in on-screen labels and log lines
+    pub colony: usize, // Colony this ant belongs to
+    pub nest_position: Vec2, // Where this ant's colony nest is
+    pub role: AntRole, // Current scout/forager task allocation
     pub carrying_food: bool,
     pub hunger: f32,
     pub sensitivity_adapt: f32,
-    pub food_collection_timer: f32, // Time spent collecting food
-    pub last_pheromone_strength: f32, // Track pheromone strength from last frame
-    pub distance_from_food: f32, // Track distance traveled since picking up food trail
-    pub distance_from_nest: f32, // Track distance traveled from nest (for nest pheromone strength)
-    pub has_exit_direction: bool, // Track if ant has already chosen an exit direction
     pub behavior_state: AntBehaviorState,
+    pub food_collection_timer: f32, // Time spent collecting food
+    pub startup_timer: f32, // Grace period for simple behavior after spawning
     pub sensing_timer: f32, // Time until next sensing check
-    pub current_direction: f32, // Current movement direction in radians
-    pub trail_strength: f32, // Strength of trail being followed
     pub momentum_timer: f32, // Time to continue in current direction
+    pub has_exit_direction: bool, // Track if ant has already chosen an exit direction
+    pub current_direction: f32, // Current movement direction in radians
+    pub facing: f32, // Actual body heading in radians, turned toward the velocity at a limited rate
+    pub separation: Vec2, // Soft-collision push away from overlapping neighbours (0-1 per neighbour)
     pub last_position: Vec2, // Previous position for stuck detection
     pub stuck_timer: f32, // Time spent in same general area
-    pub direction_changes: u32, // Count of recent direction changes
+    pub exploration_phase: f32, // Progress counter for stateful exploration strategies (spiral, sweep)
+    pub home_sector: f32, // Sector center direction for systematic sweeps
+    pub low_detail: bool, // Off-trail and between its lod_interval updates: sensing and crowding skip it this tick
+    pub since_detail_update: f32, // Seconds since sensing last ran for this ant, one tick unless it was low detail
+}
+
+/// What an ant remembers about the trail it is on and the food it carries
+#[derive(Component)]
+pub struct AntMemory {
+    pub has_found_food: bool, // Track if ant has ever found food to identify "lost" ants
+    pub last_pheromone_strength: f32, // Track pheromone strength from last frame
+    pub distance_from_food: f32, // Track distance traveled since picking up food trail
+    pub distance_from_nest: f32, // Path length travelled since leaving the nest (outbound search length)
+    pub trail_strength: f32, // Strength of trail being followed
     pub last_sensing_result: [f32; 8], // Results from last 8-direction sensing
     pub trail_memory: [f32; 5], // Recent trail directions (rolling buffer)
     pub memory_index: usize, // Current position in trail memory buffer
     pub trail_quality: f32, // Assessment of current trail quality (consistency)
     pub hysteresis_threshold: f32, // Dynamic threshold for direction changes
     pub consecutive_good_trail_time: f32, // Time spent on consistent, strong trails
-    pub food_pickup_time: f32, // When food was picked up (for delivery time tracking)
-    pub delivery_attempts: u32, // Number of times this ant has attempted delivery
+    
+    // Food source memory for trail staleness
+    pub food_source: Option<Entity>, // Food source the carried food was taken from
+    pub pickup_position: Vec2, // Where the carried food was picked up
+    pub carried_food_quality: f32, // Quality of the food source the carried food came from
+}
+
+/// An ant's lifetime counters and goal timestamps, for scoring and delivery timing
+#[derive(Component)]
+pub struct AntStats {
+    pub spawn_time: f32, // Simulation time when the ant was spawned
     pub successful_deliveries: u32, // Number of successful deliveries by this ant
-    pub startup_timer: f32, // Grace period for simple behavior after spawning
-    pub has_found_food: bool, // Track if ant has ever found food to identify "lost" ants
+    pub delivery_attempts: u32, // Number of times this ant has attempted delivery
+    pub food_pickup_time: f32, // When food was picked up (for delivery time tracking)
     pub food_carry_start_time: f32, // When ant picked up food (for return time tracking)
     pub last_goal_achievement_time: f32, // When ant last reached a goal (found food or delivered to nest)
     pub current_goal_start_time: f32, // When ant started pursuing current goal
-    
-    // New diagnostic fields for behavior analysis
+}
+
+/// Per-ant measurements for behavior analysis, logging and the inspector
+#[derive(Component)]
+pub struct AntDiagnostics {
     pub can_see_trail: bool, // Whether ant can detect any pheromone trail nearby
     pub distance_from_trail: f32, // Distance to nearest significant pheromone concentration
     pub trail_following_time: f32, // How long ant has been following current trail
     pub last_trail_contact_time: f32, // When ant last detected significant pheromone
+    pub trail_gradient_strength: f32, // Strength of pheromone gradient at current position
     pub is_swarming: bool, // Whether ant is stuck in traffic with other ants
     pub nearby_ant_count: u32, // Number of ants within close proximity
     pub time_since_progress: f32, // Time since ant made meaningful progress toward goal
     pub exploration_efficiency: f32, // Ratio of distance covered vs time spent exploring
     pub is_edge_wanderer: bool, // Whether ant is stuck wandering world edges
     pub world_edge_proximity: f32, // Distance from nearest world edge
-    pub trail_gradient_strength: f32, // Strength of pheromone gradient at current position
+    pub direction_changes: u32, // Count of recent direction changes
+}
+
+/// Movement behavior types for unified speed management
+#[derive(Debug, Clone, Copy)]
+pub enum MovementType {
+    /// Ant carrying food returning to nest
+    CarryingFood,
+    /// Ant following pheromone trail to food
+    FollowingTrail,
+    /// Ant exploring randomly for food
+    Exploring,
+    /// Ant recovering from stuck state
+    StuckRecovery,
+    /// Legacy movement (to be phased out)
+    Legacy,
 }
 
+/// Where an ant is in this tick's sensing chain. Each sensing system only takes ants left at the
+/// step it handles, so a behavior can be swapped out without the others noticing.
+#[derive(Component, Default)]
+pub enum SensingStep {
+    /// Not sensing this tick: collecting food, starting up or between low-detail updates
+    #[default]
+    Skipped,
+    /// Due to sense, nothing decided yet
+    Pending,
+    /// Keeping its heading until its sensing timer runs out
+    Holding,
+    /// Empty-handed with no trail worth following, for exploration to pick a heading
+    NoTrail(Option<SwarmSuggestion>),
+    /// A behavior has decided; stuck detection may still override it
+    Decided,
+    /// A behavior has decided and stuck detection leaves it be
+    Settled,
+}
+
+/// A less-explored heading nearby ants suggest to one that found no trail
+pub struct SwarmSuggestion {
+    pub heading: f32,
+    pub marker: Option<SwarmDecisionMarker>, // Drawn if the ant takes it, when swarm markers are shown
+}
+
+/// Heading and gait the sensing systems chose for an ant this tick, applied by steering_system.
+/// None keeps the ant on its current course.
+#[derive(Component, Default)]
+pub struct DesiredHeading(pub Option<(f32, MovementType)>);
+
 #[derive(Component)]
 pub struct DebugAnt {
     pub ant_id: u32,
@@ -230,11 +566,102 @@ pub struct Velocity {
 pub struct FoodSource {
     pub amount: f32,
     pub max_amount: f32,
+    pub quality: f32, // Nutritional value multiplier for trail deposits
+}
+
+/// Food that wanders slowly around the world (prey challenge)
+#[derive(Component)]
+pub struct Prey {
+    pub heading: f32, // Current wander direction in radians
+    pub speed: f32,   // Wander speed in units/second
+}
+
+/// Predator patrolling back and forth across the nest-food corridor (challenge 4)
+#[derive(Component)]
+pub struct Predator {
+    pub patrol_start: Vec2,
+    pub patrol_end: Vec2,
+    pub progress: f32,  // 0.0 at patrol_start, 1.0 at patrol_end
+    pub direction: f32, // +1.0 toward patrol_end, -1.0 toward patrol_start
+}
+
+/// Sprite marking an impassable water cell from a map
+#[derive(Component)]
+pub struct WaterTile;
+
+/// Depleted food source waiting to regrow in place
+#[derive(Component)]
+pub struct Regrowing {
+    pub ready_at: f32, // Elapsed time at which the source refills
+}
+
+/// Sent by food_collection_system when an ant takes food from a source. This and the other core
+/// events below are how PerformanceTracker, the event log and highlights learn what happened.
+#[derive(Event, Clone)]
+pub struct FoodPickedUp {
+    pub ant: Entity,
+    pub ant_index: usize,
+    pub source: Entity,
+    pub position: Vec2,
+    pub amount: f32,
+    pub time: f32,
+}
+
+/// Sent by food_collection_system when an ant drops its food at the nest
+#[derive(Event, Clone)]
+pub struct FoodDelivered {
+    pub ant: Entity,
+    pub ant_index: usize,
+    pub colony: usize,
+    pub position: Vec2,
+    pub delivery_time: f32, // Seconds since the food was picked up
+    pub return_time: f32,   // Seconds spent carrying it home
+    pub time: f32,
+}
+
+/// Sent by stuck_detection_system when an ant that barely moved for two seconds is turned loose
+#[derive(Event, Clone)]
+pub struct AntStuck {
+    pub ant: Entity,
+    pub ant_index: usize,
+    pub position: Vec2,
+    pub time: f32,
+}
+
+/// Sent by food_visual_system when a food source runs out
+#[derive(Event, Clone)]
+pub struct FoodDepleted {
+    pub source: Entity,
+    pub position: Vec2,
+    pub time: f32,
+}
+
+/// Kinds of simulation event shown in the event log panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SimEventKind {
+    Pickup,
+    Delivery,
+    Stuck,
+    FoodDepleted,
+    RockWarning,
+    PredatorKill,
+    TrailCollapse, // Trail contact fell far below its peak after trails had formed
+}
+
+/// Something worth noting at a place and time, published for the event log. The core events above
+/// are turned into these by event_log_collect_system.
+#[derive(Event, Clone)]
+pub struct SimEvent {
+    pub kind: SimEventKind,
+    pub position: Vec2,
+    pub time: f32,
+    pub ant: Option<usize>, // Index of the ant involved, if any
 }
 
 #[derive(Component)]
 pub struct Nest {
     pub capacity: f32,
+    pub colony: usize, // Colony whose ants deliver here
 }
 
 #[derive(Component)]
@@ -254,26 +681,118 @@ impl Default for PheromoneData {
     }
 }
 
+/// The sprite showing the pheromone grid, and the texture it is painted on
 #[derive(Component)]
 pub struct PheromoneVisualization {
-    pub grid_x: usize,
-    pub grid_y: usize,
+    pub image: Handle<Image>,
+}
+
+/// Which overlays are drawn. Each has its own toggle key; F1-F3 switch between the presets below.
+#[derive(Resource, Clone)]
+pub struct ViewSettings {
+    pub food_layer: bool,  // 1
+    pub nest_layer: bool,  // 2
+    pub alarm_layer: bool, // 3
+    pub ghost_layer: bool, // 4, only drawn when a snapshot was loaded with --ghost
+    pub ant_labels: bool,  // T
+    pub legend: bool,      // L
+    pub swarm_markers: bool, // V
+    pub selection_gizmos: bool, // Trajectory trail and sensing rays of the selected ants (G)
+    pub minimap: bool,     // M
+    pub trend_chart: bool, // K
+    pub world_grid: bool,  // X
+    pub system_timings: bool, // P
+}
+
+impl Default for ViewSettings {
+    fn default() -> Self {
+        Self::analysis()
+    }
+}
+
+impl ViewSettings {
+    /// Ants, food and terrain only
+    pub fn clean() -> Self {
+        Self {
+            food_layer: false,
+            nest_layer: false,
+            alarm_layer: false,
+            ghost_layer: false,
+            ant_labels: false,
+            legend: false,
+            swarm_markers: false,
+            selection_gizmos: false,
+            minimap: false,
+            trend_chart: false,
+            world_grid: false,
+            system_timings: false,
+        }
+    }
+
+    /// Trails, legend, minimap and charts; the startup view
+    pub fn analysis() -> Self {
+        Self {
+            food_layer: true,
+            nest_layer: true,
+            alarm_layer: true,
+            ghost_layer: true,
+            legend: true,
+            selection_gizmos: true,
+            minimap: true,
+            trend_chart: true,
+            ..Self::clean()
+        }
+    }
+
+    /// Everything on
+    pub fn debug() -> Self {
+        Self {
+            ant_labels: true,
+            swarm_markers: true,
+            world_grid: true,
+            system_timings: true,
+            ..Self::analysis()
+        }
+    }
 }
 
 #[derive(Component)]
 pub struct Rock {
     pub radius: f32,
+    pub blocked_count: u32, // Ant moves this rock has stopped
+}
+
+/// Rock drawn as a filled circle of its radius; maze wall colliders are drawn by their wall sprites instead
+#[derive(Component)]
+pub struct RoundRock;
+
+/// Colony-level signal driving scout/forager task allocation
+#[derive(Resource, Default)]
+pub struct ColonyState {
+    pub trail_signal: f32, // Smoothed fraction of ants in contact with trails
+    pub scout_ratio: f32,  // Current target fraction of scouts
+    pub scout_count: u32,  // Scouts after the last reassignment
+    pub role_timer: f32,   // Time until next role reassignment
+}
+
+/// Tracks progress through the gradual ant spawn schedule
+#[derive(Resource, Default)]
+pub struct AntSpawner {
+    pub spawned: usize,       // Ants spawned so far
+    pub next_spawn_time: f32, // Scheduled time of the next batch
 }
 
 #[derive(Resource)]
 pub struct ChallengeConfig {
     pub challenge_number: u32,
+    pub prey_food: bool, // Food sources wander as prey (--prey)
 }
 
 impl Default for ChallengeConfig {
     fn default() -> Self {
         Self {
             challenge_number: 1,
+            prey_food: false,
         }
     }
 }
\ No newline at end of file
@@ -1,10 +1,42 @@
 use bevy::prelude::*;
 
-#[derive(Resource)]
+/// Closest any food may start or be moved to its nest (CONSTRAINTS.md: a third of the world)
+pub const MIN_FOOD_DISTANCE: f32 = 333.0;
+
+/// Exploration pattern for ants that have no trail to follow
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExplorationStrategy {
+    RandomTurn,      // Adaptive random turns with spiral fallback for lost ants
+    LevyFlight,      // Power-law run lengths with random reorientation
+    ExpandingSpiral, // Continuous outward spiral from the search start
+    SectorSweep,     // Radial out-and-back legs across the ant's home sector
+}
+
+impl ExplorationStrategy {
+    /// Parse a strategy from its command-line name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "random" | "random-turn" => Some(Self::RandomTurn),
+            "levy" | "levy-flight" => Some(Self::LevyFlight),
+            "spiral" => Some(Self::ExpandingSpiral),
+            "sweep" | "sector-sweep" => Some(Self::SectorSweep),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
 pub struct SimConfig {
     pub world_size: usize,
-    pub initial_ants: usize,
+    pub initial_ants: usize, // Population cap reached by the spawn schedule
     pub food_sources: usize,
+    pub food_regrow_in_place: bool,
+    pub food_regrow_cooldown: f32,
+    pub placed_food_amount: f32, // Size of food sources dropped with Ctrl+click
+    
+    // Spawn schedule parameters
+    pub spawn_batch_size: usize,
+    pub spawn_interval: f32,
     
     // Pheromone parameters
     pub evap_food: f32,
@@ -15,13 +47,69 @@ pub struct SimConfig {
     pub diff_alarm: f32,
     
     // Ant behavior parameters  
+    pub max_turn_rate: f32,
+    pub separation_radius: f32,
+    pub separation_strength: f32,
+    pub exploration_strategy: ExplorationStrategy,
     pub base_exploration_noise: f32,
     pub follow_gain: f32,
     pub lay_rate_food: f32,
     pub lay_rate_nest: f32,
     pub food_quality_weight: f32,
+    pub classic_aco: bool,
+    pub aco_pheromone_q: f32,
     pub detection_threshold: f32,
     pub saturation_limit: f32,
+    pub sensor_noise_std: f32,
+    pub sensor_false_negative_rate: f32,
+    pub lod_interval: u32, // Ticks between sensing updates for empty-handed ants far from any pheromone
+    pub analysis_interval: f32, // Seconds between behavior analysis passes; 0 turns them off
+    
+    // Terrain parameters (only used when a heightmap is loaded)
+    pub terrain_height_scale: f32,
+    pub slope_cost: f32,
+    
+    // Map generator parameters (any challenge, enabled with --map-seed)
+    pub procedural_map: bool,
+    pub map_seed: u64,
+    pub map_obstacle_density: f32,
+    pub map_food_clusters: usize,
+    pub map_cluster_size: usize,
+    pub map_terrain_roughness: f32,
+    pub map_terrain_relief: f32,
+    
+    // Maze parameters (challenge 3)
+    pub maze_corridor_width: f32,
+    pub maze_wall_thickness: f32,
+    pub maze_seed: u64,
+    
+    // Predator parameters (challenge 4)
+    pub predator_count: usize,
+    pub predator_speed: f32,
+    pub predator_kill_radius: f32,
+    pub predator_alarm_amount: f32,
+    
+    // Colony race parameters (challenge 5)
+    pub colony_race_target: u32,
+    
+    // Moving target parameters (challenge 6)
+    pub moving_target_interval: f32,
+    pub moving_target_seed: u64, // Seeds the starting sites and every relocation, so runs repeat
+    
+    // Task allocation parameters
+    pub scout_ratio_min: f32,
+    pub scout_ratio_max: f32,
+    pub trail_signal_saturation: f32,
+    pub role_update_interval: f32,
+    pub scout_trail_threshold: f32,
+    pub levy_exponent: f32,
+    pub levy_min_step: f32,
+    pub levy_max_step: f32,
+    
+    // Prey parameters (challenge 7, or --prey on challenges 1 and 2)
+    pub prey_speed: f32,
+    pub prey_turn_rate: f32,
+    pub prey_trail_falloff: f32,
 }
 
 impl Default for SimConfig {
@@ -30,6 +118,12 @@ impl Default for SimConfig {
             world_size: 1000,
             initial_ants: 50,       // Back to original 50 ants  
             food_sources: 10,       // Back to original 10 food sources - no cheating!
+            food_regrow_in_place: false, // Depleted food respawns elsewhere unless --regrow is set
+            food_regrow_cooldown: 20.0,  // Seconds a depleted source stays empty before regrowing
+            placed_food_amount: 100.0,   // Same as a scenario food source
+            
+            spawn_batch_size: 50,   // Whole colony in the first batch; lower (e.g. 5) to study small-population trail formation
+            spawn_interval: 10.0,   // Seconds between batches
             
             evap_food: 0.0002,     // GENERATION 79: Revert to successful Generation 79 base settings
             evap_nest: 0.0005,      // Back to Generation 54 successful value
@@ -38,13 +132,61 @@ impl Default for SimConfig {
             diff_nest: 0.05,        // Back to Generation 54 successful value
             diff_alarm: 0.2,
             
+            max_turn_rate: std::f32::consts::TAU, // Radians/second - a full reversal takes 0.5s
+            separation_radius: 12.0,   // One ant body width - closer neighbours push apart
+            separation_strength: 40.0, // Units/second of push at full overlap; 0 lets ants stack
+            exploration_strategy: ExplorationStrategy::RandomTurn, // Proven Generation 87 behavior
             base_exploration_noise: 0.02,    // GENERATION 79: Revert to successful Generation 79 base
             follow_gain: 3.5,       // GENERATION 79: Revert to successful Generation 79 base
             lay_rate_food: 42.0,    // CYCLE 5: Slightly increased trail deposition
             lay_rate_nest: 25.0,    // NEST PHEROMONE FIX: Strong nest trails from successful food carriers
             food_quality_weight: 1.0,
+            classic_aco: false,        // Textbook ACO deposits (--aco) for comparison with literature
+            aco_pheromone_q: 25000.0,  // ACO deposit constant Q: a 600-unit tour lays ~42 per unit, matching lay_rate_food
             detection_threshold: 0.0008,  // CYCLE 3: Revert to Gen 79 base
             saturation_limit: 10.0,    // GENERATION 75: Revert to optimal saturation level
+            sensor_noise_std: 0.0,     // Perfect sensing by default; raise to test trail-following robustness
+            sensor_false_negative_rate: 0.0, // Probability each pheromone sample is missed entirely
+            lod_interval: 1,           // Every ant senses every tick; 4 keeps huge colonies cheap (--lod)
+            analysis_interval: 0.5,    // Diagnostics and debug-ant logging twice a second (--analysis-interval, --no-analysis)
+            
+            terrain_height_scale: 100.0, // World units of elevation between black and white pixels
+            slope_cost: 4.0,             // Speed divisor per unit of uphill slope
+            
+            procedural_map: false,
+            map_seed: 0,
+            map_obstacle_density: 0.3,   // Rocks per 100x100 area (0.3 = ~30 rocks)
+            map_food_clusters: 3,
+            map_cluster_size: 3,         // Food sources per cluster
+            map_terrain_roughness: 0.004, // Noise frequency - lower gives broader hills
+            map_terrain_relief: 0.5,     // 0 = flat, 1 = full elevation range
+            
+            maze_corridor_width: 120.0,  // Wide enough that rock avoidance doesn't trigger mid-corridor
+            maze_wall_thickness: 12.0,
+            maze_seed: 42,
+            
+            predator_count: 3,
+            predator_speed: 60.0,        // Slightly faster than a carrying ant
+            predator_kill_radius: 14.0,
+            predator_alarm_amount: 30.0, // Alarm released at each kill - enough to outweigh a fresh food trail
+            
+            colony_race_target: 100,     // Deliveries needed to win the two-colony race
+            
+            moving_target_interval: 30.0, // Seconds between food relocations
+            moving_target_seed: 6,
+            
+            scout_ratio_min: 0.05,       // Few scouts once trails are established
+            scout_ratio_max: 0.6,        // Mostly scouts while no trails exist
+            trail_signal_saturation: 0.4, // Trail-contact fraction treated as "strong trails exist"
+            role_update_interval: 5.0,   // Seconds between role reassignments
+            scout_trail_threshold: 0.6,  // Scouts ignore trails weaker than this
+            levy_exponent: 2.0,          // Power-law exponent for Levy flight step lengths
+            levy_min_step: 20.0,         // Shortest Levy flight run (world units)
+            levy_max_step: 400.0,        // Longest Levy flight run (world units)
+            
+            prey_speed: 8.0,             // Slow wander so trails stay briefly useful
+            prey_turn_rate: 1.5,         // Max random heading change per second (radians)
+            prey_trail_falloff: 150.0,   // Prey displacement at which carrier trails fade to minimum
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,123 @@
+/// Cells across and down the contact sheet
+const SHEET_GRID: u32 = 3;
+/// Width of the thumbnail; height follows the frame's aspect ratio
+const THUMBNAIL_WIDTH: u32 = 240;
+/// Downscaled frames kept while recording; when full, every other one is dropped and the
+/// sampling stride doubles, so the kept frames always span the whole run
+const MAX_SAMPLES: usize = 24;
+/// Dark gap between contact sheet cells
+const SHEET_GAP: u32 = 2;
+
+/// Frames sampled across the run for the contact sheet, plus the latest frame for the thumbnail
+#[derive(Default)]
+pub struct RunSamples {
+    width: u32,
+    height: u32,
+    samples: Vec<(usize, Vec<u8>)>, // Capture index and the frame scaled down to one sheet cell
+    stride: usize,
+    seen: usize,
+    last: Vec<u8>,
+}
+
+impl RunSamples {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height, stride: 1, ..Default::default() }
+    }
+
+    fn cell_size(&self) -> (u32, u32) {
+        ((self.width / SHEET_GRID).max(1), (self.height / SHEET_GRID).max(1))
+    }
+
+    /// Called with every captured RGBA frame of the main video
+    pub fn add(&mut self, frame: &[u8]) {
+        if self.width == 0 || frame.len() != (self.width * self.height * 4) as usize {
+            return;
+        }
+        if self.seen.is_multiple_of(self.stride) {
+            let (cell_width, cell_height) = self.cell_size();
+            self.samples.push((self.seen, downscale(frame, self.width, self.height, cell_width, cell_height)));
+            if self.samples.len() > MAX_SAMPLES {
+                let stride = self.stride * 2;
+                self.samples.retain(|(index, _)| index % stride == 0);
+                self.stride = stride;
+            }
+        }
+        self.seen += 1;
+        self.last.clear();
+        self.last.extend_from_slice(frame);
+    }
+
+    /// The final frame scaled to THUMBNAIL_WIDTH, as (width, height, RGBA)
+    pub fn thumbnail(&self) -> Option<(u32, u32, Vec<u8>)> {
+        if self.last.is_empty() {
+            return None;
+        }
+        let width = THUMBNAIL_WIDTH.min(self.width);
+        let height = (self.height * width / self.width).max(1);
+        Some((width, height, downscale(&self.last, self.width, self.height, width, height)))
+    }
+
+    /// Nine frames evenly spread from the first capture to the last, in reading order, as (width, height, RGBA)
+    pub fn contact_sheet(&self) -> Option<(u32, u32, Vec<u8>)> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let (cell_width, cell_height) = self.cell_size();
+        let mut cells: Vec<&[u8]> = self.samples.iter().map(|(_, cell)| cell.as_slice()).collect();
+        // The final frame closes the sheet even when it fell between samples
+        let final_cell = downscale(&self.last, self.width, self.height, cell_width, cell_height);
+        if self.samples.last().is_some_and(|(index, _)| index + 1 < self.seen) {
+            cells.push(&final_cell);
+        }
+
+        let count = (SHEET_GRID * SHEET_GRID) as usize;
+        let picked: Vec<&[u8]> = if cells.len() <= count {
+            cells
+        } else {
+            (0..count).map(|i| cells[i * (cells.len() - 1) / (count - 1)]).collect()
+        };
+
+        let sheet_width = SHEET_GRID * cell_width + (SHEET_GRID + 1) * SHEET_GAP;
+        let sheet_height = SHEET_GRID * cell_height + (SHEET_GRID + 1) * SHEET_GAP;
+        let mut sheet = vec![0u8; (sheet_width * sheet_height * 4) as usize];
+        for alpha in sheet.iter_mut().skip(3).step_by(4) {
+            *alpha = 255;
+        }
+        for (i, cell) in picked.iter().enumerate() {
+            let (column, row) = (i as u32 % SHEET_GRID, i as u32 / SHEET_GRID);
+            let (x0, y0) = (SHEET_GAP + column * (cell_width + SHEET_GAP), SHEET_GAP + row * (cell_height + SHEET_GAP));
+            for y in 0..cell_height {
+                let from = (y * cell_width * 4) as usize;
+                let to = (((y0 + y) * sheet_width + x0) * 4) as usize;
+                sheet[to..to + (cell_width * 4) as usize].copy_from_slice(&cell[from..from + (cell_width * 4) as usize]);
+            }
+        }
+        Some((sheet_width, sheet_height, sheet))
+    }
+}
+
+/// Box-filter an RGBA frame down to (new_width, new_height), averaging every source pixel in each target pixel
+fn downscale(frame: &[u8], width: u32, height: u32, new_width: u32, new_height: u32) -> Vec<u8> {
+    let mut scaled = vec![0u8; (new_width * new_height * 4) as usize];
+    for y in 0..new_height {
+        let (y_start, y_end) = (y * height / new_height, ((y + 1) * height / new_height).max(y * height / new_height + 1));
+        for x in 0..new_width {
+            let (x_start, x_end) = (x * width / new_width, ((x + 1) * width / new_width).max(x * width / new_width + 1));
+            let mut sum = [0u32; 4];
+            for source_y in y_start..y_end.min(height) {
+                for source_x in x_start..x_end.min(width) {
+                    let idx = ((source_y * width + source_x) * 4) as usize;
+                    for channel in 0..4 {
+                        sum[channel] += frame[idx + channel] as u32;
+                    }
+                }
+            }
+            let count = ((y_end.min(height) - y_start) * (x_end.min(width) - x_start)).max(1);
+            let idx = ((y * new_width + x) * 4) as usize;
+            for channel in 0..4 {
+                scaled[idx + channel] = (sum[channel] / count) as u8;
+            }
+        }
+    }
+    scaled
+}
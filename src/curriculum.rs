@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::components::*;
+use crate::config::*;
+use crate::colors::*;
+use crate::challenges::*;
+use crate::scoring::*;
+use crate::systems::WorldTeardown;
+use crate::timed_events::{EventSchedule, StaleTrailProbe};
+
+/// How one curriculum stage ended
+pub struct StageResult {
+    pub challenge: u32,
+    pub name: String,
+    pub score: f32,
+    pub pass_score: f32,
+    pub verdict: Verdict,
+    pub reason: String,
+    pub deliveries: u32,
+}
+
+/// Challenges run back to back in one process with a cumulative score (`--curriculum [N]`)
+#[derive(Resource)]
+pub struct Curriculum {
+    pub stages: Vec<u32>, // Challenge numbers in order
+    pub current: usize,
+    pub base_config: SimConfig, // Config before any challenge adjusts it
+    pub results: Vec<StageResult>,
+}
+
+impl Curriculum {
+    /// The first `stage_count` registered challenges, or all of them
+    pub fn new(stage_count: Option<usize>, base_config: SimConfig) -> Self {
+        let mut stages: Vec<u32> = challenge_registry(&base_config).iter()
+            .filter(|challenge| !challenge.free_play())
+            .map(|challenge| challenge.number())
+            .collect();
+        stages.sort();
+        if let Some(count) = stage_count {
+            stages.truncate(count.max(1));
+        }
+        Self { stages, current: 0, base_config, results: Vec::new() }
+    }
+
+    pub fn cumulative_score(&self) -> f32 {
+        self.results.iter().map(|result| result.score).sum()
+    }
+
+    /// Markdown report with one row per stage and the combined total
+    fn report(&self) -> String {
+        let mut lines = vec![
+            "| Stage | Challenge | Score | Pass | Verdict | Deliveries | Reason |".to_string(),
+            "|---|---|---|---|---|---|---|".to_string(),
+        ];
+        for (stage, result) in self.results.iter().enumerate() {
+            lines.push(format!("| {} | {} {} | {:.1} | {:.0} | {} | {} | {} |",
+                stage + 1, result.challenge, result.name, result.score, result.pass_score,
+                result.verdict.label(), result.deliveries, result.reason));
+        }
+        let passed = self.results.iter().filter(|result| result.verdict == Verdict::Success).count();
+        lines.push(format!("\nTotal score: {:.1} | Stages passed: {}/{}", self.cumulative_score(), passed, self.results.len()));
+        lines.join("\n")
+    }
+}
+
+/// The challenge being run and everything scored or scheduled per stage
+#[derive(SystemParam)]
+pub struct StageState<'w> {
+    challenge: ResMut<'w, ActiveChallenge>,
+    challenge_score: ResMut<'w, ChallengeScore>,
+    performance_tracker: ResMut<'w, PerformanceTracker>,
+    event_schedule: ResMut<'w, EventSchedule>,
+    stale_trail_probe: ResMut<'w, StaleTrailProbe>,
+}
+
+/// What the next stage's world is built from
+#[derive(SystemParam)]
+pub struct StageSetup<'w> {
+    config: ResMut<'w, SimConfig>,
+    color_config: Res<'w, ColorConfig>,
+    challenge_config: Res<'w, ChallengeConfig>,
+}
+
+/// Record the finished stage, then rebuild the world for the next challenge or end the curriculum
+pub fn curriculum_system(
+    mut commands: Commands,
+    mut curriculum: Option<ResMut<Curriculum>>,
+    stage: StageState,
+    mut teardown: WorldTeardown,
+    setup: StageSetup,
+    mut exit_writer: EventWriter<AppExit>,
+    time: Res<Time>,
+) {
+    let Some(ref mut curriculum) = curriculum else { return };
+    let StageState { mut challenge, mut challenge_score, mut performance_tracker, mut event_schedule, mut stale_trail_probe } = stage;
+    let StageSetup { mut config, color_config, challenge_config } = setup;
+
+    // Stage still running, or the final combined verdict has already been posted
+    if challenge_score.verdict == Verdict::Running || curriculum.current >= curriculum.stages.len() {
+        return;
+    }
+
+    curriculum.results.push(StageResult {
+        challenge: challenge.0.number(),
+        name: challenge.0.name(),
+        score: challenge_score.score,
+        pass_score: challenge_score.pass_score,
+        verdict: challenge_score.verdict,
+        reason: challenge_score.reason.clone(),
+        deliveries: performance_tracker.successful_deliveries,
+    });
+    curriculum.current += 1;
+    info!("📚 Stage {}/{} done: {} scored {:.1} ({}) - cumulative {:.1}",
+        curriculum.current, curriculum.stages.len(), challenge.0.name(), challenge_score.score,
+        challenge_score.verdict.label(), curriculum.cumulative_score());
+
+    if curriculum.current >= curriculum.stages.len() {
+        // Post the combined result so the video and generation_info.json record the whole curriculum
+        let report = curriculum.report();
+        info!("📚 Curriculum complete\n{}", report);
+        let path = format!("curriculum_{}.md", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+        match std::fs::write(&path, &report) {
+            Ok(()) => info!("💾 Saved curriculum report to {}", path),
+            Err(e) => error!("❌ Failed to write {}: {}", path, e),
+        }
+
+        let all_passed = curriculum.results.iter().all(|result| result.verdict == Verdict::Success);
+        *challenge_score = ChallengeScore {
+            challenge: 0,
+            score: curriculum.cumulative_score(),
+            pass_score: curriculum.results.iter().map(|result| result.pass_score).sum(),
+            verdict: if all_passed { Verdict::Success } else { Verdict::Failure },
+            reason: format!("Curriculum of {} stages", curriculum.results.len()),
+        };
+        exit_writer.send(AppExit::Success);
+        return;
+    }
+
+    // Tear down the finished stage
+    let now = time.elapsed_seconds();
+    teardown.clear(&mut commands, now);
+
+    // Load the next challenge on a fresh copy of the base config
+    let next = find_challenge(curriculum.stages[curriculum.current], &curriculum.base_config);
+    let mut stage_config = curriculum.base_config.clone();
+    next.configure(&mut stage_config);
+    *config = stage_config;
+    info!("📋 {}", next.describe());
+
+    let mut world = ChallengeWorld {
+        commands: &mut commands,
+        config: &config,
+        color_config: &color_config,
+        prey_food: challenge_config.prey_food,
+    };
+    for (colony, nest_pos) in next.nest_positions().iter().enumerate() {
+        spawn_nest(&mut world, *nest_pos, colony);
+    }
+    next.setup(&mut world);
+
+    // Stage timers count from now
+    let mut events = next.timed_events();
+    for event in events.iter_mut() {
+        event.at += now;
+    }
+    *event_schedule = EventSchedule::new(events);
+    *stale_trail_probe = StaleTrailProbe::default();
+    *performance_tracker = PerformanceTracker { simulation_start_time: now, ..default() };
+    *challenge_score = ChallengeScore::new(next.number(), next.pass_score());
+    challenge.0 = next;
+}
@@ -0,0 +1,332 @@
+use bevy::prelude::*;
+use crate::components::*;
+use crate::config::*;
+use crate::colors::*;
+use crate::challenges::*;
+use crate::worldmap::*;
+use crate::systems::spawn_ant;
+use crate::playback::*;
+use crate::pheromones::*;
+
+/// What a left click places in the editor
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EditorTool {
+    Food,
+    Rock,
+    Water,
+    Nest,
+    Ant,
+    Predator,
+    Pheromone,
+}
+
+impl EditorTool {
+    fn next(self) -> Self {
+        match self {
+            EditorTool::Food => EditorTool::Rock,
+            EditorTool::Rock => EditorTool::Water,
+            EditorTool::Water => EditorTool::Nest,
+            EditorTool::Nest => EditorTool::Ant,
+            EditorTool::Ant => EditorTool::Predator,
+            EditorTool::Predator => EditorTool::Pheromone,
+            EditorTool::Pheromone => EditorTool::Food,
+        }
+    }
+}
+
+/// Map editor mode: E toggles, Tab cycles tools, left click places, right click removes, Ctrl+S saves
+#[derive(Resource)]
+pub struct EditorState {
+    pub active: bool,
+    pub tool: EditorTool,
+    pub rock_drag_start: Option<Vec2>, // Center of the rock being dragged out with the Rock tool
+    pub brush_channel: PheromoneType, // Channel painted by the Pheromone tool (C cycles)
+    pub brush_radius: f32, // [ and ] shrink or grow the brush
+}
+
+impl Default for EditorState {
+    fn default() -> Self {
+        Self {
+            active: false,
+            tool: EditorTool::Food,
+            rock_drag_start: None,
+            brush_channel: PheromoneType::Food,
+            brush_radius: 15.0,
+        }
+    }
+}
+
+/// Radius of rocks placed with a plain click (no drag)
+const EDITOR_ROCK_RADIUS: f32 = 20.0;
+
+/// Drags shorter than this count as a plain click
+const EDITOR_MIN_DRAG: f32 = 5.0;
+
+/// How close a right click must be to remove something
+const EDITOR_PICK_RADIUS: f32 = 25.0;
+
+/// Pheromone the brush deposits per second at its center
+const BRUSH_DEPOSIT_RATE: f32 = 40.0;
+
+/// Half-length of the patrol line given to predators placed by the editor
+const EDITOR_PATROL_HALF_LENGTH: f32 = 100.0;
+
+pub fn editor_toggle_system(
+    input: Res<ButtonInput<KeyCode>>,
+    mut editor: ResMut<EditorState>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut next_state: ResMut<NextState<SimulationState>>,
+    world_map: Option<Res<WorldMap>>,
+    mut commands: Commands,
+) {
+    if input.just_pressed(KeyCode::KeyE) {
+        editor.active = !editor.active;
+        if editor.active {
+            // Edit on a paused world
+            set_paused(true, &mut next_state, &mut virtual_time);
+            if world_map.is_none() {
+                commands.insert_resource(WorldMap::empty());
+            }
+            info!("✏️ Editor ON (paused) - Tab: tool ({:?}) | Left click: place (drag to size rocks, hold to paint pheromone) | Right click: remove | C, [ ]: brush | Ctrl+S: save | E: resume",
+                editor.tool);
+        } else {
+            set_paused(false, &mut next_state, &mut virtual_time);
+            info!("✏️ Editor OFF - simulation resumed");
+        }
+    }
+
+    if editor.active && input.just_pressed(KeyCode::Tab) {
+        editor.tool = editor.tool.next();
+        editor.rock_drag_start = None;
+        info!("✏️ Tool: {:?}", editor.tool);
+    }
+}
+
+pub fn editor_input_system(
+    mut commands: Commands,
+    mut editor: ResMut<EditorState>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    input: Res<ButtonInput<KeyCode>>,
+    debug_info: Res<DebugInfo>,
+    mut world_map: Option<ResMut<WorldMap>>,
+    food_query: Query<(Entity, &Transform), With<FoodSource>>,
+    rock_query: Query<(Entity, &Transform, &Rock)>,
+    nest_query: Query<(Entity, &Transform, &Nest)>,
+    water_query: Query<(Entity, &Transform), With<WaterTile>>,
+    ant_query: Query<(Entity, &Transform), With<AntCore>>,
+    predator_query: Query<(Entity, &Transform), With<Predator>>,
+    config: Res<SimConfig>,
+    color_config: Res<ColorConfig>,
+    time: Res<Time>,
+) {
+    if !editor.active {
+        editor.rock_drag_start = None;
+        return;
+    }
+
+    let cursor = debug_info.cursor_world_pos;
+    let mut world = ChallengeWorld {
+        commands: &mut commands,
+        config: &config,
+        color_config: &color_config,
+        prey_food: false,
+    };
+
+    if mouse_input.just_pressed(MouseButton::Left) {
+        match editor.tool {
+            EditorTool::Food => {
+                spawn_food_source(&mut world, cursor);
+            }
+            EditorTool::Rock => {
+                // Placed on release, sized by the drag
+                editor.rock_drag_start = Some(cursor);
+            }
+            EditorTool::Water => {
+                let cell = snap_to_lattice(cursor);
+                if let Some(ref mut map) = world_map {
+                    if !map.water.is_water(cell.x, cell.y) {
+                        map.water.set_square(cell, LATTICE_SPACING * 0.5, true);
+                        spawn_water_tile(&mut world, cell);
+                    }
+                }
+            }
+            EditorTool::Nest => {
+                let colony = nest_query.iter().count();
+                spawn_nest(&mut world, cursor, colony);
+            }
+            EditorTool::Ant => {
+                // New ants join the closest colony
+                let (colony, nest_position) = nest_query.iter()
+                    .map(|(_, t, nest)| (nest.colony, t.translation.truncate()))
+                    .min_by(|a, b| a.1.distance(cursor).total_cmp(&b.1.distance(cursor)))
+                    .unwrap_or((0, Vec2::ZERO));
+                let ant = spawn_ant(world.commands, 0, &config, time.elapsed_seconds(), colony, nest_position);
+                world.commands.entity(ant).insert(Transform::from_xyz(cursor.x, cursor.y, 6.0));
+            }
+            EditorTool::Predator => {
+                let offset = Vec2::new(EDITOR_PATROL_HALF_LENGTH, 0.0);
+                spawn_predator(&mut world, cursor - offset, cursor + offset, 0.5);
+            }
+            EditorTool::Pheromone => {} // Painted continuously by pheromone_brush_system
+        }
+    }
+
+    if mouse_input.just_released(MouseButton::Left) {
+        if let Some(center) = editor.rock_drag_start.take() {
+            let drag = center.distance(cursor);
+            let radius = if drag < EDITOR_MIN_DRAG { EDITOR_ROCK_RADIUS } else { drag };
+            spawn_rock(&mut world, center, radius);
+            info!("🪨 Rock (r={:.0}) placed at ({:.0}, {:.0})", radius, center.x, center.y);
+        }
+    }
+
+    if mouse_input.just_pressed(MouseButton::Right) {
+        let nearest = |positions: Vec<(Entity, Vec2)>| {
+            positions.into_iter()
+                .filter(|(_, position)| position.distance(cursor) < EDITOR_PICK_RADIUS)
+                .min_by(|a, b| a.1.distance(cursor).total_cmp(&b.1.distance(cursor)))
+                .map(|(entity, _)| entity)
+        };
+
+        let target = match editor.tool {
+            EditorTool::Food => nearest(food_query.iter().map(|(e, t)| (e, t.translation.truncate())).collect()),
+            EditorTool::Rock => {
+                // The rock under the cursor, however large, before anything merely nearby
+                rock_query.iter()
+                    .filter(|(_, t, rock)| t.translation.truncate().distance(cursor) <= rock.radius)
+                    .min_by(|a, b| a.1.translation.truncate().distance(cursor).total_cmp(&b.1.translation.truncate().distance(cursor)))
+                    .map(|(entity, _, _)| entity)
+                    .or_else(|| nearest(rock_query.iter().map(|(e, t, _)| (e, t.translation.truncate())).collect()))
+            }
+            EditorTool::Nest => nearest(nest_query.iter().map(|(e, t, _)| (e, t.translation.truncate())).collect()),
+            EditorTool::Ant => nearest(ant_query.iter().map(|(e, t)| (e, t.translation.truncate())).collect()),
+            EditorTool::Predator => nearest(predator_query.iter().map(|(e, t)| (e, t.translation.truncate())).collect()),
+            EditorTool::Pheromone => None,
+            EditorTool::Water => {
+                let cell = snap_to_lattice(cursor);
+                if let Some(ref mut map) = world_map {
+                    map.water.set_square(cell, LATTICE_SPACING * 0.5, false);
+                }
+                water_query.iter()
+                    .find(|(_, t)| t.translation.truncate().distance(cell) < 1.0)
+                    .map(|(entity, _)| entity)
+            }
+        };
+
+        if let Some(entity) = target {
+            world.commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    let ctrl = input.pressed(KeyCode::ControlLeft) || input.pressed(KeyCode::ControlRight);
+    if ctrl && input.just_pressed(KeyCode::KeyS) {
+        let layout = MapLayout {
+            nests: nest_query.iter().map(|(_, t, _)| t.translation.truncate()).collect(),
+            food: food_query.iter().map(|(_, t)| t.translation.truncate()).collect(),
+            rocks: rock_query.iter().map(|(_, t, rock)| (t.translation.truncate(), rock.radius)).collect(),
+        };
+
+        let empty_map = WorldMap::empty();
+        let water = world_map.as_ref().map_or(&empty_map.water, |map| &map.water);
+        let path = format!("maps/map_{}.png", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+
+        let result = std::fs::create_dir_all("maps")
+            .map_err(|e| e.into())
+            .and_then(|_| save_map_png(&path, &layout, water));
+        match result {
+            Ok(()) => info!("💾 Saved map to {} - run with --map {}", path, path),
+            Err(e) => error!("❌ Failed to save map {}: {}", path, e),
+        }
+    }
+}
+
+/// Outline of the rock being dragged out
+pub fn editor_preview_system(editor: Res<EditorState>, debug_info: Res<DebugInfo>, mut gizmos: Gizmos) {
+    if let Some(center) = editor.rock_drag_start {
+        let drag = center.distance(debug_info.cursor_world_pos);
+        let radius = if drag < EDITOR_MIN_DRAG { EDITOR_ROCK_RADIUS } else { drag };
+        gizmos.circle_2d(center, radius, Color::srgb(0.8, 0.7, 0.5));
+    }
+}
+
+/// Pheromone tool: hold left to paint the brush channel, hold right to erase it
+pub fn pheromone_brush_system(
+    mut editor: ResMut<EditorState>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    input: Res<ButtonInput<KeyCode>>,
+    debug_info: Res<DebugInfo>,
+    mut pheromone_grid: ResMut<PheromoneGrid>,
+    real_time: Res<Time<Real>>,
+    mut gizmos: Gizmos,
+    color_config: Res<ColorConfig>,
+) {
+    if !editor.active || editor.tool != EditorTool::Pheromone {
+        return;
+    }
+
+    if input.just_pressed(KeyCode::KeyC) {
+        editor.brush_channel = match editor.brush_channel {
+            PheromoneType::Food => PheromoneType::Nest,
+            PheromoneType::Nest => PheromoneType::Alarm,
+            PheromoneType::Alarm => PheromoneType::Food,
+        };
+        info!("🖌️ Brush channel: {:?}", editor.brush_channel);
+    }
+    if input.just_pressed(KeyCode::BracketLeft) {
+        editor.brush_radius = (editor.brush_radius - 5.0).max(5.0);
+    }
+    if input.just_pressed(KeyCode::BracketRight) {
+        editor.brush_radius = (editor.brush_radius + 5.0).min(100.0);
+    }
+
+    let cursor = debug_info.cursor_world_pos;
+    let color = match editor.brush_channel {
+        PheromoneType::Food => color_config.food_pheromone,
+        PheromoneType::Nest => color_config.nest_pheromone,
+        PheromoneType::Alarm => color_config.alarm_pheromone,
+    };
+    gizmos.circle_2d(cursor, editor.brush_radius, color);
+
+    // Real time, since the editor keeps the simulation paused
+    if mouse_input.pressed(MouseButton::Left) {
+        let amount = BRUSH_DEPOSIT_RATE * real_time.delta_seconds();
+        pheromone_grid.deposit_disc(cursor, editor.brush_radius, editor.brush_channel, amount);
+    } else if mouse_input.pressed(MouseButton::Right) {
+        pheromone_grid.clear_disc(cursor, editor.brush_radius, editor.brush_channel);
+    }
+}
+
+/// Ctrl+click drops a food source at the cursor while the simulation runs
+pub fn food_drop_system(
+    mut commands: Commands,
+    editor: Res<EditorState>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    input: Res<ButtonInput<KeyCode>>,
+    debug_info: Res<DebugInfo>,
+    config: Res<SimConfig>,
+    color_config: Res<ColorConfig>,
+) {
+    // The editor has its own food tool
+    if editor.active || !mouse_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+    if !(input.pressed(KeyCode::ControlLeft) || input.pressed(KeyCode::ControlRight)) {
+        return;
+    }
+
+    let cursor = debug_info.cursor_world_pos;
+    let amount = config.placed_food_amount;
+    let mut world = ChallengeWorld {
+        commands: &mut commands,
+        config: &config,
+        color_config: &color_config,
+        prey_food: false,
+    };
+    spawn_food_source(&mut world, cursor).insert(FoodSource { amount, max_amount: amount, quality: 1.0 });
+    info!("🍎 Dropped food ({:.0}) at ({:.0}, {:.0})", amount, cursor.x, cursor.y);
+}
+
+/// Center of the water lattice cell containing a world position
+fn snap_to_lattice(position: Vec2) -> Vec2 {
+    ((position / LATTICE_SPACING).floor() + Vec2::splat(0.5)) * LATTICE_SPACING
+}
@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::collections::VecDeque;
+use crate::components::*;
+
+/// Entries kept; older ones scroll off the top
+const EVENT_LOG_CAPACITY: usize = 200;
+
+/// Recent simulation events for the scrollable panel, toggled with J
+#[derive(Resource, Default)]
+pub struct EventLog {
+    pub visible: bool,
+    pub entries: VecDeque<SimEvent>,
+}
+
+impl SimEventKind {
+    fn label(self) -> &'static str {
+        match self {
+            SimEventKind::Pickup => "pickup",
+            SimEventKind::Delivery => "delivery",
+            SimEventKind::Stuck => "stuck",
+            SimEventKind::FoodDepleted => "food depleted",
+            SimEventKind::RockWarning => "rock warning",
+            SimEventKind::PredatorKill => "predator kill",
+            SimEventKind::TrailCollapse => "trail collapse",
+        }
+    }
+
+    pub(crate) fn color(self) -> egui::Color32 {
+        match self {
+            SimEventKind::Pickup => egui::Color32::from_rgb(255, 220, 0),
+            SimEventKind::Delivery => egui::Color32::from_rgb(0, 220, 0),
+            SimEventKind::Stuck => egui::Color32::from_rgb(255, 120, 120),
+            SimEventKind::FoodDepleted => egui::Color32::from_rgb(255, 140, 0),
+            SimEventKind::RockWarning => egui::Color32::from_rgb(220, 120, 255),
+            SimEventKind::PredatorKill => egui::Color32::from_rgb(200, 30, 30),
+            SimEventKind::TrailCollapse => egui::Color32::from_rgb(120, 180, 255),
+        }
+    }
+}
+
+pub fn event_log_toggle_system(input: Res<ButtonInput<KeyCode>>, mut log: ResMut<EventLog>) {
+    if input.just_pressed(KeyCode::KeyJ) {
+        log.visible = !log.visible;
+    }
+}
+
+/// Collects events every frame, whether or not the panel is open, turning the core simulation
+/// events into log entries alongside the SimEvents other systems publish
+pub fn event_log_collect_system(
+    mut log: ResMut<EventLog>,
+    mut sim_events: EventReader<SimEvent>,
+    mut pickups: EventReader<FoodPickedUp>,
+    mut deliveries: EventReader<FoodDelivered>,
+    mut stuck_events: EventReader<AntStuck>,
+    mut depleted_events: EventReader<FoodDepleted>,
+) {
+    let picked_up = pickups.read().map(|event| SimEvent {
+        kind: SimEventKind::Pickup,
+        position: event.position,
+        time: event.time,
+        ant: Some(event.ant_index),
+    });
+    let delivered = deliveries.read().map(|event| SimEvent {
+        kind: SimEventKind::Delivery,
+        position: event.position,
+        time: event.time,
+        ant: Some(event.ant_index),
+    });
+    let stuck = stuck_events.read().map(|event| SimEvent {
+        kind: SimEventKind::Stuck,
+        position: event.position,
+        time: event.time,
+        ant: Some(event.ant_index),
+    });
+    let depleted = depleted_events.read().map(|event| SimEvent {
+        kind: SimEventKind::FoodDepleted,
+        position: event.position,
+        time: event.time,
+        ant: None,
+    });
+    let mut incoming: Vec<SimEvent> = sim_events.read().cloned()
+        .chain(picked_up).chain(delivered).chain(stuck).chain(depleted)
+        .collect();
+    incoming.sort_by(|a, b| a.time.total_cmp(&b.time));
+    if incoming.is_empty() {
+        return;
+    }
+    log.entries.extend(incoming);
+    while log.entries.len() > EVENT_LOG_CAPACITY {
+        log.entries.pop_front();
+    }
+}
+
+/// Newest entries at the bottom; clicking one centres the camera on where it happened
+pub fn event_log_panel_system(
+    mut contexts: EguiContexts,
+    mut log: ResMut<EventLog>,
+    mut debug_info: ResMut<DebugInfo>,
+    mut camera_query: Query<&mut Transform, With<Camera>>,
+) {
+    if !log.visible {
+        return;
+    }
+
+    let mut jump_to = None;
+    let mut visible = log.visible;
+    egui::Window::new("Events (J to hide)")
+        .open(&mut visible)
+        .default_pos(egui::pos2(400.0, 500.0))
+        .default_size(egui::vec2(340.0, 220.0))
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} entries", log.entries.len()));
+                if ui.button("Clear").clicked() {
+                    log.entries.clear();
+                }
+            });
+            egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+                for entry in log.entries.iter() {
+                    let ant = entry.ant.map_or(String::new(), |index| format!(" #{}", index));
+                    let text = egui::RichText::new(format!(
+                        "{:>7.1}s  {}{} at ({:.0}, {:.0})",
+                        entry.time, entry.kind.label(), ant, entry.position.x, entry.position.y,
+                    )).monospace().color(entry.kind.color());
+                    if ui.selectable_label(false, text).clicked() {
+                        jump_to = Some(entry.position);
+                    }
+                }
+            });
+        });
+    log.visible = visible;
+
+    if let (Some(position), Ok(mut camera_transform)) = (jump_to, camera_query.get_single_mut()) {
+        camera_transform.translation = position.extend(camera_transform.translation.z);
+        debug_info.follow_selected = false;
+    }
+}
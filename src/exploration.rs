@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+use crate::components::*;
+use crate::config::*;
+
+/// Exploration speed used to convert run lengths into run durations
+const EXPLORING_SPEED: f32 = 50.0;
+
+/// Pick a new heading for an exploring ant that has no trail to follow.
+/// Updates `ant.current_direction` and returns the time until the next decision.
+pub fn apply_exploration_strategy(
+    strategy: ExplorationStrategy,
+    ant: &mut AntCore,
+    ant_stats: &AntStats,
+    ant_diagnostics: &AntDiagnostics,
+    pos: Vec2,
+    config: &SimConfig,
+    elapsed_time: f32,
+) -> f32 {
+    match strategy {
+        ExplorationStrategy::RandomTurn => explore_random_turn(ant, ant_stats, ant_diagnostics, elapsed_time),
+        ExplorationStrategy::LevyFlight => explore_levy_flight(ant, config),
+        ExplorationStrategy::ExpandingSpiral => explore_expanding_spiral(ant),
+        ExplorationStrategy::SectorSweep => explore_sector_sweep(ant, pos),
+    }
+}
+
+/// Adaptive random turns that widen with search time, switching to a spiral once the ant is lost
+fn explore_random_turn(ant: &mut AntCore, ant_stats: &AntStats, ant_diagnostics: &AntDiagnostics, elapsed_time: f32) -> f32 {
+    // Adaptive exploration: more aggressive as search time increases
+    let search_time = if ant_stats.last_goal_achievement_time > 0.0 {
+        elapsed_time - ant_stats.last_goal_achievement_time
+    } else {
+        // Time since startup ended (when ant became active)
+        (elapsed_time - ant_stats.spawn_time - 1.0).max(0.0) // Startup was 1.0s
+    };
+
+    // CYCLE 3: Smarter exploration with spiral search pattern for lost ants
+    let exploration_factor = (search_time / 60.0).min(1.0);
+
+    if ant_diagnostics.time_since_progress > 10.0 {
+        // CYCLE 5: Earlier and more optimized spiral search
+        let lost_duration = ant_diagnostics.time_since_progress - 10.0;
+        let spiral_angle = lost_duration * 1.0; // Even faster spiral
+        ant.current_direction += spiral_angle.sin() * 0.45; // Slightly more aggressive
+    } else {
+        // Normal exploration
+        let base_angle = 1.2;
+        let max_angle = 2.2;
+        let angle_range = base_angle + (max_angle - base_angle) * exploration_factor;
+
+        let angle_change = (rand::random::<f32>() - 0.5) * angle_range;
+        ant.current_direction += angle_change;
+    }
+
+    // Back to Generation 51 successful exploration sensing
+    let base_sensing = 0.6;
+    let min_sensing = 0.3;
+    let sensing_time = base_sensing - (base_sensing - min_sensing) * exploration_factor;
+    sensing_time + rand::random::<f32>() * 0.2
+}
+
+/// Levy flight: uniformly random headings with power-law distributed run lengths
+fn explore_levy_flight(ant: &mut AntCore, config: &SimConfig) -> f32 {
+    ant.current_direction = rand::random::<f32>() * std::f32::consts::TAU;
+    levy_flight_step(config) / EXPLORING_SPEED
+}
+
+/// Draw a Levy flight run length from a truncated power-law distribution
+pub fn levy_flight_step(config: &SimConfig) -> f32 {
+    let u = rand::random::<f32>().max(1e-6);
+    let step = config.levy_min_step * u.powf(-1.0 / (config.levy_exponent - 1.0));
+    step.min(config.levy_max_step)
+}
+
+/// Expanding spiral: constant-length steps with a turn rate that shrinks as the spiral grows
+fn explore_expanding_spiral(ant: &mut AntCore) -> f32 {
+    ant.exploration_phase += 1.0;
+    let turn = 1.2 / (1.0 + ant.exploration_phase * 0.15);
+    ant.current_direction += turn;
+    0.4
+}
+
+/// Systematic sector sweep: radial out-and-back legs fanning across the ant's home sector
+fn explore_sector_sweep(ant: &mut AntCore, pos: Vec2) -> f32 {
+    let sector_width = std::f32::consts::TAU / 8.0;
+    let legs_per_sweep = 5.0;
+    let leg = ant.exploration_phase as u32;
+    ant.exploration_phase += 1.0;
+
+    if leg.is_multiple_of(2) {
+        // Outbound leg: fan offset steps across the sector on each pass
+        let slot = ((leg / 2) as f32) % legs_per_sweep;
+        let offset = (slot / (legs_per_sweep - 1.0) - 0.5) * sector_width;
+        ant.current_direction = ant.home_sector + offset;
+        // Long enough to reach the far edge of the challenge band
+        (480.0 - pos.length()).max(100.0) / EXPLORING_SPEED
+    } else {
+        // Inbound leg: head back toward the nest ring to start the next pass
+        let to_nest = -pos.normalize_or_zero();
+        ant.current_direction = to_nest.y.atan2(to_nest.x);
+        (pos.length() - 150.0).max(50.0) / EXPLORING_SPEED
+    }
+}
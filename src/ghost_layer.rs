@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use crate::components::*;
+use crate::pheromones::*;
+use crate::terrain::read_png_rgb;
+use crate::video::pheromone_intensity;
+
+/// Where Y writes the current pheromone field
+pub const SNAPSHOT_PATH: &str = "pheromone_snapshot.png";
+/// Opacity of the strongest ghost cell
+const GHOST_ALPHA: f32 = 0.5;
+const WORLD_SIZE: f32 = 1000.0;
+
+/// Snapshot passed with `--ghost <path>`, drawn under the live pheromone grid for A/B comparison
+#[derive(Resource)]
+pub struct GhostSnapshot {
+    pub path: String,
+}
+
+/// The ghost sprite, shown or hidden with the 4 layer toggle
+#[derive(Component)]
+pub struct GhostLayerSprite;
+
+/// Write the grid as a PNG with the display intensity of food, nest and alarm in R, G and B.
+/// Rows run top to bottom like the map PNGs, so the image reads the same way as the world.
+pub fn save_pheromone_snapshot(path: &str, grid: &PheromoneGrid) -> Result<(), Box<dyn std::error::Error>> {
+    let mut pixels = Vec::with_capacity(grid.width * grid.height * 3);
+    for row in 0..grid.height {
+        let y = grid.height - 1 - row;
+        for x in 0..grid.width {
+            let idx = y * grid.width + x;
+            for value in [grid.food_trail[idx], grid.nest_trail[idx], grid.alarm[idx]] {
+                pixels.push((pheromone_intensity(value) * 255.0) as u8);
+            }
+        }
+    }
+
+    let file = std::fs::File::create(path)?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), grid.width as u32, grid.height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels)?;
+    Ok(())
+}
+
+/// Y saves the live pheromone field so a later run can load it with `--ghost`
+pub fn pheromone_snapshot_system(input: Res<ButtonInput<KeyCode>>, pheromone_grid: Option<Res<PheromoneGrid>>) {
+    if !input.just_pressed(KeyCode::KeyY) {
+        return;
+    }
+    let Some(grid) = pheromone_grid else { return };
+    match save_pheromone_snapshot(SNAPSHOT_PATH, &grid) {
+        Ok(()) => info!("📸 Pheromone snapshot saved to {} (load it with --ghost {})", SNAPSHOT_PATH, SNAPSHOT_PATH),
+        Err(e) => error!("❌ Failed to save pheromone snapshot: {}", e),
+    }
+}
+
+/// Turn the snapshot into a translucent grey sprite covering the world, just below the live grid.
+/// Grey keeps the old trails apart from the live colored ones; brightness is the strongest channel.
+pub fn setup_ghost_layer(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    snapshot: Option<Res<GhostSnapshot>>,
+    view: Res<ViewSettings>,
+) {
+    let Some(snapshot) = snapshot else { return };
+    let (width, height, pixels) = match read_png_rgb(&snapshot.path) {
+        Ok(image) => image,
+        Err(e) => {
+            error!("❌ Failed to load pheromone snapshot {}: {}", snapshot.path, e);
+            return;
+        }
+    };
+
+    let data: Vec<u8> = pixels.iter()
+        .flat_map(|&[r, g, b]| {
+            let alpha = (r.max(g).max(b) as f32 * GHOST_ALPHA) as u8;
+            [255, 255, 255, alpha]
+        })
+        .collect();
+    let image = Image::new(
+        Extent3d { width: width as u32, height: height as u32, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+
+    commands.spawn((
+        SpriteBundle {
+            texture: images.add(image),
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(WORLD_SIZE)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, -11.0),
+            visibility: if view.ghost_layer { Visibility::Inherited } else { Visibility::Hidden },
+            ..default()
+        },
+        GhostLayerSprite,
+    ));
+    info!("👻 Ghost pheromone layer from {} ({}x{}), 4 toggles it", snapshot.path, width, height);
+}
+
+pub fn ghost_layer_visibility_system(view: Res<ViewSettings>, mut ghost_query: Query<&mut Visibility, With<GhostLayerSprite>>) {
+    for mut visibility in ghost_query.iter_mut() {
+        *visibility = if view.ghost_layer { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts};
+use crate::components::*;
+
+const GRID_SPACING: f32 = 100.0;
+const WORLD_HALF_SIZE: f32 = 500.0;
+
+/// X shows or hides the coordinate grid
+pub fn world_grid_toggle_system(input: Res<ButtonInput<KeyCode>>, mut view: ResMut<ViewSettings>) {
+    if input.just_pressed(KeyCode::KeyX) {
+        view.world_grid = !view.world_grid;
+    }
+}
+
+/// 100-unit grid, brighter axes through the origin and the world boundary, with coordinates
+/// along the bottom and left edges of the screen
+pub fn world_grid_system(
+    mut gizmos: Gizmos,
+    mut contexts: EguiContexts,
+    view: Res<ViewSettings>,
+    camera_query: Query<(&Camera, &GlobalTransform, &Transform)>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+) {
+    if !view.world_grid {
+        return;
+    }
+
+    let line_count = (WORLD_HALF_SIZE / GRID_SPACING) as i32;
+    for i in -line_count..=line_count {
+        let offset = i as f32 * GRID_SPACING;
+        let color = if i == 0 { Color::srgba(1.0, 1.0, 1.0, 0.5) } else { Color::srgba(1.0, 1.0, 1.0, 0.15) };
+        gizmos.line_2d(Vec2::new(offset, -WORLD_HALF_SIZE), Vec2::new(offset, WORLD_HALF_SIZE), color);
+        gizmos.line_2d(Vec2::new(-WORLD_HALF_SIZE, offset), Vec2::new(WORLD_HALF_SIZE, offset), color);
+    }
+    gizmos.rect_2d(Vec2::ZERO, 0.0, Vec2::splat(WORLD_HALF_SIZE * 2.0), Color::srgb(1.0, 0.85, 0.0));
+
+    let (Ok((camera, camera_global, camera_transform)), Ok(window)) = (camera_query.get_single(), window_query.get_single()) else { return };
+    // Visible world rectangle, so labels stay pinned to the screen edges while panning
+    let center = camera_transform.translation.truncate();
+    let half_extent = window.size() * 0.5 * camera_transform.scale.truncate();
+    let (view_min, view_max) = (center - half_extent, center + half_extent);
+    let margin = 6.0 * camera_transform.scale.x;
+
+    let font = egui::FontId::monospace(11.0);
+    let color = egui::Color32::from_rgb(200, 200, 200);
+    let painter = contexts.ctx_mut().layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("world_grid")));
+    for i in -line_count..=line_count {
+        let offset = i as f32 * GRID_SPACING;
+        if offset > view_min.x && offset < view_max.x {
+            if let Some(screen) = camera.world_to_viewport(camera_global, Vec3::new(offset, view_min.y + margin, 0.0)) {
+                painter.text(egui::pos2(screen.x + 2.0, screen.y) / ui_scale.0, egui::Align2::LEFT_BOTTOM, format!("x={}", offset), font.clone(), color);
+            }
+        }
+        if offset > view_min.y && offset < view_max.y {
+            if let Some(screen) = camera.world_to_viewport(camera_global, Vec3::new(view_min.x + margin, offset, 0.0)) {
+                painter.text(egui::pos2(screen.x, screen.y - 2.0) / ui_scale.0, egui::Align2::LEFT_BOTTOM, format!("y={}", offset), font.clone(), color);
+            }
+        }
+    }
+}
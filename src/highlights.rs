@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use crate::components::*;
+use crate::config::SimConfig;
+use crate::video::{FramePool, FrameSink};
+
+/// Simulation seconds kept on each side of a highlighted event
+const HIGHLIGHT_WINDOW: f32 = 5.0;
+/// Clips per run; later triggers are ignored so a predator gauntlet doesn't write dozens
+const MAX_HIGHLIGHTS: usize = 6;
+
+/// Trails count as formed once the trail signal reaches this share of saturation,
+/// and as collapsed when it falls below COLLAPSE_FRACTION of its peak
+const TRAILS_FORMED: f32 = 0.5;
+const COLLAPSE_FRACTION: f32 = 0.4;
+
+/// An event worth a clip, waiting for the frames after it
+struct Highlight {
+    name: &'static str,
+    time: f32,
+}
+
+/// Short clips around notable events, cut from the main video's frames as they are captured
+#[derive(Resource, Default)]
+pub struct HighlightClips {
+    recent: VecDeque<(f32, Vec<u8>)>, // Captured frames with their time, back to the earliest window still needed
+    pending: Vec<Highlight>,
+    triggered: usize,
+    last_trigger: Option<f32>,
+    seen_delivery: bool,
+    pub encoding: Vec<FrameSink>, // Clips whose encoder thread may still be running
+    pub saved: Vec<String>, // Clip files written this run
+}
+
+impl HighlightClips {
+    /// Queue a clip unless it overlaps the previous one or the run already has enough
+    fn trigger(&mut self, name: &'static str, time: f32) {
+        if self.triggered >= MAX_HIGHLIGHTS || self.last_trigger.is_some_and(|last| time - last < HIGHLIGHT_WINDOW) {
+            return;
+        }
+        self.triggered += 1;
+        self.last_trigger = Some(time);
+        self.pending.push(Highlight { name, time });
+        info!("✨ Highlight at {:.1}s: {}", time, name.replace('_', " "));
+    }
+
+    /// Keep a captured frame for any clip that may still need it, handing back to `pool` the frames
+    /// no clip needs any more and the oldest beyond `budget` bytes
+    pub fn record(&mut self, time: f32, frame: &[u8], budget: usize, pool: &FramePool) {
+        if self.triggered >= MAX_HIGHLIGHTS && self.pending.is_empty() {
+            for (_, frame) in self.recent.drain(..) {
+                pool.give(frame);
+            }
+            return;
+        }
+        self.recent.push_back((time, pool.copy_of(frame)));
+        let keep_from = self.pending.first().map_or(time, |highlight| highlight.time.min(time)) - HIGHLIGHT_WINDOW;
+        while self.recent.front().is_some_and(|(captured, _)| *captured < keep_from)
+            || self.recent.len() * frame.len().max(1) > budget {
+            let Some((_, expired)) = self.recent.pop_front() else { break };
+            pool.give(expired);
+        }
+    }
+
+    /// Clips whose window has passed by `now`, or every pending clip when `flush` is set at the end of
+    /// the run, as a name and the frames inside the window
+    pub fn take_finished(&mut self, now: f32, flush: bool, pool: &FramePool) -> Vec<(&'static str, Vec<Vec<u8>>)> {
+        let (done, waiting): (Vec<Highlight>, Vec<Highlight>) = self.pending.drain(..)
+            .partition(|highlight| flush || highlight.time + HIGHLIGHT_WINDOW <= now);
+        self.pending = waiting;
+        done.into_iter()
+            .map(|highlight| {
+                let frames = self.recent.iter()
+                    .filter(|(captured, _)| (captured - highlight.time).abs() <= HIGHLIGHT_WINDOW)
+                    .map(|(_, frame)| pool.copy_of(frame))
+                    .collect();
+                (highlight.name, frames)
+            })
+            .collect()
+    }
+
+    /// One clip file per line for the metadata file
+    pub fn summary(&self) -> String {
+        self.saved.iter().map(|path| format!("{}\n", path)).collect()
+    }
+}
+
+/// Pick out the first delivery, predator kills and trail collapses from the event stream
+pub fn highlight_trigger_system(
+    mut clips: ResMut<HighlightClips>,
+    mut deliveries: EventReader<FoodDelivered>,
+    mut sim_events: EventReader<SimEvent>,
+) {
+    for delivery in deliveries.read() {
+        if !clips.seen_delivery {
+            clips.seen_delivery = true;
+            clips.trigger("first_delivery", delivery.time);
+        }
+    }
+    for event in sim_events.read() {
+        match event.kind {
+            SimEventKind::PredatorKill => clips.trigger("predator_kill", event.time),
+            SimEventKind::TrailCollapse => clips.trigger("trail_collapse", event.time),
+            _ => {}
+        }
+    }
+}
+
+/// Peak trail strength since trails last formed, and whether a collapse was already reported
+#[derive(Default)]
+pub struct TrailCollapseWatch {
+    peak: f32,
+    collapsed: bool,
+}
+
+/// Report when established trails fall apart, using the same trail signal as task allocation.
+/// Re-arms once trails form again.
+pub fn trail_collapse_system(
+    mut watch: Local<TrailCollapseWatch>,
+    mut sim_events: EventWriter<SimEvent>,
+    colony: Res<ColonyState>,
+    config: Res<SimConfig>,
+    nest_query: Query<&Transform, With<Nest>>,
+    time: Res<Time>,
+) {
+    let strength = (colony.trail_signal / config.trail_signal_saturation).clamp(0.0, 1.0);
+    if watch.collapsed {
+        if strength >= TRAILS_FORMED {
+            *watch = TrailCollapseWatch { peak: strength, collapsed: false };
+        }
+        return;
+    }
+    watch.peak = watch.peak.max(strength);
+    if watch.peak >= TRAILS_FORMED && strength < watch.peak * COLLAPSE_FRACTION {
+        watch.collapsed = true;
+        let position = nest_query.iter().next().map_or(Vec2::ZERO, |transform| transform.translation.truncate());
+        sim_events.send(SimEvent { kind: SimEventKind::TrailCollapse, position, time: time.elapsed_seconds(), ant: None });
+    }
+}
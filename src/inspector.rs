@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::config::*;
+use crate::colors::*;
+
+/// Live parameter editor, toggled with I. Edits write straight into the running resources.
+#[derive(Resource, Default)]
+pub struct InspectorState {
+    pub visible: bool,
+    pub pointer_captured: bool, // Pointer is over egui UI, so world clicks are ignored
+}
+
+/// Run condition for systems that act on mouse clicks in the world
+pub fn pointer_outside_inspector(inspector: Res<InspectorState>) -> bool {
+    !inspector.pointer_captured
+}
+
+/// While an egui text field or slider has keyboard focus, hide the keys from the game's shortcuts
+pub fn absorb_keyboard_for_egui(mut contexts: EguiContexts, mut keys: ResMut<ButtonInput<KeyCode>>) {
+    if contexts.ctx_mut().wants_keyboard_input() {
+        keys.reset_all();
+    }
+}
+
+pub fn inspector_toggle_system(input: Res<ButtonInput<KeyCode>>, mut inspector: ResMut<InspectorState>) {
+    if input.just_pressed(KeyCode::KeyI) {
+        inspector.visible = !inspector.visible;
+    }
+}
+
+pub fn inspector_ui_system(
+    mut contexts: EguiContexts,
+    mut inspector: ResMut<InspectorState>,
+    mut config: ResMut<SimConfig>,
+    mut color_config: ResMut<ColorConfig>,
+) {
+    let ctx = contexts.ctx_mut();
+    // Any egui window (dashboard, legend, chart) blocks world clicks, not only this panel
+    inspector.pointer_captured = ctx.is_pointer_over_area() || ctx.wants_pointer_input();
+    if !inspector.visible {
+        return;
+    }
+
+    egui::SidePanel::left("inspector").default_width(320.0).show(ctx, |ui| {
+        ui.heading("Inspector (I to hide)");
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::CollapsingHeader::new("Pheromones").default_open(true).show(ui, |ui| {
+                egui::Grid::new("pheromone_channels").num_columns(4).striped(true).show(ui, |ui| {
+                    ui.label("");
+                    ui.label("Evaporation");
+                    ui.label("Diffusion");
+                    ui.label("Deposit");
+                    ui.end_row();
+
+                    ui.label("Food");
+                    ui.add(egui::DragValue::new(&mut config.evap_food).speed(0.00001).range(0.0..=0.1));
+                    ui.add(egui::DragValue::new(&mut config.diff_food).speed(0.001).range(0.0..=1.0));
+                    ui.add(egui::DragValue::new(&mut config.lay_rate_food).speed(0.5).range(0.0..=200.0));
+                    ui.end_row();
+
+                    ui.label("Nest");
+                    ui.add(egui::DragValue::new(&mut config.evap_nest).speed(0.00001).range(0.0..=0.1));
+                    ui.add(egui::DragValue::new(&mut config.diff_nest).speed(0.001).range(0.0..=1.0));
+                    ui.add(egui::DragValue::new(&mut config.lay_rate_nest).speed(0.5).range(0.0..=200.0));
+                    ui.end_row();
+
+                    ui.label("Alarm");
+                    ui.add(egui::DragValue::new(&mut config.evap_alarm).speed(0.0001).range(0.0..=0.5));
+                    ui.add(egui::DragValue::new(&mut config.diff_alarm).speed(0.001).range(0.0..=1.0));
+                    ui.add(egui::DragValue::new(&mut config.predator_alarm_amount).speed(0.5).range(0.0..=200.0));
+                    ui.end_row();
+                });
+                ui.add(egui::Slider::new(&mut config.detection_threshold, 0.00001..=0.1).logarithmic(true).text("detection threshold"));
+                ui.add(egui::Slider::new(&mut config.saturation_limit, 0.1..=100.0).logarithmic(true).text("saturation limit"));
+                ui.add(egui::Slider::new(&mut config.food_quality_weight, 0.0..=5.0).text("food quality weight"));
+                ui.checkbox(&mut config.classic_aco, "classic ACO deposits");
+                ui.add(egui::Slider::new(&mut config.aco_pheromone_q, 1000.0..=100000.0).logarithmic(true).text("ACO Q"));
+            });
+
+            egui::CollapsingHeader::new("Ant behavior").default_open(true).show(ui, |ui| {
+                ui.add(egui::Slider::new(&mut config.follow_gain, 0.0..=10.0).text("follow gain"));
+                ui.add(egui::Slider::new(&mut config.base_exploration_noise, 0.0..=0.5).text("exploration noise"));
+                ui.add(egui::Slider::new(&mut config.max_turn_rate, 0.5..=20.0).text("max turn rate (rad/s)"));
+                ui.add(egui::Slider::new(&mut config.separation_radius, 0.0..=40.0).text("separation radius"));
+                ui.add(egui::Slider::new(&mut config.separation_strength, 0.0..=200.0).text("separation strength"));
+                ui.add(egui::Slider::new(&mut config.sensor_noise_std, 0.0..=1.0).text("sensor noise"));
+                ui.add(egui::Slider::new(&mut config.sensor_false_negative_rate, 0.0..=1.0).text("sensor miss rate"));
+                ui.add(egui::Slider::new(&mut config.lod_interval, 1..=8).text("off-trail sensing interval (ticks)"));
+                ui.add(egui::Slider::new(&mut config.analysis_interval, 0.0..=5.0).text("behavior analysis interval (s, 0 = off)"));
+
+                egui::ComboBox::from_label("exploration")
+                    .selected_text(format!("{:?}", config.exploration_strategy))
+                    .show_ui(ui, |ui| {
+                        for strategy in [
+                            ExplorationStrategy::RandomTurn,
+                            ExplorationStrategy::LevyFlight,
+                            ExplorationStrategy::ExpandingSpiral,
+                            ExplorationStrategy::SectorSweep,
+                        ] {
+                            ui.selectable_value(&mut config.exploration_strategy, strategy, format!("{:?}", strategy));
+                        }
+                    });
+            });
+
+            egui::CollapsingHeader::new("Colony").show(ui, |ui| {
+                ui.add(egui::Slider::new(&mut config.initial_ants, 1..=500).text("population cap"));
+                ui.add(egui::Slider::new(&mut config.spawn_batch_size, 1..=100).text("spawn batch"));
+                ui.add(egui::Slider::new(&mut config.spawn_interval, 0.5..=60.0).text("spawn interval (s)"));
+                ui.add(egui::Slider::new(&mut config.scout_ratio_min, 0.0..=1.0).text("scout ratio min"));
+                ui.add(egui::Slider::new(&mut config.scout_ratio_max, 0.0..=1.0).text("scout ratio max"));
+                ui.add(egui::Slider::new(&mut config.scout_trail_threshold, 0.0..=5.0).text("scout trail threshold"));
+                ui.checkbox(&mut config.food_regrow_in_place, "food regrows in place");
+                ui.add(egui::Slider::new(&mut config.food_regrow_cooldown, 0.0..=120.0).text("regrow cooldown (s)"));
+                ui.add(egui::Slider::new(&mut config.placed_food_amount, 1.0..=1000.0).logarithmic(true).text("Ctrl+click food amount"));
+            });
+
+            egui::CollapsingHeader::new("Terrain, predators and prey").show(ui, |ui| {
+                ui.add(egui::Slider::new(&mut config.terrain_height_scale, 0.0..=500.0).text("terrain height scale"));
+                ui.add(egui::Slider::new(&mut config.slope_cost, 0.0..=20.0).text("slope cost"));
+                ui.add(egui::Slider::new(&mut config.predator_speed, 0.0..=200.0).text("predator speed"));
+                ui.add(egui::Slider::new(&mut config.predator_kill_radius, 0.0..=50.0).text("predator kill radius"));
+                ui.add(egui::Slider::new(&mut config.prey_speed, 0.0..=50.0).text("prey speed"));
+                ui.add(egui::Slider::new(&mut config.prey_turn_rate, 0.0..=10.0).text("prey turn rate"));
+            });
+
+            egui::CollapsingHeader::new("Colors").show(ui, |ui| {
+                color_row(ui, "food pheromone", &mut color_config.food_pheromone);
+                color_row(ui, "nest pheromone", &mut color_config.nest_pheromone);
+                color_row(ui, "alarm pheromone", &mut color_config.alarm_pheromone);
+                color_row(ui, "nest", &mut color_config.nest);
+                color_row(ui, "food source", &mut color_config.food_source);
+                color_row(ui, "ant exploring", &mut color_config.ant_exploring);
+                color_row(ui, "ant carrying food", &mut color_config.ant_carrying_food);
+                color_row(ui, "ant collecting", &mut color_config.ant_collecting);
+                color_row(ui, "predator", &mut color_config.predator);
+                color_row(ui, "rock", &mut color_config.rock);
+            });
+
+            if ui.button("Reset all to defaults").clicked() {
+                *config = SimConfig::default();
+                *color_config = ColorConfig::default();
+            }
+        });
+    });
+}
+
+/// Color picker bound to a Bevy color
+fn color_row(ui: &mut egui::Ui, label: &str, color: &mut Color) {
+    ui.horizontal(|ui| {
+        let mut rgba = color.to_srgba().to_f32_array();
+        if ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed() {
+            *color = Color::srgba(rgba[0], rgba[1], rgba[2], rgba[3]);
+        }
+        ui.label(label);
+    });
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use crate::components::*;
+
+/// Simulation ticks per second unless `--sim-hz` says otherwise
+pub const DEFAULT_SIM_HZ: f64 = 60.0;
+
+/// Where a moving entity stood after the previous and the latest fixed tick. The simulation sees
+/// `current`; between ticks the sprite is drawn part way from `previous` to `current`, so motion
+/// stays smooth whatever the render frame rate is compared to the tick rate.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct TickPositions {
+    pub previous: Vec2,
+    pub current: Vec2,
+}
+
+/// Parse the `--sim-hz` value, a tick rate in ticks per second
+pub fn parse_sim_hz(value: &str) -> Option<f64> {
+    value.parse::<f64>().ok().filter(|hz| hz.is_finite() && *hz > 0.0)
+}
+
+/// FixedPreUpdate: put the simulated position back before the tick, undoing the interpolation
+pub fn restore_tick_positions(mut moving: Query<(&mut Transform, &mut TickPositions)>) {
+    for (mut transform, mut positions) in moving.iter_mut() {
+        transform.translation.x = positions.current.x;
+        transform.translation.y = positions.current.y;
+        positions.previous = positions.current;
+    }
+}
+
+/// FixedPostUpdate: remember where the tick left each ant, prey and predator, starting to track new ones
+pub fn record_tick_positions(
+    mut commands: Commands,
+    mut tracked: Query<(&Transform, &mut TickPositions)>,
+    untracked: Query<(Entity, &Transform), (Without<TickPositions>, Or<(With<AntCore>, With<Prey>, With<Predator>)>)>,
+) {
+    for (transform, mut positions) in tracked.iter_mut() {
+        positions.current = transform.translation.truncate();
+    }
+    for (entity, transform) in untracked.iter() {
+        let position = transform.translation.truncate();
+        commands.entity(entity).insert(TickPositions { previous: position, current: position });
+    }
+}
+
+/// PostUpdate, before transforms propagate: draw each tracked entity between its last two tick positions
+pub fn interpolate_tick_positions(fixed_time: Res<Time<Fixed>>, mut moving: Query<(&mut Transform, &TickPositions)>) {
+    let blend = fixed_time.overstep_fraction();
+    for (mut transform, positions) in moving.iter_mut() {
+        let position = positions.previous.lerp(positions.current, blend);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts};
+use crate::components::*;
+use crate::colors::*;
+use crate::video::ant_rgb;
+
+/// Labels are hidden once the camera is zoomed out past this scale (world units per pixel)
+const LABEL_MAX_ZOOM: f32 = 1.5;
+/// World units between an ant's centre and its label
+const LABEL_OFFSET: f32 = 12.0;
+
+/// One-letter code for the label: E exploring, S sensing, F following, T tracking
+fn state_code(state: AntBehaviorState) -> char {
+    match state {
+        AntBehaviorState::Exploring => 'E',
+        AntBehaviorState::Sensing => 'S',
+        AntBehaviorState::Following => 'F',
+        AntBehaviorState::Tracking => 'T',
+    }
+}
+
+/// T shows or hides the index and state labels
+pub fn ant_label_toggle_system(input: Res<ButtonInput<KeyCode>>, mut view: ResMut<ViewSettings>) {
+    if input.just_pressed(KeyCode::KeyT) {
+        view.ant_labels = !view.ant_labels;
+        info!("🏷️ Ant labels {}", if view.ant_labels { "on" } else { "off" });
+    }
+}
+
+/// `#index code` above every visible ant, tinted like the ant so carriers stand out
+pub fn ant_label_system(
+    mut contexts: EguiContexts,
+    view: Res<ViewSettings>,
+    color_config: Res<ColorConfig>,
+    camera_query: Query<(&Camera, &GlobalTransform, &Transform)>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    ant_query: Query<(&Transform, &AntCore)>,
+    ui_scale: Res<UiScale>,
+) {
+    if !view.ant_labels {
+        return;
+    }
+    let (Ok((camera, camera_global, camera_transform)), Ok(window)) = (camera_query.get_single(), window_query.get_single()) else { return };
+    if camera_transform.scale.x > LABEL_MAX_ZOOM {
+        return;
+    }
+
+    let font = egui::FontId::monospace(11.0);
+    // Behind egui windows and panels, above the world
+    let painter = contexts.ctx_mut().layer_painter(egui::LayerId::new(egui::Order::Background, egui::Id::new("ant_labels")));
+    for (transform, ant_state) in ant_query.iter() {
+        let anchor = transform.translation + Vec3::Y * LABEL_OFFSET;
+        let Some(screen) = camera.world_to_viewport(camera_global, anchor) else { continue };
+        if screen.x < 0.0 || screen.y < 0.0 || screen.x > window.width() || screen.y > window.height() {
+            continue;
+        }
+        let (r, g, b) = ant_rgb(&color_config, ant_state);
+        painter.text(
+            egui::pos2(screen.x, screen.y) / ui_scale.0, // egui points shrink or grow with the UI scale
+            egui::Align2::CENTER_BOTTOM,
+            format!("#{} {}", ant_state.index, state_code(ant_state.behavior_state)),
+            font.clone(),
+            egui::Color32::from_rgb(r, g, b),
+        );
+    }
+}
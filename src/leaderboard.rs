@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::fs;
+use crate::config::*;
+use crate::scoring::*;
+
+/// Local file holding the best run per challenge
+pub const LEADERBOARD_FILE: &str = "leaderboard.json";
+
+/// Best run recorded for one challenge
+#[derive(Clone, Debug)]
+pub struct Record {
+    pub score: f32,
+    pub verdict: String,
+    pub config_hash: String, // Fingerprint of the SimConfig that set the record
+    pub seed: Option<u64>,   // Map seed when the layout was generated
+    pub date: String,
+}
+
+/// Best score per challenge number, persisted to `leaderboard.json`
+#[derive(Resource, Default)]
+pub struct Leaderboard {
+    pub records: BTreeMap<u32, Record>,
+}
+
+impl Leaderboard {
+    /// Load the leaderboard, starting empty if the file is missing or unreadable
+    pub fn load() -> Self {
+        let Ok(content) = fs::read_to_string(LEADERBOARD_FILE) else {
+            return Self::default();
+        };
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else {
+            warn!("⚠️ {} is not valid JSON, starting a fresh leaderboard", LEADERBOARD_FILE);
+            return Self::default();
+        };
+
+        let mut records = BTreeMap::new();
+        if let Some(challenges) = json["challenges"].as_object() {
+            for (number, record) in challenges {
+                let (Ok(number), Some(score)) = (number.parse::<u32>(), record["score"].as_f64()) else {
+                    continue;
+                };
+                records.insert(number, Record {
+                    score: score as f32,
+                    verdict: record["verdict"].as_str().unwrap_or_default().to_string(),
+                    config_hash: record["config_hash"].as_str().unwrap_or_default().to_string(),
+                    seed: record["seed"].as_u64(),
+                    date: record["date"].as_str().unwrap_or_default().to_string(),
+                });
+            }
+        }
+        Self { records }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let challenges: serde_json::Map<String, serde_json::Value> = self.records.iter()
+            .map(|(number, record)| (number.to_string(), serde_json::json!({
+                "score": record.score,
+                "verdict": record.verdict,
+                "config_hash": record.config_hash,
+                "seed": record.seed,
+                "date": record.date,
+            })))
+            .collect();
+        let json = serde_json::json!({ "challenges": challenges });
+        fs::write(LEADERBOARD_FILE, serde_json::to_string_pretty(&json)?)?;
+        Ok(())
+    }
+
+    /// Keep `record` if it beats the current best; returns true when it does
+    pub fn submit(&mut self, challenge: u32, record: Record) -> bool {
+        let beaten = self.records.get(&challenge).is_none_or(|best| record.score > best.score);
+        if beaten {
+            self.records.insert(challenge, record);
+        }
+        beaten
+    }
+
+    /// HUD line describing the record for a challenge
+    pub fn record_line(&self, challenge: u32) -> String {
+        match self.records.get(&challenge) {
+            Some(record) => format!("Record to beat: {:.1} ({}, config {})", record.score, record.date, record.config_hash),
+            None => "Record to beat: none yet".to_string(),
+        }
+    }
+}
+
+/// Stable fingerprint of every simulation parameter (FNV-1a over the debug representation)
+pub fn config_hash(config: &SimConfig) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in format!("{:?}", config).bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:08x}", hash >> 32)
+}
+
+/// Submit each decided run (and each curriculum stage) to the leaderboard
+pub fn leaderboard_system(
+    mut leaderboard: ResMut<Leaderboard>,
+    challenge_score: Res<ChallengeScore>,
+    config: Res<SimConfig>,
+    mut submitted: Local<bool>,
+) {
+    if challenge_score.verdict == Verdict::Running {
+        *submitted = false;
+        return;
+    }
+    if *submitted {
+        return;
+    }
+    *submitted = true;
+
+    let record = Record {
+        score: challenge_score.score,
+        verdict: challenge_score.verdict.label().to_string(),
+        config_hash: config_hash(&config),
+        seed: if config.procedural_map { Some(config.map_seed) } else { None },
+        date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+    };
+
+    let previous = leaderboard.records.get(&challenge_score.challenge).map(|best| best.score);
+    if leaderboard.submit(challenge_score.challenge, record) {
+        match previous {
+            Some(previous) => info!("🥇 New record for challenge {}: {:.1} (was {:.1})", challenge_score.challenge, challenge_score.score, previous),
+            None => info!("🥇 First record for challenge {}: {:.1}", challenge_score.challenge, challenge_score.score),
+        }
+        if let Err(e) = leaderboard.save() {
+            error!("❌ Failed to save {}: {}", LEADERBOARD_FILE, e);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use crate::colors::*;
+use crate::components::ViewSettings;
+
+/// L shows or hides the color key
+pub fn color_legend_toggle_system(input: Res<ButtonInput<KeyCode>>, mut view: ResMut<ViewSettings>) {
+    if input.just_pressed(KeyCode::KeyL) {
+        view.legend = !view.legend;
+    }
+}
+
+fn to_egui(color: Color) -> egui::Color32 {
+    let [r, g, b, _] = color.to_srgba().to_u8_array();
+    egui::Color32::from_rgb(r, g, b)
+}
+
+/// Square swatch followed by its label
+fn legend_row(ui: &mut egui::Ui, color: Color, label: &str) {
+    ui.horizontal(|ui| {
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+        ui.painter().rect_filled(rect, 2.0, to_egui(color));
+        ui.label(label);
+    });
+}
+
+/// Color key built from the live ColorConfig, bottom-right left of the minimap.
+/// Redrawn every frame, so inspector color edits show up immediately.
+pub fn color_legend_system(mut contexts: EguiContexts, view: Res<ViewSettings>, color_config: Res<ColorConfig>) {
+    if !view.legend {
+        return;
+    }
+
+    egui::Area::new(egui::Id::new("color_legend"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-220.0, -70.0))
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Frame::none().fill(egui::Color32::from_black_alpha(160)).inner_margin(6.0).show(ui, |ui| {
+                ui.label(egui::RichText::new("Legend (L to hide)").strong());
+                ui.label("Ants");
+                legend_row(ui, color_config.ant_exploring, "exploring");
+                legend_row(ui, color_config.ant_collecting, "collecting");
+                legend_row(ui, color_config.ant_carrying_food, "carrying food");
+                legend_row(ui, color_config.colony_tint(color_config.ant_exploring, 1), "rival colony (exploring)");
+                ui.label("Pheromones");
+                legend_row(ui, color_config.food_pheromone, "food trail");
+                legend_row(ui, color_config.nest_pheromone, "nest trail");
+                legend_row(ui, color_config.alarm_pheromone, "alarm");
+                ui.label("World");
+                legend_row(ui, color_config.nest, "nest");
+                legend_row(ui, color_config.food_source, "food source");
+                legend_row(ui, color_config.rock, "rock");
+                legend_row(ui, color_config.predator, "predator");
+            });
+        });
+}
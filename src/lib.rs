@@ -0,0 +1,50 @@
+//! The ant simulation as a library. AntSimPlugin runs all of it inside any Bevy app, or SimulationPlugin
+//! plus any of ChallengePlugin, DebugUiPlugin and VideoPlugin runs just those parts; main.rs adds
+//! AntSimPlugin with the command-line flags applied, and the benchmarks in benches/ drive its systems directly.
+
+pub mod components;
+pub mod systems;
+pub mod pheromones;
+pub mod config;
+pub mod video;
+pub mod colors;
+pub mod terrain;
+pub mod exploration;
+pub mod maze;
+pub mod challenges;
+pub mod mapgen;
+pub mod worldmap;
+pub mod editor;
+pub mod scoring;
+pub mod timed_events;
+pub mod tournament;
+pub mod curriculum;
+pub mod leaderboard;
+pub mod playback;
+pub mod inspector;
+pub mod minimap;
+pub mod trends;
+pub mod logging;
+pub mod labels;
+pub mod legend;
+pub mod event_log;
+pub mod grid_overlay;
+pub mod profiling;
+pub mod annotations;
+pub mod ui_scale;
+pub mod colony_panel;
+pub mod ghost_layer;
+pub mod camera_path;
+pub mod highlights;
+pub mod contact_sheet;
+pub mod sonification;
+pub mod replay;
+pub mod replay_timeline;
+pub mod spatial_index;
+pub mod interpolation;
+pub mod policy;
+pub mod plugin;
+
+pub use plugin::{AntSimPlugin, SimulationPlugin, ChallengePlugin, DebugUiPlugin, VideoPlugin};
+pub use config::SimConfig;
+pub use components::*;
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use bevy::log::tracing_subscriber::{self, prelude::*, reload, EnvFilter, Registry};
+
+/// Console verbosity levels cycled with the backquote key, quietest first
+const LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+/// Engine crates stay quiet whatever our own verbosity is
+fn filter_for(level: &str) -> EnvFilter {
+    EnvFilter::new(format!("info,wgpu=error,naga=warn,antsim={}", level))
+}
+
+/// Current console verbosity for the simulation's own log lines, adjustable while running
+#[derive(Resource)]
+pub struct LogVerbosity {
+    pub level: &'static str,
+    handle: reload::Handle<EnvFilter, Registry>,
+}
+
+impl LogVerbosity {
+    pub fn set(&mut self, level: &'static str) {
+        self.level = level;
+        if let Err(e) = self.handle.modify(|filter| *filter = filter_for(level)) {
+            eprintln!("Failed to change log level: {}", e);
+        }
+    }
+}
+
+/// Parse a `--log-level` value
+pub fn parse_log_level(name: &str) -> Option<&'static str> {
+    LEVELS.iter().copied().find(|level| level.eq_ignore_ascii_case(name))
+}
+
+/// Install the global logger; replaces Bevy's LogPlugin so the filter can be swapped at runtime
+pub fn init_logging(level: &'static str) -> LogVerbosity {
+    let (filter, handle) = reload::Layer::new(filter_for(level));
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_target(false))
+        .init();
+    LogVerbosity { level, handle }
+}
+
+/// Backquote cycles error -> warn -> info -> debug -> trace
+pub fn log_verbosity_toggle_system(input: Res<ButtonInput<KeyCode>>, mut verbosity: ResMut<LogVerbosity>) {
+    if input.just_pressed(KeyCode::Backquote) {
+        let current = LEVELS.iter().position(|&level| level == verbosity.level).unwrap_or(2);
+        let next = LEVELS[(current + 1) % LEVELS.len()];
+        verbosity.set(next);
+        // Shown even at error level so the change is visible
+        eprintln!("📝 Console log level: {}", next);
+    }
+}
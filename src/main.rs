@@ -1,39 +1,424 @@
 use bevy::prelude::*;
 use std::env;
 
-mod components;
-mod systems;
-mod pheromones;
-mod config;
-mod video;
-mod colors;
-
-use components::*;
-use systems::*;
-use config::*;
-use pheromones::*;
-use video::*;
-use colors::*;
+use antsim::AntSimPlugin;
+use antsim::config::*;
+use antsim::components::*;
+use antsim::video::*;
+use antsim::terrain::*;
+use antsim::mapgen::*;
+use antsim::worldmap::*;
+use antsim::scoring::*;
+use antsim::timed_events::*;
+use antsim::tournament::*;
+use antsim::curriculum::*;
+use antsim::logging::*;
+use antsim::ui_scale::*;
+use antsim::ghost_layer::*;
+use antsim::camera_path::*;
+use antsim::interpolation::*;
+use antsim::maze::{MIN_CORRIDOR_WIDTH, MAX_CORRIDOR_WIDTH};
+use antsim::policy::ActivePolicy;
+use antsim::sonification::Soundtrack;
 
 fn main() {
     // Parse command line arguments for challenge selection
     let args: Vec<String> = env::args().collect();
     let mut challenge_number = 1u32;
     
+    // Look for --log-level argument first so everything after it goes through the logger
+    let mut log_level = "info";
+    for i in 0..args.len() {
+        if args[i] == "--log-level" && i + 1 < args.len() {
+            match parse_log_level(&args[i + 1]) {
+                Some(level) => log_level = level,
+                None => eprintln!("❌ Unknown log level '{}' (expected error, warn, info, debug or trace)", args[i + 1]),
+            }
+        }
+    }
+    let log_verbosity = init_logging(log_level);
+    
     // Look for --challenge argument
     for i in 0..args.len() {
         if args[i] == "--challenge" && i + 1 < args.len() {
             if let Ok(num) = args[i + 1].parse::<u32>() {
                 challenge_number = num;
-                println!("🎯 Running Challenge {}", challenge_number);
+                info!("🎯 Running Challenge {}", challenge_number);
                 break;
             }
         }
     }
     
-    let challenge_config = ChallengeConfig { challenge_number };
+    // Look for --tournament argument (variants file, each run as a separate process on the same seeded maps)
+    for i in 0..args.len() {
+        if args[i] == "--tournament" && i + 1 < args.len() {
+            let mut seeds = DEFAULT_TOURNAMENT_SEEDS.to_vec();
+            for j in 0..args.len() {
+                if args[j] == "--tournament-seeds" && j + 1 < args.len() {
+                    seeds = args[j + 1].split(',').filter_map(|seed| seed.trim().parse().ok()).collect();
+                }
+            }
+            match load_variants(&args[i + 1]) {
+                Ok(variants) => run_tournament(&variants, &seeds, challenge_number),
+                Err(e) => error!("❌ Failed to load tournament variants {}: {}", args[i + 1], e),
+            }
+            return;
+        }
+    }
+    
+    // Look for --result-file argument (set by the tournament for each run)
+    let mut result_file = None;
+    for i in 0..args.len() {
+        if args[i] == "--result-file" && i + 1 < args.len() {
+            result_file = Some(ResultFile(args[i + 1].clone()));
+            break;
+        }
+    }
+    
+    // Look for --heightmap argument (grayscale PNG, white = high ground)
+    let mut heightmap = None;
+    for i in 0..args.len() {
+        if args[i] == "--heightmap" && i + 1 < args.len() {
+            match Heightmap::from_png(&args[i + 1]) {
+                Ok(map) => {
+                    info!("⛰️ Loaded heightmap {} ({}x{})", args[i + 1], map.width, map.height);
+                    heightmap = Some(map);
+                }
+                Err(e) => error!("❌ Failed to load heightmap {}: {}", args[i + 1], e),
+            }
+            break;
+        }
+    }
+    
+    // Look for --map argument (PNG layout: yellow nest, green food, gray rocks, blue water)
+    let mut world_map = None;
+    for i in 0..args.len() {
+        if args[i] == "--map" && i + 1 < args.len() {
+            match WorldMap::from_png(&args[i + 1]) {
+                Ok(map) => {
+                    info!("🖼️ Loaded map {}: {} nests, {} food sources, {} rock cells",
+                        args[i + 1], map.nests.len(), map.food.len(), map.rocks.len());
+                    world_map = Some(map);
+                }
+                Err(e) => error!("❌ Failed to load map {}: {}", args[i + 1], e),
+            }
+            break;
+        }
+    }
+    
+    // Look for --events argument (timed disruptions, one per line: "30 rock 100 50 25",
+    // plus video camera keyframes: "20 camera -150 80 2.5")
+    let mut scripted_events = Vec::new();
+    let mut scripted_camera = Vec::new();
+    for i in 0..args.len() {
+        if args[i] == "--events" && i + 1 < args.len() {
+            match EventSchedule::load_events(&args[i + 1]) {
+                Ok(events) => {
+                    info!("⏰ Loaded {} timed events from {}", events.len(), args[i + 1]);
+                    scripted_events = events;
+                }
+                Err(e) => error!("❌ Failed to load events {}: {}", args[i + 1], e),
+            }
+            match CameraPath::load_keyframes(&args[i + 1]) {
+                Ok(keyframes) => scripted_camera = keyframes,
+                Err(e) => error!("❌ Failed to load camera keyframes {}: {}", args[i + 1], e),
+            }
+            break;
+        }
+    }
+    
+    // Look for --prey flag (scattered food wanders like challenge 7's prey and must be re-discovered)
+    let prey_food = args.iter().any(|arg| arg == "--prey");
+    if prey_food {
+        info!("🦗 Prey mode: food sources wander slowly around the world");
+    }
+    
+    // Look for --exploration argument (random, levy, spiral, sweep)
+    let mut sim_config = SimConfig::default();
+    for i in 0..args.len() {
+        if args[i] == "--exploration" && i + 1 < args.len() {
+            match ExplorationStrategy::from_name(&args[i + 1]) {
+                Some(strategy) => {
+                    sim_config.exploration_strategy = strategy;
+                    info!("🧭 Exploration strategy: {:?}", strategy);
+                }
+                None => error!("❌ Unknown exploration strategy '{}' (expected random, levy, spiral or sweep)", args[i + 1]),
+            }
+            break;
+        }
+    }
+    
+    // Look for --policy argument (hand-tuned, aco)
+    let mut policy = ActivePolicy::default();
+    for i in 0..args.len() {
+        if args[i] == "--policy" && i + 1 < args.len() {
+            match ActivePolicy::from_name(&args[i + 1]) {
+                Some(chosen) => {
+                    policy = chosen;
+                    info!("🧠 Decision policy: {}", policy.0.name());
+                }
+                None => error!("❌ Unknown policy '{}' (expected hand-tuned or aco)", args[i + 1]),
+            }
+            break;
+        }
+    }
+    
+    // Look for sensor noise arguments (--sensor-noise <std>, --sensor-miss <probability>)
+    for i in 0..args.len() {
+        if i + 1 >= args.len() {
+            break;
+        }
+        if args[i] == "--sensor-noise" {
+            if let Ok(value) = args[i + 1].parse::<f32>() {
+                sim_config.sensor_noise_std = value.max(0.0);
+                info!("📡 Sensor noise std: {:.3}", sim_config.sensor_noise_std);
+            }
+        } else if args[i] == "--sensor-miss" {
+            if let Ok(value) = args[i + 1].parse::<f32>() {
+                sim_config.sensor_false_negative_rate = value.clamp(0.0, 1.0);
+                info!("📡 Sensor false-negative rate: {:.2}", sim_config.sensor_false_negative_rate);
+            }
+        }
+    }
+    
+    // Look for --lod <n> (ants far from any pheromone sense every n ticks)
+    for i in 0..args.len() {
+        if args[i] == "--lod" && i + 1 < args.len() {
+            if let Ok(value) = args[i + 1].parse::<u32>() {
+                sim_config.lod_interval = value.max(1);
+                info!("🔭 Level of detail: off-trail ants sense every {} ticks", sim_config.lod_interval);
+            }
+        }
+    }
+    
+    // Look for --analysis-interval <s> (seconds between behavior analysis passes) and --no-analysis
+    for i in 0..args.len() {
+        if args[i] == "--analysis-interval" && i + 1 < args.len() {
+            if let Ok(value) = args[i + 1].parse::<f32>() {
+                sim_config.analysis_interval = value.max(0.0);
+                info!("📊 Behavior analysis every {:.2}s", sim_config.analysis_interval);
+            }
+        }
+    }
+    if args.iter().any(|arg| arg == "--no-analysis") {
+        sim_config.analysis_interval = 0.0;
+        info!("📊 Behavior analysis off");
+    }
+    
+    // Look for --aco flag (classical ant colony optimization deposit rules)
+    if args.iter().any(|arg| arg == "--aco") {
+        sim_config.classic_aco = true;
+        info!("📚 Classical ACO mode: only returning food carriers lay pheromone");
+    }
+    
+    // Look for --regrow flag (depleted food regrows in place instead of moving)
+    if args.iter().any(|arg| arg == "--regrow") {
+        sim_config.food_regrow_in_place = true;
+        info!("🌱 Food regrowth: depleted sources refill in place after {:.0}s", sim_config.food_regrow_cooldown);
+    }
+    
+    // Look for --food-amount <n> (size of food sources dropped with Ctrl+click)
+    for i in 0..args.len() {
+        if args[i] == "--food-amount" && i + 1 < args.len() {
+            if let Ok(value) = args[i + 1].parse::<f32>() {
+                sim_config.placed_food_amount = value.max(1.0);
+            }
+        }
+    }
+    
+    // Look for --ghost <path> (pheromone snapshot saved with Y, drawn under the live trails)
+    let mut ghost_snapshot = None;
+    for i in 0..args.len() {
+        if args[i] == "--ghost" && i + 1 < args.len() {
+            ghost_snapshot = Some(GhostSnapshot { path: args[i + 1].clone() });
+        }
+    }
     
-    App::new()
+    // Look for video arguments (--video-size <WxH>, --video-fps <n>, --video-duration <seconds> or
+    // --video-speedup <x>, --video-bitrate <ffmpeg rate, e.g. 4M>, --video-format mp4|webm|gif,
+    // --video-source rendered|screen, --video-crop auto|x0,y0,x1,y1, --video-memory <MiB>)
+    let mut video_recorder = VideoRecorder::default();
+    let (mut video_fps, mut video_speedup) = (video_recorder.fps, video_recorder.speedup);
+    for i in 0..args.len() {
+        if i + 1 >= args.len() {
+            break;
+        }
+        if args[i] == "--video-size" {
+            match parse_video_size(&args[i + 1]) {
+                Some((width, height)) => {
+                    video_recorder.frame_width = width;
+                    video_recorder.frame_height = height;
+                }
+                None => warn!("⚠️ Ignoring --video-size '{}' (expected WIDTHxHEIGHT, at least {}x{})", args[i + 1], MIN_VIDEO_SIZE, MIN_VIDEO_SIZE),
+            }
+        } else if args[i] == "--video-fps" {
+            match args[i + 1].parse::<u32>() {
+                Ok(fps) if fps > 0 => video_fps = fps,
+                _ => warn!("⚠️ Ignoring --video-fps '{}' (expected a positive whole number)", args[i + 1]),
+            }
+        } else if args[i] == "--video-speedup" {
+            match args[i + 1].parse::<f32>() {
+                Ok(speedup) if speedup > 0.0 => {
+                    video_speedup = speedup;
+                    video_recorder.target_duration = None;
+                }
+                _ => warn!("⚠️ Ignoring --video-speedup '{}' (expected a positive number)", args[i + 1]),
+            }
+        } else if args[i] == "--video-duration" {
+            match args[i + 1].parse::<f32>() {
+                Ok(duration) if duration >= 1.0 => video_recorder.target_duration = Some(duration),
+                _ => warn!("⚠️ Ignoring --video-duration '{}' (expected at least 1 second)", args[i + 1]),
+            }
+        } else if args[i] == "--video-bitrate" {
+            video_recorder.bitrate = args[i + 1].clone();
+        } else if args[i] == "--video-source" {
+            match VideoSource::from_name(&args[i + 1]) {
+                Some(source) => video_recorder.source = source,
+                None => warn!("⚠️ Unknown --video-source '{}' (expected rendered or screen)", args[i + 1]),
+            }
+        } else if args[i] == "--video-crop" {
+            match VideoCrop::from_arg(&args[i + 1]) {
+                Some(crop) => video_recorder.crop = crop,
+                None => warn!("⚠️ Ignoring --video-crop '{}' (expected auto, whole or x0,y0,x1,y1)", args[i + 1]),
+            }
+        } else if args[i] == "--video-format" {
+            match VideoFormat::from_name(&args[i + 1]) {
+                Some(format) => video_recorder.format = format,
+                None => warn!("⚠️ Unknown --video-format '{}' (expected mp4, webm or gif)", args[i + 1]),
+            }
+        } else if args[i] == "--video-memory" {
+            match args[i + 1].parse::<usize>() {
+                Ok(megabytes) if megabytes > 0 => video_recorder.memory_budget = megabytes * 1024 * 1024,
+                _ => warn!("⚠️ Ignoring --video-memory '{}' (expected a positive number of MiB)", args[i + 1]),
+            }
+        }
+    }
+    // Look for --hw-encode flag (NVENC, Quick Sync, AMF or VideoToolbox when one works, else libx264)
+    if args.iter().any(|arg| arg == "--hw-encode") {
+        video_recorder.hardware_encoding = true;
+    }
+    
+    // Look for --pheromone-video flag (second video of the pheromone field alone, frame-for-frame with the main one)
+    if args.iter().any(|arg| arg == "--pheromone-video") {
+        video_recorder.pheromone_stream = Some(FrameStream::default());
+        info!("🧪 Pheromone video: writing the pheromone field alongside the main video");
+    }
+    
+    // Look for --video-audio flag (delivery ticks, goal-time drone and alarm bursts muxed into the video)
+    if args.iter().any(|arg| arg == "--video-audio") {
+        if video_recorder.format == VideoFormat::Gif {
+            warn!("⚠️ GIFs have no sound, ignoring --video-audio");
+        } else {
+            video_recorder.soundtrack = Some(Soundtrack::default());
+            info!("🔊 Video audio: deliveries, goal time and alarm spikes will be audible");
+        }
+    }
+    
+    // Whichever of --video-duration and --video-speedup comes last decides the cadence
+    match video_recorder.target_duration {
+        Some(duration) => {
+            video_recorder.plan_timelapse(duration, TIME_LIMIT, video_fps);
+            if video_recorder.fps < video_fps {
+                info!("🎞️ {:.0}s timelapse at {} fps instead of {} (too few simulation ticks or frames for more)",
+                    duration, video_recorder.fps, video_fps);
+            }
+        }
+        None => video_recorder.set_cadence(video_fps, video_speedup),
+    }
+    
+    // Look for --ui-scale <factor> (text and panel size; Ctrl+Plus/Minus adjusts it live)
+    let mut ui_scale = 1.0;
+    for i in 0..args.len() {
+        if args[i] == "--ui-scale" && i + 1 < args.len() {
+            match parse_ui_scale(&args[i + 1]) {
+                Some(value) => ui_scale = value,
+                None => warn!("⚠️ Ignoring --ui-scale '{}' (expected a positive number)", args[i + 1]),
+            }
+        }
+    }
+    
+    // Look for --sim-hz <ticks per second> (the fixed simulation timestep; sprites are interpolated between ticks)
+    let mut sim_hz = DEFAULT_SIM_HZ;
+    for i in 0..args.len() {
+        if args[i] == "--sim-hz" && i + 1 < args.len() {
+            match parse_sim_hz(&args[i + 1]) {
+                Some(value) => sim_hz = value,
+                None => warn!("⚠️ Ignoring --sim-hz '{}' (expected a positive number)", args[i + 1]),
+            }
+        }
+    }
+    if sim_hz != DEFAULT_SIM_HZ {
+        info!("⏲️ Simulation ticks at {} Hz", sim_hz);
+    }
+    
+    // Look for maze arguments (--maze-seed <n>, --corridor-width <units>)
+    for i in 0..args.len() {
+        if i + 1 >= args.len() {
+            break;
+        }
+        if args[i] == "--maze-seed" {
+            if let Ok(value) = args[i + 1].parse::<u64>() {
+                sim_config.maze_seed = value;
+            }
+        } else if args[i] == "--corridor-width" {
+            if let Ok(value) = args[i + 1].parse::<f32>() {
+                sim_config.maze_corridor_width = value.clamp(MIN_CORRIDOR_WIDTH, MAX_CORRIDOR_WIDTH);
+            }
+        }
+    }
+    
+    // Look for map generator arguments (--map-seed <n> enables it, the rest tune density)
+    for i in 0..args.len() {
+        if i + 1 >= args.len() {
+            break;
+        }
+        if args[i] == "--map-seed" {
+            if let Ok(value) = args[i + 1].parse::<u64>() {
+                sim_config.procedural_map = true;
+                sim_config.map_seed = value;
+            }
+        } else if args[i] == "--obstacle-density" {
+            if let Ok(value) = args[i + 1].parse::<f32>() {
+                sim_config.map_obstacle_density = value.max(0.0);
+            }
+        } else if args[i] == "--food-clusters" {
+            if let Ok(value) = args[i + 1].parse::<usize>() {
+                sim_config.map_food_clusters = value;
+            }
+        } else if args[i] == "--terrain-relief" {
+            if let Ok(value) = args[i + 1].parse::<f32>() {
+                sim_config.map_terrain_relief = value.clamp(0.0, 1.0);
+            }
+        }
+    }
+    
+    let mut generated_map = None;
+    if sim_config.procedural_map {
+        let mut map = GeneratedMap::generate(&sim_config);
+        info!("🗺️ Generated map (seed {}): {} rocks, {} food sources, terrain {}",
+            map.seed, map.rocks.len(), map.food.len(), if map.heightmap.is_some() { "on" } else { "flat" });
+        // An explicit --heightmap wins over generated terrain
+        if heightmap.is_none() {
+            heightmap = map.heightmap.take();
+        }
+        generated_map = Some(map);
+    }
+    
+    // Look for --curriculum flag (challenges 1..N back to back, optional stage count)
+    let mut curriculum = None;
+    for i in 0..args.len() {
+        if args[i] == "--curriculum" {
+            let stage_count = args.get(i + 1).and_then(|value| value.parse::<usize>().ok());
+            let plan = Curriculum::new(stage_count, sim_config.clone());
+            info!("📚 Curriculum: challenges {:?}", plan.stages);
+            challenge_number = plan.stages[0];
+            curriculum = Some(plan);
+            break;
+        }
+    }
+    
+    let mut app = App::new();
+    app
         .add_plugins(DefaultPlugins.set(bevy::window::WindowPlugin {
             primary_window: Some(bevy::window::Window {
                 title: format!("Ant Simulation - Challenge {}", challenge_number).into(),
@@ -42,230 +427,45 @@ fn main() {
             }),
             exit_condition: bevy::window::ExitCondition::DontExit,
             ..default()
-        }))
-        .insert_resource(ClearColor(Color::BLACK)) // Match video background
-        .insert_resource(SimConfig::default())
-        .insert_resource(PheromoneGrid::new(1000, 1000)) // 1:1 with world grid
-        .insert_resource(DebugInfo::default())
-        .insert_resource(PerformanceTracker::default())
-        .insert_resource(VideoRecorder::default())
-        .insert_resource(ColorConfig::default())
-        .insert_resource(GenerationInfo::from_json_file())
-        .insert_resource(challenge_config)
-        .add_systems(Startup, (setup, setup_pheromone_visualization, setup_debug_ui, setup_video_camera))
-        .add_systems(
-            Update,
-            (
-                sensing_system,
-                ant_proximity_analysis_system,
-                behavior_analysis_system,
-                movement_system,
-                pheromone_deposit_system,
-                pheromone_update_system,
-                food_collection_system,
-                ant_visual_system,
-                food_visual_system,
-                update_pheromone_visualization,
-                performance_analysis_system,
-            ).chain()
-        )
-        .add_systems(
-            Update,
-            (
-                exit_system,
-                exit_event_listener,
-                window_close_system,
-                restart_system,
-                camera_control_system,
-                cursor_tracking_system,
-                hover_detection_system,
-                ant_selection_system,
-                selected_ant_display_system,
-                selected_ant_outline_system,
-                update_debug_ui,
-            )
-        )
-        .add_systems(Update, video_recording_system.after(performance_analysis_system))
-        .run();
-}
-
-fn setup(mut commands: Commands, config: Res<SimConfig>, color_config: Res<ColorConfig>, challenge_config: Res<ChallengeConfig>) {
-    commands.spawn(Camera2dBundle::default());
-    
-    // Add debug text to verify rendering
-    commands.spawn(TextBundle::from_section(
-        "Ant Simulation\nRed: Exploring  Yellow: Collecting  Orange: Carrying\nWASD: Move  Wheel: Zoom  R: Restart  ESC: Exit",
-        TextStyle {
-            font_size: 24.0,
-            color: color_config.text,
-            ..default()
-        },
-    ).with_style(Style {
-        position_type: PositionType::Absolute,
-        top: Val::Px(10.0),
-        left: Val::Px(10.0),
-        ..default()
-    }));
-    
-    // Spawn nest at center
-    commands.spawn((
-        SpriteBundle {
-            sprite: Sprite {
-                color: color_config.nest,
-                custom_size: Some(Vec2::new(80.0, 80.0)),
-                ..default()
-            },
-            transform: Transform::from_xyz(0.0, 0.0, 5.0),
-            ..default()
-        },
-        Nest { capacity: 10000.0 },
-    ));
+        }).disable::<bevy::log::LogPlugin>()) // Installed by init_logging with a reloadable filter
+        .insert_resource(video_recorder) // Before the plugin, which keeps it and follows its crop
+        .insert_resource(log_verbosity)
+        .insert_resource(policy)
+        .add_plugins(AntSimPlugin {
+            config: sim_config,
+            challenge_number,
+            prey_food,
+            sim_hz,
+            ui_scale,
+            scripted_events,
+            scripted_camera,
+        });
     
-    // Spawn initial ants around nest
-    for i in 0..config.initial_ants {
-        let angle = (i as f32) * std::f32::consts::TAU / config.initial_ants as f32;
-        let x = angle.cos() * 50.0;
-        let y = angle.sin() * 50.0;
-        
-        let mut ant_bundle = commands.spawn((
-            SpriteBundle {
-                sprite: Sprite {
-                    color: color_config.ant_exploring,
-                    custom_size: Some(Vec2::new(12.0, 12.0)),
-                    ..default()
-                },
-                transform: Transform::from_xyz(x, y, 6.0),
-                ..default()
-            },
-            AntState {
-                carrying_food: false,
-                hunger: 0.0,
-                sensitivity_adapt: 1.0,
-                food_collection_timer: 0.0,
-                last_pheromone_strength: 0.0,
-                distance_from_food: 0.0,
-                distance_from_nest: 0.0,
-                has_exit_direction: false,
-                behavior_state: AntBehaviorState::Exploring,
-                sensing_timer: rand::random::<f32>() * 2.0, // Random initial sensing delay
-                current_direction: angle,
-                trail_strength: 0.0,
-                momentum_timer: 0.0,
-                last_position: Vec2::new(x, y),
-                stuck_timer: 0.0,
-                direction_changes: 0,
-                last_sensing_result: [0.0; 8],
-                trail_memory: [angle; 5], // Initialize with current direction
-                memory_index: 0,
-                trail_quality: 0.0,
-                hysteresis_threshold: config.detection_threshold,
-                consecutive_good_trail_time: 0.0,
-                food_pickup_time: 0.0,
-                delivery_attempts: 0,
-                successful_deliveries: 0,
-                startup_timer: 1.0, // Minimal startup time - ants should start working quickly
-                has_found_food: false, // Track if ant has ever found food
-                food_carry_start_time: 0.0, // When ant picked up food
-                last_goal_achievement_time: 0.0, // Initialize as never achieved a goal
-                current_goal_start_time: 0.0, // Will be set when startup timer expires
-                
-                // Initialize new diagnostic fields
-                can_see_trail: false,
-                distance_from_trail: f32::INFINITY,
-                trail_following_time: 0.0,
-                last_trail_contact_time: 0.0,
-                is_swarming: false,
-                nearby_ant_count: 0,
-                time_since_progress: 0.0,
-                exploration_efficiency: 0.0,
-                is_edge_wanderer: false,
-                world_edge_proximity: 0.0,
-                trail_gradient_strength: 0.0,
-            },
-            Velocity {
-                x: (rand::random::<f32>() * 2.0 - 1.0) * 1.5,
-                y: (rand::random::<f32>() * 2.0 - 1.0) * 1.5,
-            },
-        ));
-        
-        // Mark the first ant for debugging
-        if i == 0 {
-            ant_bundle.insert(DebugAnt { ant_id: 0 });
-            println!("🐜 DEBUG ANT #0 spawned at position ({:.1}, {:.1}) with direction {:.2} radians", x, y, angle);
-        }
+    if let Some(heightmap) = heightmap {
+        app.insert_resource(heightmap);
     }
-    
-    // CHALLENGE MODE: All food sources FAR from nest (minimum 1/3 world size away)
-    let mut food_positions = Vec::new();
-    for _i in 0..config.food_sources {
-        let angle = rand::random::<f32>() * std::f32::consts::TAU;
-        // Minimum distance = 1/3 world size = 333 units from nest
-        // Maximum distance = 1/2 world size = 500 units from nest  
-        let distance = 333.0 + rand::random::<f32>() * 167.0; // 333-500 units away
-        let x = angle.cos() * distance;
-        let y = angle.sin() * distance;
-        
-        food_positions.push(Vec2::new(x, y));
-        
-        commands.spawn((
-            SpriteBundle {
-                sprite: Sprite {
-                    color: color_config.food_source,
-                    custom_size: Some(Vec2::new(30.0, 30.0)),
-                    ..default()
-                },
-                transform: Transform::from_xyz(x, y, 2.0),
-                ..default()
-            },
-            FoodSource { amount: 100.0, max_amount: 100.0 }, // Back to original food amount
-        ));
+    if let Some(generated_map) = generated_map {
+        app.insert_resource(generated_map);
+    }
+    if let Some(world_map) = world_map {
+        app.insert_resource(world_map);
+    }
+    if let Some(result_file) = result_file {
+        app.insert_resource(result_file);
+    }
+    if let Some(curriculum) = curriculum {
+        app.insert_resource(curriculum);
+    }
+    if let Some(ghost_snapshot) = ghost_snapshot {
+        app.insert_resource(ghost_snapshot);
     }
     
-    // Challenge 2: Add rocks halfway between nest and food sources
-    if challenge_config.challenge_number == 2 {
-        let nest_position = Vec2::new(0.0, 0.0);
-        let rock_radius = 15.0 * 1.5; // 50% wider than food sources (30.0 * 1.5 / 2)
-        
-        for food_pos in &food_positions {
-            // Place rock halfway between nest and food source
-            let midpoint = (nest_position + *food_pos) * 0.5;
-            
-            // Create circular rock using multiple small sprites
-            let rock_entity = commands.spawn((
-                SpatialBundle::from_transform(Transform::from_xyz(midpoint.x, midpoint.y, 3.0)),
-                Rock { radius: rock_radius },
-            )).id();
-            
-            // Fill the circle with small square sprites
-            let sprite_size = 4.0;
-            let num_steps = (rock_radius * 2.0 / sprite_size) as i32;
-            
-            for x_step in -num_steps..=num_steps {
-                for y_step in -num_steps..=num_steps {
-                    let x_offset = x_step as f32 * sprite_size;
-                    let y_offset = y_step as f32 * sprite_size;
-                    let distance_from_center = (x_offset * x_offset + y_offset * y_offset).sqrt();
-                    
-                    // Only place sprites within the circular boundary
-                    if distance_from_center <= rock_radius {
-                        commands.spawn(SpriteBundle {
-                            sprite: Sprite {
-                                color: Color::srgb(0.35, 0.3, 0.25),
-                                custom_size: Some(Vec2::new(sprite_size, sprite_size)),
-                                ..default()
-                            },
-                            transform: Transform::from_xyz(
-                                midpoint.x + x_offset,
-                                midpoint.y + y_offset,
-                                3.0
-                            ),
-                            ..default()
-                        });
-                    }
-                }
-            }
-        }
-        
-        println!("🪨 Challenge 2: Spawned {} rocks with radius {:.1} as obstacles", food_positions.len(), rock_radius);
+    app.run();
+    
+    // Dropping the app closes the window; videos and metadata from the last run may still be writing
+    let pending_saves = app.world_mut().remove_resource::<PendingSaves>();
+    drop(app);
+    if let Some(pending_saves) = pending_saves {
+        pending_saves.wait();
     }
-}
\ No newline at end of file
+}
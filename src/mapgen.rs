@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use noise::{Fbm, NoiseFn, Perlin};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::config::*;
+use crate::terrain::Heightmap;
+
+/// Resolution of generated heightmaps (stretched over the 1000x1000 world like loaded ones)
+const TERRAIN_RESOLUTION: usize = 250;
+
+/// Clear radius around the nest so the colony can always get out
+const NEST_CLEARANCE: f32 = 120.0;
+
+/// Reproducible random layout: obstacles, food clusters and terrain from one seed
+#[derive(Resource)]
+pub struct GeneratedMap {
+    pub seed: u64,
+    pub rocks: Vec<(Vec2, f32)>, // Center and radius
+    pub food: Vec<Vec2>,
+    pub heightmap: Option<Heightmap>,
+}
+
+impl GeneratedMap {
+    /// Generate a layout from the map generator knobs in `config`
+    pub fn generate(config: &SimConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.map_seed);
+        let bound = 460.0;
+
+        // Food clusters first so obstacles can keep clear of them
+        let mut food = Vec::new();
+        for _ in 0..config.map_food_clusters {
+            let angle = rng.gen::<f32>() * std::f32::consts::TAU;
+            let distance = MIN_FOOD_DISTANCE + rng.gen::<f32>() * (440.0 - MIN_FOOD_DISTANCE);
+            let center = Vec2::new(angle.cos(), angle.sin()) * distance;
+
+            for _ in 0..config.map_cluster_size {
+                let offset = Vec2::new(rng.gen::<f32>() - 0.5, rng.gen::<f32>() - 0.5) * 80.0;
+                let position = (center + offset).clamp(Vec2::splat(-bound), Vec2::splat(bound));
+                // The offset can pull a source back toward the nest; push it out to the minimum again
+                food.push(if position.length() < MIN_FOOD_DISTANCE {
+                    position.normalize_or(center.normalize()) * MIN_FOOD_DISTANCE
+                } else {
+                    position
+                });
+            }
+        }
+
+        // Obstacle field: density is rocks per 100x100 area, rejected if too close to nest, food or each other
+        let target_rocks = (config.map_obstacle_density * 100.0) as usize;
+        let mut rocks: Vec<(Vec2, f32)> = Vec::new();
+        let mut attempts = 0;
+        while rocks.len() < target_rocks && attempts < target_rocks * 20 {
+            attempts += 1;
+            let position = Vec2::new(rng.gen::<f32>() * 2.0 - 1.0, rng.gen::<f32>() * 2.0 - 1.0) * bound;
+            let radius = 10.0 + rng.gen::<f32>() * 25.0;
+
+            let blocks_nest = position.length() < NEST_CLEARANCE + radius;
+            let blocks_food = food.iter().any(|f| f.distance(position) < radius + 40.0);
+            let overlaps = rocks.iter().any(|(p, r)| p.distance(position) < r + radius + 20.0);
+            if !blocks_nest && !blocks_food && !overlaps {
+                rocks.push((position, radius));
+            }
+        }
+
+        let heightmap = if config.map_terrain_relief > 0.0 {
+            Some(generate_terrain(config.map_seed, config.map_terrain_roughness, config.map_terrain_relief))
+        } else {
+            None
+        };
+
+        Self { seed: config.map_seed, rocks, food, heightmap }
+    }
+}
+
+/// Fractal noise terrain, `relief` scales hill height (1.0 = full 0-1 elevation range)
+fn generate_terrain(seed: u64, roughness: f32, relief: f32) -> Heightmap {
+    let fbm = Fbm::<Perlin>::new(seed as u32);
+    let cell_size = 1000.0 / TERRAIN_RESOLUTION as f64;
+    let mut elevation = Vec::with_capacity(TERRAIN_RESOLUTION * TERRAIN_RESOLUTION);
+
+    for row in 0..TERRAIN_RESOLUTION {
+        for col in 0..TERRAIN_RESOLUTION {
+            let x = col as f64 * cell_size * roughness as f64;
+            let y = row as f64 * cell_size * roughness as f64;
+            let value = (fbm.get([x, y]) as f32 * 0.5 + 0.5).clamp(0.0, 1.0);
+            elevation.push(0.5 + (value - 0.5) * relief);
+        }
+    }
+
+    Heightmap { width: TERRAIN_RESOLUTION, height: TERRAIN_RESOLUTION, elevation }
+}
@@ -0,0 +1,135 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+const NORTH: usize = 0;
+const EAST: usize = 1;
+const SOUTH: usize = 2;
+const WEST: usize = 3;
+
+/// Narrowest corridor that still fits the 50-unit ring ants spawn on inside the nest cell
+pub const MIN_CORRIDOR_WIDTH: f32 = 110.0;
+/// Widest corridor that keeps a 960-unit maze at 5x5 cells or more, so some cells lie beyond MIN_FOOD_DISTANCE
+pub const MAX_CORRIDOR_WIDTH: f32 = 180.0;
+
+/// Perfect maze on a square grid of cells centered on the nest
+pub struct Maze {
+    pub size: usize,                // Cells per side (always odd so the nest sits in the middle cell)
+    pub cell_size: f32,             // Corridor width plus wall thickness
+    walls: Vec<[bool; 4]>,          // Per cell: north, east, south, west wall present
+}
+
+impl Maze {
+    /// Carve a maze filling the world with a recursive backtracker, starting from the nest cell
+    pub fn generate(world_extent: f32, corridor_width: f32, wall_thickness: f32, seed: u64) -> Self {
+        let cell_size = corridor_width + wall_thickness;
+        let mut size = ((world_extent / cell_size) as usize).max(3);
+        if size.is_multiple_of(2) {
+            size -= 1;
+        }
+
+        let mut maze = Self { size, cell_size, walls: vec![[true; 4]; size * size] };
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut visited = vec![false; size * size];
+        let start = maze.nest_cell();
+        let mut stack = vec![start];
+        visited[start.1 * size + start.0] = true;
+
+        while let Some(&(col, row)) = stack.last() {
+            let unvisited: Vec<(usize, usize, usize)> = maze.neighbours(col, row)
+                .into_iter()
+                .filter(|&(c, r, _)| !visited[r * size + c])
+                .collect();
+
+            if unvisited.is_empty() {
+                stack.pop();
+                continue;
+            }
+
+            let (next_col, next_row, side) = unvisited[rng.gen_range(0..unvisited.len())];
+            maze.walls[row * size + col][side] = false;
+            maze.walls[next_row * size + next_col][(side + 2) % 4] = false;
+            visited[next_row * size + next_col] = true;
+            stack.push((next_col, next_row));
+        }
+
+        maze
+    }
+
+    pub fn nest_cell(&self) -> (usize, usize) {
+        (self.size / 2, self.size / 2)
+    }
+
+    /// World position of a cell's center (nest cell is at the origin)
+    pub fn cell_center(&self, col: usize, row: usize) -> Vec2 {
+        let half = (self.size / 2) as f32;
+        Vec2::new((col as f32 - half) * self.cell_size, (row as f32 - half) * self.cell_size)
+    }
+
+    /// Cell with the longest corridor path from the nest among those whose centre is at least
+    /// `min_distance` from it in a straight line, or None if no cell is that far out
+    pub fn farthest_cell(&self, min_distance: f32) -> Option<(usize, usize)> {
+        let mut distance = vec![usize::MAX; self.size * self.size];
+        let start = self.nest_cell();
+        let mut queue = std::collections::VecDeque::from([start]);
+        distance[start.1 * self.size + start.0] = 0;
+        let mut farthest: Option<(usize, usize)> = None;
+
+        while let Some((col, row)) = queue.pop_front() {
+            let current = distance[row * self.size + col];
+            let far_enough = self.cell_center(col, row).length() >= min_distance;
+            if far_enough && farthest.is_none_or(|(c, r)| current > distance[r * self.size + c]) {
+                farthest = Some((col, row));
+            }
+
+            for (next_col, next_row, side) in self.neighbours(col, row) {
+                let next = next_row * self.size + next_col;
+                if !self.walls[row * self.size + col][side] && distance[next] == usize::MAX {
+                    distance[next] = current + 1;
+                    queue.push_back((next_col, next_row));
+                }
+            }
+        }
+
+        farthest
+    }
+
+    /// Every standing wall as a world-space line segment, each shared wall listed once
+    pub fn wall_segments(&self) -> Vec<(Vec2, Vec2)> {
+        let half = self.cell_size * 0.5;
+        let mut segments = Vec::new();
+
+        for row in 0..self.size {
+            for col in 0..self.size {
+                let center = self.cell_center(col, row);
+                let walls = self.walls[row * self.size + col];
+
+                if walls[NORTH] {
+                    segments.push((center + Vec2::new(-half, half), center + Vec2::new(half, half)));
+                }
+                if walls[EAST] {
+                    segments.push((center + Vec2::new(half, -half), center + Vec2::new(half, half)));
+                }
+                // South and west walls are only unique along the outer boundary
+                if row == 0 && walls[SOUTH] {
+                    segments.push((center + Vec2::new(-half, -half), center + Vec2::new(half, -half)));
+                }
+                if col == 0 && walls[WEST] {
+                    segments.push((center + Vec2::new(-half, -half), center + Vec2::new(-half, half)));
+                }
+            }
+        }
+
+        segments
+    }
+
+    /// In-bounds neighbouring cells with the side of this cell that faces them
+    fn neighbours(&self, col: usize, row: usize) -> Vec<(usize, usize, usize)> {
+        let mut result = Vec::with_capacity(4);
+        if row + 1 < self.size { result.push((col, row + 1, NORTH)); }
+        if col + 1 < self.size { result.push((col + 1, row, EAST)); }
+        if row > 0 { result.push((col, row - 1, SOUTH)); }
+        if col > 0 { result.push((col - 1, row, WEST)); }
+        result
+    }
+}
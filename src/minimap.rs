@@ -0,0 +1,192 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::window::PrimaryWindow;
+use crate::components::*;
+use crate::pheromones::*;
+use crate::colors::*;
+use crate::video::{pheromone_intensity, ant_rgb};
+
+/// Minimap edge length in pixels; the whole 1000x1000 world is squeezed into it
+const MINIMAP_SIZE: u32 = 200;
+const WORLD_SIZE: f32 = 1000.0;
+/// Seconds between redraws (real time, so the viewport box still tracks the camera while paused)
+const MINIMAP_REFRESH: f32 = 0.2;
+
+/// Image the minimap is drawn into
+#[derive(Resource)]
+pub struct MinimapImage(pub Handle<Image>);
+
+/// UI node showing the minimap, hidden when the view settings turn it off (M)
+#[derive(Component)]
+pub struct MinimapNode;
+
+pub fn setup_minimap(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = Image::new_fill(
+        Extent3d { width: MINIMAP_SIZE, height: MINIMAP_SIZE, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    let handle = images.add(image);
+
+    // Bottom-right, above the playback status
+    commands.spawn(ImageBundle {
+        image: UiImage::new(handle.clone()),
+        style: Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(70.0),
+            right: Val::Px(10.0),
+            width: Val::Px(MINIMAP_SIZE as f32),
+            height: Val::Px(MINIMAP_SIZE as f32),
+            border: UiRect::all(Val::Px(1.0)),
+            ..default()
+        },
+        ..default()
+    }).insert((BorderColor(Color::srgb(0.5, 0.5, 0.5)), MinimapNode));
+    commands.insert_resource(MinimapImage(handle));
+}
+
+/// Minimap pixel for a world position, or None when it is off the map
+fn world_to_minimap(position: Vec2) -> Option<(u32, u32)> {
+    let x = (position.x + WORLD_SIZE / 2.0) / WORLD_SIZE * MINIMAP_SIZE as f32;
+    // Image rows run top to bottom, world y runs bottom to top
+    let y = (WORLD_SIZE / 2.0 - position.y) / WORLD_SIZE * MINIMAP_SIZE as f32;
+    if x < 0.0 || y < 0.0 || x >= MINIMAP_SIZE as f32 || y >= MINIMAP_SIZE as f32 {
+        return None;
+    }
+    Some((x as u32, y as u32))
+}
+
+fn put_pixel(data: &mut [u8], x: u32, y: u32, (r, g, b): (u8, u8, u8)) {
+    let idx = ((y * MINIMAP_SIZE + x) * 4) as usize;
+    data[idx] = r;
+    data[idx + 1] = g;
+    data[idx + 2] = b;
+    data[idx + 3] = 255;
+}
+
+/// Square dot of the given half-size centred on a world position
+fn put_dot(data: &mut [u8], position: Vec2, half_size: i32, color: (u8, u8, u8)) {
+    let Some((cx, cy)) = world_to_minimap(position) else { return };
+    for dy in -half_size..=half_size {
+        for dx in -half_size..=half_size {
+            let (x, y) = (cx as i32 + dx, cy as i32 + dy);
+            if x >= 0 && y >= 0 && x < MINIMAP_SIZE as i32 && y < MINIMAP_SIZE as i32 {
+                put_pixel(data, x as u32, y as u32, color);
+            }
+        }
+    }
+}
+
+/// The minimap image and the UI node showing it
+#[derive(SystemParam)]
+pub struct MinimapCanvas<'w, 's> {
+    minimap: Res<'w, MinimapImage>,
+    images: ResMut<'w, Assets<Image>>,
+    node_query: Query<'w, 's, &'static mut Visibility, With<MinimapNode>>,
+}
+
+/// What the minimap plots: trails, ants, food, nests and the main camera's viewport
+#[derive(SystemParam)]
+pub struct MinimapSubjects<'w, 's> {
+    pheromone_grid: Res<'w, PheromoneGrid>,
+    ant_query: Query<'w, 's, (&'static Transform, &'static AntCore)>,
+    food_query: Query<'w, 's, &'static Transform, With<FoodSource>>,
+    nest_query: Query<'w, 's, &'static Transform, With<Nest>>,
+    camera_query: Query<'w, 's, &'static Transform, With<Camera>>,
+    window_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
+/// Redraw the minimap from the pheromone grid and entity positions, plus the camera viewport
+pub fn minimap_system(
+    mut canvas: MinimapCanvas,
+    subjects: MinimapSubjects,
+    color_config: Res<ColorConfig>,
+    view: Res<ViewSettings>,
+    real_time: Res<Time<Real>>,
+    mut refresh_timer: Local<f32>,
+) {
+    if let Ok(mut visibility) = canvas.node_query.get_single_mut() {
+        let wanted = if view.minimap { Visibility::Inherited } else { Visibility::Hidden };
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+    if !view.minimap {
+        return;
+    }
+
+    *refresh_timer += real_time.delta_seconds();
+    if *refresh_timer < MINIMAP_REFRESH {
+        return;
+    }
+    *refresh_timer = 0.0;
+
+    let Some(image) = canvas.images.get_mut(&canvas.minimap.0) else { return };
+    let data = &mut image.data;
+
+    // Pheromone background: strongest cell in each block, food over nest like the video
+    let pheromone_grid = &subjects.pheromone_grid;
+    let block_w = pheromone_grid.width / MINIMAP_SIZE as usize;
+    let block_h = pheromone_grid.height / MINIMAP_SIZE as usize;
+    let (food_r, _, food_b) = color_config.food_pheromone_rgb();
+    let (nest_r, nest_g, _) = color_config.nest_pheromone_rgb();
+    for y in 0..MINIMAP_SIZE {
+        // Grid row 0 is the bottom of the world
+        let grid_y0 = (MINIMAP_SIZE - 1 - y) as usize * block_h;
+        for x in 0..MINIMAP_SIZE {
+            let grid_x0 = x as usize * block_w;
+            let mut food: f32 = 0.0;
+            let mut nest: f32 = 0.0;
+            for grid_y in grid_y0..grid_y0 + block_h {
+                for grid_x in grid_x0..grid_x0 + block_w {
+                    let idx = grid_y * pheromone_grid.width + grid_x;
+                    food = food.max(pheromone_grid.food_trail[idx]);
+                    nest = nest.max(pheromone_grid.nest_trail[idx]);
+                }
+            }
+
+            let (food, nest) = (pheromone_intensity(food), pheromone_intensity(nest));
+            let color = if food > nest {
+                (food_r, (food * 255.0) as u8, food_b)
+            } else if nest > 0.0 {
+                (nest_r, nest_g, (nest * 255.0) as u8)
+            } else {
+                (0, 0, 0)
+            };
+            put_pixel(data, x, y, color);
+        }
+    }
+
+    for transform in subjects.food_query.iter() {
+        put_dot(data, transform.translation.truncate(), 2, color_config.food_source_rgb());
+    }
+    for transform in subjects.nest_query.iter() {
+        put_dot(data, transform.translation.truncate(), 3, color_config.nest_rgb());
+    }
+    for (transform, ant_state) in subjects.ant_query.iter() {
+        put_dot(data, transform.translation.truncate(), 0, ant_rgb(&color_config, ant_state));
+    }
+
+    // Outline of what the main camera currently shows
+    if let (Ok(camera), Ok(window)) = (subjects.camera_query.get_single(), subjects.window_query.get_single()) {
+        let center = camera.translation.truncate();
+        let half_extent = Vec2::new(window.width(), window.height()) * 0.5 * camera.scale.truncate();
+        let corner_min = (center - half_extent).clamp(Vec2::splat(-WORLD_SIZE / 2.0), Vec2::splat(WORLD_SIZE / 2.0 - 0.01));
+        let corner_max = (center + half_extent).clamp(Vec2::splat(-WORLD_SIZE / 2.0), Vec2::splat(WORLD_SIZE / 2.0 - 0.01));
+        // Both corners are clamped onto the map, so the conversions always succeed
+        if let (Some((left, bottom)), Some((right, top))) = (world_to_minimap(corner_min), world_to_minimap(corner_max)) {
+            for x in left..=right {
+                put_pixel(data, x, top, (255, 255, 255));
+                put_pixel(data, x, bottom, (255, 255, 255));
+            }
+            for y in top..=bottom {
+                put_pixel(data, left, y, (255, 255, 255));
+                put_pixel(data, right, y, (255, 255, 255));
+            }
+        }
+    }
+}
@@ -1,5 +1,31 @@
 use bevy::prelude::*;
 use rayon::prelude::*;
+use std::sync::LazyLock;
+use wide::f32x8;
+
+/// Concentrations at or below this draw as nothing in the window and the video
+pub const VISIBLE_FLOOR: f32 = 0.01;
+/// Side of a dirty-tracking tile in grid cells (5x5 pheromone sprites)
+const TILE_SIZE: usize = 25;
+/// World units across the area world_to_grid maps, centred on the origin
+const WORLD_SIZE: usize = 1000;
+
+/// Angles and unit vectors of N headings spaced evenly around the circle, heading i at i * TAU / N,
+/// computed once so fixed-direction scans don't call cos and sin for every ant
+pub struct Compass<const N: usize> {
+    pub angles: [f32; N],
+    pub headings: [Vec2; N],
+}
+
+impl<const N: usize> Compass<N> {
+    fn new() -> Self {
+        let angles = std::array::from_fn(|i| (i as f32) * std::f32::consts::TAU / N as f32);
+        Self { angles, headings: angles.map(|angle| Vec2::new(angle.cos(), angle.sin())) }
+    }
+}
+
+pub static COMPASS_8: LazyLock<Compass<8>> = LazyLock::new(Compass::new);
+pub static COMPASS_12: LazyLock<Compass<12>> = LazyLock::new(Compass::new);
 
 #[derive(Resource)]
 pub struct PheromoneGrid {
@@ -13,11 +39,18 @@ pub struct PheromoneGrid {
     food_trail_buffer: Vec<f32>,
     nest_trail_buffer: Vec<f32>,
     alarm_buffer: Vec<f32>,
+    
+    // Tiles whose drawn look may have changed since the visualization last redrew them
+    tiles_across: usize,
+    dirty_tiles: Vec<bool>,
+    visible_tiles: Vec<bool>, // Tiles holding a visible concentration after the last update
 }
 
 impl PheromoneGrid {
     pub fn new(width: usize, height: usize) -> Self {
         let size = width * height;
+        let tiles_across = width.div_ceil(TILE_SIZE);
+        let tile_count = tiles_across * height.div_ceil(TILE_SIZE);
         Self {
             width,
             height,
@@ -27,13 +60,44 @@ impl PheromoneGrid {
             food_trail_buffer: vec![0.0; size],
             nest_trail_buffer: vec![0.0; size],
             alarm_buffer: vec![0.0; size],
+            tiles_across,
+            dirty_tiles: vec![true; tile_count], // A new grid replaces whatever was drawn before
+            visible_tiles: vec![false; tile_count],
         }
     }
     
+    /// Dirty-tracking tile holding a grid cell
+    pub fn tile_of(&self, idx: usize) -> usize {
+        (idx / self.width / TILE_SIZE) * self.tiles_across + (idx % self.width) / TILE_SIZE
+    }
+    
+    pub fn tile_count(&self) -> usize {
+        self.dirty_tiles.len()
+    }
+    
+    /// Tiles changed since clear_dirty_tiles, for redrawing only those
+    pub fn dirty_tiles(&self) -> impl Iterator<Item = usize> + '_ {
+        self.dirty_tiles.iter().enumerate().filter(|(_, dirty)| **dirty).map(|(tile, _)| tile)
+    }
+    
+    pub fn clear_dirty_tiles(&mut self) {
+        self.dirty_tiles.fill(false);
+    }
+    
+    /// Whether any cell of a tile is above VISIBLE_FLOOR in any channel
+    fn tile_visible(&self, tile: usize) -> bool {
+        let (x0, y0) = ((tile % self.tiles_across) * TILE_SIZE, (tile / self.tiles_across) * TILE_SIZE);
+        (y0..(y0 + TILE_SIZE).min(self.height)).any(|y| {
+            let row = y * self.width + x0..y * self.width + (x0 + TILE_SIZE).min(self.width);
+            self.food_trail[row.clone()].iter().chain(&self.nest_trail[row.clone()]).chain(&self.alarm[row])
+                .any(|&value| value > VISIBLE_FLOOR)
+        })
+    }
+    
     pub fn world_to_grid(&self, x: f32, y: f32) -> Option<usize> {
         // Map world coordinates (-500 to +500) to grid coordinates (0 to 999)
         // 1:1 mapping - each world unit = one grid cell
-        let world_size = 1000.0;
+        let world_size = WORLD_SIZE as f32;
         
         let grid_x = (x + world_size * 0.5) as i32;
         let grid_y = (y + world_size * 0.5) as i32;
@@ -66,107 +130,278 @@ impl PheromoneGrid {
     }
     
     pub fn sample_directional(&self, x: f32, y: f32, direction: f32, distance: f32, pheromone_type: PheromoneType) -> f32 {
+        self.sample_toward(x, y, Vec2::new(direction.cos(), direction.sin()), distance, pheromone_type)
+    }
+    
+    /// sample_directional along a unit vector, for scans that take their headings from a Compass
+    pub fn sample_toward(&self, x: f32, y: f32, heading: Vec2, distance: f32, pheromone_type: PheromoneType) -> f32 {
         let data = match pheromone_type {
             PheromoneType::Food => &self.food_trail,
             PheromoneType::Nest => &self.nest_trail,
             PheromoneType::Alarm => &self.alarm,
         };
         
-        let sample_x = x + direction.cos() * distance;
-        let sample_y = y + direction.sin() * distance;
+        let sample_x = x + heading.x * distance;
+        let sample_y = y + heading.y * distance;
         
-        if let Some(idx) = self.world_to_grid(sample_x, sample_y) {
-            // Sample a 3x3 area and average
-            let mut total = 0.0;
-            let mut count = 0;
-            
-            for dx in -1..=1 {
-                for dy in -1..=1 {
-                    let grid_x = (sample_x + dx as f32) as i32;
-                    let grid_y = (sample_y + dy as f32) as i32;
-                    
-                    if let Some(neighbor_idx) = self.world_to_grid(grid_x as f32, grid_y as f32) {
-                        if neighbor_idx < data.len() {
-                            total += data[neighbor_idx];
-                            count += 1;
-                        }
-                    }
+        if self.world_to_grid(sample_x, sample_y).is_none() {
+            return 0.0;
+        }
+        
+        // Sample a 3x3 area and average. Neighbour coordinates are truncated in world space, as
+        // world_to_grid(trunc(sample + d)) would, but converted once per row and column.
+        let column = |d: i32| (sample_x + d as f32) as i32 + (WORLD_SIZE / 2) as i32;
+        let row = |d: i32| (sample_y + d as f32) as i32 + (WORLD_SIZE / 2) as i32;
+        let columns = [column(-1), column(0), column(1)];
+        let rows = [row(-1), row(0), row(1)];
+        let mut total = 0.0;
+        let mut count = 0;
+        
+        for grid_x in columns {
+            for grid_y in rows {
+                if grid_x >= 0 && grid_x < self.width as i32 && grid_y >= 0 && grid_y < self.height as i32 {
+                    total += data[grid_y as usize * self.width + grid_x as usize];
+                    count += 1;
                 }
             }
-            
-            if count > 0 { total / count as f32 } else { 0.0 }
-        } else {
-            0.0
         }
+        
+        if count > 0 { total / count as f32 } else { 0.0 }
     }
     
     pub fn sample_all_directions(&self, x: f32, y: f32, pheromone_type: PheromoneType) -> [f32; 8] {
-        let directions = [
-            0.0,                    // North
-            std::f32::consts::PI / 4.0,       // NE
-            std::f32::consts::PI / 2.0,       // East
-            3.0 * std::f32::consts::PI / 4.0, // SE
-            std::f32::consts::PI,             // South
-            5.0 * std::f32::consts::PI / 4.0, // SW
-            3.0 * std::f32::consts::PI / 2.0, // West
-            7.0 * std::f32::consts::PI / 4.0, // NW
-        ];
-        
+        // Heading 0 is +x, then counterclockwise in 45 degree steps
         let sensing_distance = 25.0;
-        let mut samples = [0.0; 8];
-        
-        for (i, &direction) in directions.iter().enumerate() {
-            samples[i] = self.sample_directional(x, y, direction, sensing_distance, pheromone_type);
-        }
-        
-        samples
+        let compass = &*COMPASS_8;
+        std::array::from_fn(|i| self.sample_toward(x, y, compass.headings[i], sensing_distance, pheromone_type))
     }
     
     pub fn deposit(&mut self, x: f32, y: f32, pheromone_type: PheromoneType, amount: f32) {
         if let Some(idx) = self.world_to_grid(x, y) {
-            match pheromone_type {
-                PheromoneType::Food => self.food_trail[idx] += amount,
-                PheromoneType::Nest => self.nest_trail[idx] += amount,
-                PheromoneType::Alarm => self.alarm[idx] += amount,
+            self.deposit_at(idx, pheromone_type, amount);
+        }
+    }
+    
+    /// Queue a deposit for apply_deposits instead of writing it now
+    pub fn queue_deposit(&self, queue: &mut Vec<PheromoneDeposit>, x: f32, y: f32, pheromone_type: PheromoneType, amount: f32) {
+        if let Some(idx) = self.world_to_grid(x, y) {
+            queue.push(PheromoneDeposit { idx: idx as u32, pheromone_type, amount });
+        }
+    }
+    
+    /// Apply and empty a queue of deposits. Sorted by channel and cell, the grid is written in memory order,
+    /// one band of tile rows per thread.
+    pub fn apply_deposits(&mut self, queue: &mut Vec<PheromoneDeposit>) {
+        // Ordering a cell's deposits by amount as well makes its sum independent of the order they were queued in
+        queue.sort_unstable_by(|a, b| {
+            (a.pheromone_type as u8, a.idx).cmp(&(b.pheromone_type as u8, b.idx)).then(a.amount.total_cmp(&b.amount))
+        });
+        let (width, band) = (self.width, self.width * TILE_SIZE);
+        for pheromone_type in [PheromoneType::Food, PheromoneType::Nest, PheromoneType::Alarm] {
+            let start = queue.partition_point(|deposit| (deposit.pheromone_type as u8) < pheromone_type as u8);
+            let end = queue.partition_point(|deposit| (deposit.pheromone_type as u8) <= pheromone_type as u8);
+            if start == end {
+                continue;
             }
+            let deposits = &queue[start..end];
+            let channel = match pheromone_type {
+                PheromoneType::Food => &mut self.food_trail,
+                PheromoneType::Nest => &mut self.nest_trail,
+                PheromoneType::Alarm => &mut self.alarm,
+            };
+            channel.par_chunks_mut(band).zip(self.dirty_tiles.par_chunks_mut(self.tiles_across)).enumerate()
+                .for_each(|(row, (cells, tiles))| {
+                    let first = row * band;
+                    let from = deposits.partition_point(|deposit| (deposit.idx as usize) < first);
+                    let to = deposits.partition_point(|deposit| (deposit.idx as usize) < first + cells.len());
+                    for deposit in &deposits[from..to] {
+                        let cell = deposit.idx as usize - first;
+                        cells[cell] += deposit.amount;
+                        tiles[(cell % width) / TILE_SIZE] = true;
+                    }
+                });
+        }
+        queue.clear();
+    }
+    
+    /// Deposit into a cell by its grid index
+    pub fn deposit_at(&mut self, idx: usize, pheromone_type: PheromoneType, amount: f32) {
+        self.channel_mut(pheromone_type)[idx] += amount;
+        let tile = self.tile_of(idx);
+        self.dirty_tiles[tile] = true;
+    }
+    
+    fn channel_mut(&mut self, pheromone_type: PheromoneType) -> &mut Vec<f32> {
+        match pheromone_type {
+            PheromoneType::Food => &mut self.food_trail,
+            PheromoneType::Nest => &mut self.nest_trail,
+            PheromoneType::Alarm => &mut self.alarm,
+        }
+    }
+    
+    /// Deposit into every cell within `radius` of `center`, tapering linearly to zero at the edge
+    pub fn deposit_disc(&mut self, center: Vec2, radius: f32, pheromone_type: PheromoneType, amount: f32) {
+        self.for_each_in_disc(center, radius, pheromone_type, |value, distance| {
+            *value += amount * (1.0 - distance / radius);
+        });
+    }
+    
+    /// Zero one channel in every cell within `radius` of `center`
+    pub fn clear_disc(&mut self, center: Vec2, radius: f32, pheromone_type: PheromoneType) {
+        self.for_each_in_disc(center, radius, pheromone_type, |value, _| *value = 0.0);
+    }
+    
+    fn for_each_in_disc(&mut self, center: Vec2, radius: f32, pheromone_type: PheromoneType, mut apply: impl FnMut(&mut f32, f32)) {
+        let mut cells = Vec::new();
+        let steps = radius.ceil() as i32;
+        for dy in -steps..=steps {
+            for dx in -steps..=steps {
+                let distance = Vec2::new(dx as f32, dy as f32).length();
+                if distance <= radius {
+                    if let Some(idx) = self.world_to_grid(center.x + dx as f32, center.y + dy as f32) {
+                        cells.push((idx, distance));
+                    }
+                }
+            }
+        }
+        for &(idx, _) in &cells {
+            let tile = self.tile_of(idx);
+            self.dirty_tiles[tile] = true;
+        }
+        let channel = self.channel_mut(pheromone_type);
+        for (idx, distance) in cells {
+            apply(&mut channel[idx], distance);
         }
     }
     
     pub fn update(&mut self, evap_rates: (f32, f32, f32), diff_rates: (f32, f32, f32)) {
-        // Evaporation - use parallel iterator directly on slices
-        self.food_trail.par_iter_mut().for_each(|val| *val *= 1.0 - evap_rates.0);
-        self.nest_trail.par_iter_mut().for_each(|val| *val *= 1.0 - evap_rates.1);
-        self.alarm.par_iter_mut().for_each(|val| *val *= 1.0 - evap_rates.2);
+        // Evaporation - written into the buffers, which diffusion then reads
+        evaporate_into(&self.food_trail, &mut self.food_trail_buffer, evap_rates.0);
+        evaporate_into(&self.nest_trail, &mut self.nest_trail_buffer, evap_rates.1);
+        evaporate_into(&self.alarm, &mut self.alarm_buffer, evap_rates.2);
         
-        // Simple diffusion - copy to buffer, then average with neighbors
-        self.food_trail_buffer.copy_from_slice(&self.food_trail);
-        self.nest_trail_buffer.copy_from_slice(&self.nest_trail);
-        self.alarm_buffer.copy_from_slice(&self.alarm);
+        // Simple diffusion - average each cell with its neighbors in the buffer
+        diffuse(&self.food_trail_buffer, &mut self.food_trail, self.width, diff_rates.0);
+        diffuse(&self.nest_trail_buffer, &mut self.nest_trail, self.width, diff_rates.1);
+        diffuse(&self.alarm_buffer, &mut self.alarm, self.width, diff_rates.2);
         
-        for y in 1..self.height-1 {
-            for x in 1..self.width-1 {
-                let idx = y * self.width + x;
-                let neighbors = [
-                    idx - self.width - 1, idx - self.width, idx - self.width + 1,
-                    idx - 1,               idx,               idx + 1,
-                    idx + self.width - 1,  idx + self.width,  idx + self.width + 1,
-                ];
-                
-                let food_avg: f32 = neighbors.iter().map(|&i| self.food_trail_buffer[i]).sum::<f32>() / 9.0;
-                let nest_avg: f32 = neighbors.iter().map(|&i| self.nest_trail_buffer[i]).sum::<f32>() / 9.0;
-                let alarm_avg: f32 = neighbors.iter().map(|&i| self.alarm_buffer[i]).sum::<f32>() / 9.0;
-                
-                self.food_trail[idx] = self.food_trail[idx] * (1.0 - diff_rates.0) + food_avg * diff_rates.0;
-                self.nest_trail[idx] = self.nest_trail[idx] * (1.0 - diff_rates.1) + nest_avg * diff_rates.1;
-                self.alarm[idx] = self.alarm[idx] * (1.0 - diff_rates.2) + alarm_avg * diff_rates.2;
+        // Evaporation and diffusion only change the picture where something was or now is visible
+        let visible: Vec<bool> = (0..self.tile_count()).into_par_iter().map(|tile| self.tile_visible(tile)).collect();
+        for (tile, now_visible) in visible.iter().enumerate() {
+            if *now_visible || self.visible_tiles[tile] {
+                self.dirty_tiles[tile] = true;
             }
         }
+        self.visible_tiles = visible;
     }
 }
 
-#[derive(Copy, Clone)]
+/// Write `values` scaled down by the evaporation `rate` into `buffer`
+fn evaporate_into(values: &[f32], buffer: &mut [f32], rate: f32) {
+    buffer.par_iter_mut().zip(values.par_iter()).for_each(|(evaporated, value)| *evaporated = value * (1.0 - rate));
+}
+
+/// Eight cells of `slice` starting at `at`, as one SIMD vector
+fn lanes(slice: &[f32], at: usize) -> f32x8 {
+    f32x8::from(<[f32; 8]>::try_from(&slice[at..at + 8]).unwrap())
+}
+
+/// Blend every interior cell toward the 3x3 mean of `buffer` by `rate`; border cells just take the buffer.
+/// Rows are independent, so they run in parallel. Each row first sums its three-row columns, then adds
+/// each column to the two beside it, eight cells at a time, with a scalar pass for the leftover cells.
+fn diffuse(buffer: &[f32], values: &mut [f32], width: usize, rate: f32) {
+    const LANES: usize = 8;
+    let height = values.len() / width;
+    let (keep, blend) = (1.0 - rate, rate / 9.0);
+    let (keep_lanes, blend_lanes) = (f32x8::splat(keep), f32x8::splat(blend));
+    values.par_chunks_mut(width).enumerate().for_each_init(Vec::new, |columns: &mut Vec<f32>, (y, row)| {
+        let own = &buffer[y * width..(y + 1) * width];
+        if y == 0 || y + 1 == height || width < 3 {
+            row.copy_from_slice(own);
+            return;
+        }
+        let above = &buffer[(y - 1) * width..y * width];
+        let below = &buffer[(y + 1) * width..(y + 2) * width];
+        
+        columns.resize(width, 0.0);
+        let mut x = 0;
+        while x + LANES <= width {
+            let sum = lanes(above, x) + lanes(own, x) + lanes(below, x);
+            columns[x..x + LANES].copy_from_slice(sum.as_array_ref());
+            x += LANES;
+        }
+        for x in x..width {
+            columns[x] = above[x] + own[x] + below[x];
+        }
+        
+        row[0] = own[0];
+        let mut x = 1;
+        while x + LANES < width {
+            let sum = lanes(columns, x - 1) + lanes(columns, x) + lanes(columns, x + 1);
+            let blended = lanes(own, x) * keep_lanes + sum * blend_lanes;
+            row[x..x + LANES].copy_from_slice(blended.as_array_ref());
+            x += LANES;
+        }
+        for x in x..width - 1 {
+            row[x] = own[x] * keep + (columns[x - 1] + columns[x] + columns[x + 1]) * blend;
+        }
+        row[width - 1] = own[width - 1];
+    });
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum PheromoneType {
     Food,
     Nest,
     Alarm,
-}
\ No newline at end of file
+}
+
+/// A deposit queued by queue_deposit, waiting for apply_deposits
+#[derive(Copy, Clone, Debug)]
+pub struct PheromoneDeposit {
+    idx: u32,
+    pheromone_type: PheromoneType,
+    amount: f32,
+}
+
+/// Imperfect pheromone sensing: additive Gaussian noise plus missed detections
+#[derive(Clone, Copy)]
+pub struct SensorModel {
+    pub noise_std: f32,           // Standard deviation of additive noise
+    pub false_negative_rate: f32, // Probability a sample reads as zero
+}
+
+impl SensorModel {
+    /// Apply the noise model to a true concentration reading
+    pub fn perceive(&self, reading: f32) -> f32 {
+        if self.false_negative_rate > 0.0 && rand::random::<f32>() < self.false_negative_rate {
+            return 0.0;
+        }
+        
+        if self.noise_std > 0.0 {
+            // Box-Muller transform for a standard normal sample
+            let u1 = rand::random::<f32>().max(1e-7);
+            let u2 = rand::random::<f32>();
+            let gaussian = (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos();
+            (reading + gaussian * self.noise_std).max(0.0)
+        } else {
+            reading
+        }
+    }
+    
+    pub fn sample_directional(&self, grid: &PheromoneGrid, x: f32, y: f32, direction: f32, distance: f32, pheromone_type: PheromoneType) -> f32 {
+        self.perceive(grid.sample_directional(x, y, direction, distance, pheromone_type))
+    }
+    
+    pub fn sample_toward(&self, grid: &PheromoneGrid, x: f32, y: f32, heading: Vec2, distance: f32, pheromone_type: PheromoneType) -> f32 {
+        self.perceive(grid.sample_toward(x, y, heading, distance, pheromone_type))
+    }
+    
+    pub fn sample_all_directions(&self, grid: &PheromoneGrid, x: f32, y: f32, pheromone_type: PheromoneType) -> [f32; 8] {
+        let mut samples = grid.sample_all_directions(x, y, pheromone_type);
+        for sample in samples.iter_mut() {
+            *sample = self.perceive(*sample);
+        }
+        samples
+    }
+}
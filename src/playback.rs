@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+
+/// Whether the simulation systems run this frame. Camera, UI and editor systems always run.
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SimulationState {
+    #[default]
+    Running,
+    Paused,
+    Stepping, // Paused, but running exactly one tick
+}
+
+/// Simulation speeds reachable with +/-, as multiples of real time
+pub const SPEED_STEPS: [f32; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+
+/// Run condition for the simulation schedule
+pub fn simulation_active(state: Res<State<SimulationState>>) -> bool {
+    *state.get() != SimulationState::Paused
+}
+
+/// Whether the fixed tick underway runs the simulation. A single step keeps the clock going until
+/// the first tick comes round and lets only that one through.
+#[derive(Resource, Default)]
+pub struct SimTick {
+    run: bool,
+    stepped: bool,
+}
+
+/// FixedFirst: open or close the simulation for this tick
+pub fn open_sim_tick(state: Res<State<SimulationState>>, mut tick: ResMut<SimTick>) {
+    tick.run = match state.get() {
+        SimulationState::Running => true,
+        SimulationState::Paused => false,
+        SimulationState::Stepping => !tick.stepped,
+    };
+    tick.stepped = *state.get() == SimulationState::Stepping;
+}
+
+/// Run condition for the fixed-timestep simulation systems
+pub fn sim_tick_open(tick: Res<SimTick>) -> bool {
+    tick.run
+}
+
+/// Pause or resume both the simulation systems and the virtual clock they read
+pub fn set_paused(paused: bool, next_state: &mut NextState<SimulationState>, virtual_time: &mut Time<Virtual>) {
+    if paused {
+        next_state.set(SimulationState::Paused);
+        virtual_time.pause();
+    } else {
+        next_state.set(SimulationState::Running);
+        virtual_time.unpause();
+    }
+}
+
+#[derive(Component)]
+pub struct PlaybackText;
+
+pub fn setup_playback_ui(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 22.0,
+                color: Color::srgb(1.0, 1.0, 1.0),
+                ..default()
+            },
+        ).with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            right: Val::Px(10.0),
+            ..default()
+        }),
+        PlaybackText,
+    ));
+}
+
+/// Space pauses/resumes, Period advances one tick while paused, +/- change the speed
+pub fn playback_control_system(
+    input: Res<ButtonInput<KeyCode>>,
+    state: Res<State<SimulationState>>,
+    mut next_state: ResMut<NextState<SimulationState>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+) {
+    if input.just_pressed(KeyCode::Space) {
+        let paused = *state.get() == SimulationState::Running;
+        set_paused(paused, &mut next_state, &mut virtual_time);
+        info!("{}", if paused { "⏸️ Paused - Space: resume | Period: step" } else { "▶️ Resumed" });
+    } else if input.just_pressed(KeyCode::Period) && *state.get() == SimulationState::Paused {
+        // The clock runs again until the next fixed tick, which the simulation runs alone
+        let overstep = fixed_time.overstep();
+        fixed_time.discard_overstep(overstep);
+        next_state.set(SimulationState::Stepping);
+        virtual_time.unpause();
+    }
+    
+    // Ctrl+Plus/Minus belongs to the UI scale
+    let ctrl = input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let faster = !ctrl && (input.just_pressed(KeyCode::Equal) || input.just_pressed(KeyCode::NumpadAdd));
+    let slower = !ctrl && (input.just_pressed(KeyCode::Minus) || input.just_pressed(KeyCode::NumpadSubtract));
+    if faster || slower {
+        // Snap to the nearest step, then move one step along
+        let speed = virtual_time.relative_speed();
+        let current = SPEED_STEPS.iter()
+            .position(|&step| step >= speed - 0.001)
+            .unwrap_or(SPEED_STEPS.len() - 1);
+        let next = if faster {
+            (current + 1).min(SPEED_STEPS.len() - 1)
+        } else {
+            current.saturating_sub(1)
+        };
+        virtual_time.set_relative_speed(SPEED_STEPS[next]);
+        info!("⏩ Simulation speed {}x", SPEED_STEPS[next]);
+    }
+}
+
+/// Runs last in the frame: once the single step's tick has run, freeze again before the clock advances
+pub fn finish_single_step(
+    state: Res<State<SimulationState>>,
+    mut next_state: ResMut<NextState<SimulationState>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut tick: ResMut<SimTick>,
+) {
+    if *state.get() == SimulationState::Stepping && tick.stepped {
+        tick.stepped = false;
+        set_paused(true, &mut next_state, &mut virtual_time);
+    }
+}
+
+pub fn update_playback_ui(
+    state: Res<State<SimulationState>>,
+    virtual_time: Res<Time<Virtual>>,
+    mut text_query: Query<&mut Text, With<PlaybackText>>,
+) {
+    if let Ok(mut text) = text_query.get_single_mut() {
+        let status = match state.get() {
+            SimulationState::Running => "▶ Running (Space: pause)",
+            SimulationState::Paused | SimulationState::Stepping => "⏸ PAUSED (Space: resume, Period: step)",
+        };
+        text.sections[0].value = format!("{}\nSpeed {}x (+/-)", status, virtual_time.relative_speed());
+    }
+}
@@ -0,0 +1,319 @@
+use bevy::prelude::*;
+use bevy::app::PluginGroupBuilder;
+use crate::components::*;
+use crate::systems::*;
+use crate::config::SimConfig;
+use crate::pheromones::*;
+use crate::video::*;
+use crate::colors::*;
+use crate::challenges::*;
+use crate::editor::*;
+use crate::scoring::*;
+use crate::timed_events::*;
+use crate::tournament::{write_run_result_system, ResultFile};
+use crate::curriculum::*;
+use crate::leaderboard::*;
+use crate::playback::*;
+use crate::inspector::*;
+use crate::minimap::*;
+use crate::trends::*;
+use crate::logging::*;
+use crate::labels::*;
+use crate::legend::*;
+use crate::event_log::*;
+use crate::replay_timeline::*;
+use crate::grid_overlay::*;
+use crate::profiling::*;
+use crate::annotations::*;
+use crate::ui_scale::*;
+use crate::colony_panel::*;
+use crate::ghost_layer::*;
+use crate::camera_path::*;
+use crate::highlights::*;
+use crate::interpolation::*;
+use crate::replay::*;
+use crate::spatial_index::*;
+use crate::policy::ActivePolicy;
+
+/// Every part of the simulation: the world and its ticks, the debug UI, video recording and the
+/// challenge extras. Apps that only need some of them add those plugins on their own instead, always
+/// starting with SimulationPlugin. Optional inputs are resources inserted by the app before adding it
+/// (a VideoRecorder with the wanted video settings, a Heightmap, WorldMap or GeneratedMap, a
+/// Curriculum, a ResultFile, a GhostSnapshot, the LogVerbosity from init_logging); everything else
+/// comes from the fields here.
+pub struct AntSimPlugin {
+    pub config: SimConfig,
+    pub challenge_number: u32,
+    pub prey_food: bool, // Food sources wander and must be re-discovered
+    pub sim_hz: f64, // Fixed simulation ticks per second
+    pub ui_scale: f32,
+    pub scripted_events: Vec<TimedEvent>, // Run on top of the challenge's own timed events
+    pub scripted_camera: Vec<CameraKeyframe>, // Replaces the challenge's video camera path when not empty
+}
+
+impl Default for AntSimPlugin {
+    fn default() -> Self {
+        Self {
+            config: SimConfig::default(),
+            challenge_number: 1,
+            prey_food: false,
+            sim_hz: DEFAULT_SIM_HZ,
+            ui_scale: 1.0,
+            scripted_events: Vec::new(),
+            scripted_camera: Vec::new(),
+        }
+    }
+}
+
+impl PluginGroup for AntSimPlugin {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(SimulationPlugin {
+                config: self.config,
+                challenge_number: self.challenge_number,
+                prey_food: self.prey_food,
+                sim_hz: self.sim_hz,
+            })
+            .add(ChallengePlugin { scripted_events: self.scripted_events })
+            .add(DebugUiPlugin { ui_scale: self.ui_scale })
+            .add(VideoPlugin { scripted_camera: self.scripted_camera })
+    }
+}
+
+/// The world and its fixed ticks: the challenge's layout and config, ants, pheromones, food, the
+/// sprites that draw them, plus the keys that pause, restart, quit and move the camera. Enough on
+/// its own for headless experiments.
+pub struct SimulationPlugin {
+    pub config: SimConfig,
+    pub challenge_number: u32,
+    pub prey_food: bool, // Food sources wander and must be re-discovered
+    pub sim_hz: f64, // Fixed simulation ticks per second
+}
+
+impl Default for SimulationPlugin {
+    fn default() -> Self {
+        Self { config: SimConfig::default(), challenge_number: 1, prey_food: false, sim_hz: DEFAULT_SIM_HZ }
+    }
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        let mut config = self.config.clone();
+        let challenge_config = ChallengeConfig { challenge_number: self.challenge_number, prey_food: self.prey_food };
+        let challenge = find_challenge(self.challenge_number, &config);
+        challenge.configure(&mut config);
+        info!("📋 {}", challenge.describe());
+        let challenge_score = ChallengeScore::new(challenge.number(), challenge.pass_score());
+        app.init_resource::<ActivePolicy>(); // Kept if the app inserted its own
+        let decisions = app.world().resource::<ActivePolicy>().0.decision_systems();
+        
+        app
+            .insert_resource(ClearColor(Color::BLACK)) // Match video background
+            .insert_resource(config)
+            .insert_resource(PheromoneGrid::new(1000, 1000)) // 1:1 with world grid
+            .insert_resource(DebugInfo::default())
+            .insert_resource(PerformanceTracker::default())
+            .insert_resource(AntSpawner::default())
+            .insert_resource(ColonyState::default())
+            .insert_resource(ColorConfig::default())
+            .insert_resource(challenge_config)
+            .insert_resource(ActiveChallenge(challenge))
+            .insert_resource(challenge_score)
+            .init_resource::<ViewSettings>()
+            .init_resource::<PerformanceHistory>()
+            .insert_resource(SpatialIndex::default())
+            .insert_resource(Time::<Fixed>::from_hz(self.sim_hz))
+            .insert_resource(SimTick::default())
+            .insert_resource(SwarmDecisionLog::default())
+            .init_resource::<SensingClock>()
+            .init_state::<SimulationState>()
+            .add_event::<FoodPickedUp>()
+            .add_event::<FoodDelivered>()
+            .add_event::<AntStuck>()
+            .add_event::<FoodDepleted>()
+            .add_event::<SimEvent>()
+            .add_systems(Startup, (setup, setup_pheromone_visualization))
+            // The simulation runs on the fixed timestep so results don't depend on the frame rate
+            .add_systems(FixedFirst, open_sim_tick)
+            .add_systems(FixedPreUpdate, restore_tick_positions)
+            .add_systems(
+                FixedUpdate,
+                (
+                    ant_spawner_system,
+                    colony_role_system,
+                    spatial_index_system,
+                    ant_timer_system,
+                    sensing_systems(decisions),
+                    possessed_control_system,
+                    ant_proximity_analysis_system,
+                    behavior_analysis_system.run_if(analysis_due),
+                    movement_system,
+                    prey_movement_system,
+                    predator_system,
+                    pheromone_deposit_system,
+                    pheromone_update_system,
+                    food_collection_system,
+                    food_visual_system,
+                    performance_tracker_system,
+                    performance_analysis_system,
+                ).chain().run_if(sim_tick_open)
+            )
+            .add_systems(FixedUpdate, trail_collapse_system.after(colony_role_system).run_if(sim_tick_open))
+            .add_systems(FixedPostUpdate, record_tick_positions)
+            .add_systems(PostUpdate, interpolate_tick_positions.before(bevy::transform::TransformSystem::TransformPropagate))
+            .add_systems(Update, ant_visual_system.run_if(simulation_active.or_else(ants_without_mesh)))
+            .add_systems(Update, rock_mesh_system)
+            // Also redrawn while paused when a layer is toggled or the pheromone brush paints
+            .add_systems(Update, update_pheromone_visualization
+                .run_if(simulation_active.or_else(resource_changed::<ViewSettings>).or_else(resource_changed::<PheromoneGrid>)))
+            .add_systems(
+                Update,
+                (
+                    exit_system,
+                    exit_event_listener,
+                    window_close_system,
+                    restart_system,
+                    camera_control_system,
+                    playback_control_system,
+                )
+            )
+            .add_systems(Update, performance_history_system.run_if(simulation_active))
+            .add_systems(Last, finish_single_step);
+        add_timing_diagnostics(app);
+    }
+}
+
+/// The challenge's scripted events and stale-trail probe, the leaderboard, curriculum stages and the
+/// tournament's result file
+#[derive(Default)]
+pub struct ChallengePlugin {
+    pub scripted_events: Vec<TimedEvent>, // Run on top of the challenge's own timed events
+}
+
+impl Plugin for ChallengePlugin {
+    fn build(&self, app: &mut App) {
+        let mut timed_events = Vec::new();
+        let leaderboard = Leaderboard::load();
+        if let Some(challenge) = app.world().get_resource::<ActiveChallenge>() {
+            info!("🥇 {}", leaderboard.record_line(challenge.0.number()));
+            timed_events = challenge.0.timed_events();
+        }
+        timed_events.extend(self.scripted_events.iter().cloned());
+        
+        app
+            .insert_resource(leaderboard)
+            .insert_resource(EventSchedule::new(timed_events))
+            .insert_resource(StaleTrailProbe::default())
+            .init_resource::<Annotations>()
+            .add_systems(
+                FixedUpdate,
+                (timed_event_system, stale_trail_probe_system).chain()
+                    .after(food_collection_system)
+                    .before(food_visual_system)
+                    .run_if(sim_tick_open)
+            )
+            .add_systems(Update, write_run_result_system.after(curriculum_system))
+            .add_systems(Update, curriculum_system.before(video_recording_system))
+            .add_systems(Update, leaderboard_system.before(curriculum_system).run_if(not(resource_exists::<ResultFile>))); // Tournament child runs report to their parent instead
+    }
+}
+
+/// Everything on screen besides the world itself: HUD text, inspector, minimap, panels, overlays,
+/// labels, the map editor and annotations
+pub struct DebugUiPlugin {
+    pub ui_scale: f32,
+}
+
+impl Default for DebugUiPlugin {
+    fn default() -> Self {
+        Self { ui_scale: 1.0 }
+    }
+}
+
+impl Plugin for DebugUiPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<bevy_egui::EguiPlugin>() {
+            app.add_plugins(bevy_egui::EguiPlugin);
+        }
+        app
+            .insert_resource(EditorState::default())
+            .insert_resource(InspectorState::default())
+            .init_resource::<ViewSettings>()
+            .insert_resource(UiScale(self.ui_scale))
+            .insert_resource(ColonyPanel::default())
+            .insert_resource(StatsDashboard::default())
+            .insert_resource(EventLog::default())
+            .insert_resource(ReplayTimeline::default())
+            .init_resource::<Annotations>()
+            .add_systems(Startup, (setup_debug_ui, setup_playback_ui, setup_minimap, setup_ghost_layer))
+            .add_systems(
+                Update,
+                (
+                    cursor_tracking_system,
+                    hover_detection_system,
+                    ant_selection_system.run_if(pointer_outside_inspector),
+                    selected_ant_display_system,
+                    selected_ant_outline_system,
+                    trajectory_trail_system,
+                    food_drop_system.run_if(pointer_outside_inspector),
+                    update_debug_ui,
+                    update_objective_hud,
+                    editor_toggle_system,
+                    editor_input_system.after(cursor_tracking_system).run_if(pointer_outside_inspector),
+                    update_playback_ui,
+                    inspector_toggle_system,
+                    inspector_ui_system,
+                )
+            )
+            .add_systems(Update, (minimap_system, pheromone_layer_toggle_system, performance_chart_system, editor_preview_system, sensing_gizmo_system, swarm_decision_gizmo_system, pheromone_brush_system.run_if(pointer_outside_inspector), log_verbosity_toggle_system.run_if(resource_exists::<LogVerbosity>), ant_label_toggle_system, ant_label_system, color_legend_toggle_system, color_legend_system, camera_bookmark_system, view_settings_system, stats_dashboard_toggle_system, stats_dashboard_system, event_log_toggle_system, event_log_collect_system, event_log_panel_system))
+            .add_systems(Update, (world_grid_toggle_system, world_grid_system, timing_overlay_toggle_system, timing_overlay_system, possess_toggle_system))
+            .add_systems(Update, (annotation_input_system.run_if(pointer_outside_inspector), annotation_ui_system, annotation_render_system))
+            .add_systems(Update, (ui_scale_hotkey_system, apply_ui_scale_system.run_if(resource_changed::<UiScale>)).chain())
+            .add_systems(Update, (colony_panel_toggle_system, colony_panel_system))
+            .add_systems(Update, (replay_timeline_toggle_system, replay_timeline_collect_system, replay_timeline_panel_system))
+            .add_systems(Update, (pheromone_snapshot_system, ghost_layer_visibility_system.run_if(resource_changed::<ViewSettings>)))
+            .add_systems(PreUpdate, absorb_keyboard_for_egui.after(bevy::input::InputSystem));
+    }
+}
+
+/// The run's video with its highlight clips, the Z replay buffer and the end-of-run saves. Follows
+/// the VideoRecorder the app inserted before adding it, or records with the defaults.
+#[derive(Default)]
+pub struct VideoPlugin {
+    pub scripted_camera: Vec<CameraKeyframe>, // Replaces the challenge's camera path when not empty
+}
+
+impl Plugin for VideoPlugin {
+    fn build(&self, app: &mut App) {
+        // Scripted keyframes replace the challenge's own camera path, and a video crop replaces it too
+        let cropped = app.world().get_resource::<VideoRecorder>().is_some_and(|recorder| recorder.crop != VideoCrop::Whole);
+        let camera_path = CameraPath::new(if !self.scripted_camera.is_empty() {
+            self.scripted_camera.clone()
+        } else if cropped {
+            Vec::new()
+        } else {
+            app.world().get_resource::<ActiveChallenge>().map_or_else(Vec::new, |challenge| challenge.0.camera_path())
+        });
+        if !camera_path.keyframes.is_empty() {
+            info!("🎬 Video camera path with {} keyframes", camera_path.keyframes.len());
+        }
+        
+        app
+            .init_resource::<VideoRecorder>() // Kept if the app inserted its own
+            .insert_resource(ScreenCapture::default())
+            .insert_resource(GenerationInfo::from_json_file())
+            .insert_resource(camera_path)
+            .insert_resource(HighlightClips::default())
+            .insert_resource(ReplayBuffer::default())
+            .insert_resource(PendingSaves::default())
+            .init_resource::<Annotations>()
+            .add_systems(Startup, setup_video_camera)
+            .add_systems(Update, video_recording_system)
+            .add_systems(Update, (highlight_trigger_system.before(video_recording_system), replay_hotkey_system.before(video_recording_system)));
+    }
+}
+
+fn setup(mut commands: Commands, layout: ChallengeLayout) {
+    commands.spawn(Camera2dBundle::default());
+    layout.spawn(&mut commands);
+}
@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+use bevy::ecs::schedule::SystemConfigs;
+use crate::components::*;
+use crate::config::SimConfig;
+use crate::pheromones::*;
+use crate::systems::{trail_following_system, exploration_system, rock_avoidance_system, carrying_navigation_system};
+
+/// What an ant senses when it is due a decision, the same for every ObservationPolicy
+#[derive(Debug, Clone)]
+pub struct AntObservation {
+    pub position: Vec2,
+    pub heading: f32,
+    pub carrying_food: bool,
+    pub to_nest: Vec2, // Offset from the ant to its own nest
+    pub food_trail: [f32; 8], // Perceived pheromone along each COMPASS_8 heading, sensor noise included
+    pub nest_trail: [f32; 8],
+    pub alarm: [f32; 8],
+    pub time: f32,
+}
+
+/// A policy's answer to an observation
+#[derive(Debug, Clone, Copy)]
+pub struct AntAction {
+    pub heading: f32,
+    pub movement: MovementType,
+    pub next_decision: f32, // Seconds the ant holds this heading before it is asked again
+}
+
+/// A decision backend: the systems that turn what ants sense into where they go next. They run
+/// after sensing_gate_system and pick up every ant it left SensingStep::Pending, writing the chosen
+/// heading to DesiredHeading and leaving the ant Decided, Settled or Holding, so stuck detection and
+/// steering work the same under any policy.
+pub trait AntPolicy: Send + Sync + 'static {
+    /// Short name for logs
+    fn name(&self) -> &'static str;
+
+    /// The systems that decide for Pending ants, chained in the order they hand ants along
+    fn decision_systems(&self) -> SystemConfigs;
+}
+
+/// A policy that decides each ant from its AntObservation alone; observation_decisions turns it
+/// into decision systems. Implementations are asked from several threads at once, so any state
+/// they learn belongs in a lock or in the observation.
+pub trait ObservationPolicy: Send + Sync + 'static {
+    /// The ant's next heading, or None to keep its course until the next decision
+    fn act(&self, observation: &AntObservation) -> Option<AntAction>;
+}
+
+/// The hand-tuned trail following, exploration, rock avoidance and carrying navigation. These read
+/// far more than an AntObservation holds (swarm state, rocks, diagnostics), so each stays a system
+/// of its own and hands the ants it can't decide for to the next.
+pub struct HandTunedPolicy;
+
+impl AntPolicy for HandTunedPolicy {
+    fn name(&self) -> &'static str {
+        "hand-tuned"
+    }
+
+    fn decision_systems(&self) -> SystemConfigs {
+        (
+            trail_following_system,
+            exploration_system,
+            rock_avoidance_system,
+            carrying_navigation_system,
+        ).chain()
+    }
+}
+
+/// Textbook ACO transition rule: each heading is picked with probability proportional to
+/// (pheromone + floor)^alpha * visibility^beta, where visibility favours the way the ant is going,
+/// or the nest for food carriers, and rules out turning back
+#[derive(Clone, Copy)]
+pub struct ClassicAcoPolicy {
+    pub alpha: f32,
+    pub beta: f32,
+    pub pheromone_floor: f32, // Keeps unmarked headings possible so ants still explore
+    pub decision_interval: f32,
+}
+
+impl Default for ClassicAcoPolicy {
+    fn default() -> Self {
+        Self { alpha: 2.0, beta: 1.0, pheromone_floor: 0.05, decision_interval: 0.25 }
+    }
+}
+
+impl AntPolicy for ClassicAcoPolicy {
+    fn name(&self) -> &'static str {
+        "aco"
+    }
+
+    fn decision_systems(&self) -> SystemConfigs {
+        observation_decisions(*self)
+    }
+}
+
+impl ObservationPolicy for ClassicAcoPolicy {
+    fn act(&self, observation: &AntObservation) -> Option<AntAction> {
+        let forward = Vec2::from_angle(observation.heading);
+        let (trail, preferred) = if observation.carrying_food {
+            (&observation.nest_trail, observation.to_nest.try_normalize().unwrap_or(forward))
+        } else {
+            (&observation.food_trail, forward)
+        };
+
+        let compass = &*COMPASS_8;
+        let weights: [f32; 8] = std::array::from_fn(|i| {
+            let visibility = (1.0 + compass.headings[i].dot(preferred)) / 2.0;
+            (trail[i] + self.pheromone_floor).powf(self.alpha) * visibility.powf(self.beta)
+        });
+        let total: f32 = weights.iter().sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        // Roulette-wheel selection
+        let mut pick = rand::random::<f32>() * total;
+        let chosen = weights.iter().position(|&weight| {
+            pick -= weight;
+            pick <= 0.0
+        }).unwrap_or(7);
+
+        let movement = if observation.carrying_food {
+            MovementType::CarryingFood
+        } else if trail[chosen] > self.pheromone_floor {
+            MovementType::FollowingTrail
+        } else {
+            MovementType::Exploring
+        };
+        Some(AntAction { heading: compass.angles[chosen], movement, next_decision: self.decision_interval })
+    }
+}
+
+/// The policy every ant decides with; the hand-tuned one unless `--policy` or the embedding app
+/// picked another. SimulationPlugin schedules its decision systems when it is built.
+#[derive(Resource)]
+pub struct ActivePolicy(pub Box<dyn AntPolicy>);
+
+impl Default for ActivePolicy {
+    fn default() -> Self {
+        Self(Box::new(HandTunedPolicy))
+    }
+}
+
+impl ActivePolicy {
+    /// Parse a policy from its command-line name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "hand-tuned" | "default" => Some(Self(Box::new(HandTunedPolicy))),
+            "aco" => Some(Self(Box::new(ClassicAcoPolicy::default()))),
+            _ => None,
+        }
+    }
+}
+
+/// A decision system that asks `policy` for a heading for every ant due a decision. Ants between
+/// decisions hold their heading, as they do under the hand-tuned policy.
+pub fn observation_decisions<P: ObservationPolicy>(policy: P) -> SystemConfigs {
+    (move |mut ants: Query<(&Transform, &mut AntCore, &mut SensingStep, &mut DesiredHeading), Without<Possessed>>,
+           pheromone_grid: Option<Res<PheromoneGrid>>,
+           config: Res<SimConfig>,
+           time: Res<Time>| {
+        let Some(grid) = pheromone_grid else { return };
+        let sensor = SensorModel {
+            noise_std: config.sensor_noise_std,
+            false_negative_rate: config.sensor_false_negative_rate,
+        };
+        let now = time.elapsed_seconds();
+
+        ants.par_iter_mut().for_each(|(transform, mut ant, mut step, mut desired)| {
+            if !matches!(*step, SensingStep::Pending) {
+                return;
+            }
+            if ant.sensing_timer > 0.0 {
+                *step = SensingStep::Holding;
+                return;
+            }
+
+            let position = transform.translation.truncate();
+            let observation = AntObservation {
+                position,
+                heading: ant.current_direction,
+                carrying_food: ant.carrying_food,
+                to_nest: ant.nest_position - position,
+                food_trail: sensor.sample_all_directions(&grid, position.x, position.y, PheromoneType::Food),
+                nest_trail: sensor.sample_all_directions(&grid, position.x, position.y, PheromoneType::Nest),
+                alarm: sensor.sample_all_directions(&grid, position.x, position.y, PheromoneType::Alarm),
+                time: now,
+            };
+            if let Some(action) = policy.act(&observation) {
+                desired.0 = Some((action.heading, action.movement));
+                ant.sensing_timer = action.next_decision;
+                ant.behavior_state = if matches!(action.movement, MovementType::FollowingTrail) {
+                    AntBehaviorState::Following
+                } else {
+                    AntBehaviorState::Exploring
+                };
+            }
+            *step = SensingStep::Decided;
+        });
+    }).into_configs()
+}
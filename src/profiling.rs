@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use bevy::diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin, RegisterDiagnostic};
+use bevy_egui::{egui, EguiContexts};
+use std::time::Instant;
+use crate::components::ViewSettings;
+
+/// Milliseconds spent inside the hottest simulation systems, measured each time they run
+pub const SENSING_TIME: DiagnosticPath = DiagnosticPath::const_new("antsim/sensing");
+pub const PHEROMONE_UPDATE_TIME: DiagnosticPath = DiagnosticPath::const_new("antsim/pheromone_update_system");
+pub const PHEROMONE_VISUALIZATION_TIME: DiagnosticPath = DiagnosticPath::const_new("antsim/update_pheromone_visualization");
+
+const SYSTEM_TIMINGS: [(&str, DiagnosticPath); 3] = [
+    ("sensing", SENSING_TIME),
+    ("pheromone update", PHEROMONE_UPDATE_TIME),
+    ("pheromone visuals", PHEROMONE_VISUALIZATION_TIME),
+];
+
+/// Frame time and entity count diagnostics plus the per-system timings above
+pub fn add_timing_diagnostics(app: &mut App) {
+    app.add_plugins((FrameTimeDiagnosticsPlugin, EntityCountDiagnosticsPlugin));
+    for (_, path) in SYSTEM_TIMINGS {
+        app.register_diagnostic(Diagnostic::new(path).with_suffix("ms"));
+    }
+}
+
+/// Record the time since `started` under `path`
+pub fn record_system_time(diagnostics: &mut Diagnostics, path: &DiagnosticPath, started: Instant) {
+    diagnostics.add_measurement(path, || started.elapsed().as_secs_f64() * 1000.0);
+}
+
+/// P shows or hides the timing overlay
+pub fn timing_overlay_toggle_system(input: Res<ButtonInput<KeyCode>>, mut view: ResMut<ViewSettings>) {
+    if input.just_pressed(KeyCode::KeyP) {
+        view.system_timings = !view.system_timings;
+    }
+}
+
+/// Smoothed FPS, frame time and per-system cost, top centre. Systems that didn't run (paused) show their last value.
+pub fn timing_overlay_system(mut contexts: EguiContexts, view: Res<ViewSettings>, store: Res<DiagnosticsStore>) {
+    if !view.system_timings {
+        return;
+    }
+    let smoothed = |path: &DiagnosticPath| store.get(path).and_then(|diagnostic| diagnostic.smoothed());
+
+    egui::Area::new(egui::Id::new("timing_overlay"))
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 10.0))
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Frame::none().fill(egui::Color32::from_black_alpha(160)).inner_margin(6.0).show(ui, |ui| {
+                let fps = smoothed(&FrameTimeDiagnosticsPlugin::FPS).unwrap_or(0.0);
+                let frame_ms = smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME).unwrap_or(0.0);
+                let entities = smoothed(&EntityCountDiagnosticsPlugin::ENTITY_COUNT).unwrap_or(0.0);
+                ui.label(egui::RichText::new(format!("{:.0} FPS  {:.2} ms/frame  {:.0} entities", fps, frame_ms, entities)).monospace());
+
+                for (name, path) in SYSTEM_TIMINGS {
+                    let ms = smoothed(&path).unwrap_or(0.0);
+                    let share = if frame_ms > 0.0 { ms / frame_ms * 100.0 } else { 0.0 };
+                    ui.label(egui::RichText::new(format!("{:<18} {:>6.2} ms  {:>3.0}%", name, ms, share)).monospace());
+                }
+            });
+        });
+}
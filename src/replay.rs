@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use crate::video::{FramePool, FrameSink};
+
+/// Simulation seconds of video kept for the save-replay hotkey
+pub const REPLAY_SECONDS: f32 = 30.0;
+
+/// Rolling buffer of the last REPLAY_SECONDS of captured frames, recorded in free play as well as
+/// challenge runs, so anything interesting can be kept after it happened
+#[derive(Resource, Default)]
+pub struct ReplayBuffer {
+    frames: VecDeque<(f32, Vec<u8>)>, // Captured frames with their time
+    save_requested: bool,
+    pub saved: usize, // Replays started this session, for numbering the files
+    pub encoding: Vec<FrameSink>, // Replays whose encoder thread may still be running
+}
+
+impl ReplayBuffer {
+    /// Keep a captured frame, handing back to `pool` those older than REPLAY_SECONDS and the oldest
+    /// beyond `budget` bytes
+    pub fn record(&mut self, time: f32, frame: &[u8], budget: usize, pool: &FramePool) {
+        self.frames.push_back((time, pool.copy_of(frame)));
+        while self.frames.front().is_some_and(|(captured, _)| *captured < time - REPLAY_SECONDS)
+            || self.frames.len() * frame.len().max(1) > budget {
+            let Some((_, expired)) = self.frames.pop_front() else { break };
+            pool.give(expired);
+        }
+    }
+
+    /// Capture times of the oldest and newest buffered frames
+    pub fn span(&self) -> Option<(f32, f32)> {
+        Some((self.frames.front()?.0, self.frames.back()?.0))
+    }
+
+    /// The last frame captured at or before `time` (the oldest if none was), with its capture time
+    pub fn frame_at(&self, time: f32) -> Option<(f32, &[u8])> {
+        let index = self.frames.partition_point(|(captured, _)| *captured <= time).saturating_sub(1);
+        self.frames.get(index).map(|(captured, frame)| (*captured, frame.as_slice()))
+    }
+
+    /// Copies of the buffered frames if the hotkey was pressed since the last call
+    pub fn take_requested(&mut self, pool: &FramePool) -> Option<Vec<Vec<u8>>> {
+        if !std::mem::take(&mut self.save_requested) {
+            return None;
+        }
+        Some(self.frames.iter().map(|(_, frame)| pool.copy_of(frame)).collect())
+    }
+}
+
+/// Z saves the last 30 seconds of video
+pub fn replay_hotkey_system(input: Res<ButtonInput<KeyCode>>, mut replay: ResMut<ReplayBuffer>) {
+    if input.just_pressed(KeyCode::KeyZ) {
+        replay.save_requested = true;
+        info!("⏺️ Saving the last {:.0}s of video", REPLAY_SECONDS);
+    }
+}
@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::collections::VecDeque;
+use crate::components::*;
+use crate::replay::{ReplayBuffer, REPLAY_SECONDS};
+
+/// Width of the frame preview and the timeline under it, in points
+const TIMELINE_WIDTH: f32 = 240.0;
+/// How close to a tick, in points, a click has to land to jump to it
+const TICK_HIT_RADIUS: f32 = 4.0;
+
+/// Something marked on the timeline
+struct TimelineTick {
+    time: f32,
+    kind: SimEventKind, // Delivery or PredatorKill
+    position: Vec2,
+}
+
+/// Player for the replay buffer's frames, toggled with U: play/pause, a scrub slider and a tick for
+/// every delivery and death the buffer covers
+#[derive(Resource, Default)]
+pub struct ReplayTimeline {
+    pub visible: bool,
+    playhead: Option<f32>, // None follows the newest frame
+    playing: bool,
+    ticks: VecDeque<TimelineTick>,
+    texture: Option<egui::TextureHandle>,
+    shown: Option<f32>, // Capture time of the frame in the texture
+}
+
+pub fn replay_timeline_toggle_system(input: Res<ButtonInput<KeyCode>>, mut timeline: ResMut<ReplayTimeline>) {
+    if input.just_pressed(KeyCode::KeyU) {
+        timeline.visible = !timeline.visible;
+    }
+}
+
+/// Keeps ticks for the deliveries and predator kills of the last REPLAY_SECONDS, whether or not the
+/// timeline is open
+pub fn replay_timeline_collect_system(
+    mut timeline: ResMut<ReplayTimeline>,
+    mut deliveries: EventReader<FoodDelivered>,
+    mut sim_events: EventReader<SimEvent>,
+    time: Res<Time>,
+) {
+    let delivered = deliveries.read()
+        .map(|event| TimelineTick { time: event.time, kind: SimEventKind::Delivery, position: event.position });
+    let killed = sim_events.read()
+        .filter(|event| event.kind == SimEventKind::PredatorKill)
+        .map(|event| TimelineTick { time: event.time, kind: event.kind, position: event.position });
+    timeline.ticks.extend(delivered.chain(killed));
+
+    let oldest = time.elapsed_seconds() - REPLAY_SECONDS;
+    timeline.ticks.retain(|tick| tick.time >= oldest);
+}
+
+/// The frame at the playhead above the controls; clicking a tick jumps there and centres the
+/// camera on where it happened, so the spot can be inspected in the live world
+pub fn replay_timeline_panel_system(
+    mut contexts: EguiContexts,
+    mut timeline: ResMut<ReplayTimeline>,
+    replay: Option<Res<ReplayBuffer>>,
+    video_recorder: Option<Res<VideoRecorder>>,
+    mut debug_info: ResMut<DebugInfo>,
+    mut camera_query: Query<&mut Transform, With<Camera>>,
+    real_time: Res<Time<Real>>,
+) {
+    if !timeline.visible {
+        return;
+    }
+    let timeline = &mut *timeline;
+
+    let span = replay.as_ref().and_then(|replay| replay.span());
+    if let (Some((start, end)), true) = (span, timeline.playing) {
+        let next = timeline.playhead.unwrap_or(end) + real_time.delta_seconds();
+        if next >= end {
+            // Caught up with the recording
+            timeline.playhead = None;
+            timeline.playing = false;
+        } else {
+            timeline.playhead = Some(next.max(start));
+        }
+    }
+
+    // Upload the frame at the playhead when it changes
+    if let (Some(replay), Some(recorder), Some((_, end))) = (replay.as_ref(), video_recorder.as_ref(), span) {
+        if let Some((captured, frame)) = replay.frame_at(timeline.playhead.unwrap_or(end)) {
+            let size = [recorder.frame_width as usize, recorder.frame_height as usize];
+            if timeline.shown != Some(captured) && frame.len() == size[0] * size[1] * 4 {
+                let image = egui::ColorImage::from_rgba_unmultiplied(size, frame);
+                match timeline.texture {
+                    Some(ref mut texture) => texture.set(image, egui::TextureOptions::LINEAR),
+                    None => timeline.texture = Some(contexts.ctx_mut().load_texture("replay_frame", image, egui::TextureOptions::LINEAR)),
+                }
+                timeline.shown = Some(captured);
+            }
+        }
+    }
+
+    let mut jump_to = None;
+    let mut visible = timeline.visible;
+    egui::Window::new("Replay (U to hide)")
+        .open(&mut visible)
+        .default_pos(egui::pos2(760.0, 80.0))
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let Some((start, end)) = span else {
+                ui.label("No frames recorded yet");
+                return;
+            };
+
+            if let (Some(texture), Some(recorder)) = (timeline.texture.as_ref(), video_recorder.as_ref()) {
+                let aspect = recorder.frame_height as f32 / recorder.frame_width.max(1) as f32;
+                ui.image((texture.id(), egui::vec2(TIMELINE_WIDTH, TIMELINE_WIDTH * aspect)));
+            }
+
+            ui.horizontal(|ui| {
+                let label = if timeline.playing { "⏸" } else { "▶" };
+                if ui.button(label).clicked() {
+                    timeline.playing = !timeline.playing;
+                    if timeline.playing && timeline.playhead.is_none() {
+                        // Play from the start of the buffer rather than sitting at the live edge
+                        timeline.playhead = Some(start);
+                    }
+                }
+                let mut position = timeline.playhead.unwrap_or(end);
+                ui.spacing_mut().slider_width = TIMELINE_WIDTH - 40.0;
+                if ui.add(egui::Slider::new(&mut position, start..=end).show_value(false)).changed() {
+                    timeline.playhead = Some(position);
+                }
+            });
+
+            // Tick strip lined up under the slider
+            let (rect, response) = ui.allocate_exact_size(egui::vec2(TIMELINE_WIDTH, 14.0), egui::Sense::click());
+            let painter = ui.painter_at(rect);
+            painter.hline(rect.x_range(), rect.center().y, egui::Stroke::new(1.0, egui::Color32::DARK_GRAY));
+            let to_x = |time: f32| rect.left() + (time - start) / (end - start).max(0.001) * rect.width();
+            for tick in timeline.ticks.iter().filter(|tick| tick.time >= start && tick.time <= end) {
+                painter.vline(to_x(tick.time), rect.y_range(), egui::Stroke::new(2.0, tick.kind.color()));
+            }
+            if let Some(x) = response.interact_pointer_pos().filter(|_| response.clicked()).map(|pos| pos.x) {
+                jump_to = timeline.ticks.iter()
+                    .filter(|tick| tick.time >= start && tick.time <= end && (to_x(tick.time) - x).abs() <= TICK_HIT_RADIUS)
+                    .min_by(|a, b| (to_x(a.time) - x).abs().total_cmp(&(to_x(b.time) - x).abs()))
+                    .map(|tick| (tick.time, tick.position));
+            }
+
+            let shown = timeline.playhead.map_or("live".to_string(), |playhead| format!("{:.1}s ago", end - playhead));
+            let deaths = timeline.ticks.iter().filter(|tick| tick.kind == SimEventKind::PredatorKill).count();
+            ui.label(format!("{} | {} deliveries, {} deaths in the last {:.0}s", shown, timeline.ticks.len() - deaths, deaths, end - start));
+        });
+    timeline.visible = visible;
+
+    if let Some((time, position)) = jump_to {
+        timeline.playhead = Some(time);
+        timeline.playing = false;
+        if let Ok(mut camera_transform) = camera_query.get_single_mut() {
+            camera_transform.translation = position.extend(camera_transform.translation.z);
+            debug_info.follow_selected = false;
+        }
+    }
+}
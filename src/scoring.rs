@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use crate::components::*;
+
+/// Length of a scored run in seconds
+pub const TIME_LIMIT: f32 = 90.0;
+
+/// Points subtracted for each ant lost to predators
+pub const LOST_ANT_PENALTY: f32 = 2.0;
+
+/// Score needed to pass a challenge that doesn't set its own bar
+pub const DEFAULT_PASS_SCORE: f32 = 40.0;
+
+/// Where a scored run stands
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Running,
+    Success,
+    Failure,
+}
+
+impl Verdict {
+    pub fn label(self) -> &'static str {
+        match self {
+            Verdict::Running => "RUNNING",
+            Verdict::Success => "SUCCESS",
+            Verdict::Failure => "FAILURE",
+        }
+    }
+}
+
+/// Live score for the active challenge, shown in the UI and video and saved to generation_info.json
+#[derive(Resource)]
+pub struct ChallengeScore {
+    pub challenge: u32,
+    pub score: f32,
+    pub pass_score: f32,
+    pub verdict: Verdict,
+    pub reason: String, // Why the run ended, empty while running
+}
+
+impl ChallengeScore {
+    pub fn new(challenge: u32, pass_score: f32) -> Self {
+        Self {
+            challenge,
+            score: 0.0,
+            pass_score,
+            verdict: Verdict::Running,
+            reason: String::new(),
+        }
+    }
+
+    /// Settle the verdict once; later calls are ignored
+    pub fn finish(&mut self, verdict: Verdict, reason: String) {
+        if self.verdict == Verdict::Running {
+            self.verdict = verdict;
+            self.reason = reason;
+        }
+    }
+}
+
+/// Deliveries weighted by when they happened minus a penalty for lost ants.
+/// A delivery at t=0 is worth 2 points, falling to 1 point at the time limit,
+/// so colonies that establish trails early score higher.
+pub fn time_weighted_score(tracker: &PerformanceTracker) -> f32 {
+    let delivery_points: f32 = tracker.delivery_timestamps.iter()
+        .map(|&t| 1.0 + (1.0 - t / TIME_LIMIT).clamp(0.0, 1.0))
+        .sum();
+    delivery_points - tracker.ants_lost as f32 * LOST_ANT_PENALTY
+}
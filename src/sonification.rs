@@ -0,0 +1,130 @@
+use std::io::{BufWriter, Write};
+
+/// Audio samples per second of the soundtrack
+const SAMPLE_RATE: u32 = 44_100;
+
+/// The drone's pitch falls from TONE_HIGH_HZ at instant returns to TONE_LOW_HZ once the average
+/// goal time reaches SLOW_GOAL_TIME, so a colony that is getting faster audibly rises
+const TONE_LOW_HZ: f32 = 220.0;
+const TONE_HIGH_HZ: f32 = 880.0;
+const SLOW_GOAL_TIME: f32 = 60.0;
+const TONE_LEVEL: f32 = 0.12;
+
+/// Delivery ticks: a short decaying blip, at most TICKS_PER_FRAME per frame so fast timelapses don't smear
+const TICK_HZ: f32 = 1760.0;
+const TICK_DECAY: f32 = 0.025;
+const TICK_LEVEL: f32 = 0.45;
+const TICKS_PER_FRAME: u32 = 8;
+
+/// Alarm bursts: the alarm total has to jump to ALARM_SPIKE times its recent average (and past
+/// ALARM_FLOOR) to sound, and bursts are at least ALARM_COOLDOWN seconds of audio apart
+const ALARM_SPIKE: f32 = 2.0;
+const ALARM_FLOOR: f32 = 1.0;
+const ALARM_BURST: f32 = 0.15;
+const ALARM_LEVEL: f32 = 0.35;
+const ALARM_COOLDOWN: f32 = 0.3;
+
+/// What the soundtrack follows, sampled once per captured frame
+struct Cue {
+    deliveries: u32,
+    goal_time: f32,
+    alarm: f32,
+}
+
+/// Soundtrack for the run's video (`--video-audio`): a tick per delivery, a drone pitched by the average
+/// goal time and a noise burst when alarm pheromone spikes, one cue per video frame so it stays in sync
+#[derive(Default)]
+pub struct Soundtrack {
+    cues: Vec<Cue>,
+}
+
+impl Soundtrack {
+    /// Called once for every frame of the main video
+    pub fn record(&mut self, deliveries: u32, goal_time: f32, alarm: f32) {
+        self.cues.push(Cue { deliveries, goal_time, alarm });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cues.is_empty()
+    }
+
+    /// Mono samples covering the cues played back at `fps`
+    fn render(&self, fps: u32) -> Vec<f32> {
+        let samples_per_frame = SAMPLE_RATE as f32 / fps.max(1) as f32;
+        let mut samples = vec![0.0f32; (self.cues.len() as f32 * samples_per_frame).ceil() as usize];
+        let mut phase = 0.0f32;
+        let mut noise_state: u32 = 0x9e37_79b9;
+        let mut alarm_average = 0.0f32;
+        let mut last_burst = f32::NEG_INFINITY;
+        let mut previous_deliveries = self.cues.first().map_or(0, |cue| cue.deliveries);
+
+        for (frame, cue) in self.cues.iter().enumerate() {
+            let start = (frame as f32 * samples_per_frame) as usize;
+            let end = (((frame + 1) as f32 * samples_per_frame) as usize).min(samples.len());
+            let frame_start_seconds = start as f32 / SAMPLE_RATE as f32;
+
+            // Drone, silent until the first goal time is known
+            if cue.goal_time > 0.0 {
+                let speed = 1.0 - (cue.goal_time / SLOW_GOAL_TIME).clamp(0.0, 1.0);
+                let frequency = TONE_LOW_HZ * (TONE_HIGH_HZ / TONE_LOW_HZ).powf(speed);
+                for sample in &mut samples[start..end] {
+                    phase = (phase + frequency / SAMPLE_RATE as f32).fract();
+                    *sample += (phase * std::f32::consts::TAU).sin() * TONE_LEVEL;
+                }
+            }
+
+            // Ticks spread evenly across the frame
+            let new_deliveries = cue.deliveries.saturating_sub(previous_deliveries).min(TICKS_PER_FRAME);
+            previous_deliveries = cue.deliveries;
+            for tick in 0..new_deliveries {
+                let offset = start + ((end - start) as f32 * tick as f32 / new_deliveries as f32) as usize;
+                let length = ((TICK_DECAY * 4.0) * SAMPLE_RATE as f32) as usize;
+                for (i, sample) in samples.iter_mut().skip(offset).take(length).enumerate() {
+                    let t = i as f32 / SAMPLE_RATE as f32;
+                    *sample += (t * TICK_HZ * std::f32::consts::TAU).sin() * (-t / TICK_DECAY).exp() * TICK_LEVEL;
+                }
+            }
+
+            // Noise burst on an alarm spike
+            let spike = cue.alarm > ALARM_FLOOR && cue.alarm > alarm_average * ALARM_SPIKE;
+            if spike && frame_start_seconds - last_burst >= ALARM_COOLDOWN {
+                last_burst = frame_start_seconds;
+                let length = (ALARM_BURST * SAMPLE_RATE as f32) as usize;
+                for (i, sample) in samples.iter_mut().skip(start).take(length).enumerate() {
+                    noise_state ^= noise_state << 13;
+                    noise_state ^= noise_state >> 17;
+                    noise_state ^= noise_state << 5;
+                    let noise = noise_state as f32 / u32::MAX as f32 * 2.0 - 1.0;
+                    *sample += noise * (1.0 - i as f32 / length as f32) * ALARM_LEVEL;
+                }
+            }
+            alarm_average = if frame == 0 { cue.alarm } else { alarm_average * 0.9 + cue.alarm * 0.1 };
+        }
+        samples
+    }
+
+    /// Write the soundtrack for video at `fps` as a 16-bit mono WAV file
+    pub fn write_wav(&self, path: &str, fps: u32) -> std::io::Result<()> {
+        let samples = self.render(fps);
+        let data_bytes = (samples.len() * 2) as u32;
+        let mut writer = BufWriter::new(std::fs::File::create(path)?);
+        writer.write_all(b"RIFF")?;
+        writer.write_all(&(36 + data_bytes).to_le_bytes())?;
+        writer.write_all(b"WAVEfmt ")?;
+        writer.write_all(&16u32.to_le_bytes())?; // Format chunk size
+        writer.write_all(&1u16.to_le_bytes())?; // PCM
+        writer.write_all(&1u16.to_le_bytes())?; // Mono
+        writer.write_all(&SAMPLE_RATE.to_le_bytes())?;
+        writer.write_all(&(SAMPLE_RATE * 2).to_le_bytes())?; // Byte rate
+        writer.write_all(&2u16.to_le_bytes())?; // Block align
+        writer.write_all(&16u16.to_le_bytes())?; // Bits per sample
+        writer.write_all(b"data")?;
+        writer.write_all(&data_bytes.to_le_bytes())?;
+        for sample in samples {
+            // Soft clip so overlapping ticks and bursts saturate instead of wrapping
+            let value = (sample.tanh() * i16::MAX as f32) as i16;
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        writer.flush()
+    }
+}
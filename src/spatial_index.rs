@@ -0,0 +1,212 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::components::*;
+
+/// Side of a rock or food bucket; about the widest rock clearance the sensing code checks, so range queries touch few buckets
+const CELL_SIZE: f32 = 50.0;
+/// Side of an ant bucket; ants are far denser, and crowding checks only reach about this far
+const ANT_CELL_SIZE: f32 = 25.0;
+const WORLD_HALF_SIZE: f32 = 500.0;
+
+/// A rock as the index saw it when it was last rebuilt
+#[derive(Clone, Copy, Debug)]
+pub struct IndexedRock {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub radius: f32,
+}
+
+/// A food source as the index saw it when it was last rebuilt; the amount left is read from the entity
+#[derive(Clone, Copy, Debug)]
+pub struct IndexedFood {
+    pub entity: Entity,
+    pub position: Vec2,
+}
+
+/// An ant as the index saw it at the start of the tick, before anything moved
+#[derive(Clone, Copy, Debug)]
+pub struct IndexedAnt {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub carrying_food: bool,
+    pub successful_deliveries: u32,
+}
+
+/// Items bucketed by the cell holding their centre, stored sorted by bucket so a row of neighbouring
+/// buckets is one contiguous slice. Queries widen by the largest radius so an item whose edge reaches
+/// into range is found even when its centre sits in a neighbouring bucket.
+struct BucketGrid<T> {
+    items: Vec<(Vec2, f32, T)>, // Centre, radius, item; sorted by bucket
+    cell_starts: Vec<u32>,      // Bucket c holds items[cell_starts[c]..cell_starts[c + 1]]
+    unsorted: Vec<(Vec2, f32, T)>, // Rebuild scratch, kept for its capacity
+    cell_size: f32,
+    cells_per_side: usize,
+    max_radius: f32,
+}
+
+impl<T: Copy> Default for BucketGrid<T> {
+    fn default() -> Self {
+        Self::with_cell_size(CELL_SIZE)
+    }
+}
+
+impl<T: Copy> BucketGrid<T> {
+    fn with_cell_size(cell_size: f32) -> Self {
+        let cells_per_side = (2.0 * WORLD_HALF_SIZE / cell_size).ceil() as usize;
+        Self {
+            items: Vec::new(),
+            cell_starts: vec![0; cells_per_side * cells_per_side + 1],
+            unsorted: Vec::new(),
+            cell_size,
+            cells_per_side,
+            max_radius: 0.0,
+        }
+    }
+    
+    /// Bucket column or row for a world coordinate, clamped so anything outside the world lands in an edge bucket
+    fn cell_coordinate(&self, value: f32) -> usize {
+        (((value + WORLD_HALF_SIZE) / self.cell_size).floor().max(0.0) as usize).min(self.cells_per_side - 1)
+    }
+    
+    fn cell_of(&self, position: Vec2) -> usize {
+        self.cell_coordinate(position.y) * self.cells_per_side + self.cell_coordinate(position.x)
+    }
+    
+    /// Replace the contents with `entries` (centre, radius, item), counting-sorted into their buckets
+    fn rebuild(&mut self, entries: impl IntoIterator<Item = (Vec2, f32, T)>) {
+        let mut unsorted = std::mem::take(&mut self.unsorted);
+        unsorted.clear();
+        unsorted.extend(entries);
+        
+        self.cell_starts.fill(0);
+        self.max_radius = 0.0;
+        for &(position, radius, _) in &unsorted {
+            let cell = self.cell_of(position);
+            self.cell_starts[cell + 1] += 1;
+            self.max_radius = self.max_radius.max(radius);
+        }
+        for cell in 1..self.cell_starts.len() {
+            self.cell_starts[cell] += self.cell_starts[cell - 1];
+        }
+        
+        // Each item goes to the next free slot of its bucket; the copy is only there to size the vector
+        self.items.clear();
+        self.items.extend_from_slice(&unsorted);
+        let mut next = self.cell_starts.clone();
+        for &entry in &unsorted {
+            let cell = self.cell_of(entry.0);
+            self.items[next[cell] as usize] = entry;
+            next[cell] += 1;
+        }
+        self.unsorted = unsorted;
+    }
+
+    /// Items whose edge is closer than `reach` to `point`
+    fn within(&self, point: Vec2, reach: f32) -> impl Iterator<Item = &(Vec2, f32, T)> {
+        let extent = reach.max(0.0) + self.max_radius;
+        let (x0, x1) = (self.cell_coordinate(point.x - extent), self.cell_coordinate(point.x + extent));
+        let (y0, y1) = (self.cell_coordinate(point.y - extent), self.cell_coordinate(point.y + extent));
+        let cells_per_side = self.cells_per_side;
+        (y0..=y1)
+            .flat_map(move |y| {
+                let (first, last) = (y * cells_per_side + x0, y * cells_per_side + x1);
+                &self.items[self.cell_starts[first] as usize..self.cell_starts[last + 1] as usize]
+            })
+            .filter(move |(position, radius, _)| point.distance(*position) < radius + reach)
+    }
+}
+
+/// Rocks, food and ants bucketed on a coarse grid, so per-ant sensing, collision, pickup and crowding
+/// checks only look at what's nearby instead of everything in the world. Rocks and food are static
+/// between spawns, despawns and relocations, which is when spatial_index_system rebuilds them; ants
+/// are rebuilt every tick.
+#[derive(Resource)]
+pub struct SpatialIndex {
+    rocks: BucketGrid<Entity>,
+    food: BucketGrid<Entity>,
+    ants: BucketGrid<IndexedAnt>,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self { rocks: BucketGrid::default(), food: BucketGrid::default(), ants: BucketGrid::with_cell_size(ANT_CELL_SIZE) }
+    }
+}
+
+impl SpatialIndex {
+    /// Whether there are any rocks at all, so callers can skip collision checks on open maps
+    pub fn has_rocks(&self) -> bool {
+        !self.rocks.items.is_empty()
+    }
+
+    /// Rocks whose edge is closer than `clearance` to `point`
+    pub fn rocks_within(&self, point: Vec2, clearance: f32) -> impl Iterator<Item = IndexedRock> + '_ {
+        self.rocks.within(point, clearance)
+            .map(|&(position, radius, entity)| IndexedRock { entity, position, radius })
+    }
+
+    /// The rock with the nearest centre among rocks_within(point, clearance)
+    pub fn nearest_rock(&self, point: Vec2, clearance: f32) -> Option<IndexedRock> {
+        self.rocks_within(point, clearance)
+            .min_by(|a, b| point.distance(a.position).total_cmp(&point.distance(b.position)))
+    }
+
+    /// Food sources closer than `range` to `point`
+    pub fn food_within(&self, point: Vec2, range: f32) -> impl Iterator<Item = IndexedFood> + '_ {
+        self.food.within(point, range)
+            .map(|&(position, _, entity)| IndexedFood { entity, position })
+    }
+
+    /// Ants closer than `range` to `point`, including any ant standing on it
+    pub fn ants_within(&self, point: Vec2, range: f32) -> impl Iterator<Item = &IndexedAnt> + '_ {
+        self.ants.within(point, range).map(|(_, _, ant)| ant)
+    }
+}
+
+/// Rocks and food sources spawned, despawned or moved since spatial_index_system last ran
+#[derive(SystemParam)]
+pub struct LayoutChanges<'w, 's> {
+    changed_rocks: Query<'w, 's, (), (With<Rock>, Changed<Transform>)>,
+    changed_food: Query<'w, 's, (), (With<FoodSource>, Changed<Transform>)>,
+    removed_rocks: RemovedComponents<'w, 's, Rock>,
+    removed_food: RemovedComponents<'w, 's, FoodSource>,
+}
+
+impl LayoutChanges<'_, '_> {
+    pub fn rocks_changed(&mut self) -> bool {
+        self.removed_rocks.read().count() > 0 || !self.changed_rocks.is_empty()
+    }
+
+    pub fn food_changed(&mut self) -> bool {
+        self.removed_food.read().count() > 0 || !self.changed_food.is_empty()
+    }
+}
+
+/// Rebuild the rocks or the food side of the index when one of them was spawned, despawned or moved since
+/// the last run. Prey are food that walks, so the food side is rebuilt every tick while any are alive,
+/// and the ants always are.
+pub fn spatial_index_system(
+    mut index: ResMut<SpatialIndex>,
+    ants: Query<(Entity, &Transform, &AntCore, &AntStats)>,
+    rocks: Query<(Entity, &Transform, &Rock)>,
+    food_sources: Query<(Entity, &Transform), With<FoodSource>>,
+    mut changes: LayoutChanges,
+) {
+    if changes.rocks_changed() {
+        index.rocks.rebuild(rocks.iter().map(|(entity, transform, rock)| (transform.translation.truncate(), rock.radius, entity)));
+        debug!("🗂️ Spatial index: {} rocks", index.rocks.items.len());
+    }
+    if changes.food_changed() {
+        index.food.rebuild(food_sources.iter().map(|(entity, transform)| (transform.translation.truncate(), 0.0, entity)));
+    }
+    
+    index.ants.rebuild(ants.iter().map(|(entity, transform, ant, ant_stats)| {
+        let position = transform.translation.truncate();
+        (position, 0.0, IndexedAnt {
+            entity,
+            position,
+            carrying_food: ant.carrying_food,
+            successful_deliveries: ant_stats.successful_deliveries,
+        })
+    }));
+}
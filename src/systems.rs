@@ -1,25 +1,31 @@
 use bevy::prelude::*;
 use bevy::window::{WindowCloseRequested, PrimaryWindow};
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::texture::ImageSampler;
 use rand::{Rng, random};
 use crate::components::*;
 use crate::config::*;
 use crate::pheromones::*;
 use crate::colors::*;
-
-/// Movement behavior types for unified speed management
-#[derive(Debug, Clone, Copy)]
-pub enum MovementType {
-    /// Ant carrying food returning to nest
-    CarryingFood,
-    /// Ant following pheromone trail to food
-    FollowingTrail,
-    /// Ant exploring randomly for food
-    Exploring,
-    /// Ant recovering from stuck state
-    StuckRecovery,
-    /// Legacy movement (to be phased out)
-    Legacy,
-}
+use crate::terrain::*;
+use crate::exploration::*;
+use crate::challenges::*;
+use crate::worldmap::*;
+use crate::scoring::*;
+use crate::curriculum::Curriculum;
+use crate::leaderboard::Leaderboard;
+use crate::video::pheromone_intensity;
+use crate::editor::EditorState;
+use crate::spatial_index::SpatialIndex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Instant;
+use bevy::utils::Parallel;
+use bevy::diagnostic::Diagnostics;
+use bevy::ecs::schedule::SystemConfigs;
+use bevy::ecs::system::SystemParam;
+use crate::profiling::*;
 
 /// Unified function to set ant velocity based on movement type and direction
 fn set_ant_velocity(velocity: &mut Velocity, direction: f32, movement_type: MovementType) {
@@ -52,852 +58,1134 @@ fn set_ant_velocity_from_vector(velocity: &mut Velocity, direction_vec: Vec2, mo
     }
 }
 
-pub fn sensing_system(
-    mut ants: Query<(Entity, &Transform, &mut AntState, &mut Velocity, Option<&DebugAnt>)>,
-    rocks: Query<(&Transform, &Rock), Without<AntState>>,
-    mut pheromone_grid: Option<ResMut<PheromoneGrid>>,
+/// Per-frame ant bookkeeping that doesn't need a decision: sensing, startup and diagnostic timers and
+/// edge proximity. Runs before the sensing systems, which then only does the heavy work for ants whose
+/// sensing_timer has run out.
+/// Distance around a low-detail ant that must be free of pheromone; well past the 20-30 unit sensing reach,
+/// so an ant is back to full detail before a trail could steer it
+const LOD_TRAIL_CLEARANCE: f32 = 40.0;
+
+/// Whether no pheromone the ant could react to lies at `pos` or on a ring LOD_TRAIL_CLEARANCE around it
+fn off_trail(grid: &PheromoneGrid, pos: Vec2, threshold: f32) -> bool {
+    let clear = |point: Vec2| match grid.world_to_grid(point.x, point.y) {
+        Some(idx) => grid.food_trail[idx] < threshold && grid.nest_trail[idx] < threshold && grid.alarm[idx] < threshold,
+        None => true,
+    };
+    clear(pos) && (0..8).all(|i| {
+        let angle = i as f32 * std::f32::consts::TAU / 8.0;
+        clear(pos + Vec2::new(angle.cos(), angle.sin()) * LOD_TRAIL_CLEARANCE)
+    })
+}
+
+/// Per-tick timers and edge proximity, plus the level-of-detail decision: an empty-handed ant with no pheromone
+/// in reach only senses and checks crowding every `lod_interval` ticks, moving on its last velocity in between
+pub fn ant_timer_system(
+    mut ants: Query<(&Transform, &mut AntCore, &mut AntDiagnostics, Has<SensingDebug>), Without<Possessed>>,
+    pheromone_grid: Option<Res<PheromoneGrid>>,
     config: Res<SimConfig>,
     time: Res<Time>,
+    mut tick: Local<u32>,
 ) {
-    if let Some(mut grid) = pheromone_grid {
-        // CYCLE 17: Pre-collect all ant positions and success data for formation flying
-        let ant_positions: Vec<(Entity, Vec2, bool, u32)> = ants.iter()
-            .map(|(entity, transform, ant, _, _)| {
-                (entity, transform.translation.truncate(), ant.carrying_food, ant.successful_deliveries)
-            })
-            .collect();
+    let delta_time = time.delta_seconds();
+    let lod_interval = config.lod_interval.max(1);
+    *tick = tick.wrapping_add(1);
+    for (transform, mut ant, mut ant_diagnostics, inspected) in ants.iter_mut() {
+        let pos = transform.translation;
         
-        for (entity, transform, mut ant, mut velocity, debug_ant) in ants.iter_mut() {
-            let pos = transform.translation;
-            let delta_time = time.delta_seconds();
-            
-            // Update timers
-            ant.sensing_timer -= delta_time;
-            ant.startup_timer -= delta_time;
-            
-            // Update diagnostic timers
-            ant.time_since_progress += delta_time;
-            ant.trail_following_time += delta_time;
-            
-            // Calculate world edge proximity for edge-wandering detection
-            let world_half_size = 500.0; // Assuming 1000x1000 world
-            let x_edge_dist = world_half_size - pos.x.abs();
-            let y_edge_dist = world_half_size - pos.y.abs();
-            ant.world_edge_proximity = x_edge_dist.min(y_edge_dist);
-            ant.is_edge_wanderer = ant.world_edge_proximity < 50.0 && ant.time_since_progress > 10.0;
-            
-            // Don't process ants that are collecting food or still in startup
-            if ant.food_collection_timer > 0.0 || ant.startup_timer > 0.0 {
-                continue;
+        // Update timers
+        ant.sensing_timer -= delta_time;
+        ant.startup_timer -= delta_time;
+        
+        // Update diagnostic timers
+        ant_diagnostics.time_since_progress += delta_time;
+        ant_diagnostics.trail_following_time += delta_time;
+        
+        // Calculate world edge proximity for edge-wandering detection
+        let world_half_size = 500.0; // Assuming 1000x1000 world
+        let x_edge_dist = world_half_size - pos.x.abs();
+        let y_edge_dist = world_half_size - pos.y.abs();
+        ant_diagnostics.world_edge_proximity = x_edge_dist.min(y_edge_dist);
+        ant_diagnostics.is_edge_wanderer = ant_diagnostics.world_edge_proximity < 50.0 && ant_diagnostics.time_since_progress > 10.0;
+        
+        // LEVEL OF DETAIL: Ants are spread over the interval by index so the skipped work evens out per tick
+        if !ant.low_detail {
+            ant.since_detail_update = 0.0;
+        }
+        ant.since_detail_update += delta_time;
+        let due = (*tick as usize).wrapping_add(ant.index) % lod_interval as usize == 0;
+        ant.low_detail = !due
+            && !inspected
+            && !ant.carrying_food
+            && ant.food_collection_timer <= 0.0
+            && ant.startup_timer <= 0.0
+            && pheromone_grid.as_ref().is_some_and(|grid| off_trail(grid, pos.truncate(), config.detection_threshold));
+    }
+}
+
+/// When this tick's sensing systems started, so SENSING_TIME covers the whole chain
+#[derive(Resource, Default)]
+pub struct SensingClock(Option<Instant>);
+
+/// The sensing systems in the order they hand ants along, with `decisions` (the active
+/// AntPolicy's decision systems) between the gate and stuck detection. Each step only picks up ants
+/// the ones before it left undecided (see SensingStep) and writes its choice to DesiredHeading.
+pub fn sensing_systems(decisions: SystemConfigs) -> SystemConfigs {
+    (
+        sensing_gate_system,
+        decisions,
+        stuck_detection_system,
+        steering_system,
+        debug_ant_sensing_log_system,
+    ).chain()
+}
+
+/// Which ants sense this tick: those collecting food, still starting up or between low-detail
+/// updates sit the whole chain out
+pub fn sensing_gate_system(
+    mut ants: Query<(&AntCore, &mut SensingStep, &mut DesiredHeading), Without<Possessed>>,
+    pheromone_grid: Option<Res<PheromoneGrid>>,
+    mut clock: ResMut<SensingClock>,
+) {
+    clock.0 = Some(Instant::now());
+    let sensing = pheromone_grid.is_some();
+    for (ant, mut step, mut desired) in ants.iter_mut() {
+        desired.0 = None;
+        *step = if !sensing || ant.food_collection_timer > 0.0 || ant.startup_timer > 0.0 || ant.low_detail {
+            SensingStep::Skipped
+        } else {
+            SensingStep::Pending
+        };
+    }
+}
+
+/// Empty-handed ants due a decision scan the food trail around them and follow the best direction.
+/// Those with nothing worth following, or too close to the nest to follow anything, are handed to
+/// exploration_system.
+pub fn trail_following_system(
+    mut ants: Query<(Entity, &Transform, &mut AntCore, &AntStats, &mut AntDiagnostics, &mut SensingStep, &mut DesiredHeading, Option<&mut SensingDebug>), Without<Possessed>>,
+    spatial_index: Res<SpatialIndex>,
+    pheromone_grid: Option<Res<PheromoneGrid>>,
+    mut swarm_log: ResMut<SwarmDecisionLog>,
+    view: Res<ViewSettings>,
+    config: Res<SimConfig>,
+    time: Res<Time>,
+    scratch: Local<Parallel<SensingScratch>>,
+    mut markers: Local<Parallel<Vec<(Entity, SwarmDecisionMarker)>>>,
+) {
+    let _span = trace_span!("trail_following_system").entered();
+    let Some(grid) = pheromone_grid else { return };
+    // Imperfect chemoreception applied to every pheromone sample used for decisions
+    let sensor = SensorModel {
+        noise_std: config.sensor_noise_std,
+        false_negative_rate: config.sensor_false_negative_rate,
+    };
+
+    // Each ant decides on its own thread; swarm markers are queued per thread and logged once every ant is done
+    ants.par_iter_mut().for_each(|(entity, transform, mut ant, ant_stats, mut ant_diagnostics, mut step, mut desired, mut sensing_debug)| {
+        if ant.carrying_food || !matches!(*step, SensingStep::Pending) {
+            return;
+        }
+        let pos = transform.translation;
+        let home = ant.nest_position; // Own colony's nest
+
+        // ANTI-CLUSTERING: Near-nest exclusion zone - exploration sends the ant outward instead of pheromone following
+        if Vec2::new(pos.x, pos.y).distance(home) < 100.0 {
+            *step = SensingStep::NoTrail(None);
+            return;
+        }
+
+        // The trail scan, swarm analysis and exploration step only run once a decision is due;
+        // in between the ant holds its heading and only the stuck check runs
+        if ant.sensing_timer > 0.0 {
+            *step = SensingStep::Holding;
+            return;
+        }
+
+        let mut scratch = scratch.borrow_local_mut();
+        let pheromone_readings = sensor.sample_all_directions(&grid, pos.x, pos.y, PheromoneType::Food);
+        let mut best_direction = ant.current_direction;
+        let mut max_pheromone = 0.0;
+        let mut found_trail = false;
+
+        // CYCLE 22: Collective swarm intelligence integration
+        let swarm_context = analyze_local_swarm_intelligence(pos.x, pos.y, &ant, &ant_diagnostics, entity, &spatial_index, &mut scratch.swarm);
+
+        // DIAGNOSTIC ANALYSIS: Update ant-centric state tracking
+        let current_pheromone = pheromone_readings[0]; // Center position
+        ant_diagnostics.can_see_trail = current_pheromone > config.detection_threshold;
+
+        // Calculate distance to nearest significant pheromone concentration
+        let mut min_trail_distance = f32::INFINITY;
+        for &heading in &COMPASS_8.headings {
+            let sample_distance = 15.0; // Distance for directional sampling
+            let sample_x = pos.x + heading.x * sample_distance;
+            let sample_y = pos.y + heading.y * sample_distance;
+            let sample_strength = sensor.sample_toward(&grid, sample_x, sample_y, heading, 5.0, PheromoneType::Food);
+
+            if sample_strength > config.detection_threshold {
+                min_trail_distance = min_trail_distance.min(sample_distance);
             }
-            
-            // For carrying food: use nest pheromone following with smart obstacle avoidance
-            if ant.carrying_food {
-                ant.sensing_timer = 0.2; // CYCLE 14: Ultra-fast sensing for food-carrying ants
-                
-                // SIMPLIFIED NEST PHEROMONE FOLLOWING: Focus on stronger detection and faster following
-                if ant.sensing_timer <= 0.0 {
-                    let mut max_nest_pheromone = 0.0;
-                    let mut best_pheromone_direction = ant.current_direction;
-                    let mut found_nest_trail = false;
-                    
-                    // Enhanced 12-direction sampling with better range
-                    for i in 0..12 {
-                        let angle = (i as f32) * std::f32::consts::TAU / 12.0;
-                        let sample_x = pos.x + angle.cos() * 20.0; // Increased range
-                        let sample_y = pos.y + angle.sin() * 20.0;
-                        
-                        let nest_strength = grid.sample_directional(sample_x, sample_y, angle, 8.0, PheromoneType::Nest);
-                        
-                        // Lower threshold and momentum bonus for better trail detection
-                        if nest_strength > 0.05 { // Much lower threshold
-                            // Momentum bonus: prefer directions closer to current heading
-                            let angle_diff = (angle - ant.current_direction).abs();
-                            let angle_diff_normalized = if angle_diff > std::f32::consts::PI {
-                                std::f32::consts::TAU - angle_diff
-                            } else {
-                                angle_diff
-                            };
-                            let momentum_bonus = (1.0 - angle_diff_normalized / std::f32::consts::PI) * 0.2;
-                            
-                            let effective_strength = nest_strength + momentum_bonus;
-                            
-                            if effective_strength > max_nest_pheromone {
-                                max_nest_pheromone = effective_strength;
-                                best_pheromone_direction = angle;
-                                found_nest_trail = true;
-                            }
+        }
+        ant_diagnostics.distance_from_trail = min_trail_distance;
+
+        // Update trail contact timing
+        if ant_diagnostics.can_see_trail {
+            ant_diagnostics.last_trail_contact_time = time.elapsed_seconds();
+            ant_diagnostics.trail_following_time = 0.0; // Reset - starting new trail section
+        }
+
+        // Calculate pheromone gradient strength for behavior analysis
+        let max_reading = pheromone_readings.iter().skip(1).copied().fold(0.0f32, f32::max);
+        let min_reading = pheromone_readings.iter().skip(1).copied().fold(f32::INFINITY, f32::min);
+        ant_diagnostics.trail_gradient_strength = max_reading - min_reading.min(max_reading);
+
+        // Advanced gradient analysis with predictive lookahead
+        let mut ray_scores = [None; 8];
+
+        for (i, &pheromone_strength) in pheromone_readings.iter().enumerate() {
+            if pheromone_strength > 0.15 {
+                let (angle, heading) = (COMPASS_8.angles[i], COMPASS_8.headings[i]);
+
+                // Calculate momentum bonus for maintaining direction
+                let angle_diff = (angle - ant.current_direction).abs();
+                let angle_diff_normalized = if angle_diff > std::f32::consts::PI {
+                    std::f32::consts::TAU - angle_diff
+                } else {
+                    angle_diff
+                };
+                let momentum_bonus = (1.0 - angle_diff_normalized / std::f32::consts::PI) * 1.2; // Increased momentum for better trail commitment
+
+                // Additional persistence bonus if ant has been following trails successfully
+                let persistence_bonus = if ant.behavior_state == AntBehaviorState::Following {
+                    0.25 // Enhanced commitment to successful trail following
+                } else {
+                    0.0
+                };
+
+                // TRAIL DIRECTION DETECTION: Compare pheromone strength ahead vs behind to determine trail direction
+                let search_distance = 20.0;
+                let ahead_x = pos.x + heading.x * search_distance;
+                let ahead_y = pos.y + heading.y * search_distance;
+                let ahead_pheromone = sensor.sample_toward(&grid, ahead_x, ahead_y, heading, 5.0, PheromoneType::Food);
+
+                let behind_x = pos.x - heading.x * search_distance;
+                let behind_y = pos.y - heading.y * search_distance;
+                let behind_pheromone = sensor.sample_toward(&grid, behind_x, behind_y, heading, 5.0, PheromoneType::Food);
+
+                // Trail direction bonus: stronger reward for following toward stronger pheromone (toward food)
+                let trail_direction_bonus = if ahead_pheromone > behind_pheromone + 0.05 {
+                    0.8 // Strong bonus for following trail toward food
+                } else if behind_pheromone > ahead_pheromone + 0.05 {
+                    -0.4 // Penalty for going away from food
+                } else {
+                    0.2 // Neutral bonus for unclear direction
+                };
+
+                // CYCLE 20: Dynamic trail width adaptation for highway detection
+                let (left, right) = (heading.perp(), -heading.perp());
+
+                // Multi-layer sampling: near (core trail), mid (established width), far (highway detection)
+                let near_left = sensor.sample_toward(&grid, pos.x, pos.y, left, 5.0, PheromoneType::Food);
+                let near_right = sensor.sample_toward(&grid, pos.x, pos.y, right, 5.0, PheromoneType::Food);
+                let mid_left = sensor.sample_toward(&grid, pos.x, pos.y, left, 10.0, PheromoneType::Food);
+                let mid_right = sensor.sample_toward(&grid, pos.x, pos.y, right, 10.0, PheromoneType::Food);
+                let far_left = sensor.sample_toward(&grid, pos.x, pos.y, left, 18.0, PheromoneType::Food);
+                let far_right = sensor.sample_toward(&grid, pos.x, pos.y, right, 18.0, PheromoneType::Food);
+
+                // Detect highway patterns based on pheromone distribution width
+                let core_strength = (near_left + near_right) / 2.0;
+                let mid_strength = (mid_left + mid_right) / 2.0;
+                let far_strength = (far_left + far_right) / 2.0;
+
+                let trail_width_factor = if core_strength > 0.3 && mid_strength > 0.2 && far_strength > 0.1 {
+                    1.5 // SUPERHIGHWAY - massive bonus for ultra-wide established trails
+                } else if core_strength > 0.2 && mid_strength > 0.15 {
+                    1.35 // HIGHWAY - major bonus for well-established wide trails
+                } else if core_strength > 0.15 && (mid_strength > 0.1 || far_strength > 0.08) {
+                    1.2 // WIDE TRAIL - good bonus for expanding trails
+                } else if near_left > 0.1 || near_right > 0.1 {
+                    1.1 // STANDARD TRAIL - small bonus
+                } else {
+                    1.0 // NARROW TRAIL - no bonus
+                };
+
+                // CYCLE 21: Traffic flow optimization - lane assignment based on highway patterns
+                let left_side_strength = (mid_left + near_left) / 2.0;
+                let right_side_strength = (mid_right + near_right) / 2.0;
+
+                // Detect if we're on a highway (wide trail with traffic)
+                let is_highway = core_strength > 0.25 && mid_strength > 0.2;
+                let total_side_strength = left_side_strength + right_side_strength;
+
+                let centering_bonus = if is_highway && total_side_strength > 0.3 {
+                    // HIGHWAY TRAFFIC RULES: Food-seeking ants prefer right side, food-carrying prefer left side
+                    let traffic_direction = heading;
+                    let to_nest = (home - Vec2::new(pos.x, pos.y)).normalize();
+                    let toward_nest = traffic_direction.dot(to_nest) > 0.3; // Are we generally heading toward nest?
+
+                    if !ant.carrying_food {
+                        // Food-seeking ants: prefer right side of highway (away from nest traffic)
+                        if !toward_nest && right_side_strength > left_side_strength {
+                            0.4 // Strong bonus for following outbound lane
+                        } else if toward_nest && left_side_strength > right_side_strength {
+                            -0.2 // Small penalty for going against traffic flow
+                        } else {
+                            0.1 // Default small bonus
                         }
-                    }
-                    
-                    // If we found a good nest trail, follow it (with rock avoidance and loop detection)
-                    if found_nest_trail {
-                        // CYCLE 19: Loop detection - if following trails but not making progress, occasionally break away
-                        let should_break_from_trail = ant.time_since_progress > 12.0 && 
-                                                     ant.behavior_state == AntBehaviorState::Following &&
-                                                     (time.elapsed_seconds() * ant.successful_deliveries as f32 + 1.0).sin() > 0.7; // Occasional break-away
-                        
-                        if !should_break_from_trail {
-                            // Check if the pheromone direction is safe from rocks
-                            let test_pos = Vec2::new(pos.x, pos.y) + Vec2::new(best_pheromone_direction.cos(), best_pheromone_direction.sin()) * 40.0;
-                            let mut pheromone_path_safe = true;
-                            
-                            for (rock_transform, rock) in rocks.iter() {
-                                let rock_pos = Vec2::new(rock_transform.translation.x, rock_transform.translation.y);
-                                let distance_to_rock = test_pos.distance(rock_pos);
-                                
-                                if distance_to_rock < rock.radius + 30.0 {
-                                    pheromone_path_safe = false;
-                                    break;
-                                }
-                            }
-                            
-                            if pheromone_path_safe {
-                                // SIMPLIFIED: Smooth but decisive nest trail following
-                                let direction_change = best_pheromone_direction - ant.current_direction;
-                                let smooth_direction_change = if direction_change.abs() > std::f32::consts::PI {
-                                    if direction_change > 0.0 { direction_change - std::f32::consts::TAU } else { direction_change + std::f32::consts::TAU }
-                                } else { direction_change };
-                                
-                                // More aggressive turning for nest trails - we want to get home quickly
-                                ant.current_direction += smooth_direction_change * 0.4;
-                                
-                                set_ant_velocity(&mut velocity, ant.current_direction, MovementType::FollowingTrail);
-                                ant.behavior_state = AntBehaviorState::Following;
-                                
-                                // Faster sensing for nest trails - frequent course corrections
-                                ant.sensing_timer = 0.1;
-                                continue; // Skip the pathfinding logic below
-                            } else {
-                                // ENHANCED NEST-SEEKING: No safe pheromone trail found, use intelligent nest-seeking
-                                let distance_to_nest = Vec2::new(pos.x, pos.y).length();
-                                
-                                if distance_to_nest < 100.0 {
-                                    // CLOSE TO NEST: Direct approach with obstacle avoidance
-                                    let to_nest = (Vec2::ZERO - Vec2::new(pos.x, pos.y)).normalize();
-                                    let direct_nest_angle = to_nest.y.atan2(to_nest.x);
-                                    
-                                    // Check if direct path to nest is safe
-                                    let mut direct_path_safe = true;
-                                    let test_distance = distance_to_nest.min(40.0);
-                                    let test_pos = Vec2::new(pos.x, pos.y) + to_nest * test_distance;
-                                    
-                                    for (rock_transform, rock) in rocks.iter() {
-                                        let rock_pos = Vec2::new(rock_transform.translation.x, rock_transform.translation.y);
-                                        if test_pos.distance(rock_pos) < rock.radius + 25.0 {
-                                            direct_path_safe = false;
-                                            break;
-                                        }
-                                    }
-                                    
-                                    if direct_path_safe {
-                                        // Direct path to nest is safe - go straight home!
-                                        ant.current_direction = direct_nest_angle;
-                                        set_ant_velocity(&mut velocity, direct_nest_angle, MovementType::CarryingFood);
-                                        ant.behavior_state = AntBehaviorState::Exploring;
-                                        ant.sensing_timer = 0.05; // Very frequent sensing near nest
-                                        continue;
-                                    }
-                                }
-                            }
+                    } else {
+                        // Food-carrying ants: prefer left side of highway (toward nest)
+                        if toward_nest && left_side_strength > right_side_strength {
+                            0.5 // Very strong bonus for following inbound lane
+                        } else if !toward_nest && right_side_strength > left_side_strength {
+                            -0.1 // Small penalty for going wrong way
+                        } else {
+                            0.2 // Default bonus
                         }
                     }
-                    
-                    // ADAPTIVE SENSING: Adjust sensing frequency based on distance to nest
-                    let distance_to_nest = Vec2::new(pos.x, pos.y).length();
-                    ant.sensing_timer = if distance_to_nest < 150.0 {
-                        0.08 // Very frequent sensing when close to nest
-                    } else if distance_to_nest < 300.0 {
-                        0.12 // Frequent sensing at medium distance
-                    } else {
-                        0.15 // Standard sensing when far from nest
-                    };
-                }
-                
-                // Emergency behaviors for stuck ants
-                if pos.x.abs() > 470.0 || pos.y.abs() > 470.0 {
-                    // Force move toward world center
-                    let to_center = Vec2::ZERO - Vec2::new(pos.x, pos.y);
-                    let escape_direction = to_center.normalize();
-                    ant.current_direction = escape_direction.y.atan2(escape_direction.x);
-                    set_ant_velocity_from_vector(&mut velocity, escape_direction, MovementType::CarryingFood);
-                    ant.behavior_state = AntBehaviorState::Exploring;
                 } else {
-                    // Check if ant is stuck on/near a rock
-                    let mut on_rock = false;
-                    let mut nearest_rock_direction = 0.0f32;
-                    let mut min_distance = f32::INFINITY;
-                    
-                    for (rock_transform, rock) in rocks.iter() {
-                        let rock_pos = Vec2::new(rock_transform.translation.x, rock_transform.translation.y);
-                        let distance = Vec2::new(pos.x, pos.y).distance(rock_pos);
-                        
-                        if distance < rock.radius + 25.0 { // Expanded sensing range
-                            on_rock = true;
-                            if distance < min_distance {
-                                min_distance = distance;
-                                // Direction AWAY from rock
-                                let away_from_rock = Vec2::new(pos.x, pos.y) - rock_pos;
-                                nearest_rock_direction = away_from_rock.normalize().y.atan2(away_from_rock.normalize().x);
-                            }
-                        }
+                    // Standard trail centering for non-highways
+                    if left_side_strength > right_side_strength + 0.1 {
+                        if (angle - ant.current_direction).abs() < std::f32::consts::PI / 4.0 { 0.3 } else { 0.0 }
+                    } else if right_side_strength > left_side_strength + 0.1 {
+                        if (angle - ant.current_direction).abs() < std::f32::consts::PI / 4.0 { 0.3 } else { 0.0 }
+                    } else {
+                        0.0 // Already centered on trail
                     }
-                    
-                    if on_rock && (ant.stuck_timer > 0.6 || min_distance < 35.0) { // CYCLE 13: Even faster reaction
-                        // CYCLE 15: Cooperative rock mapping - deposit warning pheromones
-                        let grid_pos = Vec2::new(pos.x, pos.y);
-                        if let Some(grid_idx) = grid.world_to_grid(grid_pos.x, grid_pos.y) {
-                            grid.alarm[grid_idx] += 2.0; // Strong warning signal for rock proximity
-                        }
-                        
-                        // CYCLE 9: Smart rock avoidance - curve toward nest while avoiding rock
-                        let to_nest = (Vec2::ZERO - Vec2::new(pos.x, pos.y)).normalize();
-                        let away_from_rock = Vec2::new(nearest_rock_direction.cos(), nearest_rock_direction.sin());
-                        
-                        // Blend away-from-rock with toward-nest for intelligent avoidance
-                        let smart_direction = (away_from_rock * 0.6 + to_nest * 0.4).normalize();
-                        ant.current_direction = smart_direction.y.atan2(smart_direction.x);
-                        set_ant_velocity(&mut velocity, ant.current_direction, MovementType::CarryingFood);
-                        ant.behavior_state = AntBehaviorState::Exploring;
-                        ant.sensing_timer = 0.1; // Quick re-sense
-                    } else if ant.sensing_timer <= 0.0 {
-                        let mut best_direction = ant.current_direction;
-                        
-                        // CYCLE 17: Find nearby successful leaders from pre-collected data
-                        let current_pos = Vec2::new(pos.x, pos.y);
-                        let nearby_leaders: Vec<(Vec2, u32)> = ant_positions.iter()
-                            .filter_map(|(other_entity, other_pos, carrying_food, successful_deliveries)| {
-                                if *other_entity == entity || !carrying_food || *successful_deliveries == 0 {
-                                    None
-                                } else {
-                                    let distance = current_pos.distance(*other_pos);
-                                    if distance < 30.0 {
-                                        Some((*other_pos, *successful_deliveries))
-                                    } else {
-                                        None
-                                    }
-                                }
-                            })
-                            .collect();
-                        
-                        // ENHANCED NEST-SEEKING: Intelligent nest-oriented pathfinding
-                        let nest_pos = Vec2::ZERO;
-                        let to_nest = (nest_pos - Vec2::new(pos.x, pos.y)).normalize();
-                        let ideal_direction = to_nest.y.atan2(to_nest.x);
-                        let distance_to_nest = Vec2::new(pos.x, pos.y).length();
-                        
-                        // ENHANCED PATHFINDING: Distance-aware nest-seeking with improved scoring
-                        let mut found_safe_path = false;
-                        let mut best_score = f32::NEG_INFINITY;
-                        
-                        // Adaptive direction testing based on distance to nest
-                        let num_directions = if distance_to_nest < 200.0 { 16 } else { 12 };
-                        let max_deviation = if distance_to_nest < 150.0 {
-                            std::f32::consts::PI / 3.0 // Wider search when close to nest
-                        } else {
-                            std::f32::consts::PI / 6.0 // Narrower focus when far
-                        };
-                        
-                        for i in 0..num_directions {
-                            let deviation = (i as f32 - (num_directions - 1) as f32 / 2.0) * max_deviation / (num_directions as f32 / 2.0);
-                            let test_angle = ideal_direction + deviation;
-                            
-                            // Adaptive lookahead: shorter when close to nest
-                            let test_distance = if distance_to_nest < 100.0 {
-                                20.0 // Short lookahead near nest
-                            } else {
-                                40.0 // Normal lookahead far from nest
-                            };
-                            
-                            let test_pos = Vec2::new(
-                                pos.x + test_angle.cos() * test_distance,
-                                pos.y + test_angle.sin() * test_distance
-                            );
-                            
-                            let mut path_score = 0.0;
-                            let mut is_safe = true;
-                            
-                            // Check world boundaries
-                            if test_pos.x.abs() > 475.0 || test_pos.y.abs() > 475.0 {
-                                is_safe = false;
-                            }
-                            
-                            if is_safe {
-                                // ENHANCED SCORING: Distance-aware nest-seeking optimization
-                                let path_direction = Vec2::new(test_angle.cos(), test_angle.sin());
-                                let nest_alignment = to_nest.dot(path_direction);
-                                
-                                // Distance-based nest alignment scoring
-                                let alignment_multiplier = if distance_to_nest < 150.0 {
-                                    150.0 // Very strong nest bias when close
-                                } else if distance_to_nest < 300.0 {
-                                    120.0 // Strong nest bias at medium distance
-                                } else {
-                                    100.0 // Standard nest bias when far
-                                };
-                                path_score += nest_alignment * alignment_multiplier;
-                                
-                                // Adaptive momentum bonus
-                                let current_direction_vec = Vec2::new(ant.current_direction.cos(), ant.current_direction.sin());
-                                let momentum_alignment = current_direction_vec.dot(path_direction);
-                                let momentum_bonus = if distance_to_nest < 100.0 {
-                                    15.0 // Reduced momentum near nest for better maneuvering
-                                } else {
-                                    25.0 // Standard momentum far from nest
-                                };
-                                path_score += momentum_alignment * momentum_bonus;
-                                
-                                // Progress bonus: reward paths that make clear progress toward nest
-                                let progress_bonus = if distance_to_nest > 200.0 {
-                                    let future_nest_distance = test_pos.length();
-                                    let distance_improvement = distance_to_nest - future_nest_distance;
-                                    distance_improvement * 2.0 // Bonus for making progress toward nest
-                                } else {
-                                    0.0 // Don't worry about progress when close
-                                };
-                                path_score += progress_bonus;
-                                
-                                // CYCLE 15: Cooperative rock avoidance using alarm pheromones
-                                if let Some(grid_idx) = grid.world_to_grid(test_pos.x, test_pos.y) {
-                                    let alarm_strength = grid.alarm[grid_idx];
-                                    path_score -= alarm_strength * 40.0; // Heavy penalty for alarm areas
-                                }
-                                
-                                // Enhanced rock avoidance scoring
-                                let mut min_rock_clearance = f32::INFINITY;
-                                for (rock_transform, rock) in rocks.iter() {
-                                    let rock_pos = Vec2::new(rock_transform.translation.x, rock_transform.translation.y);
-                                    let distance_to_rock = test_pos.distance(rock_pos);
-                                    let safety_buffer = rock.radius + 35.0; // Larger safety margin
-                                    
-                                    if distance_to_rock < safety_buffer {
-                                        is_safe = false;
-                                        break;
-                                    } else {
-                                        min_rock_clearance = min_rock_clearance.min(distance_to_rock - safety_buffer);
-                                    }
-                                }
-                                
-                                if is_safe {
-                                    // Reward good rock clearance (exponential benefit for safer paths)
-                                    path_score += (min_rock_clearance.min(60.0) * 1.5).powi(2) / 100.0;
-                                    
-                                    // CYCLE 17: Adaptive formation flying using pre-collected leader data
-                                    for (leader_pos, leader_deliveries) in &nearby_leaders {
-                                        let to_leader = (*leader_pos - test_pos).normalize_or_zero();
-                                        let path_to_leader_alignment = path_direction.dot(to_leader);
-                                        
-                                        // Bonus for following in the direction of successful ants (convoy effect)
-                                        let leadership_bonus = path_to_leader_alignment * (*leader_deliveries as f32).min(3.0) * 8.0;
-                                        path_score += leadership_bonus;
-                                    }
-                                    
-                                    if path_score > best_score {
-                                        best_score = path_score;
-                                        best_direction = test_angle;
-                                        found_safe_path = true;
-                                    }
-                                }
-                            }
-                        }
-                        
-                        if !found_safe_path {
-                            // Emergency: just try to move away from current position
-                            best_direction = ant.current_direction + 1.57; // Turn 90 degrees
-                        }
-                        
-                        ant.current_direction = best_direction;
-                        set_ant_velocity(&mut velocity, best_direction, MovementType::CarryingFood);
-                        
-                        // CYCLE 14: Keep ultra-fast sensing frequency for responsiveness
-                        ant.sensing_timer = 0.2;
-                        
-                        ant.behavior_state = if found_safe_path { AntBehaviorState::Following } else { AntBehaviorState::Exploring };
+                };
+
+                // SIMPLIFIED gradient system - reduce complexity to prevent oscillation
+                let immediate_gradient = pheromone_strength - current_pheromone;
+
+                let gradient_bonus = if immediate_gradient > 0.08 { // CYCLE 10: More sensitive gradient detection
+                    // Clear improvement - moving toward stronger pheromone
+                    0.45 // CYCLE 10: Slightly stronger gradient bonus
+                } else if immediate_gradient < -0.08 {
+                    // Clear decline - moving away from strong pheromone
+                    -0.35 // CYCLE 10: Stronger avoidance of declining trails
+                } else {
+                    // Marginal differences - neutral to reduce micro-oscillation
+                    0.0
+                };
+
+                // Smart momentum-gradient hybrid: reduce momentum when gradient is very strong
+                let hybrid_momentum = if immediate_gradient > 0.15 {
+                    momentum_bonus * 0.7 // Back to Generation 43 successful value
+                } else {
+                    momentum_bonus
+                };
+
+                // CYCLE 15: Cooperative rock avoidance - check for alarm pheromones
+                let sample_x = pos.x + heading.x * 15.0;
+                let sample_y = pos.y + heading.y * 15.0;
+                let alarm_penalty = if let Some(grid_idx) = grid.world_to_grid(sample_x, sample_y) {
+                    grid.alarm[grid_idx] * -20.0 // Penalty for moving toward rock warning areas
+                } else {
+                    0.0
+                };
+
+                // CYCLE 22: Add collective intelligence bonus to trail following
+                let collective_intelligence_bonus = calculate_collective_intelligence_bonus(
+                    angle, pheromone_strength, &swarm_context, ant.current_direction
+                );
+
+                // Emergency swarm dispersion if too many ants are failing in this area
+                let dispersion_penalty = if swarm_context.local_failure_rate > 0.6 && swarm_context.ant_density > 5 {
+                    if (angle - swarm_context.average_failed_direction).abs() > 1.5 {
+                        0.8 // Strong bonus for moving away from failed attempts
+                    } else {
+                        -0.5 // Penalty for following failed paths
                     }
+                } else {
+                    0.0
+                };
+
+                let effective_strength = pheromone_strength * trail_width_factor + hybrid_momentum + gradient_bonus + persistence_bonus + trail_direction_bonus + centering_bonus + alarm_penalty + collective_intelligence_bonus + dispersion_penalty;
+                ray_scores[i] = Some(effective_strength);
+
+                if effective_strength > max_pheromone {
+                    max_pheromone = effective_strength;
+                    best_direction = angle;
+                    found_trail = true;
                 }
+            }
+        }
+        if view.swarm_markers && swarm_context.local_failure_rate > 0.6 && swarm_context.ant_density > 5 && ray_scores.iter().any(Option::is_some) {
+            markers.borrow_local_mut().push((entity, swarm_context.marker(SwarmDecision::Dispersion, pos.truncate(), time.elapsed_seconds())));
+        }
+        if let Some(ref mut sensing_debug) = sensing_debug {
+            let rays = pheromone_readings.iter().zip(ray_scores).enumerate()
+                .map(|(i, (&strength, score))| SensingRay { angle: i as f32 * std::f32::consts::TAU / 8.0, strength, score })
+                .collect();
+            let chosen = if found_trail { Some(best_direction) } else { None };
+            sensing_debug.record("Exploring: food trail", rays, chosen, time.elapsed_seconds());
+        }
+
+        // CYCLE 21: Advanced congestion management with highway awareness
+        let swarming_penalty = if ant_diagnostics.is_swarming && ant_diagnostics.nearby_ant_count >= 4 {
+            // Detect if we're in highway congestion vs regular swarming
+            let highway_congestion = max_pheromone > 1.0 && ant_diagnostics.nearby_ant_count >= 6; // High pheromone + crowding = highway jam
+
+            let penalty_factor = if highway_congestion {
+                // Highway congestion - more aggressive intervention
+                (ant_diagnostics.nearby_ant_count as f32 * 0.2).min(0.6)
             } else {
-                // For exploring ants: follow FOOD pheromones (trails left by successful ants who found food)
-                
-                // ANTI-CLUSTERING: Near-nest exclusion zone - use simple radial exploration instead of pheromone following
-                let distance_from_nest = Vec2::new(pos.x, pos.y).length();
-                if distance_from_nest < 100.0 {
-                    // Near nest: use radial exploration to get away from crowded nest area
-                    let outward_direction = Vec2::new(pos.x, pos.y).normalize();
-                    ant.current_direction = outward_direction.y.atan2(outward_direction.x);
-                    set_ant_velocity(&mut velocity, ant.current_direction, MovementType::Exploring);
-                    ant.sensing_timer = 1.5; // Long commitment to outward movement
-                    ant.behavior_state = AntBehaviorState::Exploring;
-                    continue;
+                // Regular swarming - gentler intervention
+                (ant_diagnostics.nearby_ant_count as f32 * 0.12).min(0.4)
+            };
+
+            max_pheromone *= 1.0 - penalty_factor;
+
+            // Gentle deviation to maintain trail efficiency
+            let random_deviation = (rand::random::<f32>() - 0.5) * 0.3;
+            best_direction += random_deviation;
+
+            penalty_factor
+        } else if ant_diagnostics.is_swarming && ant_diagnostics.trail_following_time > 3.0 {
+            // Only light intervention for persistent swarming
+            let penalty_factor = 0.15;
+            max_pheromone *= 1.0 - penalty_factor;
+
+            let random_deviation = (rand::random::<f32>() - 0.5) * 0.2;
+            best_direction += random_deviation;
+
+            penalty_factor
+        } else {
+            0.0
+        };
+
+        // Scouts only commit to strong trails, leaving weak ones for foragers
+        let trail_commit_threshold = if ant.role == AntRole::Scout { config.scout_trail_threshold } else { 0.2 };
+
+        if found_trail && max_pheromone > trail_commit_threshold {
+            // CYCLE 19: Loop detection for food-seeking ants
+            let should_break_from_trail = ant_diagnostics.time_since_progress > 15.0 &&
+                                         ant.behavior_state == AntBehaviorState::Following &&
+                                         (time.elapsed_seconds() * (ant_stats.successful_deliveries + 1) as f32).sin() > 0.8; // Rarer break-away for exploring ants
+
+            if !should_break_from_trail {
+                // Smooth direction change for trail following
+                ant.behavior_state = AntBehaviorState::Following;
+                let angle_diff = best_direction - ant.current_direction;
+                let smooth_angle_change = if angle_diff.abs() > std::f32::consts::PI {
+                    if angle_diff > 0.0 { angle_diff - std::f32::consts::TAU } else { angle_diff + std::f32::consts::TAU }
+                } else { angle_diff };
+
+                // CYCLE 3: Revert to balanced path following
+                desired.0 = Some((ant.current_direction + smooth_angle_change * 0.22, MovementType::FollowingTrail)); // Revert to successful setting
+
+                // Back to Generation 51 successful sensing intervals
+                let trail_strength_factor = (max_pheromone - 0.2).max(0.0) / 0.8;
+                ant.sensing_timer = if max_pheromone > 0.4 {
+                    1.0 + trail_strength_factor * 0.5 // 1.0-1.5s for strong trails - much more commitment
+                } else {
+                    0.8 + trail_strength_factor * 0.4 // 0.8-1.2s for weak trails - less frequent sensing
+                };
+            }
+            *step = SensingStep::Decided;
+        } else {
+            // CYCLE 22: Nearby ants may suggest a less-explored direction for exploration_system to take
+            let suggestion = swarm_context.should_use_collective_exploration.then(|| SwarmSuggestion {
+                heading: swarm_context.suggested_exploration_direction,
+                marker: view.swarm_markers.then(|| swarm_context.marker(SwarmDecision::CollectiveExploration, pos.truncate(), time.elapsed_seconds())),
+            });
+            *step = SensingStep::NoTrail(suggestion);
+        }
+    });
+
+    for queue in markers.iter_mut() {
+        swarm_log.markers.extend(queue.drain(..));
+    }
+}
+
+/// Empty-handed ants with no trail to follow: straight out of the crowded nest area, along the
+/// swarm's suggestion, back from the world edge, or by the run's exploration strategy
+pub fn exploration_system(
+    mut ants: Query<(Entity, &Transform, &mut AntCore, &AntStats, &mut AntDiagnostics, &mut SensingStep, &mut DesiredHeading), Without<Possessed>>,
+    mut swarm_log: ResMut<SwarmDecisionLog>,
+    config: Res<SimConfig>,
+    time: Res<Time>,
+) {
+    for (entity, transform, mut ant, ant_stats, mut ant_diagnostics, mut step, mut desired) in ants.iter_mut() {
+        if !matches!(*step, SensingStep::NoTrail(_)) {
+            continue;
+        }
+        let SensingStep::NoTrail(suggestion) = std::mem::replace(&mut *step, SensingStep::Decided) else { continue };
+        let pos = transform.translation;
+        let home = ant.nest_position;
+
+        // ANTI-CLUSTERING: Near-nest exclusion zone - use simple radial exploration instead of pheromone following
+        let distance_from_nest = Vec2::new(pos.x, pos.y).distance(home);
+        if distance_from_nest < 100.0 {
+            // Near nest: use radial exploration to get away from crowded nest area
+            let outward_direction = (Vec2::new(pos.x, pos.y) - home).normalize();
+            desired.0 = Some((outward_direction.y.atan2(outward_direction.x), MovementType::Exploring));
+            ant.sensing_timer = 1.5; // Long commitment to outward movement
+            ant.behavior_state = AntBehaviorState::Exploring;
+            *step = SensingStep::Settled;
+            continue;
+        }
+
+        // CYCLE 22: Collective exploration when no trails detected
+        if let Some(suggestion) = suggestion {
+            if let Some(marker) = suggestion.marker {
+                swarm_log.markers.insert(entity, marker);
+            }
+            // Coordinate exploration with nearby ants to avoid redundant searching
+            desired.0 = Some((suggestion.heading, MovementType::Exploring));
+            ant.behavior_state = AntBehaviorState::Exploring;
+            ant.sensing_timer = 0.8; // Moderate sensing for coordinated exploration
+            ant_diagnostics.time_since_progress = 0.0;
+            *step = SensingStep::Settled;
+            continue;
+        }
+
+        // No trail found - random exploration
+        ant.behavior_state = AntBehaviorState::Exploring;
+
+        // ENHANCED EDGE-WANDERER RECOVERY: Aggressive center-seeking behavior
+        if ant_diagnostics.is_edge_wanderer || (ant_diagnostics.world_edge_proximity < 100.0 && ant_diagnostics.time_since_progress > 8.0) {
+            let center = Vec2::ZERO;
+            let to_center = center - Vec2::new(pos.x, pos.y);
+            let center_direction = to_center.normalize();
+
+            // Stronger center bias for distant ants
+            let distance_from_center = to_center.length();
+            let urgency_factor = (distance_from_center / 400.0).min(1.0);
+
+            // Mix center direction with some randomness based on urgency
+            let random_component = (rand::random::<f32>() - 0.5) * (0.8 - urgency_factor * 0.4);
+            desired.0 = Some((center_direction.y.atan2(center_direction.x) + random_component, MovementType::Exploring));
+            ant.sensing_timer = 0.3; // Very frequent sensing for recovery
+
+            // Reset progress timer on intervention
+            ant_diagnostics.time_since_progress = 0.0;
+        } else {
+            // Scouts always range widely; everyone else uses the run's configured strategy
+            let strategy = if ant.role == AntRole::Scout {
+                ExplorationStrategy::LevyFlight
+            } else {
+                config.exploration_strategy
+            };
+
+            ant.sensing_timer = apply_exploration_strategy(strategy, &mut ant, ant_stats, &ant_diagnostics, Vec2::new(pos.x, pos.y) - home, &config, time.elapsed_seconds());
+            desired.0 = Some((ant.current_direction, MovementType::Exploring));
+        }
+    }
+}
+
+/// Rock warnings one sensing pass leaves for the others, queued per thread and laid once every ant is done
+pub enum SensingEffect {
+    Alarm(usize, f32), // Grid index and amount of alarm pheromone to add
+    Event(SimEvent),
+}
+
+/// Food carriers pinned against the world edge head back toward the centre, and those up against a
+/// rock steer away from it toward the nest, leaving alarm pheromone to warn the ants behind them
+pub fn rock_avoidance_system(
+    mut ants: Query<(&Transform, &mut AntCore, &mut SensingStep, &mut DesiredHeading), Without<Possessed>>,
+    spatial_index: Res<SpatialIndex>,
+    pheromone_grid: Option<ResMut<PheromoneGrid>>,
+    mut sim_events: EventWriter<SimEvent>,
+    time: Res<Time>,
+    mut effects: Local<Parallel<Vec<SensingEffect>>>,
+) {
+    let Some(mut grid) = pheromone_grid else { return };
+    let shared_grid: &PheromoneGrid = &grid;
+    ants.par_iter_mut().for_each(|(transform, mut ant, mut step, mut desired)| {
+        if !ant.carrying_food || !matches!(*step, SensingStep::Pending) {
+            return;
+        }
+        let grid = shared_grid;
+        let pos = transform.translation;
+        let home = ant.nest_position;
+        ant.sensing_timer = 0.2; // CYCLE 14: Ultra-fast sensing for food-carrying ants
+
+        // Emergency behaviors for stuck ants
+        if pos.x.abs() > 470.0 || pos.y.abs() > 470.0 {
+            // Force move toward world center
+            let to_center = Vec2::ZERO - Vec2::new(pos.x, pos.y);
+            let escape_direction = to_center.normalize();
+            desired.0 = Some((escape_direction.y.atan2(escape_direction.x), MovementType::CarryingFood));
+            ant.behavior_state = AntBehaviorState::Exploring;
+            *step = SensingStep::Decided;
+            return;
+        }
+
+        // Check if ant is stuck on/near a rock
+        let mut on_rock = false;
+        let mut nearest_rock_direction = 0.0f32;
+        let mut min_distance = f32::INFINITY;
+
+        if let Some(rock) = spatial_index.nearest_rock(Vec2::new(pos.x, pos.y), 25.0) { // Expanded sensing range
+            on_rock = true;
+            min_distance = Vec2::new(pos.x, pos.y).distance(rock.position);
+            // Direction AWAY from rock
+            let away_from_rock = Vec2::new(pos.x, pos.y) - rock.position;
+            nearest_rock_direction = away_from_rock.normalize().y.atan2(away_from_rock.normalize().x);
+        }
+
+        if on_rock && (ant.stuck_timer > 0.6 || min_distance < 35.0) { // CYCLE 13: Even faster reaction
+            // CYCLE 15: Cooperative rock mapping - deposit warning pheromones
+            let grid_pos = Vec2::new(pos.x, pos.y);
+            if let Some(grid_idx) = grid.world_to_grid(grid_pos.x, grid_pos.y) {
+                // Only a fresh warning is news; topping up an existing one isn't logged
+                let mut queue = effects.borrow_local_mut();
+                if grid.alarm[grid_idx] < 1.0 {
+                    queue.push(SensingEffect::Event(SimEvent { kind: SimEventKind::RockWarning, position: grid_pos, time: time.elapsed_seconds(), ant: Some(ant.index) }));
                 }
-                
-                let pheromone_readings = grid.sample_all_directions(pos.x, pos.y, PheromoneType::Food);
-                let mut best_direction = ant.current_direction;
-                let mut max_pheromone = 0.0;
-                let mut found_trail = false;
-                
-                // CYCLE 22: Collective swarm intelligence integration
-                let swarm_context = analyze_local_swarm_intelligence(pos.x, pos.y, &ant, entity, &ant_positions, time.elapsed_seconds());
-                
-                // DIAGNOSTIC ANALYSIS: Update ant-centric state tracking
-                let current_pheromone = pheromone_readings[0]; // Center position
-                ant.can_see_trail = current_pheromone > config.detection_threshold;
-                
-                // Calculate distance to nearest significant pheromone concentration
-                let mut min_trail_distance = f32::INFINITY;
-                for i in 1..9 { // Skip center (index 0)
-                    let sample_distance = 15.0; // Distance for directional sampling
-                    let angle = ((i - 1) as f32) * std::f32::consts::TAU / 8.0;
-                    let sample_x = pos.x + angle.cos() * sample_distance;
-                    let sample_y = pos.y + angle.sin() * sample_distance;
-                    let sample_strength = grid.sample_directional(sample_x, sample_y, angle, 5.0, PheromoneType::Food);
-                    
-                    if sample_strength > config.detection_threshold {
-                        min_trail_distance = min_trail_distance.min(sample_distance);
+                queue.push(SensingEffect::Alarm(grid_idx, 2.0)); // Strong warning signal for rock proximity
+            }
+
+            // CYCLE 9: Smart rock avoidance - curve toward nest while avoiding rock
+            let to_nest = (home - Vec2::new(pos.x, pos.y)).normalize();
+            let away_from_rock = Vec2::new(nearest_rock_direction.cos(), nearest_rock_direction.sin());
+
+            // Blend away-from-rock with toward-nest for intelligent avoidance
+            let smart_direction = (away_from_rock * 0.6 + to_nest * 0.4).normalize();
+            desired.0 = Some((smart_direction.y.atan2(smart_direction.x), MovementType::CarryingFood));
+            ant.behavior_state = AntBehaviorState::Exploring;
+            ant.sensing_timer = 0.1; // Quick re-sense
+            *step = SensingStep::Decided;
+        }
+    });
+
+    for queue in effects.iter_mut() {
+        for effect in queue.drain(..) {
+            match effect {
+                SensingEffect::Alarm(grid_idx, amount) => grid.deposit_at(grid_idx, PheromoneType::Alarm, amount),
+                SensingEffect::Event(event) => { sim_events.send(event); }
+            }
+        }
+    }
+}
+
+/// Food carriers due a decision follow the nest trail home, or when there is none pick the safest
+/// heading toward the nest, drawn along by nearby successful ants
+pub fn carrying_navigation_system(
+    mut ants: Query<(Entity, &Transform, &mut AntCore, &AntStats, &AntDiagnostics, &mut SensingStep, &mut DesiredHeading, Option<&mut SensingDebug>), Without<Possessed>>,
+    spatial_index: Res<SpatialIndex>,
+    pheromone_grid: Option<Res<PheromoneGrid>>,
+    config: Res<SimConfig>,
+    time: Res<Time>,
+    scratch: Local<Parallel<SensingScratch>>,
+) {
+    let Some(grid) = pheromone_grid else { return };
+    // Imperfect chemoreception applied to every pheromone sample used for decisions
+    let sensor = SensorModel {
+        noise_std: config.sensor_noise_std,
+        false_negative_rate: config.sensor_false_negative_rate,
+    };
+
+    ants.par_iter_mut().for_each(|(entity, transform, mut ant, ant_stats, ant_diagnostics, mut step, mut desired, mut sensing_debug)| {
+        if !ant.carrying_food || !matches!(*step, SensingStep::Pending) {
+            return;
+        }
+        let mut scratch = scratch.borrow_local_mut();
+        let pos = transform.translation;
+        let home = ant.nest_position; // Own colony's nest
+
+        // SIMPLIFIED NEST PHEROMONE FOLLOWING: Focus on stronger detection and faster following
+        if ant.sensing_timer <= 0.0 {
+            let mut max_nest_pheromone = 0.0;
+            let mut best_pheromone_direction = ant.current_direction;
+            let mut found_nest_trail = false;
+            let mut rays = Vec::new();
+
+            // Enhanced 12-direction sampling with better range
+            for (&angle, &heading) in COMPASS_12.angles.iter().zip(&COMPASS_12.headings) {
+                let sample_x = pos.x + heading.x * 20.0; // Increased range
+                let sample_y = pos.y + heading.y * 20.0;
+
+                let nest_strength = sensor.sample_toward(&grid, sample_x, sample_y, heading, 8.0, PheromoneType::Nest);
+                let mut ray = SensingRay { angle, strength: nest_strength, score: None };
+
+                // Lower threshold and momentum bonus for better trail detection
+                if nest_strength > 0.05 { // Much lower threshold
+                    // Momentum bonus: prefer directions closer to current heading
+                    let angle_diff = (angle - ant.current_direction).abs();
+                    let angle_diff_normalized = if angle_diff > std::f32::consts::PI {
+                        std::f32::consts::TAU - angle_diff
+                    } else {
+                        angle_diff
+                    };
+                    let momentum_bonus = (1.0 - angle_diff_normalized / std::f32::consts::PI) * 0.2;
+
+                    let effective_strength = nest_strength + momentum_bonus;
+                    ray.score = Some(effective_strength);
+
+                    if effective_strength > max_nest_pheromone {
+                        max_nest_pheromone = effective_strength;
+                        best_pheromone_direction = angle;
+                        found_nest_trail = true;
                     }
                 }
-                ant.distance_from_trail = min_trail_distance;
-                
-                // Update trail contact timing
-                if ant.can_see_trail {
-                    ant.last_trail_contact_time = time.elapsed_seconds();
-                    ant.trail_following_time = 0.0; // Reset - starting new trail section
-                } 
-                
-                // Calculate pheromone gradient strength for behavior analysis
-                let max_reading = pheromone_readings.iter().skip(1).copied().fold(0.0f32, f32::max);
-                let min_reading = pheromone_readings.iter().skip(1).copied().fold(f32::INFINITY, f32::min);
-                ant.trail_gradient_strength = max_reading - min_reading.min(max_reading);
-                
-                // Advanced gradient analysis with predictive lookahead
-                
-                for (i, &pheromone_strength) in pheromone_readings.iter().enumerate() {
-                    if pheromone_strength > 0.15 {
-                        let angle = (i as f32) * std::f32::consts::TAU / 8.0;
-                        
-                        // Calculate momentum bonus for maintaining direction
-                        let angle_diff = (angle - ant.current_direction).abs();
-                        let angle_diff_normalized = if angle_diff > std::f32::consts::PI { 
-                            std::f32::consts::TAU - angle_diff 
-                        } else { 
-                            angle_diff 
-                        };
-                        let momentum_bonus = (1.0 - angle_diff_normalized / std::f32::consts::PI) * 1.2; // Increased momentum for better trail commitment
-                        
-                        // Additional persistence bonus if ant has been following trails successfully
-                        let persistence_bonus = if ant.behavior_state == AntBehaviorState::Following {
-                            0.25 // Enhanced commitment to successful trail following
-                        } else {
-                            0.0
-                        };
-                        
-                        // TRAIL DIRECTION DETECTION: Compare pheromone strength ahead vs behind to determine trail direction
-                        let search_distance = 20.0;
-                        let ahead_x = pos.x + angle.cos() * search_distance;
-                        let ahead_y = pos.y + angle.sin() * search_distance;
-                        let ahead_pheromone = grid.sample_directional(ahead_x, ahead_y, angle, 5.0, PheromoneType::Food);
-                        
-                        let behind_x = pos.x - angle.cos() * search_distance;
-                        let behind_y = pos.y - angle.sin() * search_distance;
-                        let behind_pheromone = grid.sample_directional(behind_x, behind_y, angle, 5.0, PheromoneType::Food);
-                        
-                        // Trail direction bonus: stronger reward for following toward stronger pheromone (toward food)
-                        let trail_direction_bonus = if ahead_pheromone > behind_pheromone + 0.05 {
-                            0.8 // Strong bonus for following trail toward food
-                        } else if behind_pheromone > ahead_pheromone + 0.05 {
-                            -0.4 // Penalty for going away from food
-                        } else {
-                            0.2 // Neutral bonus for unclear direction
-                        };
-                        
-                        // CYCLE 20: Dynamic trail width adaptation for highway detection
-                        let perp_angle_1 = angle + std::f32::consts::PI / 2.0;
-                        let perp_angle_2 = angle - std::f32::consts::PI / 2.0;
-                        
-                        // Multi-layer sampling: near (core trail), mid (established width), far (highway detection)
-                        let near_left = grid.sample_directional(pos.x, pos.y, perp_angle_1, 5.0, PheromoneType::Food);
-                        let near_right = grid.sample_directional(pos.x, pos.y, perp_angle_2, 5.0, PheromoneType::Food);
-                        let mid_left = grid.sample_directional(pos.x, pos.y, perp_angle_1, 10.0, PheromoneType::Food);
-                        let mid_right = grid.sample_directional(pos.x, pos.y, perp_angle_2, 10.0, PheromoneType::Food);
-                        let far_left = grid.sample_directional(pos.x, pos.y, perp_angle_1, 18.0, PheromoneType::Food);
-                        let far_right = grid.sample_directional(pos.x, pos.y, perp_angle_2, 18.0, PheromoneType::Food);
-                        
-                        // Detect highway patterns based on pheromone distribution width
-                        let core_strength = (near_left + near_right) / 2.0;
-                        let mid_strength = (mid_left + mid_right) / 2.0;
-                        let far_strength = (far_left + far_right) / 2.0;
-                        
-                        let trail_width_factor = if core_strength > 0.3 && mid_strength > 0.2 && far_strength > 0.1 {
-                            1.5 // SUPERHIGHWAY - massive bonus for ultra-wide established trails
-                        } else if core_strength > 0.2 && mid_strength > 0.15 {
-                            1.35 // HIGHWAY - major bonus for well-established wide trails  
-                        } else if core_strength > 0.15 && (mid_strength > 0.1 || far_strength > 0.08) {
-                            1.2 // WIDE TRAIL - good bonus for expanding trails
-                        } else if near_left > 0.1 || near_right > 0.1 {
-                            1.1 // STANDARD TRAIL - small bonus
-                        } else {
-                            1.0 // NARROW TRAIL - no bonus
-                        };
-                        
-                        // CYCLE 21: Traffic flow optimization - lane assignment based on highway patterns
-                        let left_side_strength = (mid_left + near_left) / 2.0;
-                        let right_side_strength = (mid_right + near_right) / 2.0;
-                        
-                        // Detect if we're on a highway (wide trail with traffic)
-                        let is_highway = core_strength > 0.25 && mid_strength > 0.2;
-                        let total_side_strength = left_side_strength + right_side_strength;
-                        
-                        let centering_bonus = if is_highway && total_side_strength > 0.3 {
-                            // HIGHWAY TRAFFIC RULES: Food-seeking ants prefer right side, food-carrying prefer left side
-                            let traffic_direction = Vec2::new(angle.cos(), angle.sin());
-                            let to_nest = (Vec2::ZERO - Vec2::new(pos.x, pos.y)).normalize();
-                            let toward_nest = traffic_direction.dot(to_nest) > 0.3; // Are we generally heading toward nest?
-                            
-                            if !ant.carrying_food {
-                                // Food-seeking ants: prefer right side of highway (away from nest traffic)
-                                if !toward_nest && right_side_strength > left_side_strength {
-                                    0.4 // Strong bonus for following outbound lane
-                                } else if toward_nest && left_side_strength > right_side_strength {
-                                    -0.2 // Small penalty for going against traffic flow
-                                } else {
-                                    0.1 // Default small bonus
-                                }
-                            } else {
-                                // Food-carrying ants: prefer left side of highway (toward nest)
-                                if toward_nest && left_side_strength > right_side_strength {
-                                    0.5 // Very strong bonus for following inbound lane
-                                } else if !toward_nest && right_side_strength > left_side_strength {
-                                    -0.1 // Small penalty for going wrong way
-                                } else {
-                                    0.2 // Default bonus
-                                }
-                            }
-                        } else {
-                            // Standard trail centering for non-highways
-                            if left_side_strength > right_side_strength + 0.1 {
-                                if (angle - ant.current_direction).abs() < std::f32::consts::PI / 4.0 { 0.3 } else { 0.0 }
-                            } else if right_side_strength > left_side_strength + 0.1 {
-                                if (angle - ant.current_direction).abs() < std::f32::consts::PI / 4.0 { 0.3 } else { 0.0 }
-                            } else {
-                                0.0 // Already centered on trail
-                            }
-                        };
-                        
-                        // SIMPLIFIED gradient system - reduce complexity to prevent oscillation
-                        let immediate_gradient = pheromone_strength - current_pheromone;
-                        
-                        let gradient_bonus = if immediate_gradient > 0.08 { // CYCLE 10: More sensitive gradient detection
-                            // Clear improvement - moving toward stronger pheromone
-                            0.45 // CYCLE 10: Slightly stronger gradient bonus
-                        } else if immediate_gradient < -0.08 {
-                            // Clear decline - moving away from strong pheromone
-                            -0.35 // CYCLE 10: Stronger avoidance of declining trails
-                        } else {
-                            // Marginal differences - neutral to reduce micro-oscillation
-                            0.0
-                        };
-                        
-                        // Smart momentum-gradient hybrid: reduce momentum when gradient is very strong
-                        let hybrid_momentum = if immediate_gradient > 0.15 {
-                            momentum_bonus * 0.7 // Back to Generation 43 successful value
-                        } else {
-                            momentum_bonus
-                        };
-                        
-                        // CYCLE 15: Cooperative rock avoidance - check for alarm pheromones
-                        let sample_x = pos.x + angle.cos() * 15.0;
-                        let sample_y = pos.y + angle.sin() * 15.0;
-                        let alarm_penalty = if let Some(grid_idx) = grid.world_to_grid(sample_x, sample_y) {
-                            grid.alarm[grid_idx] * -20.0 // Penalty for moving toward rock warning areas
-                        } else {
-                            0.0
-                        };
-                        
-                        // CYCLE 22: Add collective intelligence bonus to trail following
-                        let collective_intelligence_bonus = calculate_collective_intelligence_bonus(
-                            angle, pheromone_strength, &swarm_context, ant.current_direction
-                        );
-                        
-                        // Emergency swarm dispersion if too many ants are failing in this area
-                        let dispersion_penalty = if swarm_context.local_failure_rate > 0.6 && swarm_context.ant_density > 5 {
-                            if (angle - swarm_context.average_failed_direction).abs() > 1.5 {
-                                0.8 // Strong bonus for moving away from failed attempts
-                            } else {
-                                -0.5 // Penalty for following failed paths
+                if sensing_debug.is_some() {
+                    rays.push(ray);
+                }
+            }
+            if let Some(ref mut sensing_debug) = sensing_debug {
+                let chosen = if found_nest_trail { Some(best_pheromone_direction) } else { None };
+                sensing_debug.record("Carrying: nest trail", rays, chosen, time.elapsed_seconds());
+            }
+
+            // If we found a good nest trail, follow it (with rock avoidance and loop detection)
+            if found_nest_trail {
+                // CYCLE 19: Loop detection - if following trails but not making progress, occasionally break away
+                let should_break_from_trail = ant_diagnostics.time_since_progress > 12.0 &&
+                                             ant.behavior_state == AntBehaviorState::Following &&
+                                             (time.elapsed_seconds() * ant_stats.successful_deliveries as f32 + 1.0).sin() > 0.7; // Occasional break-away
+
+                if !should_break_from_trail {
+                    // Check if the pheromone direction is safe from rocks
+                    let test_pos = Vec2::new(pos.x, pos.y) + Vec2::new(best_pheromone_direction.cos(), best_pheromone_direction.sin()) * 40.0;
+                    let pheromone_path_safe = spatial_index.rocks_within(test_pos, 30.0).next().is_none();
+
+                    if pheromone_path_safe {
+                        // SIMPLIFIED: Smooth but decisive nest trail following
+                        let direction_change = best_pheromone_direction - ant.current_direction;
+                        let smooth_direction_change = if direction_change.abs() > std::f32::consts::PI {
+                            if direction_change > 0.0 { direction_change - std::f32::consts::TAU } else { direction_change + std::f32::consts::TAU }
+                        } else { direction_change };
+
+                        // More aggressive turning for nest trails - we want to get home quickly
+                        desired.0 = Some((ant.current_direction + smooth_direction_change * 0.4, MovementType::FollowingTrail));
+                        ant.behavior_state = AntBehaviorState::Following;
+
+                        // Faster sensing for nest trails - frequent course corrections
+                        ant.sensing_timer = 0.1;
+                        *step = SensingStep::Settled;
+                        return; // Skip the pathfinding logic below
+                    } else {
+                        // ENHANCED NEST-SEEKING: No safe pheromone trail found, use intelligent nest-seeking
+                        let distance_to_nest = Vec2::new(pos.x, pos.y).distance(home);
+
+                        if distance_to_nest < 100.0 {
+                            // CLOSE TO NEST: Direct approach with obstacle avoidance
+                            let to_nest = (home - Vec2::new(pos.x, pos.y)).normalize();
+                            let direct_nest_angle = to_nest.y.atan2(to_nest.x);
+
+                            // Check if direct path to nest is safe
+                            let test_distance = distance_to_nest.min(40.0);
+                            let test_pos = Vec2::new(pos.x, pos.y) + to_nest * test_distance;
+                            let direct_path_safe = spatial_index.rocks_within(test_pos, 25.0).next().is_none();
+
+                            if direct_path_safe {
+                                // Direct path to nest is safe - go straight home!
+                                desired.0 = Some((direct_nest_angle, MovementType::CarryingFood));
+                                ant.behavior_state = AntBehaviorState::Exploring;
+                                ant.sensing_timer = 0.05; // Very frequent sensing near nest
+                                *step = SensingStep::Settled;
+                                return;
                             }
-                        } else {
-                            0.0
-                        };
-                        
-                        let effective_strength = pheromone_strength * trail_width_factor + hybrid_momentum + gradient_bonus + persistence_bonus + trail_direction_bonus + centering_bonus + alarm_penalty + collective_intelligence_bonus + dispersion_penalty;
-                        
-                        if effective_strength > max_pheromone {
-                            max_pheromone = effective_strength;
-                            best_direction = angle;
-                            found_trail = true;
                         }
                     }
                 }
-                
-                // CYCLE 21: Advanced congestion management with highway awareness
-                let swarming_penalty = if ant.is_swarming && ant.nearby_ant_count >= 4 {
-                    // Detect if we're in highway congestion vs regular swarming
-                    let highway_congestion = max_pheromone > 1.0 && ant.nearby_ant_count >= 6; // High pheromone + crowding = highway jam
-                    
-                    let penalty_factor = if highway_congestion {
-                        // Highway congestion - more aggressive intervention
-                        (ant.nearby_ant_count as f32 * 0.2).min(0.6) 
-                    } else {
-                        // Regular swarming - gentler intervention
-                        (ant.nearby_ant_count as f32 * 0.12).min(0.4)
-                    };
-                    
-                    max_pheromone *= 1.0 - penalty_factor;
-                    
-                    // Gentle deviation to maintain trail efficiency
-                    let random_deviation = (rand::random::<f32>() - 0.5) * 0.3;
-                    best_direction += random_deviation;
-                    
-                    penalty_factor
-                } else if ant.is_swarming && ant.trail_following_time > 3.0 {
-                    // Only light intervention for persistent swarming
-                    let penalty_factor = 0.15;
-                    max_pheromone *= 1.0 - penalty_factor;
-                    
-                    let random_deviation = (rand::random::<f32>() - 0.5) * 0.2;
-                    best_direction += random_deviation;
-                    
-                    penalty_factor
+            }
+
+            // ADAPTIVE SENSING: Adjust sensing frequency based on distance to nest
+            let distance_to_nest = Vec2::new(pos.x, pos.y).distance(home);
+            ant.sensing_timer = if distance_to_nest < 150.0 {
+                0.08 // Very frequent sensing when close to nest
+            } else if distance_to_nest < 300.0 {
+                0.12 // Frequent sensing at medium distance
+            } else {
+                0.15 // Standard sensing when far from nest
+            };
+        }
+
+        if ant.sensing_timer <= 0.0 {
+            let mut best_direction = ant.current_direction;
+
+            // CYCLE 17: Find nearby successful leaders from the tick's ant index
+            let current_pos = Vec2::new(pos.x, pos.y);
+            let nearby_leaders = &mut scratch.leaders;
+            nearby_leaders.clear();
+            nearby_leaders.extend(spatial_index.ants_within(current_pos, 30.0)
+                .filter(|other| other.entity != entity && other.carrying_food && other.successful_deliveries > 0)
+                .map(|other| (other.position, other.successful_deliveries)));
+
+            // ENHANCED NEST-SEEKING: Intelligent nest-oriented pathfinding
+            let nest_pos = home;
+            let to_nest = (nest_pos - Vec2::new(pos.x, pos.y)).normalize();
+            let ideal_direction = to_nest.y.atan2(to_nest.x);
+            let distance_to_nest = Vec2::new(pos.x, pos.y).distance(home);
+
+            // ENHANCED PATHFINDING: Distance-aware nest-seeking with improved scoring
+            let mut found_safe_path = false;
+            let mut best_score = f32::NEG_INFINITY;
+            let mut rays = Vec::new();
+
+            // Adaptive direction testing based on distance to nest
+            let num_directions = if distance_to_nest < 200.0 { 16 } else { 12 };
+            let max_deviation = if distance_to_nest < 150.0 {
+                std::f32::consts::PI / 3.0 // Wider search when close to nest
+            } else {
+                std::f32::consts::PI / 6.0 // Narrower focus when far
+            };
+
+            // Fan headings are stepped round by a fixed rotation rather than each taking a cos and sin
+            let deviation_step = max_deviation / (num_directions as f32 / 2.0);
+            let first_deviation = -((num_directions - 1) as f32 / 2.0) * deviation_step;
+            let step_rotation = Vec2::from_angle(deviation_step);
+            let mut test_heading = to_nest.rotate(Vec2::from_angle(first_deviation));
+            let current_direction_vec = Vec2::new(ant.current_direction.cos(), ant.current_direction.sin());
+
+            for i in 0..num_directions {
+                let deviation = (i as f32 - (num_directions - 1) as f32 / 2.0) * max_deviation / (num_directions as f32 / 2.0);
+                let test_angle = ideal_direction + deviation;
+                let path_direction = test_heading;
+                test_heading = step_rotation.rotate(test_heading);
+
+                // Adaptive lookahead: shorter when close to nest
+                let test_distance = if distance_to_nest < 100.0 {
+                    20.0 // Short lookahead near nest
                 } else {
-                    0.0
+                    40.0 // Normal lookahead far from nest
                 };
-                
-                if found_trail && max_pheromone > 0.2 {
-                    // CYCLE 19: Loop detection for food-seeking ants
-                    let should_break_from_trail = ant.time_since_progress > 15.0 && 
-                                                 ant.behavior_state == AntBehaviorState::Following &&
-                                                 (time.elapsed_seconds() * (ant.successful_deliveries + 1) as f32).sin() > 0.8; // Rarer break-away for exploring ants
-                    
-                    if !should_break_from_trail {
-                        // Smooth direction change for trail following
-                        ant.behavior_state = AntBehaviorState::Following;
-                    let angle_diff = best_direction - ant.current_direction;
-                    let smooth_angle_change = if angle_diff.abs() > std::f32::consts::PI {
-                        if angle_diff > 0.0 { angle_diff - std::f32::consts::TAU } else { angle_diff + std::f32::consts::TAU }
-                    } else { angle_diff };
-                    
-                    // CYCLE 3: Revert to balanced path following
-                    ant.current_direction += smooth_angle_change * 0.22; // Revert to successful setting
-                    set_ant_velocity(&mut velocity, ant.current_direction, MovementType::FollowingTrail);
-                    
-                    // Back to Generation 51 successful sensing intervals
-                    let trail_strength_factor = (max_pheromone - 0.2).max(0.0) / 0.8;
-                    ant.sensing_timer = if max_pheromone > 0.4 {
-                        1.0 + trail_strength_factor * 0.5 // 1.0-1.5s for strong trails - much more commitment
+
+                let test_pos = Vec2::new(pos.x, pos.y) + path_direction * test_distance;
+
+                let mut path_score = 0.0;
+                let mut is_safe = true;
+
+                // Check world boundaries
+                if test_pos.x.abs() > 475.0 || test_pos.y.abs() > 475.0 {
+                    is_safe = false;
+                }
+
+                if is_safe {
+                    // ENHANCED SCORING: Distance-aware nest-seeking optimization
+                    let nest_alignment = to_nest.dot(path_direction);
+
+                    // Distance-based nest alignment scoring
+                    let alignment_multiplier = if distance_to_nest < 150.0 {
+                        150.0 // Very strong nest bias when close
+                    } else if distance_to_nest < 300.0 {
+                        120.0 // Strong nest bias at medium distance
                     } else {
-                        0.8 + trail_strength_factor * 0.4 // 0.8-1.2s for weak trails - less frequent sensing
+                        100.0 // Standard nest bias when far
                     };
-                    } // End of !should_break_from_trail condition
-                } else {
-                    // CYCLE 22: Collective exploration when no trails detected
-                    if swarm_context.should_use_collective_exploration {
-                        // Coordinate exploration with nearby ants to avoid redundant searching
-                        ant.current_direction = swarm_context.suggested_exploration_direction;
-                        set_ant_velocity(&mut velocity, ant.current_direction, MovementType::Exploring);
-                        ant.behavior_state = AntBehaviorState::Exploring;
-                        ant.sensing_timer = 0.8; // Moderate sensing for coordinated exploration
-                        ant.time_since_progress = 0.0;
-                        continue;
+                    path_score += nest_alignment * alignment_multiplier;
+
+                    // Adaptive momentum bonus
+                    let momentum_alignment = current_direction_vec.dot(path_direction);
+                    let momentum_bonus = if distance_to_nest < 100.0 {
+                        15.0 // Reduced momentum near nest for better maneuvering
+                    } else {
+                        25.0 // Standard momentum far from nest
+                    };
+                    path_score += momentum_alignment * momentum_bonus;
+
+                    // Progress bonus: reward paths that make clear progress toward nest
+                    let progress_bonus = if distance_to_nest > 200.0 {
+                        let future_nest_distance = test_pos.distance(home);
+                        let distance_improvement = distance_to_nest - future_nest_distance;
+                        distance_improvement * 2.0 // Bonus for making progress toward nest
+                    } else {
+                        0.0 // Don't worry about progress when close
+                    };
+                    path_score += progress_bonus;
+
+                    // CYCLE 15: Cooperative rock avoidance using alarm pheromones
+                    if let Some(grid_idx) = grid.world_to_grid(test_pos.x, test_pos.y) {
+                        let alarm_strength = grid.alarm[grid_idx];
+                        path_score -= alarm_strength * 40.0; // Heavy penalty for alarm areas
                     }
-                    
-                    // No trail found - random exploration
-                    ant.behavior_state = AntBehaviorState::Exploring;
-                    
-                    // ENHANCED EDGE-WANDERER RECOVERY: Aggressive center-seeking behavior
-                    if ant.is_edge_wanderer || (ant.world_edge_proximity < 100.0 && ant.time_since_progress > 8.0) {
-                        let center = Vec2::ZERO;
-                        let to_center = center - Vec2::new(pos.x, pos.y);
-                        let center_direction = to_center.normalize();
-                        
-                        // Stronger center bias for distant ants
-                        let distance_from_center = to_center.length();
-                        let urgency_factor = (distance_from_center / 400.0).min(1.0);
-                        
-                        // Mix center direction with some randomness based on urgency
-                        let random_component = (rand::random::<f32>() - 0.5) * (0.8 - urgency_factor * 0.4);
-                        ant.current_direction = center_direction.y.atan2(center_direction.x) + random_component;
-                        
-                        set_ant_velocity(&mut velocity, ant.current_direction, MovementType::Exploring);
-                        ant.sensing_timer = 0.3; // Very frequent sensing for recovery
-                        
-                        // Reset progress timer on intervention  
-                        ant.time_since_progress = 0.0;
-                    } else if ant.sensing_timer <= 0.0 {
-                        // Adaptive exploration: more aggressive as search time increases
-                        let search_time = if ant.last_goal_achievement_time > 0.0 {
-                            time.elapsed_seconds() - ant.last_goal_achievement_time
-                        } else {
-                            // Time since startup ended (when ant became active)
-                            (time.elapsed_seconds() - 1.0).max(0.0) // Startup was 1.0s
-                        };
-                        
-                        // CYCLE 3: Smarter exploration with spiral search pattern for lost ants
-                        let exploration_factor = (search_time / 60.0).min(1.0);
-                        
-                        if ant.time_since_progress > 10.0 {
-                            // CYCLE 5: Earlier and more optimized spiral search
-                            let lost_duration = ant.time_since_progress - 10.0;
-                            let spiral_angle = lost_duration * 1.0; // Even faster spiral
-                            ant.current_direction += spiral_angle.sin() * 0.45; // Slightly more aggressive
-                            
-                            // Very frequent sensing for rapid trail discovery
-                            ant.sensing_timer = ant.sensing_timer.min(0.3); // CYCLE 14: Faster trail discovery
+
+                    // Enhanced rock avoidance scoring. Clearance is capped at 60 below, so rocks
+                    // further than that past the safety margin can't change the score.
+                    let mut min_rock_clearance = f32::INFINITY;
+                    for rock in spatial_index.rocks_within(test_pos, 35.0 + 60.0) {
+                        let distance_to_rock = test_pos.distance(rock.position);
+                        let safety_buffer = rock.radius + 35.0; // Larger safety margin
+
+                        if distance_to_rock < safety_buffer {
+                            is_safe = false;
+                            break;
                         } else {
-                            // Normal exploration
-                            let base_angle = 1.2;
-                            let max_angle = 2.2;
-                            let angle_range = base_angle + (max_angle - base_angle) * exploration_factor;
-                            
-                            let angle_change = (rand::random::<f32>() - 0.5) * angle_range;
-                            ant.current_direction += angle_change;
+                            min_rock_clearance = min_rock_clearance.min(distance_to_rock - safety_buffer);
                         }
-                        set_ant_velocity(&mut velocity, ant.current_direction, MovementType::Exploring);
-                        
-                        // Back to Generation 51 successful exploration sensing
-                        let base_sensing = 0.6;
-                        let min_sensing = 0.3;
-                        let sensing_time = base_sensing - (base_sensing - min_sensing) * exploration_factor;
-                        ant.sensing_timer = sensing_time + rand::random::<f32>() * 0.2;
                     }
-                }
-            }
-            
-            // Basic stuck detection
-            let current_pos = Vec2::new(pos.x, pos.y);
-            let distance_moved = current_pos.distance(ant.last_position);
-            
-            if distance_moved < 5.0 {
-                ant.stuck_timer += delta_time;
-                if ant.stuck_timer > 2.0 {
-                    // Randomize direction when stuck
-                    ant.current_direction = rand::random::<f32>() * std::f32::consts::TAU;
-                    set_ant_velocity(&mut velocity, ant.current_direction, MovementType::StuckRecovery);
-                    ant.stuck_timer = 0.0;
-                    ant.behavior_state = AntBehaviorState::Exploring;
-                }
-            } else {
-                ant.stuck_timer = 0.0;
-            }
-            ant.last_position = current_pos;
-        }
-        
-        // Debug logging for debug ants
-        static mut LAST_DEBUG_LOG: f32 = 0.0;
-        let current_time = time.elapsed_seconds();
-        
-        unsafe {
-            if current_time - LAST_DEBUG_LOG > 2.0 {
-                LAST_DEBUG_LOG = current_time;
-                
-                for (entity, transform, ant, velocity, debug_ant) in ants.iter() {
-                    if let Some(debug_marker) = debug_ant {
-                        let pos = transform.translation;
-                        
-                        // Calculate distance to nest and nearest food
-                        let dist_to_nest = Vec2::new(pos.x, pos.y).length();
-                        
-                        // Get pheromone readings at current position
-                        let pheromone_readings = grid.sample_all_directions(pos.x, pos.y, PheromoneType::Food);
-                        let current_pheromone = pheromone_readings[0];
-                        let max_pheromone = pheromone_readings.iter().fold(0.0f32, |a, &b| a.max(b));
-                        
-                        // Time since last goal achievement
-                        let time_since_goal = if ant.last_goal_achievement_time > 0.0 {
-                            current_time - ant.last_goal_achievement_time
-                        } else {
-                            // Time since startup ended (when ant became active)
-                            (current_time - 1.0).max(0.0) // Startup was 1.0s
-                        };
-                        
-                        println!("🐜 DEBUG ANT #{} @ T={:.1}s | Pos=({:.0},{:.0}) DistToNest={:.0} | State={:?} | Carrying={} | TimeSinceGoal={:.1}s", 
-                            debug_marker.ant_id, current_time, pos.x, pos.y, dist_to_nest, ant.behavior_state, ant.carrying_food, time_since_goal);
-                        
-                        println!("   📡 Pheromones: Current={:.3} Max={:.3} | Direction={:.2}rad | Vel=({:.1},{:.1}) | SensingTimer={:.2}s", 
-                            current_pheromone, max_pheromone, ant.current_direction, velocity.x, velocity.y, ant.sensing_timer);
-                        
-                        if ant.stuck_timer > 1.0 {
-                            println!("   ⚠️ STUCK for {:.1}s | Last movement distance: {:.1}", ant.stuck_timer, 
-                                Vec2::new(pos.x, pos.y).distance(ant.last_position));
+
+                    if is_safe {
+                        // Reward good rock clearance (exponential benefit for safer paths)
+                        path_score += (min_rock_clearance.min(60.0) * 1.5).powi(2) / 100.0;
+
+                        // CYCLE 17: Adaptive formation flying using pre-collected leader data
+                        for (leader_pos, leader_deliveries) in nearby_leaders.iter() {
+                            let to_leader = (*leader_pos - test_pos).normalize_or_zero();
+                            let path_to_leader_alignment = path_direction.dot(to_leader);
+
+                            // Bonus for following in the direction of successful ants (convoy effect)
+                            let leadership_bonus = path_to_leader_alignment * (*leader_deliveries as f32).min(3.0) * 8.0;
+                            path_score += leadership_bonus;
                         }
-                        
-                        if ant.behavior_state == AntBehaviorState::Following {
-                            // Look ahead for predictive analysis
-                            let lookahead_x = pos.x + ant.current_direction.cos() * 15.0;
-                            let lookahead_y = pos.y + ant.current_direction.sin() * 15.0;
-                            let lookahead_pheromone = grid.sample_directional(lookahead_x, lookahead_y, ant.current_direction, 3.0, PheromoneType::Food);
-                            let immediate_gradient = max_pheromone - current_pheromone;
-                            let predictive_gradient = lookahead_pheromone - max_pheromone;
-                            
-                            println!("   🔮 Trail Analysis: ImmediateGrad={:.3} PredictiveGrad={:.3} LookaheadPheromone={:.3}", 
-                                immediate_gradient, predictive_gradient, lookahead_pheromone);
+
+                        if path_score > best_score {
+                            best_score = path_score;
+                            best_direction = test_angle;
+                            found_safe_path = true;
                         }
-                        
-                        println!("   📊 Stats: Deliveries={} Attempts={} HasFoundFood={} | ConsecutiveGoodTrail={:.1}s", 
-                            ant.successful_deliveries, ant.delivery_attempts, ant.has_found_food, ant.consecutive_good_trail_time);
-                        
-                        println!();
                     }
                 }
+                if sensing_debug.is_some() {
+                    rays.push(SensingRay { angle: test_angle, strength: 0.0, score: if is_safe { Some(path_score) } else { None } });
+                }
+            }
+            if let Some(ref mut sensing_debug) = sensing_debug {
+                let chosen = if found_safe_path { Some(best_direction) } else { None };
+                sensing_debug.record("Carrying: nest pathfinding", rays, chosen, time.elapsed_seconds());
+            }
+
+            if !found_safe_path {
+                // Emergency: just try to move away from current position
+                best_direction = ant.current_direction + 1.57; // Turn 90 degrees
+            }
+
+            desired.0 = Some((best_direction, MovementType::CarryingFood));
+
+            // CYCLE 14: Keep ultra-fast sensing frequency for responsiveness
+            ant.sensing_timer = 0.2;
+
+            ant.behavior_state = if found_safe_path { AntBehaviorState::Following } else { AntBehaviorState::Exploring };
+            *step = SensingStep::Decided;
+        }
+    });
+}
+
+/// Basic stuck detection: an ant that barely moved for two seconds turns to a random heading,
+/// overriding what the behaviors before it chose unless one settled the ant this tick
+pub fn stuck_detection_system(
+    mut ants: Query<(Entity, &Transform, &mut AntCore, &SensingStep, &mut DesiredHeading), Without<Possessed>>,
+    mut stuck_events: EventWriter<AntStuck>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_seconds();
+    for (entity, transform, mut ant, step, mut desired) in ants.iter_mut() {
+        if matches!(step, SensingStep::Skipped | SensingStep::Settled) {
+            continue;
+        }
+        let current_pos = transform.translation.truncate();
+        let delta_time = ant.since_detail_update; // Covers the ticks a low-detail ant skipped
+        let distance_moved = current_pos.distance(ant.last_position);
+
+        if distance_moved < 5.0 {
+            ant.stuck_timer += delta_time;
+            if ant.stuck_timer > 2.0 {
+                stuck_events.send(AntStuck { ant: entity, ant_index: ant.index, position: current_pos, time: now });
+                // Randomize direction when stuck
+                desired.0 = Some((rand::random::<f32>() * std::f32::consts::TAU, MovementType::StuckRecovery));
+                ant.stuck_timer = 0.0;
+                ant.behavior_state = AntBehaviorState::Exploring;
             }
+        } else {
+            ant.stuck_timer = 0.0;
         }
+        ant.last_position = current_pos;
     }
 }
 
-// New system to detect ant swarming and proximity issues
-pub fn ant_proximity_analysis_system(
-    mut ants: Query<(Entity, &Transform, &mut AntState)>,
+/// Turn each ant onto the heading the sensing systems chose for it this tick
+pub fn steering_system(mut ants: Query<(&mut AntCore, &mut Velocity, &DesiredHeading), Without<Possessed>>) {
+    for (mut ant, mut velocity, desired) in ants.iter_mut() {
+        if let Some((heading, movement_type)) = desired.0 {
+            ant.current_direction = heading;
+            set_ant_velocity(&mut velocity, heading, movement_type);
+        }
+    }
+}
+
+/// Debug ants' sensing state every two seconds, and the time the sensing chain took this tick
+pub fn debug_ant_sensing_log_system(
+    ants: Query<(&Transform, &AntCore, &AntMemory, &AntStats, &Velocity, &DebugAnt), Without<Possessed>>,
+    pheromone_grid: Option<Res<PheromoneGrid>>,
     time: Res<Time>,
+    mut clock: ResMut<SensingClock>,
+    mut last_debug_log: Local<f32>,
+    mut diagnostics: Diagnostics,
+) {
+    if let Some(started) = clock.0.take() {
+        record_system_time(&mut diagnostics, &SENSING_TIME, started);
+    }
+    let Some(grid) = pheromone_grid else { return };
+    let current_time = time.elapsed_seconds();
+
+    if current_time - *last_debug_log > 2.0 {
+        *last_debug_log = current_time;
+
+        for (transform, ant, ant_memory, ant_stats, velocity, debug_marker) in ants.iter() {
+            let pos = transform.translation;
+
+            // Calculate distance to nest and nearest food
+            let dist_to_nest = Vec2::new(pos.x, pos.y).distance(ant.nest_position);
+
+            // Get pheromone readings at current position
+            let pheromone_readings = grid.sample_all_directions(pos.x, pos.y, PheromoneType::Food);
+            let current_pheromone = pheromone_readings[0];
+            let max_pheromone = pheromone_readings.iter().fold(0.0f32, |a, &b| a.max(b));
+
+            // Time since last goal achievement
+            let time_since_goal = if ant_stats.last_goal_achievement_time > 0.0 {
+                current_time - ant_stats.last_goal_achievement_time
+            } else {
+                // Time since startup ended (when ant became active)
+                (current_time - ant_stats.spawn_time - 1.0).max(0.0) // Startup was 1.0s
+            };
+
+            debug!("🐜 DEBUG ANT #{} @ T={:.1}s | Pos=({:.0},{:.0}) DistToNest={:.0} | State={:?} | Carrying={} | TimeSinceGoal={:.1}s",
+                debug_marker.ant_id, current_time, pos.x, pos.y, dist_to_nest, ant.behavior_state, ant.carrying_food, time_since_goal);
+
+            debug!("   📡 Pheromones: Current={:.3} Max={:.3} | Direction={:.2}rad | Vel=({:.1},{:.1}) | SensingTimer={:.2}s",
+                current_pheromone, max_pheromone, ant.current_direction, velocity.x, velocity.y, ant.sensing_timer);
+
+            if ant.stuck_timer > 1.0 {
+                debug!("   ⚠️ STUCK for {:.1}s | Last movement distance: {:.1}", ant.stuck_timer,
+                    Vec2::new(pos.x, pos.y).distance(ant.last_position));
+            }
+
+            if ant.behavior_state == AntBehaviorState::Following {
+                // Look ahead for predictive analysis
+                let lookahead_x = pos.x + ant.current_direction.cos() * 15.0;
+                let lookahead_y = pos.y + ant.current_direction.sin() * 15.0;
+                let lookahead_pheromone = grid.sample_directional(lookahead_x, lookahead_y, ant.current_direction, 3.0, PheromoneType::Food);
+                let immediate_gradient = max_pheromone - current_pheromone;
+                let predictive_gradient = lookahead_pheromone - max_pheromone;
+
+                debug!("   🔮 Trail Analysis: ImmediateGrad={:.3} PredictiveGrad={:.3} LookaheadPheromone={:.3}",
+                    immediate_gradient, predictive_gradient, lookahead_pheromone);
+            }
+
+            debug!("   📊 Stats: Deliveries={} Attempts={} HasFoundFood={} | ConsecutiveGoodTrail={:.1}s",
+                ant_stats.successful_deliveries, ant_stats.delivery_attempts, ant_memory.has_found_food, ant_memory.consecutive_good_trail_time);
+        }
+    }
+}
+
+/// Q possesses the selected ant, suspending its AI, or releases it. Deselecting also releases it.
+pub fn possess_toggle_system(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    mut debug_info: ResMut<DebugInfo>,
+    possessed_query: Query<Entity, With<Possessed>>,
+    ant_query: Query<&AntCore>,
+) {
+    let mut released = false;
+    for entity in possessed_query.iter() {
+        if input.just_pressed(KeyCode::KeyQ) || debug_info.selected_entity != Some(entity) {
+            commands.entity(entity).remove::<Possessed>();
+            released = true;
+            info!("🎮 Released the possessed ant - its AI takes over again");
+        }
+    }
+    
+    if input.just_pressed(KeyCode::KeyQ) && !released {
+        if let Some((entity, ant)) = debug_info.selected_entity.and_then(|entity| ant_query.get(entity).ok().map(|ant| (entity, ant))) {
+            commands.entity(entity).insert(Possessed);
+            debug_info.follow_selected = true;
+            info!("🎮 Possessed ant #{} - arrow keys drive it, Q releases", ant.index);
+        }
+    }
+}
+
+/// Arrow keys set the possessed ant's heading; with no key held it stands still.
+/// Runs in place of the sensing systems, so pickup, delivery and trail laying still happen as usual.
+pub fn possessed_control_system(
+    mut possessed_query: Query<(&mut AntCore, &mut Velocity), With<Possessed>>,
+    input: Res<ButtonInput<KeyCode>>,
 ) {
-    let mut ant_positions: Vec<(Entity, Vec2)> = Vec::new();
+    let mut direction = Vec2::ZERO;
+    if input.pressed(KeyCode::ArrowUp) {
+        direction.y += 1.0;
+    }
+    if input.pressed(KeyCode::ArrowDown) {
+        direction.y -= 1.0;
+    }
+    if input.pressed(KeyCode::ArrowLeft) {
+        direction.x -= 1.0;
+    }
+    if input.pressed(KeyCode::ArrowRight) {
+        direction.x += 1.0;
+    }
     
-    // First pass: collect positions
-    for (entity, transform, _) in ants.iter() {
-        let pos = Vec2::new(transform.translation.x, transform.translation.y);
-        ant_positions.push((entity, pos));
+    for (mut ant, mut velocity) in possessed_query.iter_mut() {
+        ant.stuck_timer = 0.0;
+        if direction == Vec2::ZERO {
+            velocity.x = 0.0;
+            velocity.y = 0.0;
+            continue;
+        }
+        ant.current_direction = direction.y.atan2(direction.x);
+        ant.behavior_state = AntBehaviorState::Tracking;
+        set_ant_velocity(&mut velocity, ant.current_direction, MovementType::FollowingTrail);
     }
+}
+
+// Crowding and swarm detection; runs every tick because movement and sensing act on what it finds
+pub fn ant_proximity_analysis_system(
+    mut ants: Query<(Entity, &Transform, &mut AntCore, &mut AntDiagnostics)>,
+    spatial_index: Res<SpatialIndex>,
+    config: Res<SimConfig>,
+) {
+    let proximity_threshold: f32 = 25.0;
+    let reach = proximity_threshold.max(config.separation_radius);
     
-    // Second pass: analyze proximity and update states
-    for (entity, transform, mut ant_state) in ants.iter_mut() {
+    // Neighbours come from the tick's ant index, so each ant only looks at ants in nearby buckets
+    ants.par_iter_mut().for_each(|(entity, transform, mut ant_state, mut ant_diagnostics)| {
+        // Low-detail ants keep last update's crowding until they're due again
+        if ant_state.low_detail {
+            return;
+        }
         let current_pos = Vec2::new(transform.translation.x, transform.translation.y);
         let mut nearby_count = 0;
-        let proximity_threshold = 25.0;
+        let mut separation = Vec2::ZERO;
         
-        for (other_entity, other_pos) in &ant_positions {
-            if *other_entity != entity {
-                let distance = current_pos.distance(*other_pos);
+        for other in spatial_index.ants_within(current_pos, reach) {
+            if other.entity != entity {
+                let distance = current_pos.distance(other.position);
                 if distance < proximity_threshold {
                     nearby_count += 1;
                 }
+                
+                // CROWDING: Push away from overlapping neighbours, harder the deeper the overlap
+                if distance < config.separation_radius {
+                    let away = if distance > 0.01 {
+                        (current_pos - other.position) / distance
+                    } else {
+                        // Exactly stacked - split apart along a per-entity direction
+                        let angle = entity.index() as f32 * 2.399; // Golden angle spreads stacked ants evenly
+                        Vec2::new(angle.cos(), angle.sin())
+                    };
+                    separation += away * (1.0 - distance / config.separation_radius);
+                }
             }
         }
         
-        ant_state.nearby_ant_count = nearby_count;
-        ant_state.is_swarming = nearby_count >= 3 && ant_state.trail_following_time > 2.0;
-        
-        // Update exploration efficiency
-        let current_time = time.elapsed_seconds();
-        let time_delta = current_time - ant_state.current_goal_start_time;
-        if time_delta > 0.0 {
-            let distance_from_start = current_pos.distance(ant_state.last_position);
-            ant_state.exploration_efficiency = distance_from_start / time_delta.max(0.1);
-        }
+        ant_diagnostics.nearby_ant_count = nearby_count;
+        ant_state.separation = separation;
+        ant_diagnostics.is_swarming = nearby_count >= 3 && ant_diagnostics.trail_following_time > 2.0;
+    });
+}
+
+/// Run condition for behavior_analysis_system: true once every `analysis_interval` seconds, never
+/// when the interval is 0 (`--no-analysis`)
+pub fn analysis_due(config: Res<SimConfig>, time: Res<Time>, mut next_run: Local<f32>) -> bool {
+    if config.analysis_interval <= 0.0 {
+        return false;
     }
+    let current_time = time.elapsed_seconds();
+    if current_time < *next_run {
+        return false;
+    }
+    *next_run = current_time + config.analysis_interval;
+    true
 }
 
-// Comprehensive behavior analysis and logging system
+// Comprehensive behavior analysis and logging system, throttled by analysis_due
 pub fn behavior_analysis_system(
-    ants: Query<(Entity, &Transform, &AntState, Option<&DebugAnt>)>,
+    mut ants: Query<(&Transform, &AntCore, &AntStats, &mut AntDiagnostics, Option<&DebugAnt>)>,
     time: Res<Time>,
     performance_tracker: Res<PerformanceTracker>,
+    mut next_report: Local<f32>,
 ) {
     let current_time = time.elapsed_seconds();
     
@@ -911,82 +1199,120 @@ pub fn behavior_analysis_system(
     let mut total_time_since_progress = 0.0;
     let mut total_exploration_efficiency = 0.0;
     
-    for (entity, transform, ant, debug_ant) in ants.iter() {
+    for (transform, ant, ant_stats, mut ant_diagnostics, debug_ant) in ants.iter_mut() {
         total_ants += 1;
         
-        if ant.can_see_trail { ants_with_trails += 1; }
-        if ant.is_swarming { swarming_ants += 1; }
-        if ant.is_edge_wanderer { edge_wanderers += 1; }
+        // Update exploration efficiency
+        let time_delta = current_time - ant_stats.current_goal_start_time;
+        if time_delta > 0.0 {
+            let distance_from_start = transform.translation.truncate().distance(ant.last_position);
+            ant_diagnostics.exploration_efficiency = distance_from_start / time_delta.max(0.1);
+        }
+        
+        if ant_diagnostics.can_see_trail { ants_with_trails += 1; }
+        if ant_diagnostics.is_swarming { swarming_ants += 1; }
+        if ant_diagnostics.is_edge_wanderer { edge_wanderers += 1; }
         if ant.stuck_timer > 3.0 { stuck_ants += 1; }
-        if ant.exploration_efficiency > 10.0 { efficient_ants += 1; }
+        if ant_diagnostics.exploration_efficiency > 10.0 { efficient_ants += 1; }
         
-        total_time_since_progress += ant.time_since_progress;
-        total_exploration_efficiency += ant.exploration_efficiency;
+        total_time_since_progress += ant_diagnostics.time_since_progress;
+        total_exploration_efficiency += ant_diagnostics.exploration_efficiency;
         
         // Detailed logging for debug ant
-        if let Some(debug) = debug_ant {
-            if current_time.fract() < 0.1 { // Log roughly once per second
-                let pos = transform.translation;
-                println!("\n🐜 DEBUG ANT #{} ANALYSIS at {:.1}s:", debug.ant_id, current_time);
-                println!("   📍 Position: ({:.1}, {:.1}) | WorldEdgeProximity: {:.1}", pos.x, pos.y, ant.world_edge_proximity);
-                println!("   👁️ CanSeeTrail: {} | DistanceFromTrail: {:.1} | GradientStrength: {:.3}", 
-                    ant.can_see_trail, ant.distance_from_trail, ant.trail_gradient_strength);
-                println!("   🚶 TimeSinceProgress: {:.1}s | ExplorationEfficiency: {:.2}", 
-                    ant.time_since_progress, ant.exploration_efficiency);
-                println!("   👥 NearbyAnts: {} | IsSwarming: {} | IsEdgeWanderer: {}", 
-                    ant.nearby_ant_count, ant.is_swarming, ant.is_edge_wanderer);
-                println!("   🛤️ TrailFollowingTime: {:.1}s | LastTrailContact: {:.1}s ago", 
-                    ant.trail_following_time, current_time - ant.last_trail_contact_time);
-                println!("   🎯 CarryingFood: {} | BehaviorState: {:?}", ant.carrying_food, ant.behavior_state);
-            }
+        if let Some(debug_marker) = debug_ant {
+            let pos = transform.translation;
+            debug!("🐜 DEBUG ANT #{} ANALYSIS at {:.1}s:", debug_marker.ant_id, current_time);
+            debug!("   📍 Position: ({:.1}, {:.1}) | WorldEdgeProximity: {:.1}", pos.x, pos.y, ant_diagnostics.world_edge_proximity);
+            debug!("   👁️ CanSeeTrail: {} | DistanceFromTrail: {:.1} | GradientStrength: {:.3}", 
+                ant_diagnostics.can_see_trail, ant_diagnostics.distance_from_trail, ant_diagnostics.trail_gradient_strength);
+            debug!("   🚶 TimeSinceProgress: {:.1}s | ExplorationEfficiency: {:.2}", 
+                ant_diagnostics.time_since_progress, ant_diagnostics.exploration_efficiency);
+            debug!("   👥 NearbyAnts: {} | IsSwarming: {} | IsEdgeWanderer: {}", 
+                ant_diagnostics.nearby_ant_count, ant_diagnostics.is_swarming, ant_diagnostics.is_edge_wanderer);
+            debug!("   🛤️ TrailFollowingTime: {:.1}s | LastTrailContact: {:.1}s ago", 
+                ant_diagnostics.trail_following_time, current_time - ant_diagnostics.last_trail_contact_time);
+            debug!("   🎯 CarryingFood: {} | BehaviorState: {:?}", ant.carrying_food, ant.behavior_state);
         }
     }
     
     // Aggregate analysis logging every 5 seconds
-    if current_time % 5.0 < 0.1 {
+    if total_ants > 0 && current_time >= *next_report {
+        *next_report = current_time + 5.0;
         let avg_time_since_progress = total_time_since_progress / total_ants as f32;
         let avg_exploration_efficiency = total_exploration_efficiency / total_ants as f32;
         let trail_visibility_rate = (ants_with_trails as f32 / total_ants as f32) * 100.0;
         let swarming_rate = (swarming_ants as f32 / total_ants as f32) * 100.0;
         let edge_wanderer_rate = (edge_wanderers as f32 / total_ants as f32) * 100.0;
         
-        println!("\n📊 BEHAVIOR ANALYSIS REPORT at {:.1}s:", current_time);
-        println!("   Total Ants: {} | AvgTimeSinceProgress: {:.1}s | AvgExplorationEfficiency: {:.2}", 
+        debug!("📊 BEHAVIOR ANALYSIS REPORT at {:.1}s:", current_time);
+        debug!("   Total Ants: {} | AvgTimeSinceProgress: {:.1}s | AvgExplorationEfficiency: {:.2}", 
             total_ants, avg_time_since_progress, avg_exploration_efficiency);
-        println!("   TrailVisibilityRate: {:.1}% ({}/{}) | SwarmingRate: {:.1}% ({}/{})", 
+        debug!("   TrailVisibilityRate: {:.1}% ({}/{}) | SwarmingRate: {:.1}% ({}/{})", 
             trail_visibility_rate, ants_with_trails, total_ants, swarming_rate, swarming_ants, total_ants);
-        println!("   EdgeWandererRate: {:.1}% ({}/{}) | StuckAnts: {}", 
+        debug!("   EdgeWandererRate: {:.1}% ({}/{}) | StuckAnts: {}", 
             edge_wanderer_rate, edge_wanderers, total_ants, stuck_ants);
-        println!("   EfficientAnts: {} | CurrentAvgGoalTime: {:.1}s | Deliveries: {}", 
+        debug!("   EfficientAnts: {} | CurrentAvgGoalTime: {:.1}s | Deliveries: {}", 
             efficient_ants, performance_tracker.average_time_since_goal, performance_tracker.successful_deliveries);
-        println!();
     }
 }
 
 pub fn movement_system(
-    mut ants: Query<(&mut Transform, &Velocity, &AntState)>,
-    rocks: Query<(&Transform, &Rock), Without<AntState>>,
+    mut ants: Query<(&mut Transform, &Velocity, &mut AntCore, &mut AntMemory)>,
+    mut rocks: Query<&mut Rock, Without<AntCore>>,
+    spatial_index: Res<SpatialIndex>,
+    heightmap: Option<Res<Heightmap>>,
+    world_map: Option<Res<WorldMap>>,
+    config: Res<SimConfig>,
     time: Res<Time>,
 ) {
     let delta_time = time.delta_seconds();
+    let any_rocks = spatial_index.has_rocks(); // Open challenge maps skip the rock lookup entirely
     
-    for (mut ant_transform, velocity, _ant_state) in ants.iter_mut() {
+    for (mut ant_transform, velocity, mut ant_state, mut ant_memory) in ants.iter_mut() {
+        // TURN-RATE LIMIT: Body heading turns toward the requested velocity at a bounded angular speed.
+        // This is the only place heading changes take physical effect.
+        let requested = Vec2::new(velocity.x, velocity.y);
+        let speed = requested.length();
+        if speed > 0.0 {
+            let target_heading = requested.y.atan2(requested.x);
+            let mut heading_error = target_heading - ant_state.facing;
+            heading_error = (heading_error + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU) - std::f32::consts::PI;
+            let max_turn = config.max_turn_rate * delta_time;
+            ant_state.facing += heading_error.clamp(-max_turn, max_turn);
+        }
+        
         // Calculate proposed new position
-        let new_x = ant_transform.translation.x + velocity.x * delta_time;
-        let new_y = ant_transform.translation.y + velocity.y * delta_time;
-        let new_position = Vec2::new(new_x, new_y);
+        let current_position = ant_transform.translation.truncate();
+        let mut step = Vec2::new(ant_state.facing.cos(), ant_state.facing.sin()) * speed * delta_time;
+        
+        // CROWDING: Sideways shove from neighbours, independent of heading so streams can pass each other
+        step += ant_state.separation * config.separation_strength * delta_time;
         
-        // Check for collision with rocks
+        // TERRAIN: Uphill movement is slowed proportionally to slope
+        if let Some(ref heightmap) = heightmap {
+            step *= heightmap.slope_speed_factor(current_position, current_position + step, config.terrain_height_scale, config.slope_cost);
+        }
+        
+        let new_position = current_position + step;
+        let new_x = new_position.x;
+        let new_y = new_position.y;
+        
+        // Check for collision with rocks, only those whose bucket is near the new position
         let mut collision_detected = false;
+        let ant_radius = 6.0; // Half the ant size (12x12)
         
-        for (rock_transform, rock) in rocks.iter() {
-            let rock_pos = Vec2::new(rock_transform.translation.x, rock_transform.translation.y);
-            let distance = new_position.distance(rock_pos);
-            let ant_radius = 6.0; // Half the ant size (12x12)
-            
-            if distance < rock.radius + ant_radius {
+        let blocking = if any_rocks { spatial_index.rocks_within(new_position, ant_radius).next() } else { None };
+        if let Some(blocking) = blocking {
+            collision_detected = true;
+            if let Ok(mut rock) = rocks.get_mut(blocking.entity) {
+                rock.blocked_count += 1;
+            }
+        }
+        
+        // Water from a loaded map is impassable
+        if let Some(ref map) = world_map {
+            if map.water.is_water(new_x, new_y) {
                 collision_detected = true;
-                break;
             }
         }
         
@@ -994,6 +1320,11 @@ pub fn movement_system(
         if !collision_detected {
             ant_transform.translation.x = new_x;
             ant_transform.translation.y = new_y;
+            
+            // Outbound path length, frozen while carrying so the return trip knows its tour length
+            if !ant_state.carrying_food {
+                ant_memory.distance_from_nest += step.length();
+            }
         }
         // If collision detected, ant stays at current position (blocked by rock)
         
@@ -1014,19 +1345,53 @@ pub fn movement_system(
 }
 
 pub fn pheromone_deposit_system(
-    ants: Query<(&Transform, &AntState)>,
+    ants: Query<(&Transform, &AntCore, &AntMemory, &AntStats, &AntDiagnostics)>,
+    prey: Query<&Transform, (With<Prey>, Without<AntCore>)>,
     mut pheromone_grid: Option<ResMut<PheromoneGrid>>,
     config: Res<SimConfig>,
     time: Res<Time>,
+    mut thread_deposits: Local<Parallel<Vec<PheromoneDeposit>>>,
+    mut deposits: Local<Vec<PheromoneDeposit>>,
 ) {
     if let Some(ref mut grid) = pheromone_grid {
-        for (transform, ant) in ants.iter() {
+        // Ants queue their deposits in parallel against the grid as the tick left it; the queue is then
+        // applied in one cell-ordered pass
+        let shared_grid: &PheromoneGrid = grid;
+        ants.par_iter().for_each(|(transform, ant, ant_memory, ant_stats, ant_diagnostics)| {
+            let grid = shared_grid;
+            let mut queue = thread_deposits.borrow_local_mut();
             let current_pos = transform.translation;
             let last_pos = Vec3::new(ant.last_position.x, ant.last_position.y, 0.0);
             
+            // PREY: Weaken food trails that lead to where the prey used to be
+            let stale_factor = match ant_memory.food_source.and_then(|source| prey.get(source).ok()) {
+                Some(prey_transform) => {
+                    let displacement = prey_transform.translation.truncate().distance(ant_memory.pickup_position);
+                    (1.0 - displacement / config.prey_trail_falloff).max(0.1)
+                }
+                None => 1.0,
+            };
+            
             // Calculate distance moved this frame
             let movement_distance = current_pos.distance(last_pos);
             
+            // CLASSICAL ACO: Only returning carriers lay food pheromone, Q * quality / tour length per unit of path
+            if config.classic_aco {
+                if ant.carrying_food && movement_distance > 0.0 {
+                    let tour_length = ant_memory.distance_from_nest.max(1.0);
+                    let deposit_amount = config.aco_pheromone_q * config.food_quality_weight * ant_memory.carried_food_quality
+                        / tour_length * movement_distance * stale_factor;
+                    let num_deposits = (movement_distance / 0.8).ceil() as i32;
+                    
+                    for i in 0..=num_deposits {
+                        let t = if num_deposits > 0 { i as f32 / num_deposits as f32 } else { 0.0 };
+                        let deposit_pos = last_pos.lerp(current_pos, t);
+                        grid.queue_deposit(&mut queue, deposit_pos.x, deposit_pos.y, PheromoneType::Food, deposit_amount / (num_deposits + 1) as f32);
+                    }
+                }
+                return;
+            }
+            
             // Deposit pheromones along the path if ant moved significantly
             if movement_distance > 0.5 {
                 // Number of deposits based on distance moved (ensure continuous trail)
@@ -1038,19 +1403,19 @@ pub fn pheromone_deposit_system(
                     
                     if ant.carrying_food {
                         // CYCLE 16: Enhanced trail quality based on ant success and efficiency
-                        let decay_factor = (-ant.distance_from_food * 0.01).exp(); // Balanced distance decay rate
+                        let decay_factor = (-ant_memory.distance_from_food * 0.01).exp(); // Balanced distance decay rate
                         
                         // Quality multiplier based on ant success history
-                        let success_factor = if ant.successful_deliveries > 0 {
-                            1.0 + (ant.successful_deliveries as f32 * 0.1).min(0.5) // Up to 50% bonus for experienced ants
+                        let success_factor = if ant_stats.successful_deliveries > 0 {
+                            1.0 + (ant_stats.successful_deliveries as f32 * 0.1).min(0.5) // Up to 50% bonus for experienced ants
                         } else {
                             0.8 // Slight penalty for unproven ants
                         };
                         
                         // Efficiency bonus for ants making progress vs stuck ants
-                        let efficiency_factor = if ant.time_since_progress < 5.0 {
+                        let efficiency_factor = if ant_diagnostics.time_since_progress < 5.0 {
                             1.2 // 20% bonus for ants making good progress
-                        } else if ant.time_since_progress > 15.0 {
+                        } else if ant_diagnostics.time_since_progress > 15.0 {
                             0.6 // 40% penalty for stuck ants
                         } else {
                             1.0
@@ -1059,7 +1424,7 @@ pub fn pheromone_deposit_system(
                         // Speed bonus for fast-moving ants (better path quality)
                         let speed_factor = (movement_distance / 0.8).min(1.5); // Up to 50% bonus for fast ants
                         
-                        let base_deposit_amount = config.lay_rate_food * config.food_quality_weight * decay_factor * success_factor * efficiency_factor * speed_factor;
+                        let base_deposit_amount = config.lay_rate_food * config.food_quality_weight * decay_factor * success_factor * efficiency_factor * speed_factor * stale_factor;
                         
                         // CYCLE 20: Collaborative trail widening - check for nearby trail activity
                         let current_pheromone = grid.sample_directional(deposit_pos.x, deposit_pos.y, 0.0, 3.0, PheromoneType::Food);
@@ -1076,7 +1441,8 @@ pub fn pheromone_deposit_system(
                         let deposit_amount = base_deposit_amount * traffic_factor;
                         
                         // Primary deposit
-                        grid.deposit(
+                        grid.queue_deposit(
+                            &mut queue,
                             deposit_pos.x, 
                             deposit_pos.y, 
                             PheromoneType::Food, 
@@ -1084,13 +1450,13 @@ pub fn pheromone_deposit_system(
                         );
                         
                         // CYCLE 21: Lane-specific highway formation with traffic flow awareness
-                        if current_pheromone > 1.5 && ant.successful_deliveries > 1 {
+                        if current_pheromone > 1.5 && ant_stats.successful_deliveries > 1 {
                             let movement_direction_3d = (current_pos - last_pos).normalize();
                             let movement_direction = Vec2::new(movement_direction_3d.x, movement_direction_3d.y);
                             let perp_angle = movement_direction.y.atan2(movement_direction.x) + std::f32::consts::PI / 2.0;
                             
                             // Determine which lane this ant should reinforce
-                            let to_nest = (Vec2::ZERO - Vec2::new(deposit_pos.x, deposit_pos.y)).normalize();
+                            let to_nest = (ant.nest_position - Vec2::new(deposit_pos.x, deposit_pos.y)).normalize();
                             let toward_nest = movement_direction.dot(to_nest) > 0.1;
                             
                             let side_deposit = deposit_amount * 0.35; // Increased side deposit for lane definition
@@ -1098,7 +1464,8 @@ pub fn pheromone_deposit_system(
                             if toward_nest {
                                 // Food-carrying ant heading toward nest - strengthen left lane (inbound)
                                 let lane_offset = 3.5; // Closer to center for priority lane
-                                grid.deposit(
+                                grid.queue_deposit(
+                                    &mut queue,
                                     deposit_pos.x - perp_angle.cos() * lane_offset,
                                     deposit_pos.y - perp_angle.sin() * lane_offset,
                                     PheromoneType::Food,
@@ -1106,7 +1473,8 @@ pub fn pheromone_deposit_system(
                                 );
                                 
                                 // Light deposit on right lane for highway definition
-                                grid.deposit(
+                                grid.queue_deposit(
+                                    &mut queue,
                                     deposit_pos.x + perp_angle.cos() * 6.0,
                                     deposit_pos.y + perp_angle.sin() * 6.0,
                                     PheromoneType::Food,
@@ -1115,7 +1483,8 @@ pub fn pheromone_deposit_system(
                             } else {
                                 // Food-seeking ant heading away from nest - strengthen right lane (outbound)
                                 let lane_offset = 5.5; // Further from center
-                                grid.deposit(
+                                grid.queue_deposit(
+                                    &mut queue,
                                     deposit_pos.x + perp_angle.cos() * lane_offset,
                                     deposit_pos.y + perp_angle.sin() * lane_offset,
                                     PheromoneType::Food,
@@ -1123,7 +1492,8 @@ pub fn pheromone_deposit_system(
                                 );
                                 
                                 // Light deposit on left lane for highway definition
-                                grid.deposit(
+                                grid.queue_deposit(
+                                    &mut queue,
                                     deposit_pos.x - perp_angle.cos() * 4.0,
                                     deposit_pos.y - perp_angle.sin() * 4.0,
                                     PheromoneType::Food,
@@ -1134,7 +1504,7 @@ pub fn pheromone_deposit_system(
                         
                         // NEST PHEROMONE FIX: Food-carrying ants should ALSO deposit strong nest pheromones!
                         // This creates proven successful return paths for other food carriers to follow
-                        let distance_to_nest = Vec2::new(deposit_pos.x, deposit_pos.y).length();
+                        let distance_to_nest = Vec2::new(deposit_pos.x, deposit_pos.y).distance(ant.nest_position);
                         let nest_proximity_bonus = if distance_to_nest < 150.0 {
                             2.0 // Very strong bonus when approaching nest
                         } else if distance_to_nest < 300.0 {
@@ -1144,10 +1514,10 @@ pub fn pheromone_deposit_system(
                         };
                         
                         // Success-based multiplier: experienced ants lay stronger nest trails
-                        let success_multiplier = 1.0 + (ant.successful_deliveries as f32 * 0.3).min(1.5);
+                        let success_multiplier = 1.0 + (ant_stats.successful_deliveries as f32 * 0.3).min(1.5);
                         
                         // Progress bonus: ants making good progress lay stronger trails
-                        let progress_bonus = if ant.time_since_progress < 5.0 {
+                        let progress_bonus = if ant_diagnostics.time_since_progress < 5.0 {
                             1.3 // Bonus for ants making good progress toward nest
                         } else {
                             0.8 // Reduced strength for struggling ants
@@ -1156,7 +1526,8 @@ pub fn pheromone_deposit_system(
                         let nest_deposit_amount = config.lay_rate_nest * nest_proximity_bonus * success_multiplier * progress_bonus;
                         
                         // Deposit strong nest pheromones along the successful return path
-                        grid.deposit(
+                        grid.queue_deposit(
+                            &mut queue,
                             deposit_pos.x,
                             deposit_pos.y,
                             PheromoneType::Nest,
@@ -1168,12 +1539,13 @@ pub fn pheromone_deposit_system(
                         // Only successful food-carriers should create nest trails!
                         
                         // Very weak nest pheromone from experienced exploring ants only
-                        if ant.has_found_food && ant.successful_deliveries > 0 {
-                            let time_since_nest = time.elapsed_seconds() - ant.current_goal_start_time;
+                        if ant_memory.has_found_food && ant_stats.successful_deliveries > 0 {
+                            let time_since_nest = time.elapsed_seconds() - ant_stats.current_goal_start_time;
                             let time_decay = (-time_since_nest * 0.2).exp(); // Faster decay
                             let weak_deposit = config.lay_rate_nest * 0.1 * time_decay; // Much weaker
                             
-                            grid.deposit(
+                            grid.queue_deposit(
+                                &mut queue,
                                 deposit_pos.x,
                                 deposit_pos.y,
                                 PheromoneType::Nest,
@@ -1187,12 +1559,12 @@ pub fn pheromone_deposit_system(
                 // For very small movements, just deposit at current position
                 if ant.carrying_food {
                     // Food pheromone deposition
-                    let decay_factor = (-ant.distance_from_food * 0.005).exp();
-                    let food_deposit_amount = config.lay_rate_food * config.food_quality_weight * decay_factor;
-                    grid.deposit(current_pos.x, current_pos.y, PheromoneType::Food, food_deposit_amount);
+                    let decay_factor = (-ant_memory.distance_from_food * 0.005).exp();
+                    let food_deposit_amount = config.lay_rate_food * config.food_quality_weight * decay_factor * stale_factor;
+                    grid.queue_deposit(&mut queue, current_pos.x, current_pos.y, PheromoneType::Food, food_deposit_amount);
                     
                     // NEST PHEROMONE FIX: Food-carrying ants ALSO deposit nest pheromones for small movements
-                    let distance_to_nest = Vec2::new(current_pos.x, current_pos.y).length();
+                    let distance_to_nest = Vec2::new(current_pos.x, current_pos.y).distance(ant.nest_position);
                     let nest_proximity_bonus = if distance_to_nest < 150.0 {
                         2.0 // Very strong bonus when approaching nest
                     } else if distance_to_nest < 300.0 {
@@ -1201,25 +1573,28 @@ pub fn pheromone_deposit_system(
                         1.0
                     };
                     
-                    let success_multiplier = 1.0 + (ant.successful_deliveries as f32 * 0.3).min(1.5);
-                    let progress_bonus = if ant.time_since_progress < 5.0 { 1.3 } else { 0.8 };
+                    let success_multiplier = 1.0 + (ant_stats.successful_deliveries as f32 * 0.3).min(1.5);
+                    let progress_bonus = if ant_diagnostics.time_since_progress < 5.0 { 1.3 } else { 0.8 };
                     let nest_deposit_amount = config.lay_rate_nest * nest_proximity_bonus * success_multiplier * progress_bonus;
                     
-                    grid.deposit(current_pos.x, current_pos.y, PheromoneType::Nest, nest_deposit_amount);
+                    grid.queue_deposit(&mut queue, current_pos.x, current_pos.y, PheromoneType::Nest, nest_deposit_amount);
                 } else {
                     // NEST PHEROMONE FIX: Exploring ants deposit very little nest pheromone for small movements
                     // Only experienced exploring ants deposit weak nest pheromones
-                    if ant.has_found_food && ant.successful_deliveries > 0 {
-                        let time_since_nest = time.elapsed_seconds() - ant.current_goal_start_time;
+                    if ant_memory.has_found_food && ant_stats.successful_deliveries > 0 {
+                        let time_since_nest = time.elapsed_seconds() - ant_stats.current_goal_start_time;
                         let time_decay = (-time_since_nest * 0.2).exp();
                         let weak_deposit = config.lay_rate_nest * 0.1 * time_decay; // Much weaker
                         
-                        grid.deposit(current_pos.x, current_pos.y, PheromoneType::Nest, weak_deposit);
+                        grid.queue_deposit(&mut queue, current_pos.x, current_pos.y, PheromoneType::Nest, weak_deposit);
                     }
                     // Most exploring ants deposit NO nest pheromones for small movements
                 }
             }
-        }
+        });
+        
+        thread_deposits.drain_into(&mut deposits);
+        grid.apply_deposits(&mut deposits);
     }
 }
 
@@ -1227,7 +1602,10 @@ pub fn pheromone_update_system(
     mut pheromone_grid: Option<ResMut<PheromoneGrid>>,
     food_sources: Query<&Transform, With<FoodSource>>,
     config: Res<SimConfig>,
+    mut diagnostics: Diagnostics,
 ) {
+    let started = Instant::now();
+    let _span = trace_span!("pheromone_update_system").entered();
     if let Some(ref mut grid) = pheromone_grid {
         // FOOD SCENT: Food sources naturally emit pheromones in smooth circular gradient
         for food_transform in food_sources.iter() {
@@ -1269,31 +1647,27 @@ pub fn pheromone_update_system(
         
         grid.update(evap_rates, diff_rates);
     }
+    record_system_time(&mut diagnostics, &PHEROMONE_UPDATE_TIME, started);
 }
 
 pub fn food_collection_system(
-    mut ants: Query<(Entity, &Transform, &mut AntState, &mut Velocity, Option<&DebugAnt>)>,
-    mut food_sources: Query<(&Transform, &mut FoodSource)>,
-    nests: Query<&Transform, (With<Nest>, Without<AntState>)>,
-    mut performance_tracker: ResMut<PerformanceTracker>,
+    mut ants: Query<(Entity, &Transform, &mut AntCore, &mut AntMemory, &mut AntStats, &mut AntDiagnostics, &mut Velocity, Option<&DebugAnt>)>,
+    mut food_sources: Query<(Entity, &Transform, &mut FoodSource)>,
+    spatial_index: Res<SpatialIndex>,
+    mut pickups: EventWriter<FoodPickedUp>,
+    mut deliveries: EventWriter<FoodDelivered>,
     time: Res<Time>,
 ) {
-    let nest_pos = if let Ok(nest_transform) = nests.get_single() {
-        nest_transform.translation
-    } else {
-        Vec3::ZERO
-    };
-    
-    for (entity, ant_transform, mut ant, mut velocity, debug_ant) in ants.iter_mut() {
+    for (entity, ant_transform, mut ant, mut ant_memory, mut ant_stats, mut ant_diagnostics, mut velocity, debug_ant) in ants.iter_mut() {
         let ant_pos = ant_transform.translation;
+        let nest_pos = ant.nest_position.extend(ant_pos.z);
         
         if !ant.carrying_food && ant.food_collection_timer <= 0.0 {
             // Look for food sources
-            for (food_transform, food) in food_sources.iter() {
-                let food_pos = food_transform.translation;
-                let distance = ant_pos.distance(food_pos);
+            for nearby in spatial_index.food_within(ant_pos.truncate(), 25.0) { // Restored to original pickup distance
+                let Ok((_food_entity, _food_transform, food)) = food_sources.get(nearby.entity) else { continue };
                 
-                if distance < 25.0 && food.amount > 0.0 { // Restored to original pickup distance
+                if food.amount > 0.0 {
                     // Start collecting food
                     ant.food_collection_timer = 0.3;
                     velocity.x = 0.0;
@@ -1309,25 +1683,35 @@ pub fn food_collection_system(
             
             if ant.food_collection_timer <= 0.0 {
                 // Look for nearby food to take
-                for (food_transform, mut food) in food_sources.iter_mut() {
-                    let food_pos = food_transform.translation;
-                    let distance = ant_pos.distance(food_pos);
+                for nearby in spatial_index.food_within(ant_pos.truncate(), 25.0) { // Restored to original pickup distance
+                    let Ok((food_entity, _food_transform, mut food)) = food_sources.get_mut(nearby.entity) else { continue };
                     
-                    if distance < 25.0 && food.amount > 0.0 { // Restored to original pickup distance
+                    if food.amount > 0.0 {
                         let take_amount = 1.0;
                         food.amount -= take_amount;
                         ant.carrying_food = true;
-                        ant.food_pickup_time = time.elapsed_seconds();
-                        ant.has_found_food = true;
-                        ant.food_carry_start_time = time.elapsed_seconds();
-                        ant.last_goal_achievement_time = time.elapsed_seconds();
-                        ant.time_since_progress = 0.0; // Reset progress timer on food pickup
-                        performance_tracker.total_food_collected += take_amount;
+                        ant_stats.food_pickup_time = time.elapsed_seconds();
+                        ant_memory.has_found_food = true;
+                        ant_stats.food_carry_start_time = time.elapsed_seconds();
+                        ant_stats.last_goal_achievement_time = time.elapsed_seconds();
+                        ant_diagnostics.time_since_progress = 0.0; // Reset progress timer on food pickup
+                        ant_memory.food_source = Some(food_entity);
+                        ant.exploration_phase = 0.0;
+                        ant_memory.pickup_position = nearby.position;
+                        ant_memory.carried_food_quality = food.quality;
+                        pickups.send(FoodPickedUp {
+                            ant: entity,
+                            ant_index: ant.index,
+                            source: food_entity,
+                            position: ant_pos.truncate(),
+                            amount: take_amount,
+                            time: time.elapsed_seconds(),
+                        });
                         
                         // Debug logging for food pickup
                         if let Some(debug_marker) = debug_ant {
-                            let search_time = (time.elapsed_seconds() - 1.0).max(0.0); // Time since 1.0s startup ended
-                            println!("🎯 DEBUG ANT #{} FOUND FOOD! @ T={:.1}s | Pos=({:.0},{:.0}) | SearchTime={:.1}s | FoodLeft={:.1}", 
+                            let search_time = (time.elapsed_seconds() - ant_stats.spawn_time - 1.0).max(0.0); // Time since 1.0s startup ended
+                            debug!("🎯 DEBUG ANT #{} FOUND FOOD! @ T={:.1}s | Pos=({:.0},{:.0}) | SearchTime={:.1}s | FoodLeft={:.1}", 
                                 debug_marker.ant_id, time.elapsed_seconds(), ant_pos.x, ant_pos.y, search_time, food.amount);
                         }
                         
@@ -1346,30 +1730,31 @@ pub fn food_collection_system(
             if distance < 15.0 { // Much smaller radius - ants must actually reach the nest
                 // Successful delivery
                 ant.carrying_food = false;
-                ant.delivery_attempts += 1;
-                ant.successful_deliveries += 1;
-                ant.last_goal_achievement_time = time.elapsed_seconds();
-                ant.time_since_progress = 0.0; // Reset progress timer on successful delivery
+                ant_memory.food_source = None;
+                ant.exploration_phase = 0.0;
+                ant_memory.distance_from_nest = 0.0; // New outbound trip starts here
+                ant_stats.delivery_attempts += 1;
+                ant_stats.successful_deliveries += 1;
+                ant_stats.last_goal_achievement_time = time.elapsed_seconds();
+                ant_diagnostics.time_since_progress = 0.0; // Reset progress timer on successful delivery
                 
-                // Track delivery metrics
-                let delivery_time = time.elapsed_seconds() - ant.food_pickup_time;
-                let return_time = time.elapsed_seconds() - ant.food_carry_start_time;
-                performance_tracker.delivery_times.push(delivery_time);
-                performance_tracker.return_times.push(return_time);
-                performance_tracker.successful_deliveries += 1;
-                performance_tracker.last_delivery_time = time.elapsed_seconds();
-                
-                // Update averages
-                let total_time: f32 = performance_tracker.delivery_times.iter().sum();
-                performance_tracker.average_delivery_time = total_time / performance_tracker.delivery_times.len() as f32;
-                
-                let total_return_time: f32 = performance_tracker.return_times.iter().sum();
-                performance_tracker.average_return_time = total_return_time / performance_tracker.return_times.len() as f32;
+                // Delivery metrics are tallied by performance_tracker_system
+                let delivery_time = time.elapsed_seconds() - ant_stats.food_pickup_time;
+                let return_time = time.elapsed_seconds() - ant_stats.food_carry_start_time;
+                deliveries.send(FoodDelivered {
+                    ant: entity,
+                    ant_index: ant.index,
+                    colony: ant.colony,
+                    position: ant_pos.truncate(),
+                    delivery_time,
+                    return_time,
+                    time: time.elapsed_seconds(),
+                });
                 
                 // Debug logging for food delivery
                 if let Some(debug_marker) = debug_ant {
-                    println!("✅ DEBUG ANT #{} DELIVERED FOOD! @ T={:.1}s | TotalDeliveries={} | ReturnTime={:.1}s", 
-                        debug_marker.ant_id, time.elapsed_seconds(), ant.successful_deliveries, return_time);
+                    debug!("✅ DEBUG ANT #{} DELIVERED FOOD! @ T={:.1}s | TotalDeliveries={} | ReturnTime={:.1}s", 
+                        debug_marker.ant_id, time.elapsed_seconds(), ant_stats.successful_deliveries, return_time);
                 }
                 
                 
@@ -1383,12 +1768,65 @@ pub fn food_collection_system(
     }
 }
 
+/// Tally this tick's pickups, deliveries and depletions into the PerformanceTracker that scoring,
+/// the panels and the run summary read
+pub fn performance_tracker_system(
+    mut performance_tracker: ResMut<PerformanceTracker>,
+    mut pickups: EventReader<FoodPickedUp>,
+    mut deliveries: EventReader<FoodDelivered>,
+    mut depleted_events: EventReader<FoodDepleted>,
+) {
+    for pickup in pickups.read() {
+        performance_tracker.total_food_collected += pickup.amount;
+    }
+
+    let mut delivered = false;
+    for delivery in deliveries.read() {
+        performance_tracker.delivery_times.push(delivery.delivery_time);
+        performance_tracker.return_times.push(delivery.return_time);
+        performance_tracker.successful_deliveries += 1;
+        performance_tracker.last_delivery_time = delivery.time;
+        let stage_time = delivery.time - performance_tracker.simulation_start_time;
+        performance_tracker.delivery_timestamps.push(stage_time);
+        if performance_tracker.colony_deliveries.len() <= delivery.colony {
+            performance_tracker.colony_deliveries.resize(delivery.colony + 1, 0);
+        }
+        performance_tracker.colony_deliveries[delivery.colony] += 1;
+        delivered = true;
+    }
+    if delivered {
+        // Update averages
+        let total_time: f32 = performance_tracker.delivery_times.iter().sum();
+        performance_tracker.average_delivery_time = total_time / performance_tracker.delivery_times.len() as f32;
+
+        let total_return_time: f32 = performance_tracker.return_times.iter().sum();
+        performance_tracker.average_return_time = total_return_time / performance_tracker.return_times.len() as f32;
+    }
+
+    // Note depletion moments so trail analytics can separate stale trails from lost ants
+    for event in depleted_events.read() {
+        info!("🍂 Food source {:?} depleted at ({:.0}, {:.0}) t={:.1}s", event.source, event.position.x, event.position.y, event.time);
+        performance_tracker.food_depletion_times.push(event.time);
+    }
+}
+
 pub fn performance_analysis_system(
-    ants: Query<&AntState>,
+    ants: Query<(&AntCore, &AntMemory, &AntStats, &AntDiagnostics)>,
     mut performance_tracker: ResMut<PerformanceTracker>,
     mut exit_writer: EventWriter<bevy::app::AppExit>,
+    challenge: Res<ActiveChallenge>,
+    mut challenge_score: ResMut<ChallengeScore>,
+    curriculum: Option<Res<Curriculum>>,
     time: Res<Time>,
 ) {
+    if performance_tracker.simulation_start_time == 0.0 {
+        performance_tracker.simulation_start_time = time.elapsed_seconds();
+    }
+    // Time into the current run (curriculum stages restart the clock)
+    let stage_time = time.elapsed_seconds() - performance_tracker.simulation_start_time;
+    // Curriculum mode moves on to the next stage instead of exiting
+    let exit_when_decided = curriculum.is_none();
+    
     let mut stuck_count = 0;
     let mut oscillating_count = 0;
     let mut lost_count = 0;
@@ -1396,30 +1834,36 @@ pub fn performance_analysis_system(
     let runtime = time.elapsed_seconds();
     
     performance_tracker.time_since_goal_samples.clear();
+    performance_tracker.colony_ant_counts.clear();
     
-    for ant in ants.iter() {
+    for (ant, ant_memory, ant_stats, ant_diagnostics) in ants.iter() {
+        if performance_tracker.colony_ant_counts.len() <= ant.colony {
+            performance_tracker.colony_ant_counts.resize(ant.colony + 1, 0);
+        }
+        performance_tracker.colony_ant_counts[ant.colony] += 1;
+        
         if ant.stuck_timer > 3.0 {
             stuck_count += 1;
         }
         
-        if ant.direction_changes > 5 && ant.stuck_timer > 1.0 {
+        if ant_diagnostics.direction_changes > 5 && ant.stuck_timer > 1.0 {
             oscillating_count += 1;
         }
         
-        if !ant.has_found_food && ant.startup_timer <= 0.0 && runtime > 45.0 {
+        if !ant_memory.has_found_food && ant.startup_timer <= 0.0 && stage_time > 45.0 {
             lost_count += 1;
         }
         
-        if ant.carrying_food && ant.food_carry_start_time > 0.0 && 
-           runtime - ant.food_carry_start_time > 30.0 {
+        if ant.carrying_food && ant_stats.food_carry_start_time > 0.0 && 
+           runtime - ant_stats.food_carry_start_time > 30.0 {
             lost_food_carriers_count += 1;
         }
         
-        let time_since_goal = if ant.last_goal_achievement_time > 0.0 {
-            runtime - ant.last_goal_achievement_time
+        let time_since_goal = if ant_stats.last_goal_achievement_time > 0.0 {
+            runtime - ant_stats.last_goal_achievement_time
         } else {
-            // Time since startup ended (1.0s)
-            (runtime - 1.0).max(0.0)
+            // Time since startup ended (1.0s after spawning)
+            (runtime - ant_stats.spawn_time - 1.0).max(0.0)
         };
         
         if ant.startup_timer <= 0.0 {
@@ -1438,47 +1882,163 @@ pub fn performance_analysis_system(
         0.0
     };
     
-    if performance_tracker.simulation_start_time == 0.0 {
-        performance_tracker.simulation_start_time = time.elapsed_seconds();
+    if challenge_score.verdict == Verdict::Running {
+        challenge_score.score = challenge.0.score(&performance_tracker);
+    }
+    
+    // Free play runs until the user quits
+    if challenge.0.free_play() {
+        return;
     }
     
     // Auto-exit conditions
     if oscillating_count >= 20 {
-        println!("\n🚨 AUTO-EXIT: Too many oscillating ants ({})", oscillating_count);
-        exit_writer.send(AppExit::Success);
+        info!("🚨 AUTO-EXIT: Too many oscillating ants ({})", oscillating_count);
+        challenge_score.finish(Verdict::Failure, format!("Auto-exit with {} oscillating ants", oscillating_count));
+        if exit_when_decided {
+            exit_writer.send(AppExit::Success);
+        }
     }
     
     // Removed "too many lost food carriers" exit condition to allow more time for pathfinding
     
-    if time.elapsed_seconds() > 90.0 {
-        println!("\n🎉 SUCCESS: 90 seconds completed with {:.1}s avg goal time!", performance_tracker.average_time_since_goal);
-        exit_writer.send(AppExit::Success);
+    if challenge_score.verdict != Verdict::Running {
+        return;
+    }
+    
+    // Challenge-specific early finish
+    if let Some(outcome) = challenge.0.success(&performance_tracker) {
+        match outcome {
+            Outcome::Success(message) => {
+                info!("🎉 SUCCESS at {:.1}s: {}", stage_time, message);
+                challenge_score.finish(Verdict::Success, message);
+            }
+            Outcome::Failure(message) => {
+                info!("🚨 FAILURE at {:.1}s: {}", stage_time, message);
+                challenge_score.finish(Verdict::Failure, message);
+            }
+        }
+        info!("🏆 Score: {:.1} (pass {:.0})", challenge_score.score, challenge_score.pass_score);
+        if exit_when_decided {
+            exit_writer.send(AppExit::Success);
+        }
+        return;
+    }
+    
+    if stage_time > TIME_LIMIT {
+        info!("⏱️ {:.0}s completed with {:.1}s avg goal time", TIME_LIMIT, performance_tracker.average_time_since_goal);
+        if let Some(summary) = challenge.0.summary(&performance_tracker) {
+            info!("{}", summary);
+        }
+        
+        let (score, pass_score) = (challenge_score.score, challenge_score.pass_score);
+        if score >= pass_score {
+            info!("🎉 SUCCESS: score {:.1} reached the pass mark of {:.0}", score, pass_score);
+            challenge_score.finish(Verdict::Success, format!("Score {:.1} reached the pass mark of {:.0}", score, pass_score));
+        } else {
+            info!("🚨 FAILURE: score {:.1} fell short of the pass mark of {:.0}", score, pass_score);
+            challenge_score.finish(Verdict::Failure, format!("Score {:.1} fell short of the pass mark of {:.0}", score, pass_score));
+        }
+        if exit_when_decided {
+            exit_writer.send(AppExit::Success);
+        }
     }
 }
 
 // Visual and UI systems remain unchanged
+/// Side of an ant's square in world units
+const ANT_SIZE: f32 = 12.0;
+
+/// What an ant's color says it is doing
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum AntLook {
+    Exploring,
+    Collecting,
+    Carrying,
+}
+
+/// The square every ant is drawn with, and one material per look and colony, so all ants share a mesh
+/// and ants of one color batch into a single draw
+#[derive(Default)]
+pub struct AntMeshAssets {
+    mesh: Option<Mesh2dHandle>,
+    materials: HashMap<(AntLook, usize), Handle<ColorMaterial>>,
+}
+
+/// Run condition: ants spawned since ant_visual_system last ran, which would stay invisible while paused
+pub fn ants_without_mesh(ants: Query<(), (With<AntCore>, Without<Mesh2dHandle>)>) -> bool {
+    !ants.is_empty()
+}
+
+/// Point each ant at the material for its current look, giving new ants the shared mesh, and follow ant color edits
 pub fn ant_visual_system(
-    mut ants: Query<(&AntState, &mut Sprite), (With<AntState>, Without<PheromoneVisualization>)>,
+    mut commands: Commands,
+    mut ants: Query<(Entity, &AntCore, Option<&mut Handle<ColorMaterial>>)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
     color_config: Res<ColorConfig>,
+    mut assets: Local<AntMeshAssets>,
 ) {
-    for (ant, mut sprite) in ants.iter_mut() {
-        if ant.carrying_food {
-            sprite.color = color_config.ant_carrying_food;
+    let look_color = |look: AntLook, colony: usize| {
+        let color = match look {
+            AntLook::Exploring => color_config.ant_exploring,
+            AntLook::Collecting => color_config.ant_collecting,
+            AntLook::Carrying => color_config.ant_carrying_food,
+        };
+        color_config.colony_tint(color, colony)
+    };
+    let mesh = assets.mesh.get_or_insert_with(|| Mesh2dHandle(meshes.add(Rectangle::new(ANT_SIZE, ANT_SIZE)))).clone();
+    if color_config.is_changed() {
+        for (&(look, colony), handle) in &assets.materials {
+            if let Some(material) = materials.get_mut(handle) {
+                material.color = look_color(look, colony);
+            }
+        }
+    }
+    
+    for (entity, ant, current) in ants.iter_mut() {
+        let look = if ant.carrying_food {
+            AntLook::Carrying
         } else if ant.food_collection_timer > 0.0 {
-            sprite.color = color_config.ant_collecting;
+            AntLook::Collecting
         } else {
-            sprite.color = color_config.ant_exploring;
+            AntLook::Exploring
+        };
+        let material = assets.materials.entry((look, ant.colony))
+            .or_insert_with(|| materials.add(look_color(look, ant.colony)));
+        match current {
+            Some(mut current) => {
+                if *current != *material {
+                    *current = material.clone();
+                }
+            }
+            None => {
+                commands.entity(entity).insert((mesh.clone(), material.clone()));
+            }
         }
     }
 }
 
 pub fn food_visual_system(
-    mut food_sources: Query<(Entity, &FoodSource, &mut Sprite, &Transform), (With<FoodSource>, Without<PheromoneVisualization>)>,
+    mut food_sources: Query<(Entity, &mut FoodSource, &mut Sprite, &Transform, Option<&Prey>, Option<&Regrowing>), Without<PheromoneVisualization>>,
     mut commands: Commands,
+    mut depleted_events: EventWriter<FoodDepleted>,
     config: Res<SimConfig>,
     color_config: Res<ColorConfig>,
+    time: Res<Time>,
 ) {
-    for (entity, food, mut sprite, _transform) in food_sources.iter_mut() {
+    let current_time = time.elapsed_seconds();
+    
+    for (entity, mut food, mut sprite, transform, prey, regrowing) in food_sources.iter_mut() {
+        if let Some(regrowing) = regrowing {
+            // Regrow in place once the cooldown has passed, keeping existing trails valid
+            if current_time >= regrowing.ready_at {
+                food.amount = food.max_amount;
+                commands.entity(entity).remove::<Regrowing>();
+            }
+            continue;
+        }
+        
         if food.amount > 0.0 {
             let intensity = (food.amount / food.max_amount).clamp(0.3, 1.0);
             let base_color = color_config.food_source;
@@ -1489,6 +2049,19 @@ pub fn food_visual_system(
                 base_color.to_srgba().alpha
             );
         } else {
+            depleted_events.send(FoodDepleted {
+                source: entity,
+                position: transform.translation.truncate(),
+                time: current_time,
+            });
+            
+            if config.food_regrow_in_place {
+                // Leave an empty husk that refills after the cooldown
+                sprite.color = color_config.food_source.with_alpha(0.25);
+                commands.entity(entity).insert(Regrowing { ready_at: current_time + config.food_regrow_cooldown });
+                continue;
+            }
+            
             // Despawn depleted food and spawn new one
             commands.entity(entity).despawn();
             
@@ -1503,7 +2076,7 @@ pub fn food_visual_system(
                 y *= scale;
             }
             
-            commands.spawn((
+            let mut new_food = commands.spawn((
                 SpriteBundle {
                     sprite: Sprite {
                         color: color_config.food_source,
@@ -1513,9 +2086,308 @@ pub fn food_visual_system(
                     transform: Transform::from_xyz(x, y, 2.0),
                     ..default()
                 },
-                FoodSource { amount: 100.0, max_amount: 100.0 },
+                FoodSource { amount: 100.0, max_amount: 100.0, quality: 1.0 },
             ));
+            
+            // Replacement prey keeps wandering
+            if let Some(prey) = prey {
+                new_food.insert(Prey { heading: rand::random::<f32>() * std::f32::consts::TAU, speed: prey.speed });
+            }
+        }
+    }
+}
+
+/// One unit circle mesh and one material shared by every round rock, so all rocks batch into a single draw
+#[derive(Default)]
+pub struct RockMeshAssets(Option<(Mesh2dHandle, Handle<ColorMaterial>)>);
+
+/// Give newly spawned round rocks a circle mesh child scaled to their radius, and follow rock color edits
+pub fn rock_mesh_system(
+    mut commands: Commands,
+    new_rocks: Query<(Entity, &Rock), Added<RoundRock>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    color_config: Res<ColorConfig>,
+    mut assets: Local<RockMeshAssets>,
+) {
+    let (mesh, material) = assets.0.get_or_insert_with(|| (
+        Mesh2dHandle(meshes.add(Circle::new(1.0).mesh().resolution(64))),
+        materials.add(color_config.rock),
+    )).clone();
+    if color_config.is_changed() {
+        if let Some(rock_material) = materials.get_mut(&material) {
+            rock_material.color = color_config.rock;
+        }
+    }
+    
+    for (entity, rock) in new_rocks.iter() {
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn(MaterialMesh2dBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_scale(Vec3::new(rock.radius, rock.radius, 1.0)),
+                ..default()
+            });
+        });
+    }
+}
+
+pub fn prey_movement_system(
+    mut prey_query: Query<(&mut Transform, &mut Prey), Without<AntCore>>,
+    config: Res<SimConfig>,
+    time: Res<Time>,
+) {
+    let delta_time = time.delta_seconds();
+    
+    for (mut transform, mut prey) in prey_query.iter_mut() {
+        // Random walk with a bounded turn rate
+        prey.heading += (rand::random::<f32>() - 0.5) * 2.0 * config.prey_turn_rate * delta_time;
+        
+        let heading = Vec2::new(prey.heading.cos(), prey.heading.sin());
+        let mut new_pos = transform.translation.truncate() + heading * prey.speed * delta_time;
+        
+        // Stay inside the challenge band: at least 333 units from nest and inside world bounds
+        let distance_from_nest = new_pos.length();
+        if distance_from_nest < 333.0 || new_pos.x.abs() > 470.0 || new_pos.y.abs() > 470.0 {
+            // Turn back toward the band instead of crossing it
+            prey.heading += std::f32::consts::PI;
+            new_pos = if distance_from_nest < 333.0 {
+                new_pos.normalize_or_zero() * 333.0
+            } else {
+                new_pos.clamp(Vec2::splat(-470.0), Vec2::splat(470.0))
+            };
+        }
+        
+        transform.translation.x = new_pos.x;
+        transform.translation.y = new_pos.y;
+    }
+}
+
+/// Move predators along their patrols and kill ants in reach, each death releasing alarm pheromone
+pub fn predator_system(
+    mut commands: Commands,
+    mut predators: Query<(&mut Transform, &mut Predator), Without<AntCore>>,
+    ants: Query<(Entity, &Transform, &AntCore)>,
+    mut pheromone_grid: Option<ResMut<PheromoneGrid>>,
+    mut performance_tracker: ResMut<PerformanceTracker>,
+    mut sim_events: EventWriter<SimEvent>,
+    config: Res<SimConfig>,
+    time: Res<Time>,
+) {
+    let delta_time = time.delta_seconds();
+    let mut killed: Vec<Entity> = Vec::new();
+    
+    for (mut transform, mut predator) in predators.iter_mut() {
+        // Patrol back and forth between the two endpoints
+        let patrol_length = predator.patrol_start.distance(predator.patrol_end).max(1.0);
+        predator.progress += predator.direction * config.predator_speed * delta_time / patrol_length;
+        if predator.progress >= 1.0 {
+            predator.progress = 1.0;
+            predator.direction = -1.0;
+        } else if predator.progress <= 0.0 {
+            predator.progress = 0.0;
+            predator.direction = 1.0;
+        }
+        
+        let predator_pos = predator.patrol_start.lerp(predator.patrol_end, predator.progress);
+        transform.translation.x = predator_pos.x;
+        transform.translation.y = predator_pos.y;
+        
+        for (ant_entity, ant_transform, ant) in ants.iter() {
+            let ant_pos = ant_transform.translation.truncate();
+            if ant_pos.distance(predator_pos) < config.predator_kill_radius && !killed.contains(&ant_entity) {
+                killed.push(ant_entity);
+                sim_events.send(SimEvent { kind: SimEventKind::PredatorKill, position: ant_pos, time: time.elapsed_seconds(), ant: Some(ant.index) });
+                
+                // ALARM: The dying ant releases alarm pheromone that nearby ants steer away from
+                if let Some(ref mut grid) = pheromone_grid {
+                    for dx in -1..=1 {
+                        for dy in -1..=1 {
+                            grid.deposit(ant_pos.x + dx as f32 * 5.0, ant_pos.y + dy as f32 * 5.0,
+                                PheromoneType::Alarm, config.predator_alarm_amount / 9.0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    for entity in &killed {
+        commands.entity(*entity).despawn();
+    }
+    
+    if !killed.is_empty() {
+        performance_tracker.ants_lost += killed.len() as u32;
+        info!("🕷️ Predators killed {} ant(s) at {:.1}s ({} lost so far)",
+            killed.len(), time.elapsed_seconds(), performance_tracker.ants_lost);
+    }
+}
+
+/// Task allocation: the fraction of scouts falls as more of the colony is in contact with trails
+pub fn colony_role_system(
+    mut ants: Query<(&mut AntCore, &AntDiagnostics)>,
+    mut colony: ResMut<ColonyState>,
+    config: Res<SimConfig>,
+    time: Res<Time>,
+) {
+    let total_ants = ants.iter().count();
+    if total_ants == 0 {
+        return;
+    }
+    
+    let ants_on_trails = ants.iter().filter(|(ant, ant_diagnostics)| ant_diagnostics.can_see_trail || ant.carrying_food).count();
+    let trail_fraction = ants_on_trails as f32 / total_ants as f32;
+    
+    // Smooth the signal so roles don't flicker with momentary trail contacts
+    let smoothing = (time.delta_seconds() / config.role_update_interval).min(1.0);
+    colony.trail_signal += (trail_fraction - colony.trail_signal) * smoothing;
+    
+    let trail_strength = (colony.trail_signal / config.trail_signal_saturation).clamp(0.0, 1.0);
+    colony.scout_ratio = config.scout_ratio_max - (config.scout_ratio_max - config.scout_ratio_min) * trail_strength;
+    
+    colony.role_timer -= time.delta_seconds();
+    if colony.role_timer <= 0.0 {
+        colony.role_timer = config.role_update_interval;
+        
+        let mut scout_count = 0;
+        for (mut ant, _) in ants.iter_mut() {
+            // Carriers keep their role until the delivery is done
+            if !ant.carrying_food {
+                ant.role = if rand::random::<f32>() < colony.scout_ratio { AntRole::Scout } else { AntRole::Forager };
+            }
+            if ant.role == AntRole::Scout {
+                scout_count += 1;
+            }
+        }
+        colony.scout_count = scout_count;
+    }
+}
+
+/// Spawn a single ant at the nest ring. `index` fixes its position on the ring and its initial heading.
+pub fn spawn_ant(
+    commands: &mut Commands,
+    index: usize,
+    config: &SimConfig,
+    spawn_time: f32,
+    colony: usize,
+    nest_position: Vec2,
+) -> Entity {
+    let angle = (index as f32) * std::f32::consts::TAU / config.initial_ants as f32;
+    let x = nest_position.x + angle.cos() * 50.0;
+    let y = nest_position.y + angle.sin() * 50.0;
+    
+    // ant_visual_system gives it the shared ant mesh and a material for its color
+    let mut ant_bundle = commands.spawn((
+        SpatialBundle::from_transform(Transform::from_xyz(x, y, 6.0)),
+        AntCore {
+            index,
+            colony,
+            nest_position,
+            role: AntRole::Forager, // Reassigned by colony_role_system
+            carrying_food: false,
+            hunger: 0.0,
+            sensitivity_adapt: 1.0,
+            behavior_state: AntBehaviorState::Exploring,
+            food_collection_timer: 0.0,
+            startup_timer: 1.0, // Minimal startup time - ants should start working quickly
+            sensing_timer: rand::random::<f32>() * 2.0, // Random initial sensing delay
+            momentum_timer: 0.0,
+            has_exit_direction: false,
+            current_direction: angle,
+            facing: angle,
+            separation: Vec2::ZERO,
+            last_position: Vec2::new(x, y),
+            stuck_timer: 0.0,
+            exploration_phase: 0.0,
+            home_sector: angle, // Sector sweeps fan out along the spawn direction
+            low_detail: false,
+            since_detail_update: 0.0,
+        },
+        AntMemory {
+            has_found_food: false, // Track if ant has ever found food
+            last_pheromone_strength: 0.0,
+            distance_from_food: 0.0,
+            distance_from_nest: 0.0,
+            trail_strength: 0.0,
+            last_sensing_result: [0.0; 8],
+            trail_memory: [angle; 5], // Initialize with current direction
+            memory_index: 0,
+            trail_quality: 0.0,
+            hysteresis_threshold: config.detection_threshold,
+            consecutive_good_trail_time: 0.0,
+            food_source: None,
+            pickup_position: Vec2::ZERO,
+            carried_food_quality: 0.0,
+        },
+        AntStats {
+            spawn_time,
+            successful_deliveries: 0,
+            delivery_attempts: 0,
+            food_pickup_time: 0.0,
+            food_carry_start_time: 0.0, // When ant picked up food
+            last_goal_achievement_time: 0.0, // Initialize as never achieved a goal
+            current_goal_start_time: spawn_time,
+        },
+        AntDiagnostics {
+            can_see_trail: false,
+            distance_from_trail: f32::INFINITY,
+            trail_following_time: 0.0,
+            last_trail_contact_time: 0.0,
+            trail_gradient_strength: 0.0,
+            is_swarming: false,
+            nearby_ant_count: 0,
+            time_since_progress: 0.0,
+            exploration_efficiency: 0.0,
+            is_edge_wanderer: false,
+            world_edge_proximity: 0.0,
+            direction_changes: 0,
+        },
+        SensingStep::default(),
+        DesiredHeading::default(),
+        Velocity {
+            x: (rand::random::<f32>() * 2.0 - 1.0) * 1.5,
+            y: (rand::random::<f32>() * 2.0 - 1.0) * 1.5,
+        },
+    ));
+    
+    // Mark the first ant for debugging
+    if index == 0 {
+        ant_bundle.insert(DebugAnt { ant_id: 0 });
+        debug!("🐜 DEBUG ANT #0 spawned at position ({:.1}, {:.1}) with direction {:.2} radians", x, y, angle);
+    }
+    
+    ant_bundle.id()
+}
+
+/// Releases ants from the nest in batches until the population cap is reached
+pub fn ant_spawner_system(
+    mut commands: Commands,
+    mut spawner: ResMut<AntSpawner>,
+    nests: Query<(&Transform, &Nest)>,
+    config: Res<SimConfig>,
+    time: Res<Time>,
+) {
+    // Ants are dealt round-robin to each colony's nest
+    let mut colonies: Vec<(usize, Vec2)> = nests.iter()
+        .map(|(transform, nest)| (nest.colony, transform.translation.truncate()))
+        .collect();
+    colonies.sort_by_key(|(colony, _)| *colony);
+    if colonies.is_empty() {
+        colonies.push((0, Vec2::ZERO));
+    }
+    
+    while spawner.spawned < config.initial_ants && time.elapsed_seconds() >= spawner.next_spawn_time {
+        let batch_end = (spawner.spawned + config.spawn_batch_size.max(1)).min(config.initial_ants);
+        let spawn_time = spawner.next_spawn_time;
+        
+        for index in spawner.spawned..batch_end {
+            let (colony, nest_position) = colonies[index % colonies.len()];
+            spawn_ant(&mut commands, index, &config, spawn_time, colony, nest_position);
         }
+        
+        debug!("🐣 Spawned ants {}-{} of {} at {:.1}s", spawner.spawned, batch_end - 1, config.initial_ants, spawn_time);
+        spawner.spawned = batch_end;
+        spawner.next_spawn_time += config.spawn_interval;
     }
 }
 
@@ -1533,8 +2405,8 @@ pub fn exit_event_listener(
 ) {
     for exit_event in exit_events.read() {
         match exit_event {
-            AppExit::Success => println!("Application exiting successfully"),
-            AppExit::Error(code) => println!("Application exiting with error code: {}", code),
+            AppExit::Success => info!("Application exiting successfully"),
+            AppExit::Error(code) => info!("Application exiting with error code: {}", code),
         }
     }
 }
@@ -1548,290 +2420,385 @@ pub fn window_close_system(
     }
 }
 
+/// Remove every ant, clear all pheromone and restart the spawn schedule.
+/// Shared by the R restart and curriculum stage changes.
+pub fn reset_colony(
+    commands: &mut Commands,
+    ants: impl Iterator<Item = Entity>,
+    pheromone_grid: Option<&mut PheromoneGrid>,
+    spawner: &mut AntSpawner,
+    now: f32,
+) {
+    for entity in ants {
+        commands.entity(entity).despawn();
+    }
+    
+    if let Some(grid) = pheromone_grid {
+        *grid = PheromoneGrid::new(1000, 1000);
+    }
+    
+    // Restart the spawn schedule - ant_spawner_system respawns the colony
+    *spawner = AntSpawner { spawned: 0, next_spawn_time: now };
+}
+
+/// Everything a challenge's layout spawns, cleared before it is rebuilt
+pub type LayoutFilter = Or<(With<FoodSource>, With<Rock>, With<Nest>, With<Predator>, With<WaterTile>)>;
+
+/// The colony, its trails and the challenge layout, cleared on restart and between curriculum stages
+#[derive(SystemParam)]
+pub struct WorldTeardown<'w, 's> {
+    spawner: ResMut<'w, AntSpawner>,
+    pheromone_grid: Option<ResMut<'w, PheromoneGrid>>,
+    ants: Query<'w, 's, Entity, With<AntCore>>,
+    layout: Query<'w, 's, Entity, LayoutFilter>,
+}
+
+impl WorldTeardown<'_, '_> {
+    pub fn clear(&mut self, commands: &mut Commands, now: f32) {
+        reset_colony(commands, self.ants.iter(), self.pheromone_grid.as_deref_mut(), &mut self.spawner, now);
+        for entity in self.layout.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Start the challenge over (R): a fresh colony on the challenge's own layout
 pub fn restart_system(
     input: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
-    ants: Query<Entity, With<AntState>>,
-    food_sources: Query<Entity, With<FoodSource>>,
-    nests: Query<Entity, With<Nest>>,
-    pheromone_vis: Query<Entity, With<PheromoneVisualization>>,
-    config: Res<SimConfig>,
-    mut pheromone_grid: Option<ResMut<PheromoneGrid>>,
+    mut teardown: WorldTeardown,
+    layout: ChallengeLayout,
+    time: Res<Time>,
 ) {
     if input.just_pressed(KeyCode::KeyR) {
-        // Clear existing entities
-        for entity in ants.iter() {
-            commands.entity(entity).despawn();
+        teardown.clear(&mut commands, time.elapsed_seconds());
+        layout.spawn(&mut commands);
+    }
+}
+
+/// Pan speed at zoom 1, in world units per second; scaled by zoom so it's constant on screen
+const CAMERA_PAN_SPEED: f32 = 200.0;
+/// Exponential smoothing rates (per second) for panning and wheel zoom
+const CAMERA_PAN_SMOOTHING: f32 = 12.0;
+const CAMERA_ZOOM_SMOOTHING: f32 = 15.0;
+
+pub fn camera_control_system(
+    mut camera_query: Query<&mut Transform, With<Camera>>,
+    ant_query: Query<&Transform, (With<AntCore>, Without<Camera>)>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut debug_info: ResMut<DebugInfo>,
+    input: Res<ButtonInput<KeyCode>>,
+    _mouse_input: Res<ButtonInput<MouseButton>>,
+    mut scroll_events: EventReader<bevy::input::mouse::MouseWheel>,
+    _cursor_moved_events: EventReader<bevy::window::CursorMoved>,
+    real_time: Res<Time<Real>>,
+    mut motion: Local<CameraMotion>,
+) {
+    if input.just_pressed(KeyCode::KeyF) {
+        debug_info.follow_selected = !debug_info.follow_selected;
+        info!("🎥 Follow-cam {}", if debug_info.follow_selected { "on" } else { "off" });
+    }
+    
+    if let Ok(mut camera_transform) = camera_query.get_single_mut() {
+        // Real time, so the camera still moves while the simulation is paused
+        let dt = real_time.delta_seconds();
+        let mut direction = Vec2::ZERO;
+        
+        if input.pressed(KeyCode::KeyW) {
+            direction.y += 1.0;
         }
-        for entity in food_sources.iter() {
-            commands.entity(entity).despawn();
+        if input.pressed(KeyCode::KeyS) {
+            direction.y -= 1.0;
         }
-        for entity in nests.iter() {
-            commands.entity(entity).despawn();
+        if input.pressed(KeyCode::KeyA) {
+            direction.x -= 1.0;
         }
-        for entity in pheromone_vis.iter() {
-            commands.entity(entity).despawn();
+        if input.pressed(KeyCode::KeyD) {
+            direction.x += 1.0;
         }
         
-        // Reset pheromone grid
-        if let Some(ref mut grid) = pheromone_grid {
-            **grid = PheromoneGrid::new(1000, 1000);
+        // Manual panning takes the camera back
+        if direction != Vec2::ZERO {
+            debug_info.follow_selected = false;
         }
+        let target_velocity = direction.normalize_or_zero() * CAMERA_PAN_SPEED * camera_transform.scale.x;
+        motion.velocity = motion.velocity.lerp(target_velocity, 1.0 - (-CAMERA_PAN_SMOOTHING * dt).exp());
+        camera_transform.translation += (motion.velocity * dt).extend(0.0);
         
-        // Respawn nest at center
-        commands.spawn((
-            SpriteBundle {
-                sprite: Sprite {
-                    color: Color::srgb(1.0, 1.0, 0.0),
-                    custom_size: Some(Vec2::new(80.0, 80.0)),
-                    ..default()
-                },
-                transform: Transform::from_xyz(0.0, 0.0, 5.0),
-                ..default()
-            },
-            Nest { capacity: 10000.0 },
-        ));
-        
-        // Respawn ants around nest
-        for i in 0..config.initial_ants {
-            let angle = (i as f32) * std::f32::consts::TAU / config.initial_ants as f32;
-            let x = angle.cos() * 50.0;
-            let y = angle.sin() * 50.0;
-            
-            commands.spawn((
-                SpriteBundle {
-                    sprite: Sprite {
-                        color: Color::srgb(1.0, 0.0, 0.0),
-                        custom_size: Some(Vec2::new(12.0, 12.0)),
-                        ..default()
-                    },
-                    transform: Transform::from_xyz(x, y, 6.0),
-                    ..default()
-                },
-                AntState {
-                    carrying_food: false,
-                    hunger: 0.0,
-                    sensitivity_adapt: 1.0,
-                    food_collection_timer: 0.0,
-                    last_pheromone_strength: 0.0,
-                    distance_from_food: 0.0,
-                    distance_from_nest: 0.0,
-                    has_exit_direction: false,
-                    behavior_state: AntBehaviorState::Exploring,
-                    sensing_timer: rand::random::<f32>() * 2.0,
-                    current_direction: rand::random::<f32>() * std::f32::consts::TAU,
-                    trail_strength: 0.0,
-                    momentum_timer: 0.0,
-                    last_position: Vec2::new(x, y),
-                    stuck_timer: 0.0,
-                    direction_changes: 0,
-                    last_sensing_result: [0.0; 8],
-                    trail_memory: [rand::random::<f32>() * std::f32::consts::TAU; 5],
-                    memory_index: 0,
-                    trail_quality: 0.0,
-                    hysteresis_threshold: 0.0005,
-                    consecutive_good_trail_time: 0.0,
-                    food_pickup_time: 0.0,
-                    delivery_attempts: 0,
-                    successful_deliveries: 0,
-                    startup_timer: 2.0 + (i as f32) * 0.1, // Staggered startup: 2.0-5.5s range
-                    has_found_food: false,
-                    food_carry_start_time: 0.0,
-                    last_goal_achievement_time: 0.0,
-                    current_goal_start_time: 0.0,
-                    
-                    // Initialize diagnostic fields
-                    can_see_trail: false,
-                    distance_from_trail: f32::INFINITY,
-                    trail_following_time: 0.0,
-                    last_trail_contact_time: 0.0,
-                    is_swarming: false,
-                    nearby_ant_count: 0,
-                    time_since_progress: 0.0,
-                    exploration_efficiency: 0.0,
-                    is_edge_wanderer: false,
-                    world_edge_proximity: 0.0,
-                    trail_gradient_strength: 0.0,
-                },
-                Velocity {
-                    x: (rand::random::<f32>() * 2.0 - 1.0) * 1.5,
-                    y: (rand::random::<f32>() * 2.0 - 1.0) * 1.5,
-                },
-            ));
+        if debug_info.follow_selected {
+            if let Some(ant_transform) = debug_info.selected_entity.and_then(|entity| ant_query.get(entity).ok()) {
+                // Exponential smoothing on real time, so following still works while paused
+                let blend = 1.0 - (-8.0 * dt).exp();
+                let target = ant_transform.translation.truncate().extend(camera_transform.translation.z);
+                camera_transform.translation = camera_transform.translation.lerp(target, blend);
+            }
         }
         
-        // Respawn food sources
-        for i in 0..config.food_sources {
-            let (x, y) = if i < config.food_sources / 2 {
-                let angle = rand::random::<f32>() * std::f32::consts::TAU;
-                let distance = 80.0 + rand::random::<f32>() * 120.0;
-                (angle.cos() * distance, angle.sin() * distance)
-            } else {
-                let range = (config.world_size as f32) * 0.3;
-                ((rand::random::<f32>() - 0.5) * range, (rand::random::<f32>() - 0.5) * range)
-            };
-            
-            commands.spawn((
-                SpriteBundle {
-                    sprite: Sprite {
-                        color: Color::srgb(0.0, 1.0, 0.0),
-                        custom_size: Some(Vec2::new(30.0, 30.0)),
-                        ..default()
-                    },
-                    transform: Transform::from_xyz(x, y, 2.0),
-                    ..default()
-                },
-                FoodSource { amount: 100.0, max_amount: 100.0 },
-            ));
+        for event in scroll_events.read() {
+            let zoom_factor = if event.y > 0.0 { 0.9 } else { 1.1 };
+            let current = motion.zoom_target.unwrap_or(camera_transform.scale.x);
+            motion.zoom_target = Some((current * zoom_factor).clamp(0.1, 5.0));
         }
         
-        // Recreate pheromone visualization
-        let grid_size = 200;
-        let cell_size = 5.0;
-        
-        for x in 0..grid_size {
-            for y in 0..grid_size {
-                let world_x = (x as f32 - grid_size as f32 / 2.0) * cell_size;
-                let world_y = (y as f32 - grid_size as f32 / 2.0) * cell_size;
-                
-                commands.spawn((
-                    SpriteBundle {
-                        sprite: Sprite {
-                            color: Color::srgba(0.0, 0.0, 0.0, 0.0),
-                            custom_size: Some(Vec2::new(cell_size, cell_size)),
-                            ..default()
-                        },
-                        transform: Transform::from_xyz(world_x, world_y, -10.0),
-                        ..default()
-                    },
-                    PheromoneVisualization {
-                        grid_x: x,
-                        grid_y: y,
-                    },
-                ));
+        if let Some(zoom_target) = motion.zoom_target {
+            let old_scale = camera_transform.scale.x;
+            let mut new_scale = old_scale + (zoom_target - old_scale) * (1.0 - (-CAMERA_ZOOM_SMOOTHING * dt).exp());
+            if (new_scale - zoom_target).abs() < zoom_target * 0.001 {
+                new_scale = zoom_target;
+                motion.zoom_target = None;
+            }
+            
+            // Keep the world point under the cursor fixed on screen; the follow-cam zooms around the centre
+            if !debug_info.follow_selected {
+                if let Some((cursor, window_size)) = window_query.get_single().ok()
+                    .and_then(|window| window.cursor_position().map(|cursor| (cursor, window.size())))
+                {
+                    let cursor_offset = Vec2::new(cursor.x - window_size.x / 2.0, window_size.y / 2.0 - cursor.y) * old_scale;
+                    camera_transform.translation += (cursor_offset * (1.0 - new_scale / old_scale)).extend(0.0);
+                }
             }
+            camera_transform.scale = Vec3::splat(new_scale);
         }
     }
 }
 
-pub fn camera_control_system(
+const DIGIT_KEYS: [KeyCode; 10] = [
+    KeyCode::Digit0, KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4,
+    KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9,
+];
+
+/// Ctrl+0-9 saves the view, Shift+0-9 recalls it, N cycles nests and Tab cycles food sources
+pub fn camera_bookmark_system(
     mut camera_query: Query<&mut Transform, With<Camera>>,
+    nest_query: Query<(Entity, &Transform), (With<Nest>, Without<Camera>)>,
+    food_query: Query<(Entity, &Transform, &FoodSource), Without<Camera>>,
+    mut debug_info: ResMut<DebugInfo>,
+    editor: Res<EditorState>,
     input: Res<ButtonInput<KeyCode>>,
-    _mouse_input: Res<ButtonInput<MouseButton>>,
-    mut scroll_events: EventReader<bevy::input::mouse::MouseWheel>,
-    _cursor_moved_events: EventReader<bevy::window::CursorMoved>,
+    mut bookmarks: Local<CameraBookmarks>,
 ) {
-    if let Ok(mut camera_transform) = camera_query.get_single_mut() {
-        let mut camera_move = Vec3::ZERO;
-        let camera_speed = 200.0;
-        
-        if input.pressed(KeyCode::KeyW) {
-            camera_move.y += camera_speed;
-        }
-        if input.pressed(KeyCode::KeyS) {
-            camera_move.y -= camera_speed;
+    let Ok(mut camera_transform) = camera_query.get_single_mut() else { return };
+    let ctrl = input.pressed(KeyCode::ControlLeft) || input.pressed(KeyCode::ControlRight);
+    let shift = input.pressed(KeyCode::ShiftLeft) || input.pressed(KeyCode::ShiftRight);
+    
+    for (slot, key) in DIGIT_KEYS.iter().enumerate() {
+        if !input.just_pressed(*key) {
+            continue;
         }
-        if input.pressed(KeyCode::KeyA) {
-            camera_move.x -= camera_speed;
+        if ctrl {
+            bookmarks.slots[slot] = Some(*camera_transform);
+            info!("📌 Saved camera bookmark {}", slot);
+        } else if shift {
+            match bookmarks.slots[slot] {
+                Some(saved) => {
+                    *camera_transform = saved;
+                    debug_info.follow_selected = false;
+                }
+                None => info!("📌 No camera bookmark {} (Ctrl+{} saves one)", slot, slot),
+            }
         }
-        if input.pressed(KeyCode::KeyD) {
-            camera_move.x += camera_speed;
+    }
+    
+    // Sorted so repeated presses visit every target in a stable order
+    let mut target = None;
+    if input.just_pressed(KeyCode::KeyN) {
+        let mut nests: Vec<(Entity, Vec2)> = nest_query.iter()
+            .map(|(entity, transform)| (entity, transform.translation.truncate()))
+            .collect();
+        nests.sort_by_key(|(entity, _)| *entity);
+        if !nests.is_empty() {
+            target = Some(nests[bookmarks.next_nest % nests.len()].1);
+            bookmarks.next_nest = (bookmarks.next_nest + 1) % nests.len();
         }
-        
-        camera_transform.translation += camera_move * 0.016;
-        
-        for event in scroll_events.read() {
-            let zoom_factor = if event.y > 0.0 { 0.9 } else { 1.1 };
-            camera_transform.scale *= zoom_factor;
-            camera_transform.scale = camera_transform.scale.clamp(Vec3::splat(0.1), Vec3::splat(5.0));
+    }
+    // Tab switches tools while the editor is open
+    if input.just_pressed(KeyCode::Tab) && !editor.active {
+        let mut foods: Vec<(Entity, Vec2)> = food_query.iter()
+            .filter(|(_, _, food)| food.amount > 0.0)
+            .map(|(entity, transform, _)| (entity, transform.translation.truncate()))
+            .collect();
+        foods.sort_by_key(|(entity, _)| *entity);
+        if !foods.is_empty() {
+            target = Some(foods[bookmarks.next_food % foods.len()].1);
+            bookmarks.next_food = (bookmarks.next_food + 1) % foods.len();
         }
     }
+    
+    if let Some(target) = target {
+        camera_transform.translation = target.extend(camera_transform.translation.z);
+        debug_info.follow_selected = false;
+    }
 }
 
-pub fn setup_pheromone_visualization(
-    mut commands: Commands,
-    _config: Res<SimConfig>,
-) {
-    let grid_size = 200;
-    let cell_size = 5.0;
+/// Cells across each side of the pheromone texture, and their size in world units
+const PHEROMONE_VIEW_CELLS: u32 = 200;
+const PHEROMONE_VIEW_CELL_SIZE: f32 = 5.0;
+
+/// One texture covering the world, a pixel per 5x5-unit cell, so startup and restarts don't wait on
+/// tens of thousands of sprites; update_pheromone_visualization paints it from the grid
+pub fn setup_pheromone_visualization(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let mut image = Image::new_fill(
+        Extent3d { width: PHEROMONE_VIEW_CELLS, height: PHEROMONE_VIEW_CELLS, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD, // Kept on the CPU side to repaint it
+    );
+    image.sampler = ImageSampler::nearest(); // Hard-edged cells, as the sprites were
+    let image = images.add(image);
     
-    for x in 0..grid_size {
-        for y in 0..grid_size {
-            let world_x = (x as f32 - grid_size as f32 / 2.0) * cell_size;
-            let world_y = (y as f32 - grid_size as f32 / 2.0) * cell_size;
-            
-            commands.spawn((
-                SpriteBundle {
-                    sprite: Sprite {
-                        color: Color::srgba(0.0, 0.0, 0.0, 0.0),
-                        custom_size: Some(Vec2::new(cell_size, cell_size)),
-                        ..default()
-                    },
-                    transform: Transform::from_xyz(world_x, world_y, -10.0),
-                    ..default()
-                },
-                PheromoneVisualization {
-                    grid_x: x,
-                    grid_y: y,
-                },
-            ));
-        }
+    // Cell x samples the grid at world (x - 100) * 5, so the texture is offset by half a cell
+    let side = PHEROMONE_VIEW_CELLS as f32 * PHEROMONE_VIEW_CELL_SIZE;
+    commands.spawn((
+        SpriteBundle {
+            texture: image.clone(),
+            sprite: Sprite { custom_size: Some(Vec2::splat(side)), ..default() },
+            transform: Transform::from_xyz(-PHEROMONE_VIEW_CELL_SIZE / 2.0, -PHEROMONE_VIEW_CELL_SIZE / 2.0, -9.0),
+            ..default()
+        },
+        PheromoneVisualization { image },
+    ));
+}
+
+/// 1/2/3 show or hide the food, nest and alarm pheromone layers, 4 the ghost snapshot
+pub fn pheromone_layer_toggle_system(input: Res<ButtonInput<KeyCode>>, mut view: ResMut<ViewSettings>) {
+    // Ctrl/Shift + digit is a camera bookmark
+    if input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight, KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        return;
+    }
+    let toggled = if input.just_pressed(KeyCode::Digit1) {
+        view.food_layer = !view.food_layer;
+        true
+    } else if input.just_pressed(KeyCode::Digit2) {
+        view.nest_layer = !view.nest_layer;
+        true
+    } else if input.just_pressed(KeyCode::Digit3) {
+        view.alarm_layer = !view.alarm_layer;
+        true
+    } else if input.just_pressed(KeyCode::Digit4) {
+        view.ghost_layer = !view.ghost_layer;
+        true
+    } else {
+        false
+    };
+
+    if toggled {
+        let state = |visible: bool| if visible { "on" } else { "off" };
+        info!("🎨 Pheromone layers - food: {}, nest: {}, alarm: {}, ghost: {}", state(view.food_layer), state(view.nest_layer), state(view.alarm_layer), state(view.ghost_layer));
+    }
+}
+
+/// Single-key overlay toggles not owned by a more specific system, plus the F1-F3 presets
+pub fn view_settings_system(input: Res<ButtonInput<KeyCode>>, mut view: ResMut<ViewSettings>) {
+    let preset = if input.just_pressed(KeyCode::F1) {
+        Some(("clean", ViewSettings::clean()))
+    } else if input.just_pressed(KeyCode::F2) {
+        Some(("analysis", ViewSettings::analysis()))
+    } else if input.just_pressed(KeyCode::F3) {
+        Some(("debug", ViewSettings::debug()))
+    } else {
+        None
+    };
+    if let Some((name, settings)) = preset {
+        *view = settings;
+        info!("👁️ View preset: {}", name);
+    }
+
+    if input.just_pressed(KeyCode::KeyG) {
+        view.selection_gizmos = !view.selection_gizmos;
+    }
+    if input.just_pressed(KeyCode::KeyM) {
+        view.minimap = !view.minimap;
+    }
+    if input.just_pressed(KeyCode::KeyK) {
+        view.trend_chart = !view.trend_chart;
     }
 }
 
+/// Pheromone texture pixels grouped by the grid tile they sample, as (byte offset, grid index) pairs,
+/// built on the first run
+#[derive(Default)]
+pub struct PheromoneTexelTiles(Vec<Vec<(usize, usize)>>);
+
+/// Repaint the pheromone texture in tiles the grid marked dirty, or all of it when the view or colors changed
 pub fn update_pheromone_visualization(
-    mut pheromone_sprites: Query<(&mut Sprite, &mut Transform), With<PheromoneVisualization>>,
-    pheromone_grid: Option<Res<PheromoneGrid>>,
+    visualization: Query<&PheromoneVisualization>,
+    mut images: ResMut<Assets<Image>>,
+    pheromone_grid: Option<ResMut<PheromoneGrid>>,
+    view: Res<ViewSettings>,
     color_config: Res<ColorConfig>,
+    mut texel_tiles: Local<PheromoneTexelTiles>,
+    mut diagnostics: Diagnostics,
 ) {
-    if let Some(grid) = pheromone_grid {
-        for (mut sprite, mut transform) in pheromone_sprites.iter_mut() {
-            let world_x = transform.translation.x;
-            let world_y = transform.translation.y;
-            
-            if let Some(idx) = grid.world_to_grid(world_x, world_y) {
-                let food_strength = grid.food_trail[idx];
-                let nest_strength = grid.nest_trail[idx];
-                let max_strength = food_strength.max(nest_strength);
-                
-                if max_strength > 0.01 {
-                    if food_strength > nest_strength {
-                        // Logarithmic scaling: green = log(food_pheromone)^1.3 * 20, clamped to [0,255]
-                        let log_intensity = food_strength.ln().powf(1.3) * 20.0;
-                        let green_value = (log_intensity / 255.0).clamp(0.0, 1.0);
-                        let base_color = color_config.food_pheromone;
-                        sprite.color = Color::srgba(
-                            base_color.to_srgba().red,
-                            green_value, // Use calculated logarithmic green intensity
-                            base_color.to_srgba().blue,
-                            green_value // Use same value for alpha to show intensity
-                        );
-                        transform.translation.z = -9.0;
-                    } else {
-                        // Same logarithmic scaling for nest pheromone (blue)
-                        let log_intensity = nest_strength.ln().powf(1.3) * 20.0;
-                        let blue_value = (log_intensity / 255.0).clamp(0.0, 1.0);
-                        let base_color = color_config.nest_pheromone;
-                        sprite.color = Color::srgba(
-                            base_color.to_srgba().red,
-                            base_color.to_srgba().green,
-                            blue_value, // Use calculated logarithmic blue intensity
-                            blue_value // Use same value for alpha to show intensity
-                        );
-                        transform.translation.z = -10.0;
+    let started = Instant::now();
+    let _span = trace_span!("update_pheromone_visualization").entered();
+    if let Some(mut pheromone_grid) = pheromone_grid {
+        // Clearing the dirty tiles isn't a change anyone else needs to react to
+        let grid = pheromone_grid.bypass_change_detection();
+        if texel_tiles.0.is_empty() {
+            texel_tiles.0 = vec![Vec::new(); grid.tile_count()];
+            let cells = PHEROMONE_VIEW_CELLS as usize;
+            for y in 0..cells {
+                for x in 0..cells {
+                    let world_x = (x as f32 - cells as f32 / 2.0) * PHEROMONE_VIEW_CELL_SIZE;
+                    let world_y = (y as f32 - cells as f32 / 2.0) * PHEROMONE_VIEW_CELL_SIZE;
+                    if let Some(idx) = grid.world_to_grid(world_x, world_y) {
+                        // Texture rows run top to bottom
+                        let offset = ((cells - 1 - y) * cells + x) * 4;
+                        texel_tiles.0[grid.tile_of(idx)].push((offset, idx));
                     }
-                } else {
-                    sprite.color = Color::srgba(0.0, 0.0, 0.0, 0.0);
-                    transform.translation.z = -10.0;
                 }
-            } else {
-                sprite.color = Color::srgba(0.0, 0.0, 0.0, 0.0);
             }
         }
+        
+        let redraw_all = view.is_changed() || color_config.is_changed();
+        let tiles: Vec<usize> = if redraw_all { (0..grid.tile_count()).collect() } else { grid.dirty_tiles().collect() };
+        let image = visualization.get_single().ok().and_then(|visualization| images.get_mut(&visualization.image));
+        if let (Some(image), false) = (image, tiles.is_empty()) {
+            for tile in tiles {
+                for &(offset, idx) in &texel_tiles.0[tile] {
+                    image.data[offset..offset + 4].copy_from_slice(&pheromone_cell_rgba(grid, idx, &view, &color_config));
+                }
+            }
+        }
+        grid.clear_dirty_tiles();
     }
+    record_system_time(&mut diagnostics, &PHEROMONE_VISUALIZATION_TIME, started);
+}
+
+/// Texture color for grid cell `idx`
+fn pheromone_cell_rgba(grid: &PheromoneGrid, idx: usize, view: &ViewSettings, color_config: &ColorConfig) -> [u8; 4] {
+    // Logarithmic scaling: log(pheromone)^1.3 * 20, clamped to [0,255]; hidden layers count as empty
+    let food_value = if view.food_layer { pheromone_intensity(grid.food_trail[idx]) } else { 0.0 };
+    let nest_value = if view.nest_layer { pheromone_intensity(grid.nest_trail[idx]) } else { 0.0 };
+    let alarm_value = if view.alarm_layer { pheromone_intensity(grid.alarm[idx]) } else { 0.0 };
+    
+    // The strongest visible channel wins the cell
+    let color = if food_value > 0.0 && food_value > nest_value && food_value >= alarm_value {
+        let base_color = color_config.food_pheromone.to_srgba();
+        Color::srgba(
+            base_color.red,
+            food_value, // Logarithmic green intensity
+            base_color.blue,
+            food_value // Use same value for alpha to show intensity
+        )
+    } else if nest_value > 0.0 && nest_value >= alarm_value {
+        let base_color = color_config.nest_pheromone.to_srgba();
+        Color::srgba(
+            base_color.red,
+            base_color.green,
+            nest_value, // Logarithmic blue intensity
+            nest_value // Use same value for alpha to show intensity
+        )
+    } else if alarm_value > 0.0 {
+        color_config.alarm_pheromone.with_alpha(alarm_value)
+    } else {
+        return [0, 0, 0, 0];
+    };
+    color.to_srgba().to_u8_array()
 }
 
-pub fn setup_debug_ui(mut commands: Commands, color_config: Res<ColorConfig>) {
+pub fn setup_debug_ui(mut commands: Commands, color_config: Res<ColorConfig>, challenge: Res<ActiveChallenge>) {
     commands.spawn((
         TextBundle::from_section(
             "Pheromone Info",
@@ -1884,6 +2851,99 @@ pub fn setup_debug_ui(mut commands: Commands, color_config: Res<ColorConfig>) {
         }),
         PerformanceText,
     ));
+
+    // Objective HUD: challenge title, score, progress bars and controls, stacked in one panel
+    commands.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            max_width: Val::Px(520.0),
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Val::Px(8.0)),
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        ..default()
+    }).with_children(|panel| {
+        panel.spawn((
+            TextBundle::from_section(
+                format!("Challenge {}: {}\n{}", challenge.0.number(), challenge.0.name(), challenge.0.objective()),
+                TextStyle {
+                    font_size: 20.0,
+                    color: color_config.text,
+                    ..default()
+                },
+            ),
+            ObjectiveTitleText,
+        ));
+        panel.spawn((
+            TextBundle::from_section(
+                "Score",
+                TextStyle {
+                    font_size: 32.0,
+                    color: Color::srgb(1.0, 0.85, 0.0),
+                    ..default()
+                },
+            ),
+            ScoreText,
+        ));
+        panel.spawn((
+            TextBundle::from_section(
+                "Progress",
+                TextStyle {
+                    font_size: 18.0,
+                    color: color_config.text,
+                    ..default()
+                },
+            ),
+            ObjectiveText,
+        ));
+        panel.spawn(TextBundle::from_section(
+            "WASD: Move  Wheel: Zoom  Space: Pause  .: Step  +/-: Speed  Ctrl+/-: UI scale  1/2/3: Layers  4: Ghost  Y: Snapshot  F1/F2/F3: Clean/Analysis/Debug view  G/M/K/X/P: Gizmos/Minimap/Chart/Grid/Timings  Ctrl/Shift+0-9: Save/recall view  N: Nest  Tab: Food  F: Follow  Q: Possess (arrows drive)  Click/Drag: Select  Ctrl+Click: Food  Alt+Click: Marker  R: Restart  E: Editor  I: Inspector  V: Swarm  T: Labels  L: Legend  H: Stats  J: Events  U: Replay  B: Colony  `: Log level  ESC: Exit",
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.6, 0.6, 0.6),
+                ..default()
+            },
+        ));
+    });
+
+    // Selected ant diagnostics, bottom-left above the pheromone readout
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(110.0),
+                left: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                display: Display::None,
+                ..default()
+            },
+            background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            ..default()
+        },
+        SelectedAntPanel,
+    )).with_children(|panel| {
+        panel.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 13.0,
+                    color: color_config.text,
+                    ..default()
+                },
+            ),
+            SelectedAntText,
+        ));
+    });
+}
+
+/// Text progress bar like `[######----]`
+fn progress_bar(fraction: f32, width: usize) -> String {
+    let filled = (fraction.clamp(0.0, 1.0) * width as f32).round() as usize;
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
 }
 
 pub fn cursor_tracking_system(
@@ -1900,12 +2960,49 @@ pub fn cursor_tracking_system(
     }
 }
 
+/// Samples kept per channel for the hovered cell's sparkline
+const CELL_HISTORY_LEN: usize = 32;
+const CELL_HISTORY_INTERVAL: f32 = 0.25;
+
+/// Recent food/nest/alarm readings of the cell under the cursor; restarts when the cursor moves to another cell
+#[derive(Default)]
+pub struct HoveredCellHistory {
+    cell: Option<usize>,
+    last_sample: f32,
+    food: VecDeque<f32>,
+    nest: VecDeque<f32>,
+    alarm: VecDeque<f32>,
+}
+
+impl HoveredCellHistory {
+    fn push(&mut self, food: f32, nest: f32, alarm: f32) {
+        for (samples, value) in [(&mut self.food, food), (&mut self.nest, nest), (&mut self.alarm, alarm)] {
+            samples.push_back(value);
+            if samples.len() > CELL_HISTORY_LEN {
+                samples.pop_front();
+            }
+        }
+    }
+}
+
+/// Block-character sparkline scaled to the largest sample
+fn sparkline(samples: &VecDeque<f32>) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = samples.iter().cloned().fold(0.0, f32::max);
+    samples.iter()
+        .map(|value| if max > 0.0 { BARS[((value / max) * 7.0).round() as usize] } else { BARS[0] })
+        .collect()
+}
+
 pub fn hover_detection_system(
     mut debug_info: ResMut<DebugInfo>,
+    mut cell_history: Local<HoveredCellHistory>,
     pheromone_grid: Option<Res<PheromoneGrid>>,
-    ant_query: Query<(Entity, &Transform, &AntState, &Velocity), With<AntState>>,
+    ant_query: Query<(Entity, &Transform, &AntCore, &Velocity), With<AntCore>>,
     nest_query: Query<(Entity, &Transform, &Nest), With<Nest>>,
     food_query: Query<(Entity, &Transform, &FoodSource), With<FoodSource>>,
+    rock_query: Query<(Entity, &Transform, &Rock)>,
+    time: Res<Time>,
 ) {
     let cursor_pos = debug_info.cursor_world_pos;
     
@@ -1915,9 +3012,21 @@ pub fn hover_detection_system(
             let nest_strength = grid.nest_trail[idx];
             let alarm_strength = grid.alarm[idx];
             
+            let now = time.elapsed_seconds();
+            if cell_history.cell != Some(idx) {
+                *cell_history = HoveredCellHistory { cell: Some(idx), last_sample: now, ..default() };
+                cell_history.push(food_strength, nest_strength, alarm_strength);
+            } else if now - cell_history.last_sample >= CELL_HISTORY_INTERVAL {
+                cell_history.last_sample = now;
+                cell_history.push(food_strength, nest_strength, alarm_strength);
+            }
+            
             debug_info.pheromone_info = format!(
-                "Pos: ({:.1}, {:.1})\nFood: {:.3}\nNest: {:.3}\nAlarm: {:.3}",
-                cursor_pos.x, cursor_pos.y, food_strength, nest_strength, alarm_strength
+                "Pos: ({:.1}, {:.1})\nFood: {:.3} {}\nNest: {:.3} {}\nAlarm: {:.3} {}",
+                cursor_pos.x, cursor_pos.y,
+                food_strength, sparkline(&cell_history.food),
+                nest_strength, sparkline(&cell_history.nest),
+                alarm_strength, sparkline(&cell_history.alarm)
             );
         }
     }
@@ -1978,11 +3087,29 @@ pub fn hover_detection_system(
             }
         }
     }
+    
+    if debug_info.hovered_entity.is_none() {
+        for (entity, transform, rock) in rock_query.iter() {
+            let distance = cursor_pos.distance(transform.translation.truncate());
+            if distance < rock.radius {
+                debug_info.hovered_entity = Some(entity);
+                debug_info.entity_info = format!(
+                    "=== ROCK ===\nEntity: {:?}\nPos: ({:.1}, {:.1})\nRadius: {:.1}\nBlocked Moves: {}",
+                    entity,
+                    transform.translation.x, transform.translation.y,
+                    rock.radius,
+                    rock.blocked_count
+                );
+                break;
+            }
+        }
+    }
 }
 
 pub fn update_debug_ui(
     debug_info: Res<DebugInfo>,
     performance_tracker: Res<PerformanceTracker>,
+    colony: Res<ColonyState>,
     mut pheromone_text_query: Query<&mut Text, (With<PheromoneDebugText>, Without<EntityDebugText>, Without<PerformanceText>)>,
     mut entity_text_query: Query<&mut Text, (With<EntityDebugText>, Without<PheromoneDebugText>, Without<PerformanceText>)>,
     mut performance_text_query: Query<&mut Text, (With<PerformanceText>, Without<PheromoneDebugText>, Without<EntityDebugText>)>,
@@ -1997,7 +3124,7 @@ pub fn update_debug_ui(
     
     if let Ok(mut text) = performance_text_query.get_single_mut() {
         text.sections[0].value = format!(
-            "🎯 PERFORMANCE METRICS 🎯\n\n⏰ Avg Time Since Goal: {:.1}s\n\n✅ Successful Deliveries: {}\n❌ Failed Attempts: {}\n📦 Total Food Collected: {:.1}\n⏱️ Avg Delivery Time: {:.1}s\n🏠 Avg Return Time: {:.1}s\n\n🚫 Stuck Ants: {}\n🔄 Oscillating Ants: {}\n🔍 Lost Ants: {}\n🍯 Lost Food Carriers: {},",
+            "🎯 PERFORMANCE METRICS 🎯\n\n⏰ Avg Time Since Goal: {:.1}s\n\n✅ Successful Deliveries: {}\n❌ Failed Attempts: {}\n📦 Total Food Collected: {:.1}\n⏱️ Avg Delivery Time: {:.1}s\n🏠 Avg Return Time: {:.1}s\n\n🚫 Stuck Ants: {}\n🔄 Oscillating Ants: {}\n🔍 Lost Ants: {}\n🍯 Lost Food Carriers: {}\n\n🧭 Scouts: {} (target {:.0}%, trail signal {:.2})",
             performance_tracker.average_time_since_goal,
             performance_tracker.successful_deliveries,
             performance_tracker.failed_attempts,
@@ -2008,65 +3135,262 @@ pub fn update_debug_ui(
             performance_tracker.oscillating_ants_count,
             performance_tracker.lost_ants_count,
             performance_tracker.lost_food_carriers_count,
+            colony.scout_count,
+            colony.scout_ratio * 100.0,
+            colony.trail_signal,
+        );
+    }
+}
+
+pub fn update_objective_hud(
+    challenge: Res<ActiveChallenge>,
+    challenge_score: Res<ChallengeScore>,
+    performance_tracker: Res<PerformanceTracker>,
+    curriculum: Option<Res<Curriculum>>,
+    leaderboard: Option<Res<Leaderboard>>,
+    time: Res<Time>,
+    mut title_text_query: Query<&mut Text, (With<ObjectiveTitleText>, Without<ScoreText>, Without<ObjectiveText>)>,
+    mut score_text_query: Query<&mut Text, (With<ScoreText>, Without<ObjectiveText>, Without<ObjectiveTitleText>)>,
+    mut objective_text_query: Query<&mut Text, (With<ObjectiveText>, Without<ScoreText>, Without<ObjectiveTitleText>)>,
+) {
+    // Curriculum stages swap the challenge mid-run
+    if challenge.is_changed() {
+        if let Ok(mut text) = title_text_query.get_single_mut() {
+            let stage = match curriculum {
+                Some(ref curriculum) => format!("Stage {}/{} (total {:.1}) - ",
+                    curriculum.current + 1, curriculum.stages.len(), curriculum.cumulative_score()),
+                None => String::new(),
+            };
+            text.sections[0].value = format!("{}Challenge {}: {}\n{}",
+                stage, challenge.0.number(), challenge.0.name(), challenge.0.objective());
+        }
+    }
+    
+    if let Ok(mut text) = score_text_query.get_single_mut() {
+        text.sections[0].value = format!(
+            "🏆 Score: {:.1} / {:.0}  {}",
+            challenge_score.score,
+            challenge_score.pass_score,
+            challenge_score.verdict.label(),
         );
+        text.sections[0].style.color = match challenge_score.verdict {
+            Verdict::Running => Color::srgb(1.0, 0.85, 0.0),
+            Verdict::Success => Color::srgb(0.0, 1.0, 0.0),
+            Verdict::Failure => Color::srgb(1.0, 0.2, 0.2),
+        };
+    }
+    
+    if let Ok(mut text) = objective_text_query.get_single_mut() {
+        let mut lines: Vec<String> = challenge.0.progress(&performance_tracker).iter()
+            .map(|progress| format!("{} {:.0}/{:.0} {}",
+                progress_bar(progress.current / progress.target.max(1.0), 20),
+                progress.current, progress.target, progress.label))
+            .collect();
+        
+        if !challenge.0.free_play() {
+            let stage_time = time.elapsed_seconds() - performance_tracker.simulation_start_time;
+            let remaining = (TIME_LIMIT - stage_time).max(0.0);
+            lines.push(format!("{} {:.0}s remaining", progress_bar(remaining / TIME_LIMIT, 20), remaining));
+        }
+        if let Some(ref leaderboard) = leaderboard {
+            lines.push(format!("🥇 {}", leaderboard.record_line(challenge.0.number())));
+        }
+        text.sections[0].value = lines.join("\n");
     }
 }
 
+/// Drags shorter than this (world units) count as a click
+const SELECTION_DRAG_THRESHOLD: f32 = 5.0;
+
+/// Click selects the hovered ant, Shift+click adds or removes it, dragging selects every ant in the box
+/// (Shift+drag adds them). The last ant picked is the primary: it gets the detail panel, trail and follow-cam.
 pub fn ant_selection_system(
     mut debug_info: ResMut<DebugInfo>,
     mut commands: Commands,
     mouse_input: Res<ButtonInput<MouseButton>>,
-    ant_query: Query<Entity, With<AntState>>,
+    input: Res<ButtonInput<KeyCode>>,
+    editor: Res<EditorState>,
+    ant_query: Query<(Entity, &Transform), With<AntCore>>,
     selected_query: Query<Entity, With<SelectedAnt>>,
+    mut gizmos: Gizmos,
+    color_config: Res<ColorConfig>,
+    mut drag_start: Local<Option<Vec2>>,
 ) {
+    // Editor clicks place things, Ctrl+click drops food, Alt+click drops a marker
+    let ctrl = input.pressed(KeyCode::ControlLeft) || input.pressed(KeyCode::ControlRight);
+    let alt = input.pressed(KeyCode::AltLeft) || input.pressed(KeyCode::AltRight);
+    if editor.active || ctrl || alt {
+        *drag_start = None;
+        return;
+    }
+    
+    let cursor = debug_info.cursor_world_pos;
     if mouse_input.just_pressed(MouseButton::Left) {
-        let mut ant_clicked = false;
-        
-        for entity in selected_query.iter() {
-            commands.entity(entity).remove::<SelectedAnt>();
+        *drag_start = Some(cursor);
+    }
+    let Some(start) = *drag_start else { return };
+    let is_drag = start.distance(cursor) > SELECTION_DRAG_THRESHOLD;
+    
+    if mouse_input.pressed(MouseButton::Left) {
+        if is_drag {
+            gizmos.rect_2d((start + cursor) / 2.0, 0.0, (cursor - start).abs(), color_config.debug_selection);
         }
-        
-        for entity in ant_query.iter() {
-            if debug_info.hovered_entity == Some(entity) {
-                commands.entity(entity).insert(SelectedAnt);
-                debug_info.selected_entity = Some(entity);
-                ant_clicked = true;
-                break;
+        return;
+    }
+    if !mouse_input.just_released(MouseButton::Left) {
+        return;
+    }
+    *drag_start = None;
+    
+    let shift = input.pressed(KeyCode::ShiftLeft) || input.pressed(KeyCode::ShiftRight);
+    let previous: HashSet<Entity> = selected_query.iter().collect();
+    let mut selection: HashSet<Entity> = if shift { previous.clone() } else { HashSet::new() };
+    let mut primary = if shift { debug_info.selected_entity } else { None };
+    
+    if is_drag {
+        let (min, max) = (start.min(cursor), start.max(cursor));
+        for (entity, transform) in ant_query.iter() {
+            let position = transform.translation.truncate();
+            if position.cmpge(min).all() && position.cmple(max).all() {
+                selection.insert(entity);
+                primary = primary.or(Some(entity));
             }
         }
-        
-        if !ant_clicked {
-            debug_info.selected_entity = None;
+    } else if let Some(hovered) = debug_info.hovered_entity.filter(|&entity| ant_query.contains(entity)) {
+        if shift && selection.contains(&hovered) {
+            selection.remove(&hovered);
+        } else {
+            selection.insert(hovered);
+            primary = Some(hovered);
+        }
+    }
+    
+    // A deselected primary hands over to any remaining member
+    if primary.is_none_or(|entity| !selection.contains(&entity)) {
+        primary = selection.iter().next().copied();
+    }
+    
+    for &entity in previous.difference(&selection) {
+        commands.entity(entity).remove::<(SelectedAnt, TrajectoryTrail, SensingDebug)>();
+    }
+    for &entity in selection.difference(&previous) {
+        commands.entity(entity).insert(SelectedAnt);
+    }
+    if primary != debug_info.selected_entity {
+        if let Some(old) = debug_info.selected_entity.filter(|entity| selection.contains(entity)) {
+            commands.entity(old).remove::<(TrajectoryTrail, SensingDebug)>();
+        }
+        if let Some(new) = primary {
+            commands.entity(new).insert((TrajectoryTrail::default(), SensingDebug::default()));
         }
     }
+    debug_info.selected_entity = primary;
 }
 
 pub fn selected_ant_display_system(
-    mut debug_info: ResMut<DebugInfo>,
-    ant_query: Query<(Entity, &Transform, &AntState, &Velocity), With<AntState>>,
+    debug_info: Res<DebugInfo>,
+    ant_query: Query<(Entity, &Transform, &AntCore, &AntMemory, &AntStats, &AntDiagnostics, &Velocity, Option<&SensingDebug>)>,
+    selection_query: Query<(&AntCore, &AntMemory, &AntStats, &AntDiagnostics), With<SelectedAnt>>,
+    mut panel_query: Query<&mut Style, With<SelectedAntPanel>>,
+    mut text_query: Query<&mut Text, With<SelectedAntText>>,
+    time: Res<Time>,
 ) {
-    if let Some(selected_entity) = debug_info.selected_entity {
-        if let Ok((entity, transform, ant_state, velocity)) = ant_query.get(selected_entity) {
-            if debug_info.entity_info.is_empty() {
-                debug_info.entity_info = format!(
-                    "=== BASIC ANT ===\nEntity: {:?}\nPos: ({:.1}, {:.1})\nBehavior: {:?}\nCarrying Food: {}\nDirection: {:.1}°\nVelocity: ({:.2}, {:.2})\nSensing Timer: {:.2}\nStuck Timer: {:.2}",
-                    entity,
-                    transform.translation.x, transform.translation.y,
-                    ant_state.behavior_state,
-                    ant_state.carrying_food,
-                    ant_state.current_direction.to_degrees(),
-                    velocity.x, velocity.y,
-                    ant_state.sensing_timer,
-                    ant_state.stuck_timer
-                );
-            }
+    let selected = debug_info.selected_entity.and_then(|entity| ant_query.get(entity).ok());
+    if let Ok(mut style) = panel_query.get_single_mut() {
+        let display = if selected.is_some() { Display::Flex } else { Display::None };
+        if style.display != display {
+            style.display = display;
         }
     }
+    
+    let Some((entity, transform, ant_state, ant_memory, ant_stats, ant_diagnostics, velocity, sensing)) = selected else { return };
+    let Ok(mut text) = text_query.get_single_mut() else { return };
+    
+    let count = selection_query.iter().count();
+    if count > 1 {
+        let percent = |matching: usize| matching as f32 / count as f32 * 100.0;
+        let mean_progress = selection_query.iter().map(|(_, _, _, diagnostics)| diagnostics.time_since_progress).sum::<f32>() / count as f32;
+        let carrying = selection_query.iter().filter(|(ant, _, _, _)| ant.carrying_food).count();
+        let swarming = selection_query.iter().filter(|(_, _, _, diagnostics)| diagnostics.is_swarming).count();
+        let lost = selection_query.iter().filter(|(_, memory, _, _)| !memory.has_found_food).count();
+        let deliveries: u32 = selection_query.iter().map(|(_, _, stats, _)| stats.successful_deliveries).sum();
+        text.sections[0].value = format!(
+            "=== {} ANTS SELECTED ===\n\
+            Mean time since progress: {:.1}s\n\
+            Carrying food: {:.0}%\n\
+            Swarming: {:.0}%\n\
+            Never found food: {:.0}%\n\
+            Deliveries: {}\n\
+            \nPrimary: {:?} (click it alone for details)",
+            count, mean_progress, percent(carrying), percent(swarming), percent(lost), deliveries, entity,
+        );
+        return;
+    }
+    
+    let now = time.elapsed_seconds();
+    let since = |at: f32| if at > 0.0 { format!("{:.1}s ago", now - at) } else { "never".to_string() };
+    
+    text.sections[0].value = format!(
+        "=== SELECTED ANT #{} ({:?}) ===\n\
+        Colony {} | {:?} | {:?}\n\
+        \n-- Movement --\n\
+        Pos: ({:.1}, {:.1})  Facing: {:.0}°\n\
+        Heading: {:.0}°  Velocity: ({:.1}, {:.1})\n\
+        Sensing timer: {:.2}  Momentum timer: {:.2}\n\
+        \n-- Trail --\n\
+        Can see trail: {}  Distance from trail: {:.1}\n\
+        Gradient strength: {:.3}  Trail strength: {:.3}\n\
+        Trail quality: {:.2}  Following for {:.1}s\n\
+        Last trail contact: {}\n\
+        \n-- Progress --\n\
+        Time since progress: {:.1}s  Stuck timer: {:.2}\n\
+        Last goal: {}  Direction changes: {}\n\
+        Exploration efficiency: {:.2}\n\
+        Swarming: {} ({} nearby)  Edge wanderer: {}\n\
+        \n-- Deliveries --\n\
+        Carrying food: {}  Ever found food: {}\n\
+        Deliveries: {} of {} attempts{}",
+        ant_state.index, entity,
+        ant_state.colony, ant_state.role, ant_state.behavior_state,
+        transform.translation.x, transform.translation.y, ant_state.facing.to_degrees(),
+        ant_state.current_direction.to_degrees(), velocity.x, velocity.y,
+        ant_state.sensing_timer, ant_state.momentum_timer,
+        ant_diagnostics.can_see_trail, ant_diagnostics.distance_from_trail,
+        ant_diagnostics.trail_gradient_strength, ant_memory.trail_strength,
+        ant_memory.trail_quality, ant_diagnostics.trail_following_time,
+        since(ant_diagnostics.last_trail_contact_time),
+        ant_diagnostics.time_since_progress, ant_state.stuck_timer,
+        since(ant_stats.last_goal_achievement_time), ant_diagnostics.direction_changes,
+        ant_diagnostics.exploration_efficiency,
+        ant_diagnostics.is_swarming, ant_diagnostics.nearby_ant_count, ant_diagnostics.is_edge_wanderer,
+        ant_state.carrying_food, ant_memory.has_found_food,
+        ant_stats.successful_deliveries, ant_stats.delivery_attempts,
+        sensing_summary(sensing, now),
+    );
+}
+
+/// Panel section listing each ray of the last sensing decision as `angle: strength/score`
+fn sensing_summary(sensing: Option<&SensingDebug>, now: f32) -> String {
+    let Some(sensing) = sensing.filter(|sensing| !sensing.rays.is_empty()) else {
+        return String::new();
+    };
+    let rays: Vec<String> = sensing.rays.iter()
+        .map(|ray| {
+            let score = ray.score.map_or("-".to_string(), |score| format!("{:.2}", score));
+            format!("{:>4.0}°: {:.2}/{}", ray.angle.to_degrees().rem_euclid(360.0), ray.strength, score)
+        })
+        .collect();
+    let chosen = sensing.chosen.map_or("none".to_string(), |angle| format!("{:.0}°", angle.to_degrees().rem_euclid(360.0)));
+    format!(
+        "\n\n-- Last sensing ({:.1}s ago) --\n{}\nChosen heading: {}\n{}",
+        now - sensing.sensed_at, sensing.mode, chosen,
+        rays.chunks(6).map(|chunk| chunk.join("  ")).collect::<Vec<_>>().join("\n"),
+    )
 }
 
 pub fn selected_ant_outline_system(
     mut commands: Commands,
-    selected_ants: Query<(Entity, &Transform), (With<AntState>, With<SelectedAnt>)>,
+    selected_ants: Query<(Entity, &Transform), (With<AntCore>, With<SelectedAnt>)>,
     existing_outlines: Query<Entity, With<crate::components::AntOutline>>,
     color_config: Res<ColorConfig>,
 ) {
@@ -2094,9 +3418,89 @@ pub fn selected_ant_outline_system(
     }
 }
 
+/// Positions kept in the selected ant's trail
+const TRAJECTORY_LENGTH: usize = 400;
+
+/// Record the selected ant's path and draw it as a polyline that fades toward the oldest point
+pub fn trajectory_trail_system(
+    mut trail_query: Query<(&Transform, &mut TrajectoryTrail), With<SelectedAnt>>,
+    mut gizmos: Gizmos,
+    color_config: Res<ColorConfig>,
+    view: Res<ViewSettings>,
+) {
+    for (transform, mut trail) in trail_query.iter_mut() {
+        let position = transform.translation.truncate();
+        // Skip samples while the ant stands still (or the simulation is paused)
+        if trail.positions.back().is_none_or(|last| last.distance(position) > 1.0) {
+            if trail.positions.len() == TRAJECTORY_LENGTH {
+                trail.positions.pop_front();
+            }
+            trail.positions.push_back(position);
+        }
+
+        // Keeps recording while hidden so the trail is complete when shown again
+        if !view.selection_gizmos {
+            continue;
+        }
+        let count = trail.positions.len() as f32;
+        gizmos.linestrip_gradient_2d(trail.positions.iter().enumerate().map(|(i, &point)| {
+            let age_fade = (i as f32 + 1.0) / count;
+            (point, color_config.debug_selection.with_alpha(age_fade))
+        }));
+    }
+}
+
+/// Draw the primary selected ant's last sensing decision: one ray per sampled direction, longer for
+/// stronger readings, green to red by score rank (grey when rejected), and the chosen heading in yellow
+pub fn sensing_gizmo_system(
+    ant_query: Query<(&Transform, &SensingDebug)>,
+    mut gizmos: Gizmos,
+    view: Res<ViewSettings>,
+) {
+    if !view.selection_gizmos {
+        return;
+    }
+    for (transform, sensing) in ant_query.iter() {
+        let origin = transform.translation.truncate();
+        let max_strength = sensing.rays.iter().map(|ray| ray.strength).fold(0.0, f32::max);
+        let scores = sensing.rays.iter().filter_map(|ray| ray.score);
+        let (min_score, max_score) = scores.fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), score| (lo.min(score), hi.max(score)));
+
+        for ray in &sensing.rays {
+            let length = 15.0 + if max_strength > 0.0 { 25.0 * ray.strength / max_strength } else { 10.0 };
+            let color = match ray.score {
+                Some(score) => {
+                    let rank = if max_score > min_score { (score - min_score) / (max_score - min_score) } else { 1.0 };
+                    Color::srgb(1.0 - rank, rank, 0.0)
+                }
+                None => Color::srgba(0.5, 0.5, 0.5, 0.5),
+            };
+            gizmos.line_2d(origin, origin + Vec2::from_angle(ray.angle) * length, color);
+        }
+        if let Some(chosen) = sensing.chosen {
+            gizmos.arrow_2d(origin, origin + Vec2::from_angle(chosen) * 50.0, Color::srgb(1.0, 1.0, 0.0));
+        }
+    }
+}
+
+/// Buffers one sensing thread reuses from ant to ant and frame to frame instead of allocating per ant
+#[derive(Default)]
+pub struct SensingScratch {
+    leaders: Vec<(Vec2, u32)>, // Formation-flying leaders near a food-carrying ant
+    swarm: SwarmScratch,
+}
+
+/// What analyze_local_swarm_intelligence collects about nearby ants; SwarmContext borrows it
+#[derive(Default)]
+struct SwarmScratch {
+    successful_directions: Vec<f32>,
+    leader_positions: Vec<Vec2>,
+    failure_positions: Vec<Vec2>,
+}
+
 // CYCLE 22: Collective swarm intelligence structures and functions
 #[derive(Clone)]
-struct SwarmContext {
+struct SwarmContext<'a> {
     ant_density: u32,
     local_failure_rate: f32,
     average_failed_direction: f32,
@@ -2105,67 +3509,92 @@ struct SwarmContext {
     exploration_pressure: f32,
     least_explored_direction: f32,
     collective_confidence: f32,
-    successful_ant_directions: Vec<f32>,
+    successful_ant_directions: &'a [f32],
+    leader_positions: &'a [Vec2],  // Nearby ants with deliveries
+    failure_positions: &'a [Vec2], // Nearby ants without
+}
+
+impl SwarmContext<'_> {
+    fn marker(&self, decision: SwarmDecision, position: Vec2, now: f32) -> SwarmDecisionMarker {
+        let direction = match decision {
+            SwarmDecision::CollectiveExploration => self.suggested_exploration_direction,
+            SwarmDecision::Dispersion => self.average_failed_direction,
+        };
+        SwarmDecisionMarker {
+            decision,
+            position,
+            direction,
+            leaders: self.leader_positions.to_vec(),
+            failures: self.failure_positions.to_vec(),
+            at: now,
+        }
+    }
 }
 
 // CYCLE 22: Analyze local swarm intelligence to make collective decisions
-fn analyze_local_swarm_intelligence(
+fn analyze_local_swarm_intelligence<'a>(
     x: f32, y: f32,
-    ant: &AntState,
+    ant: &AntCore,
+    ant_diagnostics: &AntDiagnostics,
     entity: Entity,
-    ant_positions: &[(Entity, Vec2, bool, u32)],
-    current_time: f32
-) -> SwarmContext {
+    spatial_index: &SpatialIndex,
+    scratch: &'a mut SwarmScratch,
+) -> SwarmContext<'a> {
     let current_pos = Vec2::new(x, y);
-    let mut context = SwarmContext {
-        ant_density: 0,
-        local_failure_rate: 0.0,
-        average_failed_direction: ant.current_direction,
-        should_use_collective_exploration: false,
-        suggested_exploration_direction: ant.current_direction,
-        exploration_pressure: 0.0,
-        least_explored_direction: 0.0,
-        collective_confidence: 0.5,
-        successful_ant_directions: Vec::new(),
-    };
+    scratch.successful_directions.clear();
+    scratch.leader_positions.clear();
+    scratch.failure_positions.clear();
     
     let mut nearby_ants = 0;
     let mut struggling_ants = 0;
     let mut successful_ants = 0;
-    let mut failed_directions = Vec::new();
-    let mut successful_directions = Vec::new();
-    let mut exploration_directions = Vec::new();
+    let mut failed_direction_sum = Vec2::ZERO; // Unit vectors away from struggling ants, for their average
+    let mut direction_coverage = [0u32; 8]; // Non-carrying ants per compass direction
     
     // Analyze nearby ants within 60 unit radius for collective intelligence
-    for (other_entity, other_pos, carrying_food, successful_deliveries) in ant_positions.iter() {
-        if *other_entity == entity { continue; }
-        
-        let distance = current_pos.distance(*other_pos);
-        if distance > 60.0 { continue; }
+    for other in spatial_index.ants_within(current_pos, 60.0) {
+        if other.entity == entity { continue; }
         
         nearby_ants += 1;
         
         // Classify ant performance based on success metrics
-        if *successful_deliveries > 0 {
+        if other.successful_deliveries > 0 {
             successful_ants += 1;
+            scratch.leader_positions.push(other.position);
             // Record directions of successful ants for collective following
-            let direction_to_successful = (*other_pos - current_pos).normalize();
-            successful_directions.push(direction_to_successful.y.atan2(direction_to_successful.x));
+            let direction_to_successful = (other.position - current_pos).normalize();
+            scratch.successful_directions.push(direction_to_successful.y.atan2(direction_to_successful.x));
         } else {
             struggling_ants += 1;
+            scratch.failure_positions.push(other.position);
             // Record directions away from struggling ants
-            let direction_from_struggling = (current_pos - *other_pos).normalize();
-            failed_directions.push(direction_from_struggling.y.atan2(direction_from_struggling.x));
+            let direction_from_struggling = (current_pos - other.position).normalize();
+            let angle = direction_from_struggling.y.atan2(direction_from_struggling.x);
+            failed_direction_sum += Vec2::new(angle.cos(), angle.sin());
         }
         
         // Track exploration patterns
-        if !carrying_food {
-            let exploration_dir = (*other_pos - current_pos).normalize();
-            exploration_directions.push(exploration_dir.y.atan2(exploration_dir.x));
+        if !other.carrying_food {
+            let exploration_dir = (other.position - current_pos).normalize();
+            let dir = exploration_dir.y.atan2(exploration_dir.x);
+            let compass_index = ((dir + std::f32::consts::PI) / (std::f32::consts::TAU / 8.0)) as usize % 8;
+            direction_coverage[compass_index] += 1;
         }
     }
     
-    context.ant_density = nearby_ants;
+    let mut context = SwarmContext {
+        ant_density: nearby_ants,
+        local_failure_rate: 0.0,
+        average_failed_direction: ant.current_direction,
+        should_use_collective_exploration: false,
+        suggested_exploration_direction: ant.current_direction,
+        exploration_pressure: 0.0,
+        least_explored_direction: 0.0,
+        collective_confidence: 0.5,
+        successful_ant_directions: &scratch.successful_directions,
+        leader_positions: &scratch.leader_positions,
+        failure_positions: &scratch.failure_positions,
+    };
     
     // Calculate local failure rate and collective confidence
     if nearby_ants > 0 {
@@ -2173,22 +3602,13 @@ fn analyze_local_swarm_intelligence(
         context.collective_confidence = successful_ants as f32 / nearby_ants as f32;
         
         // Calculate average direction of failed attempts
-        if !failed_directions.is_empty() {
-            let mut sum_x = 0.0;
-            let mut sum_y = 0.0;
-            for &dir in &failed_directions {
-                sum_x += dir.cos();
-                sum_y += dir.sin();
-            }
-            context.average_failed_direction = sum_y.atan2(sum_x);
+        if struggling_ants > 0 {
+            context.average_failed_direction = failed_direction_sum.y.atan2(failed_direction_sum.x);
         }
-        
-        // Store successful directions for collective intelligence
-        context.successful_ant_directions = successful_directions;
     }
     
     // Determine if collective exploration should be used
-    let exploration_threshold = if ant.time_since_progress > 8.0 {
+    let exploration_threshold = if ant_diagnostics.time_since_progress > 8.0 {
         0.3 // Lower threshold for struggling ants
     } else {
         0.6 // Higher threshold for doing-well ants
@@ -2199,14 +3619,6 @@ fn analyze_local_swarm_intelligence(
                                                !context.successful_ant_directions.is_empty();
     
     if context.should_use_collective_exploration {
-        // Calculate least explored direction using directional analysis
-        let mut direction_coverage = [0u32; 8]; // 8 compass directions
-        
-        for &dir in &exploration_directions {
-            let compass_index = ((dir + std::f32::consts::PI) / (std::f32::consts::TAU / 8.0)) as usize % 8;
-            direction_coverage[compass_index] += 1;
-        }
-        
         // Find direction with least exploration
         let min_coverage = direction_coverage.iter().min().unwrap_or(&0);
         let least_explored_index = direction_coverage.iter().position(|&x| x == *min_coverage).unwrap_or(0);
@@ -2223,6 +3635,47 @@ fn analyze_local_swarm_intelligence(
     context
 }
 
+/// Seconds a swarm decision marker stays on screen after the rule last fired
+const SWARM_MARKER_LIFETIME: f32 = 1.5;
+
+/// V toggles swarm decision markers: a ring on the deciding ant, an arrow along the suggested
+/// direction (green) or the avoided failed direction (red), and lines to the leaders and failures it counted
+pub fn swarm_decision_gizmo_system(
+    input: Res<ButtonInput<KeyCode>>,
+    mut swarm_log: ResMut<SwarmDecisionLog>,
+    mut view: ResMut<ViewSettings>,
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+) {
+    if input.just_pressed(KeyCode::KeyV) {
+        view.swarm_markers = !view.swarm_markers;
+        info!("🐝 Swarm decision markers {}", if view.swarm_markers { "on" } else { "off" });
+    }
+    if !view.swarm_markers {
+        swarm_log.markers.clear();
+        return;
+    }
+    
+    let now = time.elapsed_seconds();
+    swarm_log.markers.retain(|_, marker| now - marker.at < SWARM_MARKER_LIFETIME);
+    
+    for marker in swarm_log.markers.values() {
+        let fade = 1.0 - (now - marker.at) / SWARM_MARKER_LIFETIME;
+        let color = match marker.decision {
+            SwarmDecision::CollectiveExploration => Color::srgba(0.2, 1.0, 0.4, fade),
+            SwarmDecision::Dispersion => Color::srgba(1.0, 0.3, 0.2, fade),
+        };
+        gizmos.circle_2d(marker.position, 8.0, color);
+        gizmos.arrow_2d(marker.position, marker.position + Vec2::from_angle(marker.direction) * 40.0, color);
+        for &leader in &marker.leaders {
+            gizmos.line_2d(marker.position, leader, Color::srgba(0.2, 0.6, 1.0, fade * 0.6));
+        }
+        for &failure in &marker.failures {
+            gizmos.line_2d(marker.position, failure, Color::srgba(0.6, 0.6, 0.6, fade * 0.4));
+        }
+    }
+}
+
 // CYCLE 22: Calculate collective intelligence bonus for trail following
 fn calculate_collective_intelligence_bonus(
     angle: f32,
@@ -2236,7 +3689,7 @@ fn calculate_collective_intelligence_bonus(
     if swarm_context.collective_confidence > 0.6 && !swarm_context.successful_ant_directions.is_empty() {
         // Calculate alignment with successful ant directions
         let mut best_alignment = -1.0;
-        for &successful_dir in &swarm_context.successful_ant_directions {
+        for &successful_dir in swarm_context.successful_ant_directions {
             let alignment = (angle.cos() * successful_dir.cos() + angle.sin() * successful_dir.sin());
             if alignment > best_alignment {
                 best_alignment = alignment;
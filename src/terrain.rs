@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+
+/// Grayscale elevation data stretched over the 1000x1000 world
+#[derive(Resource)]
+pub struct Heightmap {
+    pub width: usize,
+    pub height: usize,
+    pub elevation: Vec<f32>, // Normalized 0.0 (black) to 1.0 (white), row 0 = top of image
+}
+
+impl Heightmap {
+    /// Load a heightmap from a PNG image. Color images are converted to luminance.
+    pub fn from_png(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let (width, height, pixels) = read_png_rgb(path)?;
+        let elevation = pixels.iter()
+            .map(|px| {
+                // Standard luminance weights
+                (0.299 * px[0] as f32 + 0.587 * px[1] as f32 + 0.114 * px[2] as f32) / 255.0
+            })
+            .collect();
+
+        Ok(Self { width, height, elevation })
+    }
+
+    /// Normalized elevation (0.0-1.0) at a world position, image top = world +y
+    pub fn elevation_at(&self, x: f32, y: f32) -> f32 {
+        let world_size = 1000.0;
+        let u = ((x + world_size * 0.5) / world_size * self.width as f32) as i32;
+        let v = ((world_size * 0.5 - y) / world_size * self.height as f32) as i32;
+
+        let px = u.clamp(0, self.width as i32 - 1) as usize;
+        let py = v.clamp(0, self.height as i32 - 1) as usize;
+        self.elevation[py * self.width + px]
+    }
+
+    /// Speed multiplier for moving from one position to another.
+    /// Uphill movement is slowed proportionally to slope; flat or downhill movement is unaffected.
+    pub fn slope_speed_factor(&self, from: Vec2, to: Vec2, height_scale: f32, slope_cost: f32) -> f32 {
+        let run = from.distance(to);
+        if run < 0.001 {
+            return 1.0;
+        }
+
+        let rise = (self.elevation_at(to.x, to.y) - self.elevation_at(from.x, from.y)) * height_scale;
+        let slope = (rise / run).max(0.0);
+        1.0 / (1.0 + slope * slope_cost)
+    }
+}
+
+/// Decode a PNG into row-major RGB pixels (row 0 = top of image). Grayscale images are expanded.
+pub fn read_png_rgb(path: &str) -> Result<(usize, usize, Vec<[u8; 3]>), Box<dyn std::error::Error>> {
+    let file = std::fs::File::open(path)?;
+    let mut decoder = png::Decoder::new(file);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info()?;
+    let mut buffer = vec![0u8; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer)?;
+
+    let channels = match info.color_type {
+        png::ColorType::Grayscale => 1,
+        png::ColorType::GrayscaleAlpha => 2,
+        png::ColorType::Rgb => 3,
+        png::ColorType::Rgba => 4,
+        png::ColorType::Indexed => return Err("Indexed PNG images are not supported".into()),
+    };
+
+    let width = info.width as usize;
+    let height = info.height as usize;
+    let mut pixels = Vec::with_capacity(width * height);
+
+    for row in 0..height {
+        let row_start = row * info.line_size;
+        for col in 0..width {
+            let px = &buffer[row_start + col * channels..row_start + (col + 1) * channels];
+            pixels.push(if channels >= 3 { [px[0], px[1], px[2]] } else { [px[0], px[0], px[0]] });
+        }
+    }
+
+    Ok((width, height, pixels))
+}
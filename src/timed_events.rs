@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use crate::components::*;
+use crate::config::*;
+use crate::colors::*;
+use crate::pheromones::*;
+use crate::challenges::*;
+
+/// A mid-run disruption a scenario can script
+#[derive(Clone, Debug)]
+pub enum ScriptedAction {
+    SpawnRock { position: Vec2, radius: f32 },
+    SpawnFood { position: Vec2 },
+    DepleteFood { index: usize }, // Food sources numbered from 0 in spawn order
+    RelocateFood, // Every food source jumps to a new seeded spot 333-450 units from the nest, refilled
+}
+
+/// An action fired once the simulation clock reaches `at` seconds
+#[derive(Clone, Debug)]
+pub struct TimedEvent {
+    pub at: f32,
+    pub action: ScriptedAction,
+}
+
+impl TimedEvent {
+    /// Parse one line of an events file:
+    /// `30 rock 100 50 25` (x, y, radius), `45 food -200 120` (x, y), `60 deplete 2` (food index) or `75 relocate`
+    pub fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let at = fields.first()?.parse::<f32>().ok()?;
+        let number = |i: usize| fields.get(i).and_then(|field| field.parse::<f32>().ok());
+
+        let action = match *fields.get(1)? {
+            "rock" => ScriptedAction::SpawnRock {
+                position: Vec2::new(number(2)?, number(3)?),
+                radius: number(4).unwrap_or(25.0),
+            },
+            "food" => ScriptedAction::SpawnFood { position: Vec2::new(number(2)?, number(3)?) },
+            "deplete" => ScriptedAction::DepleteFood { index: fields.get(2)?.parse().ok()? },
+            "relocate" => ScriptedAction::RelocateFood,
+            _ => return None,
+        };
+
+        Some(Self { at, action })
+    }
+}
+
+/// Pending timed events for this run, sorted by time
+#[derive(Resource, Default)]
+pub struct EventSchedule {
+    pub events: Vec<TimedEvent>,
+    pub next: usize, // Index of the first event not yet fired
+}
+
+impl EventSchedule {
+    pub fn new(mut events: Vec<TimedEvent>) -> Self {
+        events.sort_by(|a, b| a.at.total_cmp(&b.at));
+        Self { events, next: 0 }
+    }
+
+    /// Load events from a text file, one per line; blank lines, `#` comments and the
+    /// `camera` keyframes read by `CameraPath::load_keyframes` are skipped
+    pub fn load_events(path: &str) -> Result<Vec<TimedEvent>, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let mut events = Vec::new();
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.split_whitespace().nth(1) == Some("camera") {
+                continue;
+            }
+            match TimedEvent::parse(line) {
+                Some(event) => events.push(event),
+                None => return Err(format!("line {}: can't parse \"{}\"", line_number + 1, line).into()),
+            }
+        }
+        Ok(events)
+    }
+}
+
+/// Old food sites watched after a relocation until their trail fades
+#[derive(Resource, Default)]
+pub struct StaleTrailProbe {
+    pub sites: Vec<Vec2>, // Empty when nothing is being watched
+    pub relocated_at: f32,
+    pub baseline: f32, // Food trail strength at the sites when the food moved
+}
+
+/// Radius around an old food site sampled for leftover trail
+const PROBE_RADIUS: f32 = 20.0;
+
+/// Total food trail pheromone sampled around the given sites
+fn trail_strength_at(grid: &PheromoneGrid, sites: &[Vec2]) -> f32 {
+    let mut total = 0.0;
+    for site in sites {
+        let mut dy = -PROBE_RADIUS;
+        while dy <= PROBE_RADIUS {
+            let mut dx = -PROBE_RADIUS;
+            while dx <= PROBE_RADIUS {
+                if let Some(idx) = grid.world_to_grid(site.x + dx, site.y + dy) {
+                    total += grid.food_trail[idx];
+                }
+                dx += 4.0;
+            }
+            dy += 4.0;
+        }
+    }
+    total
+}
+
+/// Record how long the trail at abandoned food sites takes to fall below half strength
+pub fn stale_trail_probe_system(
+    mut probe: ResMut<StaleTrailProbe>,
+    mut performance_tracker: ResMut<PerformanceTracker>,
+    pheromone_grid: Option<Res<PheromoneGrid>>,
+    time: Res<Time>,
+) {
+    let Some(grid) = pheromone_grid else { return };
+    if probe.sites.is_empty() {
+        return;
+    }
+
+    let elapsed = time.elapsed_seconds() - probe.relocated_at;
+    if trail_strength_at(&grid, &probe.sites) < probe.baseline * 0.5 {
+        info!("🧹 Stale trails at old food sites faded below half strength after {:.1}s", elapsed);
+        performance_tracker.stale_trail_clear_times.push(elapsed);
+        probe.sites.clear();
+    }
+}
+
+/// Fire every scheduled event whose time has come
+pub fn timed_event_system(
+    mut commands: Commands,
+    mut schedule: ResMut<EventSchedule>,
+    mut food_query: Query<(Entity, &mut FoodSource, &mut Transform)>,
+    nest_query: Query<&Transform, (With<Nest>, Without<FoodSource>)>,
+    mut performance_tracker: ResMut<PerformanceTracker>,
+    mut probe: ResMut<StaleTrailProbe>,
+    pheromone_grid: Option<Res<PheromoneGrid>>,
+    config: Res<SimConfig>,
+    color_config: Res<ColorConfig>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_seconds();
+    let mut world = ChallengeWorld {
+        commands: &mut commands,
+        config: &config,
+        color_config: &color_config,
+        prey_food: false,
+    };
+
+    while let Some(event) = schedule.events.get(schedule.next).cloned() {
+        if event.at > now {
+            break;
+        }
+        schedule.next += 1;
+
+        match event.action {
+            ScriptedAction::SpawnRock { position, radius } => {
+                spawn_rock(&mut world, position, radius);
+                info!("⏰ t={:.1}s: rock (r={:.0}) dropped at ({:.0}, {:.0})", now, radius, position.x, position.y);
+            }
+            ScriptedAction::SpawnFood { position } => {
+                spawn_food_source(&mut world, position);
+                info!("⏰ t={:.1}s: food appeared at ({:.0}, {:.0})", now, position.x, position.y);
+            }
+            ScriptedAction::DepleteFood { index } => {
+                // Entities from startup are allocated in spawn order
+                let mut sources: Vec<_> = food_query.iter_mut().collect();
+                sources.sort_by_key(|(entity, _, _)| *entity);
+                match sources.get_mut(index) {
+                    Some((_, food, _)) => {
+                        food.amount = 0.0;
+                        info!("⏰ t={:.1}s: food #{} depleted", now, index);
+                    }
+                    None => warn!("⚠️ t={:.1}s: no food #{} to deplete ({} sources)", now, index, sources.len()),
+                }
+            }
+            ScriptedAction::RelocateFood => {
+                // Seeded per relocation so a run's sites repeat, measured from the first nest
+                let relocation = performance_tracker.food_relocation_times.len() as u64 + 1;
+                let mut rng = StdRng::seed_from_u64(config.moving_target_seed.wrapping_add(relocation));
+                let nest = nest_query.iter().next().map_or(Vec2::ZERO, |transform| transform.translation.truncate());
+                let mut old_sites = Vec::new();
+                for (entity, mut food, mut transform) in food_query.iter_mut() {
+                    old_sites.push(transform.translation.truncate());
+                    let site = random_food_site(&mut rng, nest);
+                    transform.translation.x = site.x;
+                    transform.translation.y = site.y;
+                    food.amount = food.max_amount;
+                    world.commands.entity(entity).remove::<Regrowing>();
+                }
+
+                // Watch the abandoned sites to see how long their trails linger
+                let baseline = pheromone_grid.as_deref().map_or(0.0, |grid| trail_strength_at(grid, &old_sites));
+                if baseline > 0.0 {
+                    *probe = StaleTrailProbe { sites: old_sites, relocated_at: now, baseline };
+                }
+                let run_time = now - performance_tracker.simulation_start_time;
+                performance_tracker.food_relocation_times.push(run_time);
+                info!("⏰ t={:.1}s: all food relocated (old trail strength {:.1})", now, baseline);
+            }
+        }
+    }
+}
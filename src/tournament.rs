@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+use std::fs;
+use std::process::Command;
+use crate::components::*;
+use crate::scoring::*;
+use crate::annotations::Annotations;
+
+/// Seeds used when `--tournament-seeds` isn't given
+pub const DEFAULT_TOURNAMENT_SEEDS: [u64; 3] = [1, 2, 3];
+
+/// Where a single tournament run writes its result, set with `--result-file`.
+/// Runs with a result file skip video capture and leave generation_info.json alone.
+#[derive(Resource)]
+pub struct ResultFile(pub String);
+
+/// One parameter set under comparison: a name and the CLI flags that produce it
+pub struct Variant {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+/// Load variants from a text file, one per line as `name: --flag value ...`.
+/// Blank lines and `#` comments are skipped; `baseline:` with no flags runs the defaults.
+pub fn load_variants(path: &str) -> Result<Vec<Variant>, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    let mut variants = Vec::new();
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, flags) = line.split_once(':')
+            .ok_or_else(|| format!("line {}: expected \"name: --flags\", got \"{}\"", line_number + 1, line))?;
+        variants.push(Variant {
+            name: name.trim().to_string(),
+            args: flags.split_whitespace().map(str::to_string).collect(),
+        });
+    }
+    if variants.is_empty() {
+        return Err(format!("no variants in {}", path).into());
+    }
+    Ok(variants)
+}
+
+/// Outcome of one variant on one map
+struct RunResult {
+    score: f32,
+    deliveries: u64,
+    success: bool,
+}
+
+/// Aggregated results for one variant across every map
+struct Standing {
+    name: String,
+    scores: Vec<Option<f32>>, // One per seed, None if the run crashed or wrote no result
+    deliveries: u64,
+    wins: usize,
+}
+
+impl Standing {
+    fn mean_score(&self) -> f32 {
+        // Missing runs count as zero so a crashing variant can't rank on its good maps alone
+        self.scores.iter().map(|score| score.unwrap_or(0.0)).sum::<f32>() / self.scores.len().max(1) as f32
+    }
+}
+
+/// Run every variant on every seeded map as a child process, then print and save a ranked table
+pub fn run_tournament(variants: &[Variant], seeds: &[u64], challenge: u32) {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            error!("❌ Can't locate the simulation binary: {}", e);
+            return;
+        }
+    };
+
+    info!("🏟️ Tournament: {} variants x {} maps on challenge {}", variants.len(), seeds.len(), challenge);
+    let mut standings = Vec::new();
+
+    for variant in variants {
+        let mut standing = Standing { name: variant.name.clone(), scores: Vec::new(), deliveries: 0, wins: 0 };
+
+        for &seed in seeds {
+            let result_path = std::env::temp_dir().join(format!("antsim_tournament_{}_{}.json", standings.len(), seed));
+            let _ = fs::remove_file(&result_path);
+            info!("▶️ {} on map seed {}", variant.name, seed);
+
+            let status = Command::new(&exe)
+                .args(["--challenge", &challenge.to_string()])
+                .args(["--map-seed", &seed.to_string()])
+                .arg("--result-file").arg(&result_path)
+                .args(["--log-level", "warn"]) // Keep per-run chatter out of the tournament output; variant args may override
+                .args(&variant.args)
+                .status();
+            if let Err(e) = status {
+                error!("❌ Failed to launch run: {}", e);
+            }
+
+            match read_result(&result_path) {
+                Some(result) => {
+                    info!("   score {:.1} ({} deliveries, {})", result.score, result.deliveries,
+                        if result.success { "success" } else { "failure" });
+                    standing.deliveries += result.deliveries;
+                    standing.wins += result.success as usize;
+                    standing.scores.push(Some(result.score));
+                }
+                None => {
+                    warn!("   ⚠️ no result written");
+                    standing.scores.push(None);
+                }
+            }
+            let _ = fs::remove_file(&result_path);
+        }
+
+        standings.push(standing);
+    }
+
+    standings.sort_by(|a, b| b.mean_score().total_cmp(&a.mean_score()));
+    let table = format_table(&standings, seeds);
+    info!("{}", table);
+
+    let path = format!("tournament_{}.md", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+    match fs::write(&path, &table) {
+        Ok(()) => info!("💾 Saved tournament table to {}", path),
+        Err(e) => error!("❌ Failed to write {}: {}", path, e),
+    }
+}
+
+fn read_result(path: &std::path::Path) -> Option<RunResult> {
+    let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    Some(RunResult {
+        score: json["score"].as_f64()? as f32,
+        deliveries: json["successful_deliveries"].as_u64().unwrap_or(0),
+        success: json["verdict"].as_str() == Some(Verdict::Success.label()),
+    })
+}
+
+/// Markdown table ranked by mean score, one column per map seed
+fn format_table(standings: &[Standing], seeds: &[u64]) -> String {
+    let mut header = "| Rank | Variant | Mean score | Wins | Deliveries |".to_string();
+    let mut divider = "|---|---|---|---|---|".to_string();
+    for seed in seeds {
+        header.push_str(&format!(" Seed {} |", seed));
+        divider.push_str("---|");
+    }
+
+    let mut lines = vec![header, divider];
+    for (rank, standing) in standings.iter().enumerate() {
+        let mut line = format!("| {} | {} | {:.1} | {}/{} | {} |",
+            rank + 1, standing.name, standing.mean_score(), standing.wins, seeds.len(), standing.deliveries);
+        for score in &standing.scores {
+            match score {
+                Some(score) => line.push_str(&format!(" {:.1} |", score)),
+                None => line.push_str(" - |"),
+            }
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// Write this run's result for the tournament parent once the challenge is decided
+pub fn write_run_result_system(
+    result_file: Option<Res<ResultFile>>,
+    challenge_score: Res<ChallengeScore>,
+    performance_tracker: Res<PerformanceTracker>,
+    annotations: Res<Annotations>,
+    mut written: Local<bool>,
+) {
+    let Some(result_file) = result_file else { return };
+    if *written || challenge_score.verdict == Verdict::Running {
+        return;
+    }
+    *written = true;
+
+    let result = serde_json::json!({
+        "challenge": challenge_score.challenge,
+        "score": challenge_score.score,
+        "verdict": challenge_score.verdict.label(),
+        "reason": challenge_score.reason,
+        "successful_deliveries": performance_tracker.successful_deliveries,
+        "average_time_since_goal_seconds": performance_tracker.average_time_since_goal,
+        "ants_lost": performance_tracker.ants_lost,
+        "annotations": annotations.to_json(),
+    });
+    if let Err(e) = fs::write(&result_file.0, result.to_string()) {
+        error!("❌ Failed to write run result {}: {}", result_file.0, e);
+    }
+}
@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::collections::VecDeque;
+use crate::components::*;
+
+/// Seconds of history plotted in the chart
+const TREND_WINDOW: f32 = 90.0;
+/// Seconds between samples
+const SAMPLE_INTERVAL: f32 = 1.0;
+const CHART_WIDTH: f32 = 300.0;
+const CHART_HEIGHT: f32 = 110.0;
+
+/// One point of the trend chart, in stage time
+pub struct TrendSample {
+    pub time: f32,
+    pub deliveries_per_minute: f32,
+    pub average_time_since_goal: f32,
+}
+
+/// Delivery rate and goal time, sampled once a second over the whole stage.
+/// The chart shows the last TREND_WINDOW seconds; the video overlay plots all of it.
+#[derive(Resource, Default)]
+pub struct PerformanceHistory {
+    pub samples: VecDeque<TrendSample>,
+}
+
+pub fn performance_history_system(
+    mut history: ResMut<PerformanceHistory>,
+    performance_tracker: Res<PerformanceTracker>,
+    time: Res<Time>,
+) {
+    let stage_time = time.elapsed_seconds() - performance_tracker.simulation_start_time;
+    // Restarts and curriculum stages reset the stage clock
+    if history.samples.back().is_some_and(|last| last.time > stage_time) {
+        history.samples.clear();
+    }
+    if history.samples.back().is_some_and(|last| stage_time - last.time < SAMPLE_INTERVAL) {
+        return;
+    }
+
+    // Deliveries over the last minute, scaled up while less than a minute has passed
+    let window = stage_time.clamp(SAMPLE_INTERVAL, 60.0);
+    let recent = performance_tracker.delivery_timestamps.iter()
+        .filter(|&&at| at > stage_time - window)
+        .count();
+
+    history.samples.push_back(TrendSample {
+        time: stage_time,
+        deliveries_per_minute: recent as f32 * 60.0 / window,
+        average_time_since_goal: performance_tracker.average_time_since_goal,
+    });
+}
+
+/// Line chart under the performance metrics; each series is scaled to its own maximum
+pub fn performance_chart_system(mut contexts: EguiContexts, history: Res<PerformanceHistory>, view: Res<ViewSettings>) {
+    if !view.trend_chart {
+        return;
+    }
+    let Some(latest) = history.samples.back() else { return };
+    let recent: Vec<&TrendSample> = history.samples.iter().filter(|sample| sample.time >= latest.time - TREND_WINDOW).collect();
+    let delivery_color = egui::Color32::from_rgb(0, 220, 0);
+    let goal_color = egui::Color32::from_rgb(255, 140, 0);
+
+    egui::Area::new(egui::Id::new("performance_chart"))
+        .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-10.0, 400.0))
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Frame::none().fill(egui::Color32::from_black_alpha(160)).inner_margin(6.0).show(ui, |ui| {
+                let max_rate = recent.iter().map(|sample| sample.deliveries_per_minute).fold(1.0, f32::max);
+                let max_goal = recent.iter().map(|sample| sample.average_time_since_goal).fold(1.0, f32::max);
+                ui.label(egui::RichText::new(format!("Deliveries/min: {:.1} (peak {:.1})", latest.deliveries_per_minute, max_rate)).color(delivery_color));
+                ui.label(egui::RichText::new(format!("Avg time since goal: {:.1}s (peak {:.1}s)", latest.average_time_since_goal, max_goal)).color(goal_color));
+
+                let (response, painter) = ui.allocate_painter(egui::vec2(CHART_WIDTH, CHART_HEIGHT), egui::Sense::hover());
+                let rect = response.rect;
+                painter.rect_stroke(rect, 0.0, egui::Stroke::new(1.0, egui::Color32::GRAY));
+
+                // The right edge is now, the left edge TREND_WINDOW seconds ago
+                let to_screen = |time: f32, value: f32, max: f32| {
+                    let x = rect.right() - (latest.time - time) / TREND_WINDOW * rect.width();
+                    let y = rect.bottom() - (value / max) * rect.height();
+                    egui::pos2(x, y)
+                };
+                let deliveries: Vec<egui::Pos2> = recent.iter()
+                    .map(|sample| to_screen(sample.time, sample.deliveries_per_minute, max_rate))
+                    .collect();
+                let goal_times: Vec<egui::Pos2> = recent.iter()
+                    .map(|sample| to_screen(sample.time, sample.average_time_since_goal, max_goal))
+                    .collect();
+                painter.add(egui::Shape::line(goal_times, egui::Stroke::new(1.5, goal_color)));
+                painter.add(egui::Shape::line(deliveries, egui::Stroke::new(1.5, delivery_color)));
+            });
+        });
+}
+
+const HISTOGRAM_BINS: usize = 20;
+const HISTOGRAM_WIDTH: f32 = 320.0;
+const HISTOGRAM_HEIGHT: f32 = 70.0;
+
+/// Histogram window, toggled with H
+#[derive(Resource, Default)]
+pub struct StatsDashboard {
+    pub visible: bool,
+}
+
+pub fn stats_dashboard_toggle_system(input: Res<ButtonInput<KeyCode>>, mut dashboard: ResMut<StatsDashboard>) {
+    if input.just_pressed(KeyCode::KeyH) {
+        dashboard.visible = !dashboard.visible;
+    }
+}
+
+/// Distributions behind the headline averages: per-delivery times and each ant's time since progress
+pub fn stats_dashboard_system(
+    mut contexts: EguiContexts,
+    mut dashboard: ResMut<StatsDashboard>,
+    performance_tracker: Res<PerformanceTracker>,
+    ant_query: Query<&AntDiagnostics>,
+) {
+    if !dashboard.visible {
+        return;
+    }
+    let progress_times: Vec<f32> = ant_query.iter().map(|ant_diagnostics| ant_diagnostics.time_since_progress).collect();
+
+    egui::Window::new("Stats (H to hide)")
+        .open(&mut dashboard.visible)
+        .default_pos(egui::pos2(400.0, 120.0))
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            histogram(ui, "Delivery time (s)", &performance_tracker.delivery_times, egui::Color32::from_rgb(0, 200, 0));
+            histogram(ui, "Return time (s)", &performance_tracker.return_times, egui::Color32::from_rgb(255, 140, 0));
+            histogram(ui, "Time since progress, per ant (s)", &progress_times, egui::Color32::from_rgb(80, 160, 255));
+        });
+}
+
+/// Bar histogram from 0 to the largest value, with count, median and max above it
+fn histogram(ui: &mut egui::Ui, title: &str, values: &[f32], color: egui::Color32) {
+    ui.label(egui::RichText::new(title).strong());
+    if values.is_empty() {
+        ui.label("no samples yet");
+        ui.add_space(6.0);
+        return;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let max = sorted[sorted.len() - 1].max(0.001);
+    ui.label(format!("n={}  median {:.1}  max {:.1}", sorted.len(), sorted[sorted.len() / 2], max));
+
+    let mut bins = [0usize; HISTOGRAM_BINS];
+    for &value in &sorted {
+        let bin = ((value.max(0.0) / max) * HISTOGRAM_BINS as f32) as usize;
+        bins[bin.min(HISTOGRAM_BINS - 1)] += 1;
+    }
+    let tallest = bins.iter().copied().max().unwrap_or(1).max(1);
+
+    let (response, painter) = ui.allocate_painter(egui::vec2(HISTOGRAM_WIDTH, HISTOGRAM_HEIGHT), egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_stroke(rect, 0.0, egui::Stroke::new(1.0, egui::Color32::GRAY));
+    let bar_width = rect.width() / HISTOGRAM_BINS as f32;
+    for (i, &count) in bins.iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        let left = rect.left() + i as f32 * bar_width;
+        let top = rect.bottom() - count as f32 / tallest as f32 * rect.height();
+        painter.rect_filled(
+            egui::Rect::from_min_max(egui::pos2(left + 1.0, top), egui::pos2(left + bar_width - 1.0, rect.bottom())),
+            0.0,
+            color,
+        );
+    }
+    ui.add_space(6.0);
+}
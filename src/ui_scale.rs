@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use bevy_egui::EguiSettings;
+
+/// Scales Ctrl+Plus/Ctrl+Minus step through; `--ui-scale` may start anywhere in between
+const UI_SCALE_STEPS: [f32; 9] = [0.5, 0.75, 1.0, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0];
+
+/// Parse `--ui-scale <factor>`, clamped to the step range
+pub fn parse_ui_scale(value: &str) -> Option<f32> {
+    value.parse::<f32>().ok()
+        .filter(|scale| scale.is_finite() && *scale > 0.0)
+        .map(|scale| scale.clamp(UI_SCALE_STEPS[0], UI_SCALE_STEPS[UI_SCALE_STEPS.len() - 1]))
+}
+
+/// Ctrl+Plus / Ctrl+Minus make all text and panels larger or smaller
+pub fn ui_scale_hotkey_system(input: Res<ButtonInput<KeyCode>>, mut ui_scale: ResMut<UiScale>) {
+    if !(input.pressed(KeyCode::ControlLeft) || input.pressed(KeyCode::ControlRight)) {
+        return;
+    }
+    let larger = input.just_pressed(KeyCode::Equal) || input.just_pressed(KeyCode::NumpadAdd);
+    let smaller = input.just_pressed(KeyCode::Minus) || input.just_pressed(KeyCode::NumpadSubtract);
+    if !(larger || smaller) {
+        return;
+    }
+    // Snap to the nearest step, then move one step along
+    let current = UI_SCALE_STEPS.iter()
+        .position(|&step| step >= ui_scale.0 - 0.001)
+        .unwrap_or(UI_SCALE_STEPS.len() - 1);
+    let next = if larger {
+        (current + 1).min(UI_SCALE_STEPS.len() - 1)
+    } else {
+        current.saturating_sub(1)
+    };
+    ui_scale.0 = UI_SCALE_STEPS[next];
+    info!("🔠 UI scale {:.2}x", ui_scale.0);
+}
+
+/// Bevy's UiScale covers the TextBundle overlays; egui windows and panels follow it here
+pub fn apply_ui_scale_system(ui_scale: Res<UiScale>, mut egui_settings: ResMut<EguiSettings>) {
+    egui_settings.scale_factor = ui_scale.0;
+}
@@ -1,301 +1,487 @@
 use bevy::prelude::*;
+use ab_glyph::{point, Font as _, FontArc, PxScale, ScaleFont};
+use bevy::render::view::screenshot::ScreenshotManager;
+use bevy::window::PrimaryWindow;
 use crate::components::*;
 use crate::pheromones::*;
 use crate::colors::*;
+use crate::terrain::*;
+use crate::worldmap::*;
+use crate::scoring::*;
+use crate::tournament::ResultFile;
+use crate::challenges::{ActiveChallenge, Challenge};
+use crate::config::SimConfig;
+use crate::leaderboard::config_hash;
+use crate::annotations::{Annotations, ANNOTATION_RGB};
+use crate::trends::PerformanceHistory;
+use crate::camera_path::{CameraPath, VideoView};
+use crate::highlights::HighlightClips;
+use crate::replay::{ReplayBuffer, REPLAY_SECONDS};
+use crate::contact_sheet::RunSamples;
+use crate::sonification::Soundtrack;
 use std::fs;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use rayon::prelude::*;
+use std::io::{BufWriter, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
 
 #[derive(Component)]
 pub struct VideoCamera;
 
+/// Height of the dark text band at the top of each frame
+const TEXT_OVERLAY_HEIGHT: u32 = 100;
+/// Smallest frame edge that still leaves room below the text band
+pub const MIN_VIDEO_SIZE: u32 = 160;
+
+/// Parse `--video-size 1920x1080`
+pub fn parse_video_size(value: &str) -> Option<(u32, u32)> {
+    let (width, height) = value.split_once(['x', 'X'])?;
+    let (width, height) = (width.trim().parse::<u32>().ok()?, height.trim().parse::<u32>().ok()?);
+    (width >= MIN_VIDEO_SIZE && height >= MIN_VIDEO_SIZE).then_some((width, height))
+}
+
+/// Screenshots requested for the video arrive here from the render thread, already scaled to the frame size
+#[derive(Resource, Default)]
+pub struct ScreenCapture {
+    arrived: Arc<Mutex<Vec<Vec<u8>>>>,
+}
+
 #[derive(Resource)]
 pub struct VideoRenderTarget {
     pub image: Handle<Image>,
 }
 
-pub fn setup_video_camera() {
-    // Simplified setup - we'll use a different approach to capture the main camera's output
-    println!("🎥 Video recording system initialized (screenshot-based capture ready)");
+pub fn setup_video_camera(mut video_recorder: ResMut<VideoRecorder>, fonts: Res<Assets<Font>>) {
+    // The overlay text uses the same built-in font as the on-screen UI
+    video_recorder.font = fonts.get(&Handle::<Font>::default()).map(|font| font.font.clone());
+    if video_recorder.font.is_none() {
+        warn!("⚠️ Default font not loaded, rendered video frames will have no text overlay");
+    }
+    info!("🎥 Video recording system initialized (screenshot-based capture ready)");
 }
 
 pub fn video_recording_system(
     mut video_recorder: ResMut<VideoRecorder>,
     performance_tracker: Res<PerformanceTracker>,
     generation_info: Res<GenerationInfo>,
-    time: Res<Time>,
+    challenge_score: Res<ChallengeScore>,
+    (time, performance_history, camera_path, mut highlights, mut replay): (Res<Time>, Res<PerformanceHistory>, Res<CameraPath>, ResMut<HighlightClips>, ResMut<ReplayBuffer>),
     pheromone_grid: Res<PheromoneGrid>,
     color_config: Res<ColorConfig>,
-    ant_query: Query<(&Transform, &AntState), (With<AntState>, Without<Nest>)>,
+    ant_query: Query<(&Transform, &AntCore), (With<AntCore>, Without<Nest>)>,
     food_query: Query<&Transform, (With<FoodSource>, Without<Nest>)>,
     nest_query: Query<&Transform, With<Nest>>,
-    rock_query: Query<(&Transform, &Rock), (With<Rock>, Without<AntState>)>,
+    rock_query: Query<(&Transform, &Rock), (With<Rock>, Without<AntCore>)>,
+    predator_query: Query<&Transform, (With<Predator>, Without<AntCore>)>,
+    (heightmap, world_map): (Option<Res<Heightmap>>, Option<Res<WorldMap>>),
+    (result_file, challenge, config, mut pending_saves): (Option<Res<ResultFile>>, Res<ActiveChallenge>, Res<SimConfig>, ResMut<PendingSaves>),
+    (annotations, debug_info, debug_ant_query): (Res<Annotations>, Res<DebugInfo>, Query<Entity, With<DebugAnt>>),
+    (mut screenshot_manager, screen_capture, window_query): (ResMut<ScreenshotManager>, Res<ScreenCapture>, Query<Entity, With<PrimaryWindow>>),
 ) {
+    // Tournament runs only report a score. Free play has no run to film, so it only keeps the replay buffer.
+    if result_file.is_some() {
+        return;
+    }
+    let free_play = challenge.0.free_play();
+    
     // Start recording when simulation has been running for a bit
-    if !video_recorder.is_recording {
+    if !video_recorder.is_recording && free_play {
+        video_recorder.is_recording = true;
+        video_recorder.recording_started = time.elapsed_seconds();
+        video_recorder.stream = FrameStream::default();
+        video_recorder.pheromone_stream = None;
+        video_recorder.soundtrack = None;
+        info!("📹 Free play: keeping the last {:.0}s of video, press Z to save it", REPLAY_SECONDS);
+    } else if !video_recorder.is_recording {
         video_recorder.is_recording = true;
-        println!("📹 Started video recording for Test {}", video_recorder.test_number);
+        video_recorder.recording_started = time.elapsed_seconds();
+        let stem = video_stem(&generation_info);
+        *highlights = HighlightClips::default();
+        let setup = RunSetup::new(&config, challenge.0.as_ref());
+        video_recorder.container_tags = vec![
+            ("title", format!("Ant simulation generation {}: {}", generation_info.current_generation, generation_info.description)),
+            ("comment", format!("{}. {}", video_recorder.changes_description, setup.summary())),
+        ];
+        let sink = open_frame_sink(&video_recorder, &stem);
+        video_recorder.stream = FrameStream { sink, ..default() };
+        if video_recorder.pheromone_stream.is_some() {
+            let sink = open_frame_sink(&video_recorder, &pheromone_video_stem(&stem));
+            video_recorder.pheromone_stream = Some(FrameStream { sink, ..default() });
+        }
+        video_recorder.run_samples = RunSamples::new(video_recorder.frame_width, video_recorder.frame_height);
+        video_recorder.auto_crop = None;
+        if video_recorder.soundtrack.is_some() {
+            video_recorder.soundtrack = Some(Soundtrack::default());
+        }
+        info!("📹 Started video recording for Test {} ({}x{}, {} fps, {:.1}x speed, a frame every {:.2}s{})",
+            video_recorder.test_number, video_recorder.frame_width, video_recorder.frame_height,
+            video_recorder.fps, video_recorder.speedup, video_recorder.frame_interval,
+            video_recorder.target_duration.map_or(String::new(), |target| format!(", {:.0}s target", target)));
     }
     
     if video_recorder.is_recording {
+        // Screenshots requested on earlier ticks
+        let already_pending = video_recorder.stream.frames.len();
+        let arrived: Vec<Vec<u8>> = screen_capture.arrived.lock().unwrap().drain(..).collect();
+        video_recorder.stream.frames.extend(arrived);
+        
         // Update frame timer
         video_recorder.frame_timer += time.delta_seconds();
         
         
-        // Only capture frames at the specified interval
-        if video_recorder.frame_timer >= video_recorder.frame_interval {
+        // Only capture frames at the specified interval, and no more than max_frames per run
+        let captured = video_recorder.stream.frames_written + video_recorder.stream.frames.len();
+        if video_recorder.frame_timer >= video_recorder.frame_interval && captured < video_recorder.max_frames {
             video_recorder.frame_timer -= video_recorder.frame_interval; // Subtract interval instead of reset to 0
+            // A fast-forwarded or slow tick can cover several intervals; drop the backlog rather than
+            // capturing on every following tick
+            if video_recorder.frame_timer >= video_recorder.frame_interval {
+                video_recorder.frame_timer %= video_recorder.frame_interval;
+            }
+            
+            // The camera path view at this moment, or the --video-crop region without one, shared by the
+            // rendered frame and the pheromone video
+            let view = match camera_path.view_at(time.elapsed_seconds()) {
+                Some(view) => view,
+                None => crop_view(&mut video_recorder, &ant_query, &food_query, &nest_query),
+            };
+            let frame_taken = match (video_recorder.source, window_query.get_single()) {
+                (VideoSource::Screen, Ok(window)) => {
+                    // Read back what the window shows this frame; the copy lands in ScreenCapture a frame or two later
+                    let arrived = screen_capture.arrived.clone();
+                    let (width, height) = (video_recorder.frame_width, video_recorder.frame_height);
+                    let pool = video_recorder.frame_pool.clone();
+                    let requested = screenshot_manager.take_screenshot(window, move |image| {
+                        match screenshot_to_frame(image, width, height, &pool) {
+                            Some(frame) => arrived.lock().unwrap().push(frame),
+                            None => warn!("⚠️ Screenshot format not supported, frame skipped"),
+                        }
+                    });
+                    if requested.is_err() {
+                        debug!("📹 Screenshot still pending, frame skipped");
+                    }
+                    requested.is_ok()
+                }
+                _ => {
+                    // The inset follows the selected ant, or the debug ant when nothing is selected
+                    let inset_ant = debug_info.selected_entity
+                        .filter(|&entity| ant_query.contains(entity))
+                        .or_else(|| debug_ant_query.iter().next());
+                    
+                    // Create visual frame with actual simulation data, whole world unless a camera path is set
+                    capture_simulation_frame(&mut video_recorder, &performance_tracker, &performance_history, &generation_info, &challenge_score, time.elapsed_seconds(), 
+                                           &pheromone_grid, &color_config, &ant_query, &food_query, &nest_query, &rock_query,
+                                           &predator_query, heightmap.as_deref(), world_map.as_deref(),
+                                           annotations.show_in_video.then_some(&*annotations), inset_ant, view);
+                    true
+                }
+            };
             
-            // Create visual frame with actual simulation data (capture whole simulation)
-            capture_simulation_frame(&mut video_recorder, &performance_tracker, &generation_info, time.elapsed_seconds(), 
-                                   &pheromone_grid, &color_config, &ant_query, &food_query, &nest_query, &rock_query);
+            // Pheromone-only companion frame, one for every main frame so the two videos stay in step
+            if frame_taken {
+                let (width, height) = (video_recorder.frame_width, video_recorder.frame_height);
+                let pool = video_recorder.frame_pool.clone();
+                if let Some(stream) = video_recorder.pheromone_stream.as_mut() {
+                    stream.frames.push_back(render_pheromone_frame(width, height, &pheromone_grid, view, &pool));
+                }
+                if let Some(soundtrack) = video_recorder.soundtrack.as_mut() {
+                    soundtrack.record(performance_tracker.successful_deliveries, performance_tracker.average_time_since_goal,
+                        pheromone_grid.alarm.iter().sum());
+                }
+            }
             
             
             // Debug: Print frame count periodically
-            if video_recorder.frames.len() % 30 == 0 {
-                println!("📹 Captured {} frames at {:.2}s (interval={:.2}s, timer was {:.3}s)", 
-                    video_recorder.frames.len(), 
+            if captured.is_multiple_of(30) {
+                debug!("📹 Captured {} frames at {:.2}s (interval={:.2}s, timer was {:.3}s)", 
+                    captured, 
                     time.elapsed_seconds(), 
                     video_recorder.frame_interval,
                     video_recorder.frame_timer + video_recorder.frame_interval // Show what timer was before subtraction
                 );
             }
+            if captured + 1 == video_recorder.max_frames {
+                info!("📹 Reached max_frames ({}), no more frames will be captured this run", video_recorder.max_frames);
+            }
+        }
+        
+        let now = time.elapsed_seconds();
+        let recorder = &mut *video_recorder;
+        let replay_budget = recorder.replay_budget();
+        for frame in recorder.stream.frames.iter().skip(already_pending) {
+            replay.record(now, frame, replay_budget, &recorder.frame_pool);
+        }
+        save_replay(&video_recorder, &mut replay, &generation_info);
+        if free_play {
+            // Nothing else is filmed, so the frames only live on in the replay buffer
+            let recorder = &mut *video_recorder;
+            for frame in recorder.stream.frames.drain(..) {
+                recorder.frame_pool.give(frame);
+            }
+            return;
+        }
+        
+        // Clips around highlighted events, once the frames after them are in
+        let recorder = &mut *video_recorder;
+        let highlight_budget = recorder.highlight_budget();
+        for frame in recorder.stream.frames.iter().skip(already_pending) {
+            highlights.record(now, frame, highlight_budget, &recorder.frame_pool);
+            recorder.run_samples.add(frame);
         }
+        save_highlight_clips(&video_recorder, &mut highlights, &generation_info, now);
+        
+        write_pending_frames(&mut video_recorder);
     }
     
     // Check if simulation is ending and should save video
-    if should_save_video(&performance_tracker, &time) && video_recorder.is_recording {
-        save_video_on_exit(&mut video_recorder, &performance_tracker, &generation_info);
+    if should_save_video(&performance_tracker, &challenge_score, &time) && video_recorder.is_recording {
+        // Encoders for the last replay and clips start here; the save waits for them in the background
+        save_replay(&video_recorder, &mut replay, &generation_info);
+        let recorded_seconds = time.elapsed_seconds() - video_recorder.recording_started;
+        start_highlight_clips(&video_recorder, &mut highlights, &generation_info, time.elapsed_seconds(), true);
+        let setup = RunSetup::new(&config, challenge.0.as_ref());
+        let save = save_video_on_exit(&mut video_recorder, &performance_tracker, &generation_info, &challenge_score, &annotations, &mut highlights, &mut replay, &setup, recorded_seconds);
+        pending_saves.start(save);
         video_recorder.is_recording = false;
     }
 }
 
 
+/// Window screenshot as an RGBA frame of the video size, nearest-neighbour scaled
+fn screenshot_to_frame(image: Image, width: u32, height: u32, pool: &FramePool) -> Option<Vec<u8>> {
+    let rgba = image.try_into_dynamic().ok()?.to_rgba8();
+    let (source_width, source_height) = rgba.dimensions();
+    if source_width == 0 || source_height == 0 {
+        return None;
+    }
+    let source = rgba.into_raw();
+    let mut frame = pool.take((width * height * 4) as usize);
+    for y in 0..height {
+        let source_y = (y as u64 * source_height as u64 / height as u64) as u32;
+        for x in 0..width {
+            let source_x = (x as u64 * source_width as u64 / width as u64) as u32;
+            let from = ((source_y * source_width + source_x) * 4) as usize;
+            let to = ((y * width + x) * 4) as usize;
+            frame[to..to + 3].copy_from_slice(&source[from..from + 3]);
+            frame[to + 3] = 255; // Alpha holds HDR brightness on some targets, not coverage
+        }
+    }
+    Some(frame)
+}
+
+/// Display intensity (0-1) of a raw pheromone value: log(pheromone)^1.3 * 20 on a 0-255 scale
+pub fn pheromone_intensity(raw: f32) -> f32 {
+    if raw > VISIBLE_FLOOR {
+        ((raw.ln().powf(1.3) * 20.0) / 255.0).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Ant color for its current state, using the shared color config
+pub fn ant_rgb(color_config: &ColorConfig, ant_state: &AntCore) -> (u8, u8, u8) {
+    if ant_state.carrying_food {
+        color_config.ant_carrying_food_rgb()
+    } else if ant_state.food_collection_timer > 0.0 {
+        color_config.ant_collecting_rgb()
+    } else {
+        color_config.ant_exploring_rgb()
+    }
+}
+
 fn capture_simulation_frame(
     video_recorder: &mut VideoRecorder, 
     performance_tracker: &PerformanceTracker, 
+    performance_history: &PerformanceHistory,
     generation_info: &GenerationInfo,
+    challenge_score: &ChallengeScore,
     elapsed_time: f32,
     pheromone_grid: &PheromoneGrid,
     color_config: &ColorConfig,
-    ant_query: &Query<(&Transform, &AntState), (With<AntState>, Without<Nest>)>,
+    ant_query: &Query<(&Transform, &AntCore), (With<AntCore>, Without<Nest>)>,
     food_query: &Query<&Transform, (With<FoodSource>, Without<Nest>)>,
     nest_query: &Query<&Transform, With<Nest>>,
-    rock_query: &Query<(&Transform, &Rock), (With<Rock>, Without<AntState>)>,
+    rock_query: &Query<(&Transform, &Rock), (With<Rock>, Without<AntCore>)>,
+    predator_query: &Query<&Transform, (With<Predator>, Without<AntCore>)>,
+    heightmap: Option<&Heightmap>,
+    world_map: Option<&WorldMap>,
+    annotations: Option<&Annotations>,
+    inset_ant: Option<Entity>,
+    view: VideoView,
 ) {
     let target_width = video_recorder.frame_width;
     let target_height = video_recorder.frame_height;
     let frame_size = (target_width * target_height * 4) as usize;
-    let mut frame = vec![0u8; frame_size];
+    let mut frame = video_recorder.frame_pool.take(frame_size);
     
-    // Render pheromone trails as background
-    let world_size = 1000.0;
-    let grid_to_screen_x = |grid_x: usize| -> u32 {
-        ((grid_x as f32 / pheromone_grid.width as f32) * target_width as f32) as u32
+    // Camera view: the world span shown across the frame and the world point at its centre
+    let world_to_screen_x = |world_x: f32| -> i32 {
+        (((world_x - view.center.x) / view.span.x + 0.5) * target_width as f32).floor() as i32
     };
-    let grid_to_screen_y = |grid_y: usize| -> u32 {
-        ((grid_y as f32 / pheromone_grid.height as f32) * target_height as f32) as u32
+    let world_to_screen_y = |world_y: f32| -> i32 {
+        (((world_y - view.center.y) / view.span.y + 0.5) * target_height as f32).floor() as i32
     };
     
-    // Render pheromone grid
-    for grid_y in 0..pheromone_grid.height {
-        for grid_x in 0..pheromone_grid.width {
-            let grid_idx = grid_y * pheromone_grid.width + grid_x;
+    // Render pheromone trails as background, sampling the grid at each pixel's world position
+    for screen_y in 0..target_height {
+        for screen_x in 0..target_width {
+            let Vec2 { x: world_x, y: world_y } = pixel_to_world(view, target_width, target_height, screen_x, screen_y);
+            let pixel_idx = ((screen_y * target_width + screen_x) * 4) as usize;
+            frame[pixel_idx + 3] = 255;
+            
+            // Outside the world stays black
+            let Some(grid_idx) = pheromone_grid.world_to_grid(world_x, world_y) else { continue };
             
             // Get pheromone values with logarithmic scaling: log(pheromone)^1.3 * 20
             let raw_food = pheromone_grid.food_trail[grid_idx];
             let raw_nest = pheromone_grid.nest_trail[grid_idx];
             
-            let food_pheromone = if raw_food > 0.01 {
-                ((raw_food.ln().powf(1.3) * 20.0) / 255.0).clamp(0.0, 1.0)
-            } else {
-                0.0
-            };
-            
-            let nest_pheromone = if raw_nest > 0.01 {
-                ((raw_nest.ln().powf(1.3) * 20.0) / 255.0).clamp(0.0, 1.0)
-            } else {
-                0.0
-            };
-            
-            // Map to screen coordinates 
-            let screen_x = grid_to_screen_x(grid_x);
-            let screen_y = grid_to_screen_y(grid_y);
+            let food_pheromone = pheromone_intensity(raw_food);
+            let nest_pheromone = pheromone_intensity(raw_nest);
             
-            if screen_x < target_width && screen_y < target_height {
-                let pixel_idx = ((screen_y * target_width + screen_x) * 4) as usize;
-                
-                if pixel_idx + 3 < frame.len() {
-                    // Match simulation logic: use stronger pheromone and apply to specific channel
-                    if food_pheromone > nest_pheromone {
-                        // Food pheromone dominates - use green channel
-                        let (food_r, _food_g, food_b) = color_config.food_pheromone_rgb();
-                        frame[pixel_idx] = food_r;
-                        frame[pixel_idx + 1] = (food_pheromone * 255.0) as u8; // Apply intensity to green
-                        frame[pixel_idx + 2] = food_b;
-                        frame[pixel_idx + 3] = 255;
-                    } else if nest_pheromone > 0.0 {
-                        // Nest pheromone dominates - use blue channel  
-                        let (nest_r, nest_g, _nest_b) = color_config.nest_pheromone_rgb();
-                        frame[pixel_idx] = nest_r;
-                        frame[pixel_idx + 1] = nest_g;
-                        frame[pixel_idx + 2] = (nest_pheromone * 255.0) as u8; // Apply intensity to blue
-                        frame[pixel_idx + 3] = 255;
-                    } else {
-                        // No pheromone - transparent
-                        frame[pixel_idx] = 0;
-                        frame[pixel_idx + 1] = 0; 
-                        frame[pixel_idx + 2] = 0;
-                        frame[pixel_idx + 3] = 255;
-                    }
-                }
+            // Match simulation logic: use stronger pheromone and apply to specific channel
+            if food_pheromone > nest_pheromone {
+                // Food pheromone dominates - use green channel
+                let (food_r, _food_g, food_b) = color_config.food_pheromone_rgb();
+                frame[pixel_idx] = food_r;
+                frame[pixel_idx + 1] = (food_pheromone * 255.0) as u8; // Apply intensity to green
+                frame[pixel_idx + 2] = food_b;
+            } else if nest_pheromone > 0.0 {
+                // Nest pheromone dominates - use blue channel  
+                let (nest_r, nest_g, _nest_b) = color_config.nest_pheromone_rgb();
+                frame[pixel_idx] = nest_r;
+                frame[pixel_idx + 1] = nest_g;
+                frame[pixel_idx + 2] = (nest_pheromone * 255.0) as u8; // Apply intensity to blue
+            } else if world_map.is_some_and(|map| map.water.is_water(world_x, world_y)) {
+                // Water from a loaded map
+                frame[pixel_idx] = 25;
+                frame[pixel_idx + 1] = 75;
+                frame[pixel_idx + 2] = 200;
+            } else if let Some(heightmap) = heightmap {
+                // No pheromone - shade terrain by elevation (dark earth tones)
+                let shade = heightmap.elevation_at(world_x, world_y);
+                frame[pixel_idx] = (20.0 + shade * 70.0) as u8;
+                frame[pixel_idx + 1] = (15.0 + shade * 55.0) as u8;
+                frame[pixel_idx + 2] = (10.0 + shade * 40.0) as u8;
             }
         }
     }
     
-    // World bounds for simulation (assuming 1000x1000 world)
-    let world_size = 1000.0;
-    let world_to_screen_x = |world_x: f32| -> i32 {
-        ((world_x + world_size / 2.0) / world_size * target_width as f32) as i32
-    };
-    let world_to_screen_y = |world_y: f32| -> i32 {
-        ((world_y + world_size / 2.0) / world_size * target_height as f32) as i32
+    // Markers keep their pixel size at any zoom; anything off camera is clipped
+    let mut put_pixel = |px: i32, py: i32, (r, g, b): (u8, u8, u8)| {
+        if px >= 0 && px < target_width as i32 && py >= 0 && py < target_height as i32 {
+            let idx = ((py as u32 * target_width + px as u32) * 4) as usize;
+            frame[idx] = r;
+            frame[idx + 1] = g;
+            frame[idx + 2] = b;
+            frame[idx + 3] = 255;
+        }
     };
     
-    // Draw nest (yellow circle)
-    if let Ok(nest_transform) = nest_query.get_single() {
+    // Draw nests (15x15 pixel squares)
+    for nest_transform in nest_query.iter() {
         let nest_x = world_to_screen_x(nest_transform.translation.x);
         let nest_y = world_to_screen_y(nest_transform.translation.y);
-        
-        // Draw 15x15 pixel nest
         for dy in -7..8 {
             for dx in -7..8 {
-                let px = (nest_x + dx).max(0).min(target_width as i32 - 1) as u32;
-                let py = (nest_y + dy).max(0).min(target_height as i32 - 1) as u32;
-                let idx = ((py * target_width + px) * 4) as usize;
-                
-                if idx + 3 < frame.len() {
-                    let (r, g, b) = color_config.nest_rgb();
-                    frame[idx] = r;
-                    frame[idx + 1] = g;
-                    frame[idx + 2] = b;
-                    frame[idx + 3] = 255;
-                }
+                put_pixel(nest_x + dx, nest_y + dy, color_config.nest_rgb());
             }
         }
     }
     
-    // Draw food sources (green circles)
+    // Draw food sources (8x8 pixel squares)
     for food_transform in food_query.iter() {
         let food_x = world_to_screen_x(food_transform.translation.x);
         let food_y = world_to_screen_y(food_transform.translation.y);
-        
-        // Draw 8x8 pixel food
         for dy in -4..4 {
             for dx in -4..4 {
-                let px = (food_x + dx).max(0).min(target_width as i32 - 1) as u32;
-                let py = (food_y + dy).max(0).min(target_height as i32 - 1) as u32;
-                let idx = ((py * target_width + px) * 4) as usize;
-                
-                if idx + 3 < frame.len() {
-                    let (r, g, b) = color_config.food_source_rgb();
-                    frame[idx] = r;
-                    frame[idx + 1] = g;
-                    frame[idx + 2] = b;
-                    frame[idx + 3] = 255;
-                }
+                put_pixel(food_x + dx, food_y + dy, color_config.food_source_rgb());
             }
         }
     }
     
-    // Draw rocks (gray circles)
+    // Draw rocks (filled circles at their true size in the current view)
     for (rock_transform, rock) in rock_query.iter() {
         let rock_x = world_to_screen_x(rock_transform.translation.x);
         let rock_y = world_to_screen_y(rock_transform.translation.y);
-        
-        // Convert rock radius to screen pixels
-        let rock_radius_pixels = ((rock.radius / world_size) * target_height as f32) as i32;
-        
-        // Draw rock as a filled circle
+        let rock_radius_pixels = ((rock.radius / view.span.y) * target_height as f32) as i32;
+        let radius_squared = rock_radius_pixels * rock_radius_pixels;
         for dy in -rock_radius_pixels..=rock_radius_pixels {
             for dx in -rock_radius_pixels..=rock_radius_pixels {
-                // Check if pixel is within circle
-                let distance_squared = dx * dx + dy * dy;
-                let radius_squared = rock_radius_pixels * rock_radius_pixels;
-                
-                if distance_squared <= radius_squared {
-                    let px = (rock_x + dx).max(0).min(target_width as i32 - 1) as u32;
-                    let py = (rock_y + dy).max(0).min(target_height as i32 - 1) as u32;
-                    let idx = ((py * target_width + px) * 4) as usize;
-                    
-                    if idx + 3 < frame.len() {
-                        // Gray color for rocks (0.4, 0.4, 0.4)
-                        frame[idx] = (0.4 * 255.0) as u8;     // Red
-                        frame[idx + 1] = (0.4 * 255.0) as u8; // Green  
-                        frame[idx + 2] = (0.4 * 255.0) as u8; // Blue
-                        frame[idx + 3] = 255;                  // Alpha
-                    }
+                if dx * dx + dy * dy <= radius_squared {
+                    put_pixel(rock_x + dx, rock_y + dy, color_config.rock_rgb());
                 }
             }
         }
     }
     
+    // Draw predators (10x10 pixel squares)
+    for predator_transform in predator_query.iter() {
+        let predator_x = world_to_screen_x(predator_transform.translation.x);
+        let predator_y = world_to_screen_y(predator_transform.translation.y);
+        for dy in -5..5 {
+            for dx in -5..5 {
+                put_pixel(predator_x + dx, predator_y + dy, color_config.predator_rgb());
+            }
+        }
+    }
+    
     // Draw ants with state-based colors
     for (ant_transform, ant_state) in ant_query.iter() {
         let ant_x = world_to_screen_x(ant_transform.translation.x);
         let ant_y = world_to_screen_y(ant_transform.translation.y);
         
-        // Determine ant color based on state using shared config
-        let (r, g, b) = if ant_state.carrying_food {
-            color_config.ant_carrying_food_rgb()
-        } else if ant_state.food_collection_timer > 0.0 {
-            color_config.ant_collecting_rgb()
-        } else {
-            color_config.ant_exploring_rgb()
-        };
-        
         // Draw 4x4 pixel ant body (slightly larger for better visibility)
+        let color = ant_rgb(color_config, ant_state);
         for dy in -2..2 {
             for dx in -2..2 {
-                let px = (ant_x + dx).max(0).min(target_width as i32 - 1) as u32;
-                let py = (ant_y + dy).max(0).min(target_height as i32 - 1) as u32;
-                let idx = ((py * target_width + px) * 4) as usize;
-                
-                if idx + 3 < frame.len() {
-                    frame[idx] = r;
-                    frame[idx + 1] = g;
-                    frame[idx + 2] = b;
-                    frame[idx + 3] = 255;
-                }
+                put_pixel(ant_x + dx, ant_y + dy, color);
             }
         }
         
         // Add enhanced directional indicator - a 2x2 bright white square in the direction the ant is facing
-        let direction = ant_state.current_direction;
+        let direction = ant_state.facing;
         let indicator_distance = 4.0; // Pixels from center, increased for better visibility
         let indicator_x = ant_x + (direction.cos() * indicator_distance) as i32;
         let indicator_y = ant_y + (direction.sin() * indicator_distance) as i32;
-        
-        // Draw a 2x2 pixel indicator for better visibility
         for dy in -1..1 {
             for dx in -1..1 {
-                let px = indicator_x + dx;
-                let py = indicator_y + dy;
-                
-                if px >= 0 && px < target_width as i32 && 
-                   py >= 0 && py < target_height as i32 {
-                    let idx = ((py as u32 * target_width + px as u32) * 4) as usize;
-                    if idx + 3 < frame.len() {
-                        frame[idx] = 255;     // Bright white indicator
-                        frame[idx + 1] = 255;
-                        frame[idx + 2] = 255;
-                        frame[idx + 3] = 255;
+                put_pixel(indicator_x + dx, indicator_y + dy, (255, 255, 255));
+            }
+        }
+    }
+    
+    // Draw annotation markers as small flags on white poles
+    if let Some(annotations) = annotations {
+        for marker in &annotations.markers {
+            let base_x = world_to_screen_x(marker.position.x);
+            let base_y = world_to_screen_y(marker.position.y);
+            // 8 px pole with a pennant narrowing along its far half
+            for step in 0..8 {
+                put_pixel(base_x, base_y + step, (255, 255, 255));
+                if step >= 4 {
+                    for dx in 1..(9 - step) {
+                        put_pixel(base_x + dx, base_y + step, ANNOTATION_RGB);
                     }
                 }
             }
         }
     }
     
-    // Add comprehensive text overlay at top (first 85 pixels height to accommodate 5 lines)
-    let text_height = 85;
+    
+    // Zoomed picture-in-picture of one ant in the bottom-right corner
+    if let Some((transform, ant_state)) = inset_ant.and_then(|entity| ant_query.get(entity).ok()) {
+        draw_ant_inset(&mut frame, target_width, target_height, transform.translation.truncate(), ant_state,
+                       pheromone_grid, color_config, ant_query, food_query, nest_query, rock_query);
+    }
+    
+    // Add comprehensive text overlay at top (first 100 pixels height to accommodate 6 lines)
+    let text_height = TEXT_OVERLAY_HEIGHT;
     for y in 0..text_height {
         for x in 0..target_width {
             let idx = ((y * target_width + x) * 4) as usize;
@@ -309,10 +495,189 @@ fn capture_simulation_frame(
         }
     }
     
-    // Render text information (simple pixel text simulation)
-    render_text_overlay(&mut frame, target_width, target_height, generation_info, performance_tracker, elapsed_time);
+    // Render text information
+    render_text_overlay(&mut frame, target_width, target_height, generation_info, performance_tracker, performance_history, challenge_score, elapsed_time, video_recorder.font.as_ref());
     
-    video_recorder.frames.push(frame);
+    video_recorder.stream.frames.push_back(frame);
+}
+
+/// Food, nest and alarm pheromone on their own, log-scaled into the green, blue and red channels
+/// so overlapping trails mix instead of hiding each other
+fn render_pheromone_frame(width: u32, height: u32, pheromone_grid: &PheromoneGrid, view: VideoView, pool: &FramePool) -> Vec<u8> {
+    let mut frame = pool.take((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let idx = ((y * width + x) * 4) as usize;
+            frame[idx + 3] = 255;
+            let world = pixel_to_world(view, width, height, x, y);
+            let Some(grid_idx) = pheromone_grid.world_to_grid(world.x, world.y) else { continue };
+            frame[idx] = (pheromone_intensity(pheromone_grid.alarm[grid_idx]) * 255.0) as u8;
+            frame[idx + 1] = (pheromone_intensity(pheromone_grid.food_trail[grid_idx]) * 255.0) as u8;
+            frame[idx + 2] = (pheromone_intensity(pheromone_grid.nest_trail[grid_idx]) * 255.0) as u8;
+        }
+    }
+    frame
+}
+
+/// World position at the centre of frame pixel (x, y) for a camera view
+fn pixel_to_world(view: VideoView, width: u32, height: u32, x: u32, y: u32) -> Vec2 {
+    view.center + Vec2::new((x as f32 + 0.5) / width as f32 - 0.5, (y as f32 + 0.5) / height as f32 - 0.5) * view.span
+}
+
+/// World units kept around the activity box, and the smallest box auto-crop zooms to
+const CROP_MARGIN: f32 = 40.0;
+const MIN_CROP_SPAN: f32 = 150.0;
+/// Share of the way the auto-crop box shrinks toward the activity each captured frame; it grows at once
+/// so nothing walks out of frame
+const CROP_SHRINK_RATE: f32 = 0.1;
+
+/// The view for `--video-crop`: the whole world, a fixed rectangle, or the smoothed bounding box of ants,
+/// food and nests widened to the frame's aspect ratio
+fn crop_view(
+    video_recorder: &mut VideoRecorder,
+    ant_query: &Query<(&Transform, &AntCore), (With<AntCore>, Without<Nest>)>,
+    food_query: &Query<&Transform, (With<FoodSource>, Without<Nest>)>,
+    nest_query: &Query<&Transform, With<Nest>>,
+) -> VideoView {
+    match video_recorder.crop {
+        VideoCrop::Whole => VideoView::WHOLE_WORLD,
+        VideoCrop::Rect(min, max) => VideoView::from_corners(min, max),
+        VideoCrop::Auto => {
+            let positions = ant_query.iter().map(|(transform, _)| transform)
+                .chain(food_query.iter())
+                .chain(nest_query.iter())
+                .map(|transform| transform.translation.truncate());
+            let Some((min, max)) = positions.fold(None, |bounds: Option<(Vec2, Vec2)>, position| {
+                Some(bounds.map_or((position, position), |(min, max)| (min.min(position), max.max(position))))
+            }) else {
+                return VideoView::WHOLE_WORLD;
+            };
+            let center = (min + max) * 0.5;
+            let half = ((max - min) * 0.5 + Vec2::splat(CROP_MARGIN)).max(Vec2::splat(MIN_CROP_SPAN * 0.5));
+            let (target_min, target_max) = (center - half, center + half);
+            
+            let (min, max) = match video_recorder.auto_crop {
+                Some((min, max)) => (
+                    target_min.min(min.lerp(target_min, CROP_SHRINK_RATE)),
+                    target_max.max(max.lerp(target_max, CROP_SHRINK_RATE)),
+                ),
+                None => (target_min, target_max),
+            };
+            video_recorder.auto_crop = Some((min, max));
+            VideoView::from_corners(min, max).fit_aspect(video_recorder.frame_width, video_recorder.frame_height)
+        }
+    }
+}
+
+/// Edge of the picture-in-picture inset in pixels, the world span it shows, and its gap to the frame edge
+const INSET_SIZE: u32 = 128;
+const INSET_WORLD_SPAN: f32 = 120.0;
+const INSET_MARGIN: u32 = 8;
+
+/// Close-up of the world around `center`: terrain-free pheromone background, rocks, nests, food
+/// and nearby ants, with the followed ant's heading in white. Skipped when the frame is too small.
+fn draw_ant_inset(
+    frame: &mut [u8],
+    width: u32,
+    height: u32,
+    center: Vec2,
+    focus: &AntCore,
+    pheromone_grid: &PheromoneGrid,
+    color_config: &ColorConfig,
+    ant_query: &Query<(&Transform, &AntCore), (With<AntCore>, Without<Nest>)>,
+    food_query: &Query<&Transform, (With<FoodSource>, Without<Nest>)>,
+    nest_query: &Query<&Transform, With<Nest>>,
+    rock_query: &Query<(&Transform, &Rock), (With<Rock>, Without<AntCore>)>,
+) {
+    if width < INSET_SIZE + 2 * INSET_MARGIN || height < TEXT_OVERLAY_HEIGHT + INSET_SIZE + 2 * INSET_MARGIN {
+        return;
+    }
+    let (x0, y0) = (width - INSET_SIZE - INSET_MARGIN, height - INSET_SIZE - INSET_MARGIN);
+    let units_per_pixel = INSET_WORLD_SPAN / INSET_SIZE as f32;
+    let origin = center - Vec2::splat(INSET_WORLD_SPAN / 2.0);
+    // Rows grow with world y, matching the main frame
+    let to_inset = |position: Vec2| -> (i32, i32) {
+        let offset = (position - origin) / units_per_pixel;
+        (offset.x.floor() as i32, offset.y.floor() as i32)
+    };
+    let mut put_pixel = |ix: i32, iy: i32, (r, g, b): (u8, u8, u8)| {
+        if ix >= 0 && iy >= 0 && ix < INSET_SIZE as i32 && iy < INSET_SIZE as i32 {
+            let idx = (((y0 + iy as u32) * width + x0 + ix as u32) * 4) as usize;
+            frame[idx] = r;
+            frame[idx + 1] = g;
+            frame[idx + 2] = b;
+            frame[idx + 3] = 255;
+        }
+    };
+    
+    // Background: rocks, then the dominant pheromone like the main frame
+    let nearby_rocks: Vec<(Vec2, f32)> = rock_query.iter()
+        .map(|(transform, rock)| (transform.translation.truncate(), rock.radius))
+        .filter(|(position, radius)| position.distance(center) < radius + INSET_WORLD_SPAN)
+        .collect();
+    let (food_r, _, food_b) = color_config.food_pheromone_rgb();
+    let (nest_r, nest_g, _) = color_config.nest_pheromone_rgb();
+    for iy in 0..INSET_SIZE as i32 {
+        for ix in 0..INSET_SIZE as i32 {
+            let world = origin + Vec2::new(ix as f32 + 0.5, iy as f32 + 0.5) * units_per_pixel;
+            let color = if nearby_rocks.iter().any(|(position, radius)| world.distance(*position) < *radius) {
+                color_config.rock_rgb()
+            } else if let Some(idx) = pheromone_grid.world_to_grid(world.x, world.y) {
+                let food = pheromone_intensity(pheromone_grid.food_trail[idx]);
+                let nest = pheromone_intensity(pheromone_grid.nest_trail[idx]);
+                if food > nest {
+                    (food_r, (food * 255.0) as u8, food_b)
+                } else if nest > 0.0 {
+                    (nest_r, nest_g, (nest * 255.0) as u8)
+                } else {
+                    (15, 15, 15)
+                }
+            } else {
+                (0, 0, 0) // Outside the world
+            };
+            put_pixel(ix, iy, color);
+        }
+    }
+    
+    // Discs sized in world units, so the inset shows true scale
+    let mut fill_disc = |position: Vec2, radius: f32, color: (u8, u8, u8)| {
+        let (cx, cy) = to_inset(position);
+        let r = (radius / units_per_pixel).ceil() as i32;
+        for dy in -r..=r {
+            for dx in -r..=r {
+                if dx * dx + dy * dy <= r * r {
+                    put_pixel(cx + dx, cy + dy, color);
+                }
+            }
+        }
+    };
+    for transform in nest_query.iter() {
+        fill_disc(transform.translation.truncate(), 15.0, color_config.nest_rgb());
+    }
+    for transform in food_query.iter() {
+        fill_disc(transform.translation.truncate(), 8.0, color_config.food_source_rgb());
+    }
+    for (transform, ant_state) in ant_query.iter() {
+        let position = transform.translation.truncate();
+        if position.distance(center) < INSET_WORLD_SPAN {
+            fill_disc(position, 4.0, ant_rgb(color_config, ant_state));
+        }
+    }
+    
+    // Heading of the followed ant
+    let heading = Vec2::new(focus.facing.cos(), focus.facing.sin());
+    for step in 0..12 {
+        let (ix, iy) = to_inset(center + heading * (5.0 + step as f32));
+        put_pixel(ix, iy, (255, 255, 255));
+    }
+    
+    // Frame
+    for i in 0..INSET_SIZE as i32 {
+        put_pixel(i, 0, (200, 200, 200));
+        put_pixel(i, INSET_SIZE as i32 - 1, (200, 200, 200));
+        put_pixel(0, i, (200, 200, 200));
+        put_pixel(INSET_SIZE as i32 - 1, i, (200, 200, 200));
+    }
 }
 
 fn capture_placeholder_frame(video_recorder: &mut VideoRecorder) {
@@ -328,102 +693,710 @@ fn capture_placeholder_frame(video_recorder: &mut VideoRecorder) {
         frame[i + 3] = 255; // A
     }
     
-    video_recorder.frames.push(frame);
+    video_recorder.stream.frames.push_back(frame);
 }
 
-fn should_save_video(performance_tracker: &PerformanceTracker, time: &Time) -> bool {
-    // Save after 90 seconds OR if early exit conditions are met
+fn should_save_video(performance_tracker: &PerformanceTracker, challenge_score: &ChallengeScore, time: &Time) -> bool {
+    // Save after 90 seconds, once the challenge is decided, OR if early exit conditions are met
     let elapsed = time.elapsed_seconds();
-    let time_condition = elapsed > 90.0;
+    let stage_time = elapsed - performance_tracker.simulation_start_time;
+    let time_condition = stage_time > TIME_LIMIT || challenge_score.verdict != Verdict::Running;
     let early_exit_condition = performance_tracker.oscillating_ants_count >= 20 || 
                               performance_tracker.lost_food_carriers_count >= 10;
     
     if time_condition {
-        println!("🎬 Video save triggered: 90 seconds elapsed ({:.1}s)", elapsed);
+        info!("🎬 Video save triggered: challenge {} ({:.1}s)", challenge_score.verdict.label(), elapsed);
     } else if early_exit_condition && elapsed > 15.0 {  // Minimum 15 seconds of footage
-        println!("🎬 Video save triggered: Early exit condition met at {:.1}s (oscillating={}, lost_carriers={})", 
+        info!("🎬 Video save triggered: Early exit condition met at {:.1}s (oscillating={}, lost_carriers={})", 
                 elapsed, performance_tracker.oscillating_ants_count, performance_tracker.lost_food_carriers_count);
     }
     
     // Print periodic status to debug what's happening
     if elapsed > 0.0 && (elapsed as u32) % 10 == 0 && elapsed.fract() < 0.1 {
-        println!("📊 Status at {:.0}s: oscillating={}, lost_carriers={}", elapsed, performance_tracker.oscillating_ants_count, performance_tracker.lost_food_carriers_count);
+        debug!("📊 Status at {:.0}s: oscillating={}, lost_carriers={}", elapsed, performance_tracker.oscillating_ants_count, performance_tracker.lost_food_carriers_count);
     }
     
     time_condition || (early_exit_condition && elapsed > 15.0)
 }
 
-fn save_video_on_exit(video_recorder: &mut VideoRecorder, performance_tracker: &PerformanceTracker, generation_info: &GenerationInfo) {
-    // Create videos directory if it doesn't exist
-    let videos_dir = "simulation_videos";
-    if let Err(e) = fs::create_dir_all(videos_dir) {
-        println!("❌ Failed to create videos directory: {}", e);
-        return;
-    }
+/// Hand the run's output over to a RunSave: the streams with their encoders, clips and replays still
+/// encoding, and the metadata and JSON built from this moment's results
+fn save_video_on_exit(video_recorder: &mut VideoRecorder, performance_tracker: &PerformanceTracker, generation_info: &GenerationInfo, challenge_score: &ChallengeScore, annotations: &Annotations, highlights: &mut HighlightClips, replay: &mut ReplayBuffer, setup: &RunSetup, recorded_seconds: f32) -> RunSave {
+    let stem = video_stem(generation_info);
+    let filename = format!("{}.{}", stem, video_recorder.format.extension());
     
-    // Generate filename following convention: ####_description.mp4
-    let filename = format!(
-        "{}/{:04}_{}.mp4",
-        videos_dir,
-        generation_info.current_generation,
-        generation_info.description.replace(" ", "_").to_lowercase()
-    );
+    info!("📹 Saving video: {}", filename);
+    // Frames still pending go to the encoder with the rest when the save finishes the stream
+    video_recorder.stream.frames_written += video_recorder.stream.frames.len();
+    if let Some(stream) = video_recorder.pheromone_stream.as_mut() {
+        stream.frames_written += stream.frames.len();
+    }
+    let pheromone_filename = format!("{}.{}", pheromone_video_stem(&stem), video_recorder.format.extension());
+    let timing = VideoTiming::measure(video_recorder, recorded_seconds);
     
-    println!("📹 Saving video: {}", filename);
-    println!("   Changes: {}", video_recorder.changes_description);
-    println!("   Frames captured: {}", video_recorder.frames.len());
-    println!("   Final stats: {:.1}s avg goal time, {:.1}s return time", 
+    info!("   Changes: {}", video_recorder.changes_description);
+    info!("   Frames captured: {}", video_recorder.stream.frames_written);
+    info!("   Final stats: {:.1}s avg goal time, {:.1}s return time", 
         performance_tracker.average_time_since_goal,
         performance_tracker.average_return_time
     );
+    info!("   Score: {:.1} / {:.0} ({})", challenge_score.score, challenge_score.pass_score, challenge_score.verdict.label());
     
-    // Save frames as PNG sequence that can be converted to video later
-    // Each frame will be saved as PNG with mobile aspect ratio and overlays
-    
-    let frames_dir = filename.replace(".mp4", "_frames");
-    if let Err(e) = fs::create_dir_all(&frames_dir) {
-        println!("❌ Failed to create frames directory: {}", e);
-        return;
-    }
-    
-    println!("💾 Saving {} frames to: {}", video_recorder.frames.len(), frames_dir);
-    
-    // Save all frames for 15-second video (450 frames at 30fps = 15 seconds)
-    for (i, frame) in video_recorder.frames.iter().enumerate() {
-        let frame_path = format!("{}/frame_{:04}.png", frames_dir, i);
-        
-        
-        // Save as PNG image
-        let _ = save_frame_as_png(&frame_path, frame, video_recorder.frame_width, video_recorder.frame_height);
-    }
-    
-    // Create metadata file  
-    let metadata_file = filename.replace(".mp4", "_metadata.txt");
+    // Metadata file, finished with the clip list once the clips are encoded
     let metadata = format!(
-        "Generation {}\nChanges: {}\nAvg Goal Time: {:.1}s\nReturn time: {:.1}s\nFrames: {}\nDuration: {:.1} seconds (6x speed from entire simulation)\n",
+        "Generation {}\nChanges: {}\nAvg Goal Time: {:.1}s\nReturn time: {:.1}s\nChallenge {} score: {:.1} / {:.0} ({})\nFrames: {} at {} fps\nDuration: {:.1} seconds{}\nSpeedup: {:.1}x ({:.1}s of simulation, a frame every {:.3}s)\n{}",
         generation_info.current_generation,
         video_recorder.changes_description,
         performance_tracker.average_time_since_goal,
         performance_tracker.average_return_time,
-        video_recorder.frames.len(),
-        video_recorder.frames.len() as f32 / 6.0 / 30.0 // frames / speedup / fps
+        challenge_score.challenge,
+        challenge_score.score,
+        challenge_score.pass_score,
+        challenge_score.verdict.label(),
+        video_recorder.stream.frames_written,
+        video_recorder.fps,
+        timing.duration,
+        video_recorder.target_duration.map_or(String::new(), |target| format!(" (target {:.0}s)", target)),
+        timing.effective_speedup,
+        recorded_seconds,
+        video_recorder.frame_interval,
+        if video_recorder.pheromone_stream.is_some() { format!("Pheromone video: {}\n", pheromone_filename) } else { String::new() }
     );
+    let (generation_json, run_json) = generation_info_json(generation_info, performance_tracker, challenge_score, annotations, video_recorder.format, &timing, setup);
     
-    if let Err(e) = fs::write(&metadata_file, metadata) {
-        println!("❌ Failed to write metadata: {}", e);
-    } else {
-        println!("✅ Video metadata saved: {}", metadata_file);
-    }
-    
-    // Update generation_info.json with current performance metrics
-    update_generation_info(generation_info, performance_tracker);
+    let save = RunSave {
+        fps: video_recorder.fps,
+        stream: std::mem::take(&mut video_recorder.stream),
+        pheromone_stream: video_recorder.pheromone_stream.as_mut().map(std::mem::take),
+        soundtrack: video_recorder.soundtrack.take(),
+        run_samples: std::mem::take(&mut video_recorder.run_samples),
+        highlights: std::mem::take(highlights),
+        replays: std::mem::take(&mut replay.encoding),
+        metadata,
+        markers: annotations.summary(),
+        generation_json,
+        run_json,
+        filename,
+        pheromone_filename,
+        stem,
+    };
     
-    // Clear frames for next test
-    video_recorder.frames.clear();
     video_recorder.test_number += 1;
     
     // Update changes description for next test
     video_recorder.changes_description = "Algorithm optimization iteration".to_string();
+    save
+}
+
+/// Everything the end of a run still has to write, done on its own thread so the next curriculum
+/// stage carries on and the window can close while ffmpeg and the disk catch up
+pub struct RunSave {
+    stem: String,
+    filename: String,
+    pheromone_filename: String,
+    fps: u32,
+    stream: FrameStream,
+    pheromone_stream: Option<FrameStream>,
+    soundtrack: Option<Soundtrack>,
+    run_samples: RunSamples,
+    highlights: HighlightClips, // Clips whose encoder may still be running
+    replays: Vec<FrameSink>, // Replays whose encoder may still be running
+    metadata: String, // Metadata file up to the clip list
+    markers: String,
+    generation_json: serde_json::Value,
+    run_json: serde_json::Value,
+}
+
+impl RunSave {
+    fn write(mut self) {
+        let saved = finish_stream(&mut self.stream, &self.filename, self.fps);
+        if let Some(soundtrack) = self.soundtrack.as_ref().filter(|soundtrack| saved && !soundtrack.is_empty()) {
+            match mux_soundtrack(&self.filename, &self.stem, soundtrack, self.fps) {
+                Ok(()) => info!("🔊 Audio track added to {}", self.filename),
+                Err(e) => warn!("⚠️ Couldn't add the audio track to {} ({}), the video is silent", self.filename, e),
+            }
+        }
+        if let Some(stream) = self.pheromone_stream.as_mut() {
+            finish_stream(stream, &self.pheromone_filename, self.fps);
+        }
+        collect_highlight_clips(&mut self.highlights, true);
+        collect_replays(&mut self.replays, true);
+        
+        let metadata_file = format!("{}_metadata.txt", self.stem);
+        let metadata = format!("{}Highlights:\n{}Markers:\n{}", self.metadata, self.highlights.summary(), self.markers);
+        if let Err(e) = fs::write(&metadata_file, metadata) {
+            error!("❌ Failed to write metadata: {}", e);
+        } else {
+            info!("✅ Video metadata saved: {}", metadata_file);
+        }
+        save_run_previews(&self.run_samples, &self.stem);
+        
+        // Update generation_info.json with current performance metrics
+        write_generation_info(&self.generation_json, &self.run_json, &self.stem);
+        info!("🏁 Everything for {} is written", self.stem);
+    }
+}
+
+/// End-of-run saves still writing. Each waits for the one before it, so generation_info.json and the
+/// history are updated in run order, and main waits for the last once the window has closed.
+#[derive(Resource, Default)]
+pub struct PendingSaves {
+    last: Option<JoinHandle<()>>,
+}
+
+impl PendingSaves {
+    /// Write `save` on a background thread once the saves before it are done
+    pub fn start(&mut self, save: RunSave) {
+        let previous = self.last.take();
+        let started = std::thread::Builder::new()
+            .name("run save".to_string())
+            .spawn(move || {
+                if let Some(previous) = previous {
+                    let _ = previous.join();
+                }
+                save.write();
+            });
+        match started {
+            Ok(handle) => self.last = Some(handle),
+            Err(e) => error!("❌ Failed to start the end-of-run save thread: {}", e),
+        }
+    }
+
+    /// Block until every save has finished
+    pub fn wait(self) {
+        let Some(last) = self.last else { return };
+        if !last.is_finished() {
+            info!("⏳ Window closed, finishing the end-of-run saves...");
+        }
+        if last.join().is_err() {
+            error!("❌ An end-of-run save panicked");
+        }
+    }
+}
+
+/// Thumbnail of the final frame and a 3x3 contact sheet across the run, beside the metadata file
+fn save_run_previews(run_samples: &RunSamples, stem: &str) {
+    let previews = [("thumbnail", run_samples.thumbnail()), ("contact_sheet", run_samples.contact_sheet())];
+    for (name, image) in previews {
+        let Some((width, height, data)) = image else {
+            continue;
+        };
+        let path = format!("{}_{}.png", stem, name);
+        match save_frame_as_png(&path, &data, width, height) {
+            Ok(()) => info!("🖼️ Saved {}: {}", name.replace('_', " "), path),
+            Err(e) => warn!("⚠️ Failed to save {}: {}", path, e),
+        }
+    }
+}
+
+/// FFmpeg build shipped alongside the project; ffmpeg on the PATH is used when it is missing
+const LOCAL_FFMPEG: &str = "ffmpeg/ffmpeg-2025-08-20-git-4d7c609be3-full_build/bin/ffmpeg.exe";
+
+fn ffmpeg_path() -> &'static str {
+    if std::path::Path::new(LOCAL_FFMPEG).exists() { LOCAL_FFMPEG } else { "ffmpeg" }
+}
+
+/// Width of exported GIFs; height follows the frame's aspect ratio
+const GIF_WIDTH: u32 = 240;
+/// GIFs drop frames down to this rate to stay small
+const GIF_MAX_FPS: u32 = 15;
+
+/// H.264 at the given bitrate with `codec`; yuv420p needs even dimensions (Quick Sync wants nv12)
+fn mp4_output_args(bitrate: &str, codec: &str) -> Vec<String> {
+    let pix_fmt = if codec == "h264_qsv" { "nv12" } else { "yuv420p" };
+    ["-vf", "pad=ceil(iw/2)*2:ceil(ih/2)*2", "-c:v", codec, "-pix_fmt", pix_fmt, "-b:v", bitrate]
+        .iter().map(|arg| arg.to_string()).collect()
+}
+
+/// VP9 at the given bitrate, tuned for speed since frames are encoded as the run goes
+fn webm_output_args(bitrate: &str) -> Vec<String> {
+    ["-vf", "pad=ceil(iw/2)*2:ceil(ih/2)*2", "-c:v", "libvpx-vp9", "-pix_fmt", "yuv420p", "-b:v", bitrate,
+        "-deadline", "realtime", "-cpu-used", "8", "-row-mt", "1"]
+        .iter().map(|arg| arg.to_string()).collect()
+}
+
+/// Downscale, then build a palette from the whole run and dither every frame against it
+fn gif_output_args(fps: u32) -> Vec<String> {
+    let filter = format!(
+        "fps={},scale={}:-1:flags=lanczos,split[a][b];[a]palettegen=stats_mode=diff[p];[b][p]paletteuse=dither=bayer",
+        fps.min(GIF_MAX_FPS), GIF_WIDTH
+    );
+    vec!["-filter_complex".to_string(), filter, "-loop".to_string(), "0".to_string()]
+}
+
+/// GPU H.264 encoders tried in order by `--hw-encode`
+const HARDWARE_H264_ENCODERS: [&str; 4] = ["h264_nvenc", "h264_qsv", "h264_amf", "h264_videotoolbox"];
+
+/// The first hardware encoder that can encode a test frame on this machine, probed once per run.
+/// ffmpeg lists encoders it was built with even when the GPU or driver is missing, so listing isn't enough.
+fn hardware_h264_encoder() -> Option<&'static str> {
+    static PROBED: OnceLock<Option<&'static str>> = OnceLock::new();
+    *PROBED.get_or_init(|| {
+        let found = HARDWARE_H264_ENCODERS.into_iter().find(|&codec| {
+            Command::new(ffmpeg_path())
+                .args(["-hide_banner", "-loglevel", "error", "-f", "lavfi", "-i", "color=c=black:s=256x256", "-frames:v", "1"])
+                .args(mp4_output_args("1M", codec))
+                .args(["-f", "null", "-"])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .is_ok_and(|status| status.success())
+        });
+        match found {
+            Some(codec) => info!("🚀 Hardware video encoder: {}", codec),
+            None => info!("🐢 No working hardware video encoder, using libx264"),
+        }
+        found
+    })
+}
+
+/// Highlight clips play at this speedup, slower than the main video so the moment can be followed
+const HIGHLIGHT_SPEEDUP: f32 = 2.0;
+
+/// Encode the highlight clips that are ready as `..._highlight_01_first_delivery.mp4` and so on next to
+/// the video. Each clip gets its own encoder thread; clips from earlier in the run are collected once
+/// their thread is done.
+fn save_highlight_clips(video_recorder: &VideoRecorder, highlights: &mut HighlightClips, generation_info: &GenerationInfo, now: f32) {
+    start_highlight_clips(video_recorder, highlights, generation_info, now, false);
+    collect_highlight_clips(highlights, false);
+}
+
+/// Start an encoder thread for each clip whose window has passed, or for every pending clip when `flush` is set
+fn start_highlight_clips(video_recorder: &VideoRecorder, highlights: &mut HighlightClips, generation_info: &GenerationInfo, now: f32, flush: bool) {
+    let fps = ((HIGHLIGHT_SPEEDUP / video_recorder.frame_interval).round() as u32).clamp(1, video_recorder.fps);
+    for (name, frames) in highlights.take_finished(now, flush, &video_recorder.frame_pool) {
+        if frames.is_empty() {
+            continue;
+        }
+        let stem = format!("{}_highlight_{:02}_{}", video_stem(generation_info), highlights.saved.len() + highlights.encoding.len() + 1, name);
+        let mut sink = FrameSink::open(sink_spec(video_recorder, &stem, fps), frames.len());
+        for frame in frames {
+            let _ = sink.send(frame); // The channel holds the whole clip
+        }
+        sink.close_input();
+        highlights.encoding.push(sink);
+    }
+}
+
+/// Note the clips whose encoder is done, or wait for all of them when `flush` is set
+fn collect_highlight_clips(highlights: &mut HighlightClips, flush: bool) {
+    let (done, encoding): (Vec<FrameSink>, Vec<FrameSink>) = highlights.encoding.drain(..)
+        .partition(|sink| flush || sink.is_done());
+    highlights.encoding = encoding;
+    for sink in done {
+        let path = sink.path.clone();
+        match sink.finish(VecDeque::new()) {
+            Ok(SinkOutcome::Video) => {
+                info!("🎬 Highlight clip saved: {}", path);
+                highlights.saved.push(path);
+            }
+            Ok(SinkOutcome::Frames(dir)) => highlights.saved.push(dir),
+            Err(e) => warn!("⚠️ Failed to save highlight clip {}: {}", path, e),
+        }
+    }
+}
+
+/// Encode the replay buffer when the hotkey asked for it, as `..._replay_01.mp4` and so on next to the
+/// video, at the video's own speed. Like highlight clips, each replay gets its own encoder thread;
+/// the end-of-run save waits for any still running.
+fn save_replay(video_recorder: &VideoRecorder, replay: &mut ReplayBuffer, generation_info: &GenerationInfo) {
+    if let Some(frames) = replay.take_requested(&video_recorder.frame_pool) {
+        if frames.is_empty() {
+            warn!("⚠️ No frames captured yet, nothing to save");
+        } else {
+            replay.saved += 1;
+            let stem = format!("{}_replay_{:02}", video_stem(generation_info), replay.saved);
+            let mut sink = FrameSink::open(sink_spec(video_recorder, &stem, video_recorder.fps), frames.len());
+            for frame in frames {
+                let _ = sink.send(frame); // The channel holds the whole replay
+            }
+            sink.close_input();
+            replay.encoding.push(sink);
+        }
+    }
+    collect_replays(&mut replay.encoding, false);
+}
+
+/// Report the replays whose encoder is done, or wait for all of them when `flush` is set
+fn collect_replays(encoding: &mut Vec<FrameSink>, flush: bool) {
+    let (done, still_encoding): (Vec<FrameSink>, Vec<FrameSink>) = encoding.drain(..)
+        .partition(|sink| flush || sink.is_done());
+    *encoding = still_encoding;
+    for sink in done {
+        let path = sink.path.clone();
+        match sink.finish(VecDeque::new()) {
+            Ok(SinkOutcome::Video) => info!("⏺️ Replay saved: {}", path),
+            Ok(SinkOutcome::Frames(dir)) => info!("💾 Replay frames saved to: {}", dir),
+            Err(e) => warn!("⚠️ Failed to save replay {}: {}", path, e),
+        }
+    }
+}
+
+/// Everything an encoder thread needs to open its output
+struct SinkSpec {
+    path: String,
+    frames_dir: String, // PNG fallback when ffmpeg can't start or dies mid-run
+    width: u32,
+    height: u32,
+    fps: u32,
+    format: VideoFormat,
+    bitrate: String,
+    hardware: bool,
+    tags: Vec<(&'static str, String)>, // Container metadata, MP4 and WebM only
+    pool: FramePool, // Where written frames go back for reuse
+}
+
+/// `stem.mp4` (or `.webm`, `.gif`) at `fps` with the recorder's size and encoder settings
+fn sink_spec(video_recorder: &VideoRecorder, stem: &str, fps: u32) -> SinkSpec {
+    SinkSpec {
+        path: format!("{}.{}", stem, video_recorder.format.extension()),
+        frames_dir: format!("{}_frames", stem),
+        width: video_recorder.frame_width,
+        height: video_recorder.frame_height,
+        fps,
+        format: video_recorder.format,
+        bitrate: video_recorder.bitrate.clone(),
+        hardware: video_recorder.hardware_encoding,
+        tags: video_recorder.container_tags.clone(),
+        pool: video_recorder.frame_pool.clone(),
+    }
+}
+
+/// How a sink's output turned out
+pub enum SinkOutcome {
+    Video,
+    Frames(String), // PNG sequence directory, when ffmpeg couldn't finish the video
+}
+
+/// Frames are handed to a worker thread that feeds ffmpeg, or writes PNGs in parallel when ffmpeg
+/// isn't available, so neither a slow encoder nor PNG compression holds up the simulation
+pub struct FrameSink {
+    pub path: String,
+    sender: Option<SyncSender<Vec<u8>>>,
+    worker: Option<JoinHandle<Result<SinkOutcome, String>>>,
+    written: Arc<AtomicUsize>, // Frames the worker has passed on, for the progress report
+    sent: usize,
+}
+
+/// PNG fallback frames compressed together across the rayon pool
+const PNG_BATCH: usize = 16;
+/// How often a sink being finished reports its progress
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+impl FrameSink {
+    /// Start the worker thread; up to `capacity` frames can queue for it before `send` hands them back
+    fn open(spec: SinkSpec, capacity: usize) -> Self {
+        let (sender, receiver) = sync_channel(capacity.max(1));
+        let path = spec.path.clone();
+        let written = Arc::new(AtomicUsize::new(0));
+        let worker_written = written.clone();
+        let worker = std::thread::Builder::new()
+            .name("video encoder".to_string())
+            .spawn(move || run_sink(spec, receiver, worker_written));
+        let (sender, worker) = match worker {
+            Ok(worker) => (Some(sender), Some(worker)),
+            Err(e) => {
+                error!("❌ Failed to start encoder thread for {}: {}", path, e);
+                (None, None)
+            }
+        };
+        Self { path, sender, worker, written, sent: 0 }
+    }
+
+    /// Queue a frame without waiting; the frame comes back when the worker is behind or gone
+    fn send(&mut self, frame: Vec<u8>) -> Result<(), Vec<u8>> {
+        let Some(sender) = self.sender.as_ref() else { return Err(frame) };
+        match sender.try_send(frame) {
+            Ok(()) => {
+                self.sent += 1;
+                Ok(())
+            }
+            Err(TrySendError::Full(frame) | TrySendError::Disconnected(frame)) => Err(frame),
+        }
+    }
+
+    /// No more frames; the worker finishes the file on its own
+    fn close_input(&mut self) {
+        self.sender = None;
+    }
+
+    fn is_done(&self) -> bool {
+        self.worker.as_ref().is_none_or(|worker| worker.is_finished())
+    }
+
+    /// Queue `remaining` (waiting for room this time), close the input and wait for the worker,
+    /// logging progress while it catches up
+    fn finish(mut self, remaining: VecDeque<Vec<u8>>) -> Result<SinkOutcome, String> {
+        if let Some(sender) = self.sender.take() {
+            for frame in remaining {
+                if sender.send(frame).is_err() {
+                    break;
+                }
+                self.sent += 1;
+            }
+        }
+        let Some(worker) = self.worker.take() else { return Err("encoder thread never started".to_string()) };
+        
+        let mut last_report = Instant::now();
+        while !worker.is_finished() {
+            std::thread::sleep(Duration::from_millis(20));
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                info!("⏳ Finishing {}: {} of {} frames written", self.path, self.written.load(Ordering::Relaxed), self.sent);
+                last_report = Instant::now();
+            }
+        }
+        worker.join().unwrap_or_else(|_| Err("encoder thread panicked".to_string()))
+    }
+}
+
+/// Encoder thread: stream frames into ffmpeg until the sender closes, switching to a PNG sequence
+/// if ffmpeg can't start or stops accepting frames
+fn run_sink(spec: SinkSpec, receiver: Receiver<Vec<u8>>, written: Arc<AtomicUsize>) -> Result<SinkOutcome, String> {
+    let (mut output_args, codec) = match spec.format {
+        VideoFormat::Mp4 => {
+            let codec = if spec.hardware { hardware_h264_encoder().unwrap_or("libx264") } else { "libx264" };
+            (mp4_output_args(&spec.bitrate, codec), codec)
+        }
+        VideoFormat::Webm => (webm_output_args(&spec.bitrate), "libvpx-vp9"),
+        VideoFormat::Gif => (gif_output_args(spec.fps), "gif"),
+    };
+    if spec.format != VideoFormat::Gif {
+        for (key, value) in &spec.tags {
+            output_args.extend(["-metadata".to_string(), format!("{}={}", key, value)]);
+        }
+    }
+    let mut encoder = match start_encoder(&spec.path, spec.width, spec.height, spec.fps, &output_args) {
+        Ok(encoder) => {
+            info!("🎞️ Streaming frames to {} ({})", spec.path, codec);
+            Some(encoder)
+        }
+        Err(e) => {
+            // Without ffmpeg, keep the frames as a PNG sequence that can be converted later
+            warn!("⚠️ Can't encode video ({}), saving PNG frames to {} instead", e, spec.frames_dir);
+            None
+        }
+    };
+    let mut frames_dir_ready = false;
+    
+    while let Ok(frame) = receiver.recv() {
+        let mut batch = vec![frame];
+        batch.extend(receiver.try_iter().take(PNG_BATCH - 1));
+        
+        if let Some((_, stdin)) = encoder.as_mut() {
+            let mut accepted = 0;
+            for frame in &batch {
+                if let Err(e) = stdin.write_all(frame) {
+                    warn!("⚠️ Video encoder stopped ({}), saving the remaining frames as PNGs", e);
+                    encoder = None;
+                    break;
+                }
+                accepted += 1;
+                written.fetch_add(1, Ordering::Relaxed);
+            }
+            for frame in batch.drain(..accepted) {
+                spec.pool.give(frame);
+            }
+        }
+        if batch.is_empty() {
+            continue;
+        }
+        
+        if !frames_dir_ready {
+            fs::create_dir_all(&spec.frames_dir).map_err(|e| format!("failed to create frames directory: {}", e))?;
+            frames_dir_ready = true;
+        }
+        let first_index = written.load(Ordering::Relaxed);
+        batch.par_iter().enumerate().for_each(|(offset, frame)| {
+            let frame_path = format!("{}/frame_{:04}.png", spec.frames_dir, first_index + offset);
+            if let Err(e) = save_frame_as_png(&frame_path, frame, spec.width, spec.height) {
+                warn!("⚠️ Failed to save {}: {}", frame_path, e);
+            }
+        });
+        written.fetch_add(batch.len(), Ordering::Relaxed);
+        for frame in batch {
+            spec.pool.give(frame);
+        }
+    }
+    
+    match encoder {
+        Some((child, stdin)) => {
+            finish_encoder(child, stdin).map_err(|e| e.to_string())?;
+            Ok(SinkOutcome::Video)
+        }
+        _ => Ok(SinkOutcome::Frames(spec.frames_dir)),
+    }
+}
+
+/// Spent frame buffers kept at most
+const POOLED_FRAMES: usize = 32;
+
+/// Frame buffers that encoder threads, the replay buffer and highlight clips are done with, handed
+/// out again for the next captures so a long run reuses a few allocations instead of making a fresh
+/// one per frame
+#[derive(Clone, Default)]
+pub struct FramePool {
+    free: Arc<Mutex<Vec<Vec<u8>>>>,
+}
+
+impl FramePool {
+    /// A zeroed frame of `len` bytes
+    pub fn take(&self, len: usize) -> Vec<u8> {
+        let mut frame = self.free.lock().unwrap().pop().unwrap_or_default();
+        frame.clear();
+        frame.resize(len, 0);
+        frame
+    }
+
+    /// A copy of `frame` in a reused buffer
+    pub fn copy_of(&self, frame: &[u8]) -> Vec<u8> {
+        let mut copy = self.free.lock().unwrap().pop().unwrap_or_default();
+        copy.clear();
+        copy.extend_from_slice(frame);
+        copy
+    }
+
+    /// Hand a frame back; beyond POOLED_FRAMES spare buffers it is simply freed
+    pub fn give(&self, frame: Vec<u8>) {
+        let mut free = self.free.lock().unwrap();
+        if free.len() < POOLED_FRAMES {
+            free.push(frame);
+        }
+    }
+}
+
+/// One video file being written: frames captured but not yet handed to the sink, and the sink itself
+#[derive(Default)]
+pub struct FrameStream {
+    pub frames: VecDeque<Vec<u8>>, // RGBA frames waiting for the sink
+    pub frames_written: usize, // Frames handed to the encoder thread this run
+    pub sink: Option<FrameSink>, // Opened when recording starts, finished when the video is saved
+}
+
+/// `simulation_videos/####_description`, the shared prefix of the video, frames and metadata files
+fn video_stem(generation_info: &GenerationInfo) -> String {
+    format!(
+        "simulation_videos/{:04}_{}",
+        generation_info.current_generation,
+        generation_info.description.replace(" ", "_").to_lowercase()
+    )
+}
+
+/// `..._pheromones`, prefix of the pheromone-only companion video
+fn pheromone_video_stem(stem: &str) -> String {
+    format!("{}_pheromones", stem)
+}
+
+/// Start the encoder thread for the run's video at `stem`
+fn open_frame_sink(video_recorder: &VideoRecorder, stem: &str) -> Option<FrameSink> {
+    if let Err(e) = fs::create_dir_all("simulation_videos") {
+        error!("❌ Failed to create videos directory: {}", e);
+        return None;
+    }
+    let frame_bytes = (video_recorder.frame_width * video_recorder.frame_height * 4).max(1) as usize;
+    Some(FrameSink::open(sink_spec(video_recorder, stem, video_recorder.fps), video_recorder.stream_budget() / frame_bytes))
+}
+
+/// Hand every pending frame of the main and pheromone videos to their sinks
+fn write_pending_frames(video_recorder: &mut VideoRecorder) {
+    let frame_bytes = (video_recorder.frame_width * video_recorder.frame_height * 4).max(1) as usize;
+    let (budget, pool) = (video_recorder.stream_budget(), video_recorder.frame_pool.clone());
+    write_stream(&mut video_recorder.stream, frame_bytes, budget, &pool);
+    if let Some(stream) = video_recorder.pheromone_stream.as_mut() {
+        write_stream(stream, frame_bytes, budget, &pool);
+    }
+}
+
+/// Queue the stream's pending frames for its encoder thread. Frames it has no room for wait here,
+/// and the oldest go back to the pool once they exceed the stream's share of the memory budget.
+fn write_stream(stream: &mut FrameStream, frame_bytes: usize, budget: usize, pool: &FramePool) {
+    
+    while let Some(frame) = stream.frames.pop_front() {
+        let Some(sink) = stream.sink.as_mut() else {
+            stream.frames.push_front(frame);
+            break;
+        };
+        if let Err(frame) = sink.send(frame) {
+            stream.frames.push_front(frame);
+            break;
+        }
+        stream.frames_written += 1;
+    }
+    while stream.frames.len() * frame_bytes > budget {
+        if let Some(frame) = stream.frames.pop_front() {
+            pool.give(frame);
+        }
+    }
+}
+
+/// Hand over the stream's last frames and wait for its encoder to finish the file
+fn finish_stream(stream: &mut FrameStream, filename: &str, fps: u32) -> bool {
+    let Some(sink) = stream.sink.take() else {
+        warn!("⚠️ No frame sink was open for {}, nothing was saved", filename);
+        return false;
+    };
+    match sink.finish(std::mem::take(&mut stream.frames)) {
+        Ok(SinkOutcome::Video) => {
+            info!("✅ Video saved: {} ({} fps)", filename, fps);
+            return true;
+        }
+        Ok(SinkOutcome::Frames(dir)) => info!("💾 {} frames saved to: {}", stream.frames_written, dir),
+        Err(e) => error!("❌ Video encoding failed for {}: {}", filename, e),
+    }
+    false
+}
+
+/// Render the soundtrack to a WAV and remux it into the finished video, copying the video stream as is
+fn mux_soundtrack(filename: &str, stem: &str, soundtrack: &Soundtrack, fps: u32) -> Result<(), Box<dyn std::error::Error>> {
+    let audio_path = format!("{}_audio.wav", stem);
+    soundtrack.write_wav(&audio_path, fps)?;
+    let extension = filename.rsplit('.').next().unwrap_or("mp4");
+    let muxed_path = format!("{}_with_audio.{}", stem, extension);
+    let audio_codec = if extension == "webm" { "libopus" } else { "aac" };
+    let output = Command::new(ffmpeg_path())
+        .args(["-y", "-loglevel", "error", "-i", filename, "-i", &audio_path])
+        .args(["-map", "0:v", "-map", "1:a", "-map_metadata", "0", "-c:v", "copy", "-c:a", audio_codec, "-b:a", "96k", "-shortest"])
+        .arg(&muxed_path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output();
+    let _ = fs::remove_file(&audio_path);
+    let output = output.map_err(|e| format!("could not start ffmpeg: {}", e))?;
+    if !output.status.success() {
+        let _ = fs::remove_file(&muxed_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("ffmpeg {}: {}", output.status, stderr.lines().last().unwrap_or("")).into());
+    }
+    fs::rename(&muxed_path, filename)?;
+    Ok(())
+}
+
+/// Spawn ffmpeg reading raw RGBA frames from stdin; `output_args` pick the codec for `path`
+fn start_encoder(path: &str, width: u32, height: u32, fps: u32, output_args: &[String]) -> Result<(Child, BufWriter<ChildStdin>), Box<dyn std::error::Error>> {
+    let mut child = Command::new(ffmpeg_path())
+        .args(["-y", "-loglevel", "error", "-f", "rawvideo", "-pix_fmt", "rgba"])
+        .args(["-s", &format!("{}x{}", width, height), "-r", &fps.to_string(), "-i", "-"])
+        .args(output_args)
+        .arg(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not start ffmpeg: {}", e))?;
+    let stdin = BufWriter::new(child.stdin.take().ok_or("ffmpeg stdin unavailable")?);
+    Ok((child, stdin))
+}
+
+/// Close the encoder's input and wait for it to finish writing the file
+fn finish_encoder(child: Child, mut stdin: BufWriter<ChildStdin>) -> Result<(), Box<dyn std::error::Error>> {
+    stdin.flush()?;
+    drop(stdin); // Closing stdin ends the stream
+    
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("ffmpeg {}: {}", output.status, stderr.lines().last().unwrap_or("")).into());
+    }
+    Ok(())
 }
 
 fn save_frame_as_png(
@@ -435,7 +1408,7 @@ fn save_frame_as_png(
     use png::ColorType;
     use std::io::BufWriter;
     
-    println!("🔍 PNG save: {}x{}, {} bytes, path: {}", width, height, frame_data.len(), path);
+    debug!("🔍 PNG save: {}x{}, {} bytes, path: {}", width, height, frame_data.len(), path);
     
     // Check if frame data has the right size for RGBA
     let expected_size = (width * height * 4) as usize;
@@ -465,184 +1438,260 @@ fn render_text_overlay(
     height: u32,
     generation_info: &GenerationInfo,
     performance_tracker: &PerformanceTracker,
+    performance_history: &PerformanceHistory,
+    challenge_score: &ChallengeScore,
     elapsed_time: f32,
+    font: Option<&FontArc>,
 ) {
-    // Simple pixel-based text rendering - create bright colored pixels for text visibility
-    // This is a basic implementation for readability
+    // Without a font (Bevy built without default_font) the band stays blank
+    let Some(font) = font else { return };
     
     // Line 1: Generation info (y = 10-15)
     let gen_text = format!("GEN {}: {}", generation_info.current_generation, generation_info.description);
-    render_text_line(frame, width, &gen_text, 5, 10, [255, 255, 255]); // White text
+    render_text_line(frame, width, font, &gen_text, 5, 10, OVERLAY_TEXT_SIZE, [255, 255, 255]); // White text
     
     // Line 2: Primary metric - Average Time Since Goal
     let perf_text = format!("AvgGoalTime: {:.1}s | {:.1}s return", 
         performance_tracker.average_time_since_goal,
         performance_tracker.average_return_time
     );
-    render_text_line(frame, width, &perf_text, 5, 25, [0, 255, 255]); // Cyan text
+    render_text_line(frame, width, font, &perf_text, 5, 25, OVERLAY_TEXT_SIZE, [0, 255, 255]); // Cyan text
     
     // Line 3: Time and issues (y = 40-45) - Split into two lines to prevent overflow
     let time_text = format!("T: {:.0}s elapsed", elapsed_time);
-    render_text_line(frame, width, &time_text, 5, 40, [255, 255, 0]); // Yellow text
+    render_text_line(frame, width, font, &time_text, 5, 40, OVERLAY_TEXT_SIZE, [255, 255, 0]); // Yellow text
     
     // Line 4: Issues status (y = 55-60)
     let issues_text = format!("Issues: {}stuck {}lost", 
         performance_tracker.stuck_ants_count,
         performance_tracker.lost_ants_count
     );
-    render_text_line(frame, width, &issues_text, 5, 55, [255, 100, 0]); // Orange text
+    render_text_line(frame, width, font, &issues_text, 5, 55, OVERLAY_TEXT_SIZE, [255, 100, 0]); // Orange text
     
     // Line 5: Deliveries count (y = 70-75) - Move down to accommodate split lines
     let delivery_text = format!("D: {} deliveries total", performance_tracker.successful_deliveries);
-    render_text_line(frame, width, &delivery_text, 5, 70, [0, 255, 0]); // Green text
+    render_text_line(frame, width, font, &delivery_text, 5, 70, OVERLAY_TEXT_SIZE, [0, 255, 0]); // Green text
+    
+    // Line 6: Challenge score and verdict (y = 85-90)
+    let score_text = format!("SCORE {:.1} of {:.0} {}", challenge_score.score, challenge_score.pass_score, challenge_score.verdict.label());
+    let score_color = match challenge_score.verdict {
+        Verdict::Running => [255, 215, 0], // Gold text
+        Verdict::Success => [0, 255, 0],
+        Verdict::Failure => [255, 50, 50],
+    };
+    render_text_line(frame, width, font, &score_text, 5, 85, OVERLAY_TEXT_SIZE, score_color);
+    
+    // Run-so-far trends in the top-right corner, same colors as the on-screen chart
+    if width >= SPARKLINE_WIDTH + 200 {
+        let x = width - SPARKLINE_WIDTH - 6;
+        let rates: Vec<f32> = performance_history.samples.iter().map(|sample| sample.deliveries_per_minute).collect();
+        let goal_times: Vec<f32> = performance_history.samples.iter().map(|sample| sample.average_time_since_goal).collect();
+        render_text_line(frame, width, font, "D/min", x, 5, LABEL_TEXT_SIZE, [0, 220, 0]);
+        render_sparkline(frame, width, &rates, x, 15, [0, 220, 0]);
+        render_text_line(frame, width, font, "GoalT", x, 50, LABEL_TEXT_SIZE, [255, 140, 0]);
+        render_sparkline(frame, width, &goal_times, x, 60, [255, 140, 0]);
+    }
 }
 
-fn render_text_line(frame: &mut [u8], width: u32, text: &str, x_start: u32, y_start: u32, color: [u8; 3]) {
-    // Better character rendering with actual readable patterns
-    let char_width = 6;
-    let char_height = 8;
-    let char_spacing = 1;
-    
-    for (char_index, ch) in text.chars().enumerate() {
-        let char_x = x_start + (char_index as u32) * (char_width + char_spacing);
-        
-        // Get the bitmap pattern for this character
-        let pattern = get_char_pattern(ch);
-        
-        // Render the character based on its bitmap pattern
-        for (dy, row) in pattern.iter().enumerate() {
-            for dx in 0..char_width {
-                let px = char_x + dx;
-                let py = y_start + dy as u32;
-                
-                if px < width && py < 85 { // Keep within expanded text overlay area
-                    let idx = ((py * width + px) * 4) as usize;
-                    if idx + 3 < frame.len() {
-                        // Check if this pixel should be lit based on the bitmap
-                        let bit_index = char_width - 1 - dx; // Reverse for correct orientation
-                        let pixel_on = (row & (1 << bit_index)) != 0;
-                        
-                        if pixel_on {
-                            frame[idx] = color[0];     // R
-                            frame[idx + 1] = color[1]; // G
-                            frame[idx + 2] = color[2]; // B
-                            frame[idx + 3] = 255;      // Full opacity
-                        }
-                        // Don't modify transparent pixels (leave background as is)
-                    }
-                }
+/// Glyph height in pixels of the overlay lines and the smaller sparkline labels
+const OVERLAY_TEXT_SIZE: f32 = 13.0;
+const LABEL_TEXT_SIZE: f32 = 10.0;
+
+/// Size of each overlay sparkline in pixels
+const SPARKLINE_WIDTH: u32 = 90;
+const SPARKLINE_HEIGHT: u32 = 24;
+
+/// Filled sparkline of `values` squeezed into SPARKLINE_WIDTH columns, scaled to the largest value.
+/// The fill is dimmed so the top edge reads as the line.
+fn render_sparkline(frame: &mut [u8], width: u32, values: &[f32], x_start: u32, y_start: u32, color: [u8; 3]) {
+    if values.len() < 2 {
+        return;
+    }
+    let max = values.iter().cloned().fold(0.001, f32::max);
+    for column in 0..SPARKLINE_WIDTH {
+        let value = values[column as usize * (values.len() - 1) / (SPARKLINE_WIDTH - 1) as usize];
+        let bar = ((value / max).clamp(0.0, 1.0) * (SPARKLINE_HEIGHT - 1) as f32).round() as u32;
+        for dy in 0..=bar {
+            let py = y_start + SPARKLINE_HEIGHT - 1 - dy;
+            let px = x_start + column;
+            if px >= width || py >= TEXT_OVERLAY_HEIGHT {
+                continue;
             }
+            let idx = ((py * width + px) * 4) as usize;
+            let shade = if dy == bar { 1.0 } else { 0.35 };
+            frame[idx] = (color[0] as f32 * shade) as u8;
+            frame[idx + 1] = (color[1] as f32 * shade) as u8;
+            frame[idx + 2] = (color[2] as f32 * shade) as u8;
+            frame[idx + 3] = 255;
         }
     }
 }
 
-fn get_char_pattern(ch: char) -> [u8; 8] {
-    // 6x8 bitmap patterns for common characters (each u8 represents a row)
-    match ch {
-        'G' => [0b011110, 0b100001, 0b100000, 0b100111, 0b100001, 0b100001, 0b011110, 0b000000],
-        'E' => [0b111111, 0b100000, 0b100000, 0b111100, 0b100000, 0b100000, 0b111111, 0b000000],
-        'N' => [0b100001, 0b110001, 0b101001, 0b100101, 0b100011, 0b100001, 0b100001, 0b000000],
-        'R' => [0b111110, 0b100001, 0b100001, 0b111110, 0b100010, 0b100001, 0b100001, 0b000000],
-        'A' => [0b011110, 0b100001, 0b100001, 0b111111, 0b100001, 0b100001, 0b100001, 0b000000],
-        'T' => [0b111111, 0b001000, 0b001000, 0b001000, 0b001000, 0b001000, 0b001000, 0b000000],
-        'I' => [0b111111, 0b001000, 0b001000, 0b001000, 0b001000, 0b001000, 0b111111, 0b000000],
-        'O' => [0b011110, 0b100001, 0b100001, 0b100001, 0b100001, 0b100001, 0b011110, 0b000000],
-        'S' => [0b011111, 0b100000, 0b100000, 0b011110, 0b000001, 0b000001, 0b111110, 0b000000],
-        'L' => [0b100000, 0b100000, 0b100000, 0b100000, 0b100000, 0b100000, 0b111111, 0b000000],
-        'C' => [0b011110, 0b100001, 0b100000, 0b100000, 0b100000, 0b100001, 0b011110, 0b000000],
-        'D' => [0b111110, 0b100001, 0b100001, 0b100001, 0b100001, 0b100001, 0b111110, 0b000000],
-        'M' => [0b100001, 0b110011, 0b101101, 0b100001, 0b100001, 0b100001, 0b100001, 0b000000],
-        'P' => [0b111110, 0b100001, 0b100001, 0b111110, 0b100000, 0b100000, 0b100000, 0b000000],
-        'U' => [0b100001, 0b100001, 0b100001, 0b100001, 0b100001, 0b100001, 0b011110, 0b000000],
-        'V' => [0b100001, 0b100001, 0b100001, 0b100001, 0b010010, 0b001100, 0b001100, 0b000000],
-        'H' => [0b100001, 0b100001, 0b100001, 0b111111, 0b100001, 0b100001, 0b100001, 0b000000],
-        'F' => [0b111111, 0b100000, 0b100000, 0b111100, 0b100000, 0b100000, 0b100000, 0b000000],
-        'K' => [0b100001, 0b100010, 0b100100, 0b111000, 0b100100, 0b100010, 0b100001, 0b000000],
-        'Y' => [0b100001, 0b100001, 0b010010, 0b001100, 0b001000, 0b001000, 0b001000, 0b000000],
-        'B' => [0b111110, 0b100001, 0b100001, 0b111110, 0b100001, 0b100001, 0b111110, 0b000000],
-        'W' => [0b100001, 0b100001, 0b100001, 0b100001, 0b101101, 0b110011, 0b100001, 0b000000],
-        'X' => [0b100001, 0b010010, 0b001100, 0b001100, 0b010010, 0b100001, 0b100001, 0b000000],
-        'Z' => [0b111111, 0b000001, 0b000010, 0b001100, 0b010000, 0b100000, 0b111111, 0b000000],
-        'J' => [0b000001, 0b000001, 0b000001, 0b000001, 0b100001, 0b100001, 0b011110, 0b000000],
-        'Q' => [0b011110, 0b100001, 0b100001, 0b101001, 0b100010, 0b011110, 0b000001, 0b000000],
-        // Lowercase letters
-        'a' => [0b000000, 0b000000, 0b011110, 0b000001, 0b011111, 0b100001, 0b011111, 0b000000],
-        'b' => [0b100000, 0b100000, 0b111110, 0b100001, 0b100001, 0b100001, 0b111110, 0b000000],
-        'c' => [0b000000, 0b000000, 0b011110, 0b100000, 0b100000, 0b100000, 0b011110, 0b000000],
-        'd' => [0b000001, 0b000001, 0b011111, 0b100001, 0b100001, 0b100001, 0b011111, 0b000000],
-        'e' => [0b000000, 0b000000, 0b011110, 0b100001, 0b111110, 0b100000, 0b011110, 0b000000],
-        'f' => [0b001111, 0b010000, 0b010000, 0b111110, 0b010000, 0b010000, 0b010000, 0b000000],
-        'g' => [0b000000, 0b000000, 0b011111, 0b100001, 0b011111, 0b000001, 0b011110, 0b000000],
-        'h' => [0b100000, 0b100000, 0b111110, 0b100001, 0b100001, 0b100001, 0b100001, 0b000000],
-        'i' => [0b001000, 0b000000, 0b011000, 0b001000, 0b001000, 0b001000, 0b011100, 0b000000],
-        'j' => [0b000010, 0b000000, 0b000110, 0b000010, 0b000010, 0b100010, 0b011100, 0b000000],
-        'k' => [0b100000, 0b100000, 0b100010, 0b100100, 0b111000, 0b100100, 0b100010, 0b000000],
-        'l' => [0b011000, 0b001000, 0b001000, 0b001000, 0b001000, 0b001000, 0b011100, 0b000000],
-        'm' => [0b000000, 0b000000, 0b110110, 0b101101, 0b101101, 0b101101, 0b101101, 0b000000],
-        'n' => [0b000000, 0b000000, 0b111110, 0b100001, 0b100001, 0b100001, 0b100001, 0b000000],
-        'o' => [0b000000, 0b000000, 0b011110, 0b100001, 0b100001, 0b100001, 0b011110, 0b000000],
-        'p' => [0b000000, 0b000000, 0b111110, 0b100001, 0b111110, 0b100000, 0b100000, 0b000000],
-        'q' => [0b000000, 0b000000, 0b011111, 0b100001, 0b011111, 0b000001, 0b000001, 0b000000],
-        'r' => [0b000000, 0b000000, 0b101110, 0b110000, 0b100000, 0b100000, 0b100000, 0b000000],
-        's' => [0b000000, 0b000000, 0b011110, 0b100000, 0b011110, 0b000001, 0b111110, 0b000000],
-        't' => [0b010000, 0b010000, 0b111110, 0b010000, 0b010000, 0b010000, 0b001110, 0b000000],
-        'u' => [0b000000, 0b000000, 0b100001, 0b100001, 0b100001, 0b100001, 0b011111, 0b000000],
-        'v' => [0b000000, 0b000000, 0b100001, 0b100001, 0b010010, 0b010010, 0b001100, 0b000000],
-        'w' => [0b000000, 0b000000, 0b100001, 0b101101, 0b101101, 0b101101, 0b010010, 0b000000],
-        'x' => [0b000000, 0b000000, 0b100001, 0b010010, 0b001100, 0b010010, 0b100001, 0b000000],
-        'y' => [0b000000, 0b000000, 0b100001, 0b100001, 0b011111, 0b000001, 0b011110, 0b000000],
-        'z' => [0b000000, 0b000000, 0b111111, 0b000010, 0b001100, 0b100000, 0b111111, 0b000000],
-        // Emoji replacements with simple patterns  
-        '📊' => [0b111111, 0b001001, 0b010101, 0b100001, 0b100001, 0b111111, 0b000000, 0b000000], // Chart bars
-        '⏱' => [0b011110, 0b100001, 0b100101, 0b100101, 0b100001, 0b011110, 0b000000, 0b000000], // Clock
-        '️' => [0b000000, 0b000000, 0b000000, 0b000000, 0b000000, 0b000000, 0b000000, 0b000000], // Variation selector (invisible)
-        '🚨' => [0b001100, 0b010010, 0b100001, 0b111111, 0b111111, 0b111111, 0b000000, 0b000000], // Warning
-        '📈' => [0b000001, 0b000010, 0b000100, 0b001000, 0b010000, 0b100000, 0b111111, 0b000000], // Graph up
-        ' ' => [0b000000, 0b000000, 0b000000, 0b000000, 0b000000, 0b000000, 0b000000, 0b000000],
-        ':' => [0b000000, 0b011000, 0b011000, 0b000000, 0b011000, 0b011000, 0b000000, 0b000000],
-        '.' => [0b000000, 0b000000, 0b000000, 0b000000, 0b000000, 0b011000, 0b011000, 0b000000],
-        '/' => [0b000001, 0b000010, 0b000100, 0b001000, 0b010000, 0b100000, 0b000000, 0b000000],
-        '0' => [0b011110, 0b100001, 0b100011, 0b100101, 0b101001, 0b100001, 0b011110, 0b000000],
-        '1' => [0b001000, 0b011000, 0b001000, 0b001000, 0b001000, 0b001000, 0b011100, 0b000000],
-        '2' => [0b011110, 0b100001, 0b000001, 0b011110, 0b100000, 0b100000, 0b111111, 0b000000],
-        '3' => [0b011110, 0b100001, 0b000001, 0b011110, 0b000001, 0b100001, 0b011110, 0b000000],
-        '4' => [0b100001, 0b100001, 0b100001, 0b111111, 0b000001, 0b000001, 0b000001, 0b000000],
-        '5' => [0b111111, 0b100000, 0b100000, 0b111110, 0b000001, 0b000001, 0b111110, 0b000000],
-        '6' => [0b011110, 0b100000, 0b100000, 0b111110, 0b100001, 0b100001, 0b011110, 0b000000],
-        '7' => [0b111111, 0b000001, 0b000010, 0b000100, 0b001000, 0b010000, 0b100000, 0b000000],
-        '8' => [0b011110, 0b100001, 0b100001, 0b011110, 0b100001, 0b100001, 0b011110, 0b000000],
-        '9' => [0b011110, 0b100001, 0b100001, 0b011111, 0b000001, 0b000001, 0b011110, 0b000000],
-        // Default for unknown characters - show a small box
-        _ => [0b111111, 0b100001, 0b100001, 0b100001, 0b100001, 0b100001, 0b111111, 0b000000],
-    }
-}
-
-fn update_generation_info(generation_info: &GenerationInfo, performance_tracker: &PerformanceTracker) {
-    // Step 1: Archive current generation_info.json to generation_history.json
-    archive_current_generation_to_history();
-    
-    // Step 2: Update generation_info.json with new performance data
+/// Draw `text` with its top-left corner at (x_start, y_start) and glyphs `size` pixels tall.
+/// Glyph coverage is blended over the band, so small text stays smooth; nothing is drawn below it.
+fn render_text_line(frame: &mut [u8], width: u32, font: &FontArc, text: &str, x_start: u32, y_start: u32, size: f32, color: [u8; 3]) {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let mut caret = point(x_start as f32, y_start as f32 + scaled.ascent());
+    let mut previous = None;
+    for ch in text.chars() {
+        let glyph_id = scaled.glyph_id(ch);
+        if let Some(previous) = previous {
+            caret.x += scaled.kern(previous, glyph_id);
+        }
+        let glyph = glyph_id.with_scale_and_position(scaled.scale(), caret);
+        caret.x += scaled.h_advance(glyph_id);
+        previous = Some(glyph_id);
+
+        let Some(outline) = font.outline_glyph(glyph) else { continue };
+        let bounds = outline.px_bounds();
+        outline.draw(|dx, dy, coverage| {
+            let px = bounds.min.x as i32 + dx as i32;
+            let py = bounds.min.y as i32 + dy as i32;
+            if px < 0 || py < 0 || px as u32 >= width || py as u32 >= TEXT_OVERLAY_HEIGHT {
+                return;
+            }
+            let idx = ((py as u32 * width + px as u32) * 4) as usize;
+            let coverage = coverage.clamp(0.0, 1.0);
+            for channel in 0..3 {
+                let under = frame[idx + channel] as f32;
+                frame[idx + channel] = (under + (color[channel] as f32 - under) * coverage) as u8;
+            }
+            frame[idx + 3] = 255;
+        });
+    }
+}
+
+/// How the saved video maps onto the run. The effective speedup is measured from what was captured,
+/// so a run that ended early or dropped frames after a slow tick shows its real pace.
+struct VideoTiming {
+    fps: u32,
+    frames: usize,
+    duration: f32,
+    target_duration: Option<f32>,
+    effective_speedup: f32,
+}
+
+impl VideoTiming {
+    fn measure(video_recorder: &VideoRecorder, recorded_seconds: f32) -> Self {
+        let fps = video_recorder.fps.max(1);
+        let duration = video_recorder.stream.frames_written as f32 / fps as f32;
+        Self {
+            fps,
+            frames: video_recorder.stream.frames_written,
+            duration,
+            target_duration: video_recorder.target_duration,
+            effective_speedup: if duration > 0.0 { recorded_seconds / duration } else { video_recorder.speedup },
+        }
+    }
+}
+
+/// The new generation_info.json and the per-run sidecar, as the run ended
+fn generation_info_json(generation_info: &GenerationInfo, performance_tracker: &PerformanceTracker, challenge_score: &ChallengeScore, annotations: &Annotations, format: VideoFormat, timing: &VideoTiming, setup: &RunSetup) -> (serde_json::Value, serde_json::Value) {
+    let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let video_filename = format!("{:04}_{}.{}", generation_info.current_generation, generation_info.description.replace(" ", "_").to_lowercase(), format.extension());
+    let performance_metrics = serde_json::json!({
+        "average_time_since_goal_seconds": performance_tracker.average_time_since_goal,
+        "average_return_time_seconds": performance_tracker.average_return_time,
+        "successful_deliveries": performance_tracker.successful_deliveries,
+        "simulation_duration_seconds": 90,
+        "total_food_collected": performance_tracker.total_food_collected
+    });
+    let challenge = serde_json::json!({
+        "challenge": challenge_score.challenge,
+        "score": challenge_score.score,
+        "pass_score": challenge_score.pass_score,
+        "verdict": challenge_score.verdict.label(),
+        "reason": challenge_score.reason,
+        "ants_lost": performance_tracker.ants_lost
+    });
+    let video = serde_json::json!({
+        "fps": timing.fps,
+        "frames": timing.frames,
+        "duration_seconds": timing.duration,
+        "target_duration_seconds": timing.target_duration,
+        "effective_speedup": timing.effective_speedup
+    });
     let updated_json = serde_json::json!({
         "current_generation": generation_info.current_generation,
         "description": generation_info.description,
-        "timestamp": chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
-        "video_filename": format!("{:04}_{}.mp4", generation_info.current_generation, generation_info.description.replace(" ", "_").to_lowercase()),
-        "performance_metrics": {
-            "average_time_since_goal_seconds": performance_tracker.average_time_since_goal,
-            "average_return_time_seconds": performance_tracker.average_return_time,
-            "successful_deliveries": performance_tracker.successful_deliveries,
-            "simulation_duration_seconds": 90,
-            "total_food_collected": performance_tracker.total_food_collected
-        }
+        "timestamp": timestamp,
+        "video_filename": video_filename,
+        "performance_metrics": performance_metrics,
+        "challenge_score": challenge,
+        "video": video,
+        "annotations": annotations.to_json()
+    });
+    
+    // Per-run sidecar next to the video, which unlike generation_info.json isn't overwritten by the next run
+    let run_json = serde_json::json!({
+        "generation": generation_info.current_generation,
+        "description": generation_info.description,
+        "timestamp": timestamp,
+        "video_filename": video_filename,
+        "config_hash": setup.config_hash,
+        "map_seed": setup.map_seed,
+        "maze_seed": setup.maze_seed,
+        "challenge": {
+            "number": setup.challenge,
+            "name": setup.challenge_name
+        },
+        "performance_metrics": performance_metrics,
+        "challenge_score": challenge,
+        "video": video,
+        "annotations": annotations.to_json()
     });
+    (updated_json, run_json)
+}
+
+fn write_generation_info(updated_json: &serde_json::Value, run_json: &serde_json::Value, stem: &str) {
+    // Step 1: Archive current generation_info.json to generation_history.json
+    archive_current_generation_to_history();
     
-    if let Ok(json_string) = serde_json::to_string_pretty(&updated_json) {
+    // Step 2: Update generation_info.json with new performance data
+    if let Ok(json_string) = serde_json::to_string_pretty(updated_json) {
         if let Err(e) = fs::write("generation_info.json", json_string) {
-            println!("❌ Failed to update generation_info.json: {}", e);
+            error!("❌ Failed to update generation_info.json: {}", e);
         } else {
-            println!("✅ Updated generation_info.json with current performance metrics");
+            info!("✅ Updated generation_info.json with current performance metrics");
         }
     }
+    
+    // Step 3: The per-run sidecar
+    let run_file = format!("{}_run.json", stem);
+    if let Ok(json_string) = serde_json::to_string_pretty(run_json) {
+        if let Err(e) = fs::write(&run_file, json_string) {
+            error!("❌ Failed to write {}: {}", run_file, e);
+        } else {
+            info!("✅ Run details saved: {}", run_file);
+        }
+    }
+}
+
+/// How a run was set up, for the sidecar run.json and the video's container tags
+struct RunSetup {
+    config_hash: String,
+    map_seed: Option<u64>, // None when the challenge's own layout was used instead of a generated map
+    maze_seed: u64,
+    challenge: u32,
+    challenge_name: String,
+}
+
+impl RunSetup {
+    fn new(config: &SimConfig, challenge: &dyn Challenge) -> Self {
+        Self {
+            config_hash: config_hash(config),
+            map_seed: config.procedural_map.then_some(config.map_seed),
+            maze_seed: config.maze_seed,
+            challenge: challenge.number(),
+            challenge_name: challenge.name(),
+        }
+    }
+
+    /// `Challenge 3 (Maze Run), config 1a2b3c4d, maze seed 42`
+    fn summary(&self) -> String {
+        let map = self.map_seed.map_or(String::new(), |seed| format!(", map seed {}", seed));
+        format!("Challenge {} ({}), config {}{}, maze seed {}", self.challenge, self.challenge_name, self.config_hash, map, self.maze_seed)
+    }
 }
 
 fn archive_current_generation_to_history() {
@@ -674,6 +1723,7 @@ fn archive_current_generation_to_history() {
                 "timestamp": current_info["timestamp"],
                 "video_filename": current_info["video_filename"],
                 "performance_metrics": current_info["performance_metrics"],
+                "challenge_score": current_info["challenge_score"],
                 "key_changes": [
                     format!("Generation {} completed", current_info["current_generation"])
                 ]
@@ -690,9 +1740,9 @@ fn archive_current_generation_to_history() {
             // Write updated history
             if let Ok(history_string) = serde_json::to_string_pretty(&history) {
                 if let Err(e) = fs::write("generation_history.json", history_string) {
-                    println!("❌ Failed to update generation_history.json: {}", e);
+                    error!("❌ Failed to update generation_history.json: {}", e);
                 } else {
-                    println!("✅ Archived Generation {} to history", current_info["current_generation"]);
+                    info!("✅ Archived Generation {} to history", current_info["current_generation"]);
                 }
             }
         }
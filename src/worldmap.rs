@@ -0,0 +1,229 @@
+use bevy::prelude::*;
+use crate::terrain::read_png_rgb;
+
+/// World units between sampled rock and water cells
+pub const LATTICE_SPACING: f32 = 20.0;
+
+/// Pixels per side of maps written by the editor (2 world units per pixel)
+pub const MAP_RESOLUTION: usize = 500;
+
+/// What a map pixel stands for
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum MapCell {
+    Open,
+    Nest,
+    Food,
+    Rock,
+    Water,
+}
+
+impl MapCell {
+    /// Color key: yellow = nest, green = food, gray = rock, blue = water, anything else = open ground
+    fn from_rgb([r, g, b]: [u8; 3]) -> Self {
+        let (r, g, b) = (r as i32, g as i32, b as i32);
+        if r > 200 && g > 200 && b < 100 {
+            MapCell::Nest
+        } else if g > 200 && r < 100 && b < 100 {
+            MapCell::Food
+        } else if b > 200 && r < 100 && g < 150 {
+            MapCell::Water
+        } else if (r - g).abs() < 30 && (g - b).abs() < 30 && (50..=170).contains(&r) {
+            MapCell::Rock
+        } else {
+            MapCell::Open
+        }
+    }
+}
+
+/// Impassable water cells, checked by movement like rock collisions
+pub struct WaterMap {
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<bool>, // Row 0 = top of image
+}
+
+impl WaterMap {
+    pub fn is_water(&self, x: f32, y: f32) -> bool {
+        let (px, py) = world_to_pixel(x, y, self.width, self.height);
+        self.cells[py * self.width + px]
+    }
+
+    /// Set every cell inside a world-space square
+    pub fn set_square(&mut self, center: Vec2, half_size: f32, water: bool) {
+        let (min_x, min_y) = world_to_pixel(center.x - half_size, center.y + half_size, self.width, self.height);
+        let (max_x, max_y) = world_to_pixel(center.x + half_size - 0.01, center.y - half_size + 0.01, self.width, self.height);
+        for py in min_y..=max_y {
+            for px in min_x..=max_x {
+                self.cells[py * self.width + px] = water;
+            }
+        }
+    }
+}
+
+/// World layout drawn in an image editor, loaded with `--map world.png`
+#[derive(Resource)]
+pub struct WorldMap {
+    pub nests: Vec<Vec2>,        // One colony per yellow blob
+    pub food: Vec<Vec2>,         // One food source per green blob
+    pub rocks: Vec<(Vec2, f32)>, // Gray areas, sampled on a lattice
+    pub water: WaterMap,
+}
+
+impl WorldMap {
+    /// Blank map with no water, used when the editor starts without `--map`
+    pub fn empty() -> Self {
+        let resolution = MAP_RESOLUTION;
+        Self {
+            nests: Vec::new(),
+            food: Vec::new(),
+            rocks: Vec::new(),
+            water: WaterMap { width: resolution, height: resolution, cells: vec![false; resolution * resolution] },
+        }
+    }
+
+    /// Load a layout from a PNG stretched over the 1000x1000 world (image top = world +y)
+    pub fn from_png(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let (width, height, pixels) = read_png_rgb(path)?;
+        let cells: Vec<MapCell> = pixels.into_iter().map(MapCell::from_rgb).collect();
+
+        let nests = blob_centers(&cells, width, height, MapCell::Nest);
+        let food = blob_centers(&cells, width, height, MapCell::Food);
+
+        // Gray areas become touching rocks so existing rock avoidance handles them
+        let mut rocks = Vec::new();
+        let steps = (1000.0 / LATTICE_SPACING) as i32;
+        for i in 0..steps {
+            for j in 0..steps {
+                let x = -500.0 + (i as f32 + 0.5) * LATTICE_SPACING;
+                let y = -500.0 + (j as f32 + 0.5) * LATTICE_SPACING;
+                let (px, py) = world_to_pixel(x, y, width, height);
+                if cells[py * width + px] == MapCell::Rock {
+                    rocks.push((Vec2::new(x, y), LATTICE_SPACING * 0.5));
+                }
+            }
+        }
+
+        let water = WaterMap {
+            width,
+            height,
+            cells: cells.iter().map(|cell| *cell == MapCell::Water).collect(),
+        };
+
+        if nests.is_empty() {
+            warn!("⚠️ Map {} has no yellow nest pixels, keeping the challenge nest", path);
+        }
+
+        Ok(Self { nests, food, rocks, water })
+    }
+
+    /// World positions of water lattice cells, for drawing
+    pub fn water_cells(&self) -> Vec<Vec2> {
+        let steps = (1000.0 / LATTICE_SPACING) as i32;
+        let mut cells = Vec::new();
+        for i in 0..steps {
+            for j in 0..steps {
+                let x = -500.0 + (i as f32 + 0.5) * LATTICE_SPACING;
+                let y = -500.0 + (j as f32 + 0.5) * LATTICE_SPACING;
+                if self.water.is_water(x, y) {
+                    cells.push(Vec2::new(x, y));
+                }
+            }
+        }
+        cells
+    }
+}
+
+/// Pixel containing a world position, image top = world +y
+fn world_to_pixel(x: f32, y: f32, width: usize, height: usize) -> (usize, usize) {
+    let world_size = 1000.0;
+    let u = ((x + world_size * 0.5) / world_size * width as f32) as i32;
+    let v = ((world_size * 0.5 - y) / world_size * height as f32) as i32;
+    (u.clamp(0, width as i32 - 1) as usize, v.clamp(0, height as i32 - 1) as usize)
+}
+
+/// World-space centroid of each 4-connected blob of `kind` pixels
+fn blob_centers(cells: &[MapCell], width: usize, height: usize, kind: MapCell) -> Vec<Vec2> {
+    let mut visited = vec![false; cells.len()];
+    let mut centers = Vec::new();
+
+    for start in 0..cells.len() {
+        if visited[start] || cells[start] != kind {
+            continue;
+        }
+
+        let mut stack = vec![start];
+        visited[start] = true;
+        let mut sum = Vec2::ZERO;
+        let mut count = 0.0;
+
+        while let Some(idx) = stack.pop() {
+            let (col, row) = (idx % width, idx / width);
+            sum += Vec2::new(col as f32 + 0.5, row as f32 + 0.5);
+            count += 1.0;
+
+            let mut neighbours = Vec::with_capacity(4);
+            if col > 0 { neighbours.push(idx - 1); }
+            if col + 1 < width { neighbours.push(idx + 1); }
+            if row > 0 { neighbours.push(idx - width); }
+            if row + 1 < height { neighbours.push(idx + width); }
+
+            for next in neighbours {
+                if !visited[next] && cells[next] == kind {
+                    visited[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        let center = sum / count;
+        centers.push(Vec2::new(
+            center.x / width as f32 * 1000.0 - 500.0,
+            500.0 - center.y / height as f32 * 1000.0,
+        ));
+    }
+
+    centers
+}
+
+/// Layout to write back out in the `--map` color format
+pub struct MapLayout {
+    pub nests: Vec<Vec2>,
+    pub food: Vec<Vec2>,
+    pub rocks: Vec<(Vec2, f32)>,
+}
+
+/// Save a layout as a PNG that `WorldMap::from_png` reads back
+pub fn save_map_png(path: &str, layout: &MapLayout, water: &WaterMap) -> Result<(), Box<dyn std::error::Error>> {
+    let resolution = MAP_RESOLUTION;
+    let units_per_pixel = 1000.0 / resolution as f32;
+    let mut pixels = vec![[255u8; 3]; resolution * resolution];
+
+    for py in 0..resolution {
+        for px in 0..resolution {
+            let x = (px as f32 + 0.5) * units_per_pixel - 500.0;
+            let y = 500.0 - (py as f32 + 0.5) * units_per_pixel;
+            let position = Vec2::new(x, y);
+
+            let color = if layout.nests.iter().any(|nest| (position - *nest).abs().max_element() < 20.0) {
+                [255, 255, 0]
+            } else if layout.food.iter().any(|food| (position - *food).abs().max_element() < 15.0) {
+                [0, 255, 0]
+            } else if layout.rocks.iter().any(|(rock, radius)| position.distance(*rock) < *radius) {
+                [128, 128, 128]
+            } else if water.is_water(x, y) {
+                [0, 50, 255]
+            } else {
+                [255, 255, 255]
+            };
+            pixels[py * resolution + px] = color;
+        }
+    }
+
+    let file = std::fs::File::create(path)?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), resolution as u32, resolution as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels.concat())?;
+    Ok(())
+}